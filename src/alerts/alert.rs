@@ -8,6 +8,8 @@ use std::hash::{Hash, Hasher};
 
 use serde::{Deserialize, Serialize};
 
+use crate::{alerts::TimeOfDay, tmars::Phase};
+
 /// Represents an alert registered by a Matrix user for a Terraforming Mars player.
 ///
 /// An alert associates a Matrix user with a Terraforming Mars player in a specific
@@ -17,8 +19,28 @@ use serde::{Deserialize, Serialize};
 /// # Equality and Hashing
 ///
 /// Two alerts are considered equal if they have the same `room_id`, `player_id`,
-/// and `user_id`, regardless of their `notified` status or `delay` value. This
-/// allows detecting duplicate alert registrations.
+/// and `user_id`, regardless of their `notified` status, `delay` value or
+/// scheduling timestamps. This allows detecting duplicate alert registrations.
+///
+/// # Scheduling
+///
+/// When the player's turn is detected, `turn_started_at` and `notify_at` are set
+/// and persisted alongside the alert. `notify_at` is cleared once the notification
+/// has been sent, so an alert that is `notified` but still has a `notify_at` after
+/// a restart has a pending notification that must be re-armed.
+///
+/// # Reminders
+///
+/// When `repeat_interval` is set, the user is notified again every
+/// `repeat_interval` minutes while the player is still expected to play, up to
+/// `max_reminders` times (unlimited if not set). `notifications_sent` counts the
+/// notifications sent for the current turn and is reset when the turn ends.
+///
+/// # Phases
+///
+/// When `phases` isn't empty, the alert only fires while the game is in one of
+/// them. A player waited for in another phase, e.g. during the drafting where
+/// everyone plays at once, is considered as not playing.
 ///
 /// # Examples
 ///
@@ -31,6 +53,13 @@ use serde::{Deserialize, Serialize};
 ///     notified: false,
 ///     delay: 60, // 60 minutes
 ///     player_url: "https://example.com/player?id=player123".to_string(),
+///     turn_started_at: None,
+///     notify_at: None,
+///     repeat_interval: Some(30), // remind every 30 minutes
+///     max_reminders: Some(3),
+///     notifications_sent: 0,
+///     phases: Vec::new(),
+///     time_of_day: None,
 /// };
 /// ```
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -54,18 +83,104 @@ pub struct Alert {
     /// Delay in minutes before notifying the user.
     ///
     /// When the player's turn arrives, the bot waits this many minutes
-    /// before sending the notification. Must be between 1 and 10,080 (1 week).
+    /// before sending the notification. Must be between 1 and 10,080 (1 week),
+    /// unless the alert notifies at a time of day.
     pub delay: u64,
     /// URL to the player's game page.
     ///
     /// This URL points to the player's view of the game on the Terraforming Mars server.
     pub player_url: String,
+    /// Unix timestamp (in seconds) at which the player's turn was detected.
+    ///
+    /// `None` when the player is not currently being waited for.
+    #[serde(default)]
+    pub turn_started_at: Option<u64>,
+    /// Unix timestamp (in seconds) at which the notification is due.
+    ///
+    /// Cleared once the notification has been sent. Alerts persisted by older
+    /// versions don't have this field and are loaded with `None`.
+    #[serde(default)]
+    pub notify_at: Option<u64>,
+    /// Interval in minutes between reminders.
+    ///
+    /// `None` if the user is only notified once per turn.
+    #[serde(default)]
+    pub repeat_interval: Option<u64>,
+    /// Maximum number of reminders sent after the first notification.
+    ///
+    /// `None` if reminders are sent until the turn ends.
+    #[serde(default)]
+    pub max_reminders: Option<u32>,
+    /// Number of notifications sent for the current turn, first one included.
+    #[serde(default)]
+    pub notifications_sent: u32,
+    /// Phases of the game in which the alert fires.
+    ///
+    /// Empty if the alert fires in every phase, like the alerts persisted by older
+    /// versions.
+    #[serde(default)]
+    pub phases: Vec<Phase>,
+    /// Time of day at which the user is notified, instead of after the delay.
+    ///
+    /// The notification is sent at the first occurrence of this time after the
+    /// player's turn is detected. `None` if the alert notifies after the delay.
+    #[serde(default)]
+    pub time_of_day: Option<TimeOfDay>,
+}
+
+impl Alert {
+    /// Returns the Unix timestamp (in seconds) at which the first notification of a turn is due.
+    ///
+    /// # Arguments
+    ///
+    /// * `turn_started_at` - Unix timestamp (in seconds) at which the turn was detected
+    pub fn get_first_notification_at(&self, turn_started_at: u64) -> u64 {
+        match &self.time_of_day {
+            Some(time_of_day) => time_of_day
+                .get_next_after(turn_started_at)
+                .unwrap_or(turn_started_at),
+            None => turn_started_at + self.delay * 60,
+        }
+    }
+
+    /// Returns the Unix timestamp (in seconds) at which the next reminder is due.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Unix timestamp (in seconds) at which the last notification was sent
+    ///
+    /// # Returns
+    ///
+    /// `None` if the alert doesn't repeat or if all the reminders have been sent.
+    pub fn get_next_reminder_at(&self, now: u64) -> Option<u64> {
+        let repeat_interval = self.repeat_interval?;
+        let reminders_sent = self.notifications_sent.saturating_sub(1);
+
+        if self
+            .max_reminders
+            .is_some_and(|max_reminders| reminders_sent >= max_reminders)
+        {
+            return None;
+        }
+
+        Some(now + repeat_interval * 60)
+    }
+
+    /// Returns whether the alert fires while the game is in the given phase.
+    pub fn fires_in(&self, phase: &Phase) -> bool {
+        self.phases.is_empty() || self.phases.contains(phase)
+    }
+
+    /// Returns whether the last notification sent was a reminder.
+    pub fn is_reminder(&self) -> bool {
+        self.notifications_sent > 1
+    }
 }
 
 /// Implementation of partial equality for alerts.
 ///
 /// Two alerts are equal if they share the same room, player, and user,
-/// regardless of notification status or delay. This prevents users from
+/// regardless of notification status, delay or scheduling timestamps. This prevents users from
 /// registering duplicate alerts for the same player in the same room.
 impl PartialEq for Alert {
     fn eq(&self, other: &Self) -> bool {
@@ -115,6 +230,13 @@ mod tests {
             notified: false,
             delay: 10,
             player_url: "http://example.com/player1".to_string(),
+            turn_started_at: None,
+            notify_at: None,
+            repeat_interval: None,
+            max_reminders: None,
+            notifications_sent: 0,
+            phases: Vec::new(),
+            time_of_day: None,
         };
 
         let al2 = Alert {
@@ -124,6 +246,13 @@ mod tests {
             notified: true,
             delay: 20,
             player_url: "http://example.com/player1".to_string(),
+            turn_started_at: None,
+            notify_at: None,
+            repeat_interval: None,
+            max_reminders: None,
+            notifications_sent: 0,
+            phases: Vec::new(),
+            time_of_day: None,
         };
 
         assert!(al1 == al2);
@@ -139,6 +268,13 @@ mod tests {
             notified: false,
             delay: 10,
             player_url: "http://example.com/player1".to_string(),
+            turn_started_at: None,
+            notify_at: None,
+            repeat_interval: None,
+            max_reminders: None,
+            notifications_sent: 0,
+            phases: Vec::new(),
+            time_of_day: None,
         };
 
         let al2 = Alert {
@@ -148,9 +284,92 @@ mod tests {
             notified: true,
             delay: 20,
             player_url: "http://example.com/player2".to_string(),
+            turn_started_at: None,
+            notify_at: None,
+            repeat_interval: None,
+            max_reminders: None,
+            notifications_sent: 0,
+            phases: Vec::new(),
+            time_of_day: None,
         };
 
         assert!(al1 != al2);
         assert!(calculate_hash(&al1) != calculate_hash(&al2))
     }
+
+    fn create_repeating_alert(
+        repeat_interval: Option<u64>,
+        max_reminders: Option<u32>,
+        notifications_sent: u32,
+    ) -> Alert {
+        Alert {
+            room_id: "room1".to_string(),
+            player_id: "player1".to_string(),
+            user_id: "user1".to_string(),
+            notified: true,
+            delay: 10,
+            player_url: "http://example.com/player1".to_string(),
+            turn_started_at: Some(1_000),
+            notify_at: None,
+            repeat_interval,
+            max_reminders,
+            notifications_sent,
+            phases: Vec::new(),
+            time_of_day: None,
+        }
+    }
+
+    #[test]
+    fn test_get_next_reminder_at_without_repeat() {
+        let alert = create_repeating_alert(None, None, 1);
+        assert_eq!(alert.get_next_reminder_at(2_000), None);
+    }
+
+    #[test]
+    fn test_get_next_reminder_at_unlimited() {
+        let alert = create_repeating_alert(Some(30), None, 100);
+        assert_eq!(alert.get_next_reminder_at(2_000), Some(2_000 + 30 * 60));
+    }
+
+    #[test]
+    fn test_get_next_reminder_at_with_max_reminders() {
+        // First notification sent, no reminder yet
+        let alert = create_repeating_alert(Some(30), Some(2), 1);
+        assert_eq!(alert.get_next_reminder_at(2_000), Some(2_000 + 30 * 60));
+
+        // First reminder sent
+        let alert = create_repeating_alert(Some(30), Some(2), 2);
+        assert_eq!(alert.get_next_reminder_at(2_000), Some(2_000 + 30 * 60));
+
+        // All the reminders have been sent
+        let alert = create_repeating_alert(Some(30), Some(2), 3);
+        assert_eq!(alert.get_next_reminder_at(2_000), None);
+    }
+
+    #[test]
+    fn test_is_reminder() {
+        assert!(!create_repeating_alert(Some(30), None, 1).is_reminder());
+        assert!(create_repeating_alert(Some(30), None, 2).is_reminder());
+    }
+
+    #[test]
+    fn test_get_first_notification_at_after_delay() {
+        let alert = create_repeating_alert(None, None, 0);
+        assert_eq!(alert.get_first_notification_at(2_000), 2_000 + 10 * 60);
+    }
+
+    #[test]
+    fn test_get_first_notification_at_time_of_day() {
+        let mut alert = create_repeating_alert(None, None, 0);
+        alert.time_of_day = Some(TimeOfDay {
+            time: chrono::NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            timezone: chrono_tz::UTC,
+        });
+
+        // 1970-01-01 10:00 UTC, notified the next day at 09:00
+        assert_eq!(
+            alert.get_first_notification_at(10 * 60 * 60),
+            (24 + 9) * 60 * 60
+        );
+    }
 }
@@ -14,7 +14,9 @@ use tokio::{sync::Mutex, task::JoinHandle, time};
 
 use crate::{
     alerts::{Alert, AlertLoader},
-    tmars::Game,
+    settings::SettingsController,
+    tmars::{Game, GameEvent, get_game_server_name, is_namespaced_game_id, namespace_game_id},
+    utils::get_timestamp,
 };
 
 type GamesMap = HashMap<String, Game>;
@@ -27,7 +29,8 @@ const SAVE_INTERVAL_SECS: u64 = 60; // 1 minute
 /// The `AlertController` coordinates several key responsibilities:
 /// - Tracking active alerts across multiple games
 /// - Scheduling delayed notifications when a player's turn arrives
-/// - Cleaning up alerts for games that no longer exist
+/// - Deferring notifications falling into the user's quiet hours
+/// - Cleaning up alerts for games that ended or no longer exist
 /// - Persisting alerts to disk periodically
 /// - Managing background tasks for notifications
 ///
@@ -40,10 +43,13 @@ const SAVE_INTERVAL_SECS: u64 = 60; // 1 minute
 ///
 /// ```no_run
 /// use miou::alerts::AlertController;
+/// use miou::settings::SettingsController;
+/// use std::sync::Arc;
 ///
 /// # async fn example() {
 /// // Initialize the controller
-/// let mut controller = AlertController::new("alerts.json".to_string()).await;
+/// let settings_controller = Arc::new(SettingsController::new("settings.json".to_string()).await);
+/// let mut controller = AlertController::new("alerts.json".to_string(), settings_controller, 3).await;
 ///
 /// // Start automatic persistence
 /// controller.start_persistence_task();
@@ -58,6 +64,15 @@ pub struct AlertController {
     alert_loader: AlertLoader,
     /// Map of active alerts to their notification task handles
     thread_handles_map: HashMap<Alert, JoinHandle<()>>,
+    /// User preferences, used to respect the quiet hours
+    settings_controller: Arc<SettingsController>,
+    /// Number of consecutive polls a game with alerts has been missing, by game ID
+    missing_polls: HashMap<String, u32>,
+    /// Number of polls a game can be missing before its alerts are removed
+    grace_polls: u32,
+    /// IDs of the games with alerts added since the last update, checked even
+    /// without any event
+    unchecked_game_ids: HashSet<String>,
 }
 
 impl AlertController {
@@ -66,30 +81,92 @@ impl AlertController {
     /// # Arguments
     ///
     /// * `alerts_path` - Path to the JSON file where alerts are persisted
+    /// * `settings_controller` - User preferences, checked before each notification
+    /// * `grace_polls` - Number of consecutive polls a game can be missing before
+    ///   its alerts are removed, see [`AlertController::clean_alerts`]
     ///
     /// # Returns
     ///
     /// A new `AlertController` instance with alerts loaded from the specified file.
     /// If the file doesn't exist or is corrupted, starts with an empty alerts map.
     ///
+    /// # Pending Notifications
+    ///
+    /// Notifications that were still waiting for their delay when the bot stopped
+    /// are kept in the loaded alerts (see [`Alert::notify_at`]). They are re-armed
+    /// by the first call to [`AlertController::update_alerts`] if the player is
+    /// still expected to play: overdue notifications are sent right away, the
+    /// others wait for the remaining time only.
+    ///
     /// # Examples
     ///
     /// ```no_run
     /// use miou::alerts::AlertController;
+    /// use miou::settings::SettingsController;
+    /// use std::sync::Arc;
     ///
     /// # async fn example() {
-    /// let controller = AlertController::new("alerts.json".to_string()).await;
+    /// let settings_controller = Arc::new(SettingsController::new("settings.json".to_string()).await);
+    /// let controller = AlertController::new("alerts.json".to_string(), settings_controller, 3).await;
     /// # }
     /// ```
-    pub async fn new(alerts_path: String) -> Self {
+    pub async fn new(
+        alerts_path: String,
+        settings_controller: Arc<SettingsController>,
+        grace_polls: u32,
+    ) -> Self {
         let alert_loader = AlertLoader::new(alerts_path);
-        let alerts_map = Arc::new(Mutex::new(alert_loader.load().await));
+        let alerts_map = alert_loader.load().await;
         let thread_handles_map: HashMap<Alert, JoinHandle<()>> = HashMap::new();
 
+        let pending_count = alerts_map
+            .values()
+            .flatten()
+            .filter(|alert| alert.notify_at.is_some())
+            .count();
+        if pending_count > 0 {
+            info!("{} pending notifications to resume", pending_count);
+        }
+
+        let alerts_map = Arc::new(Mutex::new(alerts_map));
+
         AlertController {
             alerts_map,
             alert_loader,
             thread_handles_map,
+            settings_controller,
+            missing_polls: HashMap::new(),
+            grace_polls,
+            unchecked_game_ids: HashSet::new(),
+        }
+    }
+
+    /// Namespaces the game IDs of the alerts registered before the support of
+    /// multiple servers.
+    ///
+    /// Alerts were registered with the bare IDs of the games of a single server.
+    /// They are moved to the namespaced IDs of the games on `server_name`, the
+    /// other alerts are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - Name of the server hosting the games of the bare IDs
+    pub async fn namespace_game_ids(&self, server_name: &str) {
+        let mut alerts_map = self.alerts_map.lock().await;
+
+        let game_ids: Vec<String> = alerts_map
+            .keys()
+            .filter(|game_id| !is_namespaced_game_id(game_id))
+            .cloned()
+            .collect();
+        for game_id in game_ids {
+            let alerts = alerts_map.remove(&game_id).unwrap_or_default();
+            let namespaced_game_id = namespace_game_id(server_name, &game_id);
+            info!("move alerts of game {} to {}", game_id, namespaced_game_id);
+            alerts_map
+                .entry(namespaced_game_id)
+                .or_default()
+                .extend(alerts);
         }
     }
 
@@ -104,8 +181,8 @@ impl AlertController {
     /// ```no_run
     /// use miou::alerts::AlertController;
     ///
-    /// # async fn example() {
-    /// let controller = AlertController::new("alerts.json".to_string()).await;
+    /// # async fn example(settings_controller: std::sync::Arc<miou::settings::SettingsController>) {
+    /// let controller = AlertController::new("alerts.json".to_string(), settings_controller, 3).await;
     /// let alerts = controller.get_alerts_map().await;
     /// println!("Managing alerts for {} games", alerts.len());
     /// # }
@@ -130,8 +207,8 @@ impl AlertController {
     /// ```no_run
     /// use miou::alerts::AlertController;
     ///
-    /// # async fn example() {
-    /// let controller = AlertController::new("alerts.json".to_string()).await;
+    /// # async fn example(settings_controller: std::sync::Arc<miou::settings::SettingsController>) {
+    /// let controller = AlertController::new("alerts.json".to_string(), settings_controller, 3).await;
     /// controller.start_persistence_task();
     /// // Alerts will now be automatically saved every minute
     /// # }
@@ -150,21 +227,31 @@ impl AlertController {
         });
     }
 
-    /// Updates alerts based on current game state and triggers notifications.
+    /// Updates alerts based on the game events and triggers notifications.
     ///
-    /// This method performs three key operations:
-    /// 1. Removes alerts for games that no longer exist
-    /// 2. Identifies alerts that should fire based on current game state
-    /// 3. Schedules delayed notifications for those alerts
+    /// This method performs two key operations:
+    /// 1. Identifies alerts that should fire in the games changed by the events
+    /// 2. Schedules delayed notifications for those alerts
+    ///
+    /// Only the games with a phase or turn event, the games discovered by the
+    /// synchronization and the games with newly added alerts are checked. The first
+    /// synchronization after a restart discovers every game, so the notifications
+    /// pending before the restart are resumed then.
+    ///
+    /// Alerts of the games missing from `games_map` are left untouched, they are
+    /// removed by [`AlertController::clean_alerts`].
     ///
     /// # Arguments
     ///
     /// * `games_map` - Current state of all active games
-    /// * `on_alert_to_fire` - Callback function invoked when an alert fires
+    /// * `game_events` - Events emitted by the synchronization since the last update
+    /// * `on_alert_to_fire` - Callback function invoked when an alert fires, with the
+    ///   ID of the game of the alert
     ///
     /// # Type Parameters
     ///
-    /// * `F` - A function that takes an `Alert` and performs the notification action
+    /// * `F` - A function that takes an `Alert` and the ID of its game and performs
+    ///   the notification action, with the state of the game when it is sent
     ///
     /// # Examples
     ///
@@ -172,72 +259,127 @@ impl AlertController {
     /// use miou::alerts::{Alert, AlertController};
     /// use std::collections::HashMap;
     ///
-    /// # async fn example() {
-    /// let mut controller = AlertController::new("alerts.json".to_string()).await;
+    /// # async fn example(settings_controller: std::sync::Arc<miou::settings::SettingsController>) {
+    /// let mut controller = AlertController::new("alerts.json".to_string(), settings_controller, 3).await;
     /// let games_map = HashMap::new(); // Normally populated with actual games
+    /// let game_events = Vec::new(); // Normally received from the synchronization
     ///
-    /// controller.update_alerts(&games_map, |alert| {
-    ///     println!("Alert fired for user {}", alert.user_id);
+    /// controller.update_alerts(&games_map, &game_events, |alert, game_id| {
+    ///     println!("Alert fired for user {} in game {}", alert.user_id, game_id);
     /// }).await;
     /// # }
     /// ```
-    pub async fn update_alerts<F>(&mut self, games_map: &GamesMap, on_alert_to_fire: F)
-    where
-        F: Fn(Alert) + Send + Sync + 'static,
+    pub async fn update_alerts<F>(
+        &mut self,
+        games_map: &GamesMap,
+        game_events: &[GameEvent],
+        on_alert_to_fire: F,
+    ) where
+        F: Fn(Alert, &str) + Send + Sync + 'static,
     {
-        self.clean_alerts(games_map).await;
-        let alerts_to_fire = self.get_alerts_to_fire(games_map).await;
+        let alerts_to_fire = self.get_alerts_to_fire(games_map, game_events).await;
 
         let on_alert_to_fire = Arc::new(on_alert_to_fire);
-        self.fire_alert(alerts_to_fire, move |alert| {
+        self.fire_alert(alerts_to_fire, move |alert, game_id| {
             let on_alert_to_fire = Arc::clone(&on_alert_to_fire);
-            on_alert_to_fire(alert);
+            on_alert_to_fire(alert, game_id);
         });
     }
 
     /// Removes alerts for games that no longer exist.
     ///
-    /// Iterates through all alerts and removes entries for game IDs that are not
-    /// present in the current games map. Also aborts any pending notification tasks
-    /// for those alerts.
+    /// A game missing from a single poll may only be a hiccup of its server, so its
+    /// alerts are removed once the game has been missing for more than the grace
+    /// period of consecutive polls. A game showing up again resets its count.
+    ///
+    /// Only the successful polls of its server count for a game: a failing server
+    /// keeps the games it had, or has none if it never answered.
+    ///
+    /// The checks between two polls don't list the games, the servers only checked
+    /// must not be in `synced_servers`.
     ///
     /// # Arguments
     ///
     /// * `games_map` - Current state of all active games
+    /// * `synced_servers` - Names of the servers which listed their games during the poll
     ///
     /// # Side Effects
     ///
     /// - Removes alerts from the alerts map
     /// - Aborts notification tasks for removed alerts
     /// - Logs info messages for each cleaned game
-    async fn clean_alerts(&mut self, games_map: &GamesMap) {
+    pub async fn clean_alerts(&mut self, games_map: &GamesMap, synced_servers: &HashSet<String>) {
         let thread_handles_map = &mut self.thread_handles_map;
+        let missing_polls = &mut self.missing_polls;
+        let grace_polls = self.grace_polls;
 
+        missing_polls.retain(|game_id, _| !games_map.contains_key(game_id));
         self.alerts_map.lock().await.retain(|game_id, alerts| {
-            if games_map.contains_key(game_id) {
-                true
-            } else {
-                // Remove all thread handles associated with these alerts
-                alerts.iter().for_each(|alert| {
-                    if let Some((_, handle)) = thread_handles_map.remove_entry(alert) {
-                        handle.abort();
-                    }
-                });
-                info!("removing alerts for non-existing game {}", game_id);
-                false
+            if games_map.contains_key(game_id)
+                || !get_game_server_name(game_id)
+                    .is_some_and(|server_name| synced_servers.contains(server_name))
+            {
+                return true;
+            }
+
+            let missing_count = missing_polls.entry(game_id.clone()).or_default();
+            *missing_count += 1;
+            if *missing_count <= grace_polls {
+                info!(
+                    "game {} missing for {} polls, keeping its alerts",
+                    game_id, missing_count
+                );
+                return true;
+            }
+
+            info!("removing alerts for non-existing game {}", game_id);
+            missing_polls.remove(game_id);
+            Self::abort_notifications(thread_handles_map, alerts);
+            false
+        });
+    }
+
+    /// Removes the alerts of ended games right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `ended_game_ids` - IDs of the games confirmed ended by their server
+    pub async fn remove_ended_games_alerts(&mut self, ended_game_ids: &HashSet<String>) {
+        let mut alerts_map = self.alerts_map.lock().await;
+
+        for game_id in ended_game_ids {
+            self.missing_polls.remove(game_id);
+            if let Some(alerts) = alerts_map.remove(game_id) {
+                info!("removing alerts for ended game {}", game_id);
+                Self::abort_notifications(&mut self.thread_handles_map, &alerts);
+            }
+        }
+    }
+
+    /// Aborts the pending notification tasks of the given alerts.
+    fn abort_notifications(
+        thread_handles_map: &mut HashMap<Alert, JoinHandle<()>>,
+        alerts: &HashSet<Alert>,
+    ) {
+        alerts.iter().for_each(|alert| {
+            if let Some((_, handle)) = thread_handles_map.remove_entry(alert) {
+                handle.abort();
             }
         });
     }
 
-    /// Identifies alerts that should trigger notifications based on game state.
+    /// Identifies alerts that should trigger notifications in the changed games.
     ///
-    /// Compares the current game state against registered alerts to determine:
+    /// Compares the current state of the games changed by the events, and of the
+    /// games with newly added alerts, against their registered alerts to determine:
     /// - Which alerts need to fire (player's turn arrived, not yet notified)
+    /// - Which alerts need to be re-armed (notification pending without a running task)
     /// - Which alerts need their notified status reset (player's turn ended)
     ///
     /// # Arguments
     ///
     /// * `games_map` - Current state of all active games
+    /// * `game_events` - Events emitted by the synchronization since the last update
     ///
     /// # Returns
     ///
@@ -245,49 +387,99 @@ impl AlertController {
     ///
     /// # Behavior
     ///
-    /// For each game with registered alerts:
-    /// - If a player's turn has arrived and they haven't been notified: mark for firing
-    /// - If a player's turn has ended and they were notified: reset the notified flag
+    /// For each changed game with registered alerts, a player is only considered playing if
+    /// the game is in one of the phases of the alert (see [`Alert::fires_in`]):
+    /// - If a player's turn has arrived and they haven't been notified: record the
+    ///   turn start and the notification due date, then mark for firing
+    /// - If the notification is still due but no task is waiting for it (e.g. after
+    ///   a restart): mark for firing again with the same due date
+    /// - If a player's turn has ended and they were notified: reset the notified flag,
+    ///   the scheduling timestamps and the notification count, stopping the reminders
     /// - Abort notification tasks for alerts that are no longer needed
-    async fn get_alerts_to_fire(&mut self, games_map: &GamesMap) -> Vec<(String, Alert)> {
+    async fn get_alerts_to_fire(
+        &mut self,
+        games_map: &GamesMap,
+        game_events: &[GameEvent],
+    ) -> Vec<(String, Alert)> {
         let mut alerts_map = self.alerts_map.lock().await;
+        let now = get_timestamp();
+
+        // The turns of the players only change with these events
+        let mut changed_game_ids = std::mem::take(&mut self.unchecked_game_ids);
+        for game_event in game_events {
+            match game_event {
+                GameEvent::GameAdded { .. }
+                | GameEvent::PhaseChanged { .. }
+                | GameEvent::TurnStarted { .. }
+                | GameEvent::TurnEnded { .. } => {
+                    changed_game_ids.insert(game_event.game_id().to_owned());
+                }
+                GameEvent::GameRemoved { .. } | GameEvent::GameEnded { .. } => {}
+            }
+        }
 
         // Collection of alerts that should trigger notifications
         let mut alerts_to_fire: Vec<(String, Alert)> = Vec::new();
 
-        for (game_id, game) in games_map {
-            // Only process games that have registered alerts
-            let Some(alerts) = alerts_map.get_mut(game_id) else {
+        for game_id in &changed_game_ids {
+            // Only process existing games that have registered alerts
+            let (Some(game), Some(alerts)) = (games_map.get(game_id), alerts_map.get_mut(game_id))
+            else {
                 continue;
             };
-            // Temporary storage for alerts that need their notified status updated
-            // Tuple contains (alert, new_notified_status)
-            let mut alerts_to_update: Vec<(Alert, bool)> = Vec::new();
+            // Temporary storage for alerts that need to be updated
+            let mut alerts_to_update: Vec<Alert> = Vec::new();
 
             // Check each alert against the current game state
             for alert in alerts.iter() {
-                // If it's the player's turn and they haven't been notified yet
-                if game.waited_players.contains(&alert.player_id) && !alert.notified {
+                // A player waited for in a phase filtered out by the alert isn't notified
+                let is_waited =
+                    game.waited_players.contains(&alert.player_id) && alert.fires_in(&game.phase);
+
+                if is_waited && !alert.notified {
+                    // It's the player's turn and they haven't been notified yet
+                    // Mark this alert as notified to prevent duplicate notifications
+                    // and record when the notification is due
+                    let mut alert = alert.clone();
+                    alert.notified = true;
+                    alert.turn_started_at = Some(now);
+                    alert.notify_at = Some(alert.get_first_notification_at(now));
+                    alert.notifications_sent = 0;
+
+                    alerts_to_fire.push((game_id.clone(), alert.clone()));
+                    alerts_to_update.push(alert);
+                } else if is_waited
+                    && alert.notify_at.is_some()
+                    && !self.thread_handles_map.contains_key(alert)
+                {
+                    // The notification is still due but nothing is waiting for it
+                    // This happens when the bot restarted during the delay
+                    info!(
+                        "resuming notification of user {} in room {} for game {}",
+                        alert.user_id, alert.room_id, game_id
+                    );
                     alerts_to_fire.push((game_id.clone(), alert.clone()));
-                    // Mark this alert for updating to prevent duplicate notifications
-                    alerts_to_update.push((alert.clone(), true));
-                } else if !game.waited_players.contains(&alert.player_id) && alert.notified {
+                } else if !is_waited && alert.notified {
                     // Reset the notified flag when it's no longer the player's turn
                     // This allows re-notification on their next turn
-                    alerts_to_update.push((alert.clone(), false));
+                    let mut alert = alert.clone();
+                    alert.notified = false;
+                    alert.turn_started_at = None;
+                    alert.notify_at = None;
+                    alert.notifications_sent = 0;
+
                     // Also abort any existing notification thread since it's no longer needed
-                    if let Some((_, handle)) = self.thread_handles_map.remove_entry(alert) {
+                    if let Some((_, handle)) = self.thread_handles_map.remove_entry(&alert) {
                         handle.abort();
                     }
+                    alerts_to_update.push(alert);
                 }
             }
 
-            // Update alerts in the HashSet by removing and re-inserting
+            // Update alerts in the HashSet by replacing them
             // This is necessary because HashSet doesn't support in-place mutation
-            for (mut alert, notified) in alerts_to_update {
-                alerts.remove(&alert);
-                alert.notified = notified;
-                alerts.insert(alert);
+            for alert in alerts_to_update {
+                alerts.replace(alert);
             }
         }
 
@@ -300,22 +492,29 @@ impl AlertController {
     ///
     /// For each alert, this method:
     /// 1. Aborts any existing notification task for the same alert
-    /// 2. Spawns a new async task that waits for the alert's delay period
-    /// 3. Calls the callback function when the delay expires
-    /// 4. Stores the task handle for potential future cancellation
+    /// 2. Spawns a new async task that waits until the alert's `notify_at` timestamp
+    ///    (or for the full delay if it's not set), without waiting if it's overdue
+    /// 3. Defers the notification to the end of the user's quiet hours if needed
+    /// 4. Calls the callback function when the notification is due
+    /// 5. Schedules the next reminder if the alert repeats, or clears the alert's
+    ///    `notify_at` so the notification is not sent again
+    /// 6. Stores the task handle for potential future cancellation
+    ///
+    /// The task keeps sending reminders until all of them have been sent, the alert
+    /// is removed or the task is aborted.
     ///
     /// # Arguments
     ///
     /// * `alerts_to_fire` - Vector of (game_id, alert) tuples to schedule
-    /// * `on_alert_to_fire` - Callback function invoked after the delay
+    /// * `on_alert_to_fire` - Callback function invoked after the delay, with the game ID
     ///
     /// # Note
     ///
-    /// Tasks can be aborted if the player's turn ends before the delay expires.
+    /// Tasks are aborted when the player's turn ends, which also stops the reminders.
     fn fire_alert(
         &mut self,
         alerts_to_fire: Vec<(String, Alert)>,
-        on_alert_to_fire: impl Fn(Alert) + Send + Sync + 'static,
+        on_alert_to_fire: impl Fn(Alert, &str) + Send + Sync + 'static,
     ) {
         let on_alert_to_fire = Arc::new(on_alert_to_fire);
 
@@ -326,32 +525,126 @@ impl AlertController {
             }
 
             let on_alert_to_fire = Arc::clone(&on_alert_to_fire);
-            let alert_clone = alert.clone();
+            let alerts_map = Arc::clone(&self.alerts_map);
+            let alert_loader = self.alert_loader.clone();
+            let settings_controller = Arc::clone(&self.settings_controller);
+            let mut alert_clone = alert.clone();
             let handle = tokio::spawn(async move {
-                debug!(
-                    "waiting {} minutes before notifying user {} for game {}",
-                    alert_clone.delay, alert_clone.user_id, game_id
-                );
+                let game_id = &game_id;
+                // Send the first notification then the reminders, if any
+                loop {
+                    let wait_secs = match alert_clone.notify_at {
+                        Some(notify_at) => notify_at.saturating_sub(get_timestamp()),
+                        None => alert_clone.delay * 60,
+                    };
+
+                    debug!(
+                        "waiting {} seconds before notifying user {} for game {}",
+                        wait_secs, alert_clone.user_id, game_id
+                    );
+
+                    // Wait until the notification is due
+                    time::sleep(Duration::from_secs(wait_secs)).await;
+
+                    // Don't disturb the user during their quiet hours, wait for the end instead
+                    if let Some(quiet_hours_end) = settings_controller
+                        .get_quiet_hours_end(&alert_clone.user_id, get_timestamp())
+                        .await
+                    {
+                        info!(
+                            "deferring notification of user {} for game {} to the end of quiet hours",
+                            alert_clone.user_id, game_id
+                        );
+                        alert_clone.notify_at = Some(quiet_hours_end);
+
+                        if !Self::save_schedule(&alerts_map, &alert_loader, game_id, &alert_clone)
+                            .await
+                        {
+                            break;
+                        }
+                        continue;
+                    }
 
-                // Wait for the specified delay before sending the notification
-                time::sleep(Duration::from_secs(alert_clone.delay * 60)).await;
+                    alert_clone.notifications_sent += 1;
+                    alert_clone.notify_at = alert_clone.get_next_reminder_at(get_timestamp());
 
-                info!(
-                    "notifying user {} in room {} for game {}",
-                    alert_clone.user_id, alert_clone.room_id, game_id
-                );
+                    info!(
+                        "notifying user {} in room {} for game {} (notification {})",
+                        alert_clone.user_id,
+                        alert_clone.room_id,
+                        game_id,
+                        alert_clone.notifications_sent
+                    );
 
-                on_alert_to_fire(alert_clone);
+                    on_alert_to_fire(alert_clone.clone(), game_id);
+
+                    // Save the schedule right away so that a notification already sent
+                    // is not resumed after a restart, unlike the next reminder
+                    if !Self::save_schedule(&alerts_map, &alert_loader, game_id, &alert_clone).await
+                    {
+                        break;
+                    }
+
+                    if alert_clone.notify_at.is_none() {
+                        break;
+                    }
+                }
             });
             self.thread_handles_map.insert(alert, handle);
         }
     }
 
+    /// Saves the notification schedule of an alert in the alerts map and persists it.
+    ///
+    /// Only `notify_at` and `notifications_sent` are copied to the registered alert,
+    /// so they are resumed after a restart. The alerts are persisted right away instead
+    /// of waiting for the persistence task, a restart in between would send the same
+    /// notification again.
+    ///
+    /// # Arguments
+    ///
+    /// * `alerts_map` - The shared alerts map
+    /// * `alert_loader` - Loader persisting the alerts map
+    /// * `game_id` - The ID of the game of the alert
+    /// * `alert` - The alert holding the schedule to save
+    ///
+    /// # Returns
+    ///
+    /// `false` if the alert is not registered anymore, `true` otherwise.
+    async fn save_schedule(
+        alerts_map: &Mutex<HashMap<String, HashSet<Alert>>>,
+        alert_loader: &AlertLoader,
+        game_id: &str,
+        alert: &Alert,
+    ) -> bool {
+        let mut alerts_map = alerts_map.lock().await;
+
+        let Some((alerts, mut registered_alert)) = alerts_map.get_mut(game_id).and_then(|alerts| {
+            let registered_alert = alerts.get(alert).cloned()?;
+            Some((alerts, registered_alert))
+        }) else {
+            debug!(
+                "alert of user {} for game {} was removed, stop notifying",
+                alert.user_id, game_id
+            );
+            return false;
+        };
+
+        registered_alert.notify_at = alert.notify_at;
+        registered_alert.notifications_sent = alert.notifications_sent;
+        alerts.replace(registered_alert);
+        alert_loader.persist_alerts_map(&alerts_map).await;
+
+        true
+    }
+
     /// Adds a new alert to the alerts map.
     ///
     /// Registers an alert for a specific game. If this is the first alert for the game,
     /// creates a new entry in the map. If an identical alert already exists (same room,
-    /// player, and user), it will be replaced due to HashSet semantics.
+    /// player, and user), it is replaced and its pending notification task is aborted,
+    /// the new alert is scheduled with its own delay and reminders by the next call to
+    /// [`AlertController::update_alerts`].
     ///
     /// # Arguments
     ///
@@ -363,8 +656,8 @@ impl AlertController {
     /// ```no_run
     /// use miou::alerts::{Alert, AlertController};
     ///
-    /// # async fn example() {
-    /// let controller = AlertController::new("alerts.json".to_string()).await;
+    /// # async fn example(settings_controller: std::sync::Arc<miou::settings::SettingsController>) {
+    /// let mut controller = AlertController::new("alerts.json".to_string(), settings_controller, 3).await;
     ///
     /// let alert = Alert {
     ///     room_id: "!room:example.com".to_string(),
@@ -373,23 +666,42 @@ impl AlertController {
     ///     notified: false,
     ///     delay: 60,
     ///     player_url: "https://example.com/player".to_string(),
+    ///     turn_started_at: None,
+    ///     notify_at: None,
+    ///     repeat_interval: None,
+    ///     max_reminders: None,
+    ///     notifications_sent: 0,
+    ///     phases: Vec::new(),
+    ///     time_of_day: None,
     /// };
     ///
     /// controller.add_alert("game_id", &alert).await;
     /// # }
     /// ```
-    pub async fn add_alert(&self, game_id: &str, alert: &Alert) {
+    pub async fn add_alert(&mut self, game_id: &str, alert: &Alert) {
         let mut alerts_map = self.alerts_map.lock().await;
 
         let alerts = alerts_map
             .entry(game_id.to_owned())
             .or_insert_with(HashSet::new);
 
-        alerts.insert(alert.to_owned());
+        // The task of the replaced alert would keep its delay and reminders
+        if let Some(replaced_alert) = alerts.replace(alert.to_owned())
+            && let Some(handle) = self.thread_handles_map.remove(&replaced_alert)
+        {
+            handle.abort();
+        }
+        // The player may already be playing, no event would tell
+        self.unchecked_game_ids.insert(game_id.to_owned());
 
         info!(
-            "registered alert for player {} for game {} for user {} with delay {} minutes",
-            alert.player_id, game_id, alert.user_id, alert.delay
+            "registered alert for player {} for game {} for user {} with delay {} minutes, repeat interval {:?} and max reminders {:?}",
+            alert.player_id,
+            game_id,
+            alert.user_id,
+            alert.delay,
+            alert.repeat_interval,
+            alert.max_reminders
         );
     }
 
@@ -407,20 +719,16 @@ impl AlertController {
     /// # Behavior
     ///
     /// - If the game ID doesn't exist: returns without error
-    /// - Otherwise: removes all alerts matching the room_id AND user_id
-    ///
-    /// # Note
-    ///
-    /// This does NOT abort pending notification tasks. Those should be cleaned up
-    /// by the next call to `update_alerts`.
+    /// - Otherwise: removes all alerts matching the room_id AND user_id, and aborts
+    ///   their pending notification tasks so that no notification is sent anymore
     ///
     /// # Examples
     ///
     /// ```no_run
     /// use miou::alerts::AlertController;
     ///
-    /// # async fn example() {
-    /// let controller = AlertController::new("alerts.json".to_string()).await;
+    /// # async fn example(settings_controller: std::sync::Arc<miou::settings::SettingsController>) {
+    /// let mut controller = AlertController::new("alerts.json".to_string(), settings_controller, 3).await;
     ///
     /// controller.remove_alerts(
     ///     "game_id",
@@ -429,7 +737,7 @@ impl AlertController {
     /// ).await;
     /// # }
     /// ```
-    pub async fn remove_alerts(&self, game_id: &str, room_id: &str, user_id: &str) {
+    pub async fn remove_alerts(&mut self, game_id: &str, room_id: &str, user_id: &str) {
         let mut alerts_map = self.alerts_map.lock().await;
 
         let alerts = match alerts_map.get_mut(game_id) {
@@ -438,7 +746,10 @@ impl AlertController {
         };
 
         // Remove all the user alerts in this room for this game
-        alerts.retain(|alert| alert.room_id != *room_id || alert.user_id != *user_id);
+        let removed_alerts: HashSet<Alert> = alerts
+            .extract_if(|alert| alert.room_id == *room_id && alert.user_id == *user_id)
+            .collect();
+        Self::abort_notifications(&mut self.thread_handles_map, &removed_alerts);
 
         info!(
             "unregistered alerts for user {} in room {} for game {}",
@@ -450,11 +761,20 @@ impl AlertController {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tmars::{Phase, Player};
+    use crate::{
+        settings::{QuietHours, UserSettings},
+        tmars::Phase,
+    };
     use std::sync::atomic::{AtomicUsize, Ordering};
     use tempfile::NamedTempFile;
     use tokio::time::{Duration, sleep};
 
+    async fn create_test_controller(path: String) -> AlertController {
+        // Settings are never persisted by these tests unless they are updated
+        let settings_controller = SettingsController::new(format!("{}.settings", path)).await;
+        AlertController::new(path, Arc::new(settings_controller), 1).await
+    }
+
     fn create_test_alert(
         room_id: &str,
         player_id: &str,
@@ -469,29 +789,24 @@ mod tests {
             notified,
             delay,
             player_url: format!("https://example.com/player?id={}", player_id),
+            turn_started_at: None,
+            notify_at: None,
+            repeat_interval: None,
+            max_reminders: None,
+            notifications_sent: 0,
+            phases: Vec::new(),
+            time_of_day: None,
         }
     }
 
     fn create_test_game(id: &str, waited_players: Vec<&str>) -> Game {
-        Game {
-            id: id.to_string(),
-            phase: Phase::Action,
-            spectator_id: "spectator123".to_string(),
-            players: vec![
-                Player {
-                    id: "player1".to_string(),
-                    color: "red".to_string(),
-                    name: "Player One".to_string(),
-                    url: "https://example.com/player1".to_string(),
-                },
-                Player {
-                    id: "player2".to_string(),
-                    color: "blue".to_string(),
-                    name: "Player Two".to_string(),
-                    url: "https://example.com/player2".to_string(),
-                },
-            ],
-            waited_players: waited_players.iter().map(|s| s.to_string()).collect(),
+        Game::test(id, Phase::Action, &waited_players)
+    }
+
+    fn turn_started(game_id: &str, player_id: &str) -> GameEvent {
+        GameEvent::TurnStarted {
+            game_id: game_id.to_string(),
+            player_id: player_id.to_string(),
         }
     }
 
@@ -500,7 +815,7 @@ mod tests {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
 
-        let controller = AlertController::new(path).await;
+        let controller = create_test_controller(path).await;
         let alerts_map = controller.get_alerts_map().await;
 
         assert!(alerts_map.is_empty());
@@ -512,7 +827,7 @@ mod tests {
         let path = temp_file.path().to_str().unwrap().to_string();
 
         // Create initial controller and add an alert
-        let controller1 = AlertController::new(path.clone()).await;
+        let mut controller1 = create_test_controller(path.clone()).await;
         let alert = create_test_alert(
             "!room1:example.com",
             "player1",
@@ -530,7 +845,7 @@ mod tests {
             .await;
 
         // Create new controller - should load the persisted alert
-        let controller2 = AlertController::new(path).await;
+        let controller2 = create_test_controller(path).await;
         let loaded_alerts = controller2.get_alerts_map().await;
 
         assert_eq!(loaded_alerts.len(), 1);
@@ -542,7 +857,7 @@ mod tests {
     async fn test_add_alert_creates_new_game_entry() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         let alert = create_test_alert(
             "!room1:example.com",
@@ -562,7 +877,7 @@ mod tests {
     async fn test_add_alert_adds_to_existing_game() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         let alert1 = create_test_alert(
             "!room1:example.com",
@@ -591,7 +906,7 @@ mod tests {
     async fn test_add_alert_replaces_duplicate() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         // Same room, player, and user - should be considered duplicate
         let alert1 = create_test_alert(
@@ -613,15 +928,95 @@ mod tests {
         controller.add_alert("game1", &alert2).await;
 
         let alerts_map = controller.get_alerts_map().await;
-        // HashSet doesn't replace on duplicate insert, so only one alert exists
-        assert_eq!(alerts_map.get("game1").unwrap().len(), 1);
+        let alerts = alerts_map.get("game1").unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts.iter().next().unwrap().delay, 120);
+    }
+
+    #[tokio::test]
+    async fn test_add_alert_aborts_task_of_replaced_alert() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        let alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            60,
+            false,
+        );
+        controller.add_alert("game1", &alert).await;
+
+        let mut games_map = HashMap::new();
+        games_map.insert(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player1"]),
+        );
+        controller.update_alerts(&games_map, &[], |_, _| {}).await;
+        assert!(controller.thread_handles_map.contains_key(&alert));
+
+        // Registering again with another delay replaces the waiting task
+        let counter = Arc::new(AtomicUsize::new(0));
+        let counter_clone = Arc::clone(&counter);
+        let new_alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            0,
+            false,
+        );
+        controller.add_alert("game1", &new_alert).await;
+        assert!(!controller.thread_handles_map.contains_key(&alert));
+
+        controller
+            .update_alerts(&games_map, &[], move |alert, _game| {
+                assert_eq!(alert.delay, 0);
+                counter_clone.fetch_add(1, Ordering::SeqCst);
+            })
+            .await;
+
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_namespace_game_ids() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        let alert1 = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            60,
+            false,
+        );
+        let alert2 = create_test_alert(
+            "!room1:example.com",
+            "player2",
+            "@user2:example.com",
+            60,
+            false,
+        );
+        controller.add_alert("game1", &alert1).await;
+        controller.add_alert("friends:game2", &alert2).await;
+
+        controller.namespace_game_ids("main").await;
+
+        let alerts_map = controller.get_alerts_map().await;
+        assert_eq!(alerts_map.len(), 2);
+        assert!(alerts_map.get("main:game1").unwrap().contains(&alert1));
+        assert!(alerts_map.get("friends:game2").unwrap().contains(&alert2));
     }
 
     #[tokio::test]
     async fn test_remove_alerts_removes_matching_alerts() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         let alert = create_test_alert(
             "!room1:example.com",
@@ -644,7 +1039,7 @@ mod tests {
     async fn test_remove_alerts_keeps_different_users() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         let alert1 = create_test_alert(
             "!room1:example.com",
@@ -679,7 +1074,7 @@ mod tests {
     async fn test_remove_alerts_keeps_different_rooms() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         let alert1 = create_test_alert(
             "!room1:example.com",
@@ -710,11 +1105,51 @@ mod tests {
         assert_eq!(remaining.room_id, "!room2:example.com");
     }
 
+    #[tokio::test]
+    async fn test_remove_alerts_aborts_pending_notification() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        // The notification is due in two seconds
+        let mut alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            60,
+            true,
+        );
+        alert.notify_at = Some(get_timestamp() + 2);
+        controller.add_alert("game1", &alert).await;
+
+        let games_map = HashMap::from([(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player1"]),
+        )]);
+        let counter = Arc::new(AtomicUsize::new(0));
+        let counter_clone = Arc::clone(&counter);
+        controller
+            .update_alerts(&games_map, &[], move |_, _| {
+                counter_clone.fetch_add(1, Ordering::SeqCst);
+            })
+            .await;
+        assert!(controller.thread_handles_map.contains_key(&alert));
+
+        controller
+            .remove_alerts("game1", "!room1:example.com", "@user1:example.com")
+            .await;
+        assert!(controller.thread_handles_map.is_empty());
+
+        sleep(Duration::from_millis(2500)).await;
+
+        assert_eq!(counter.load(Ordering::SeqCst), 0);
+    }
+
     #[tokio::test]
     async fn test_remove_alerts_nonexistent_game() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         // Should not panic
         controller
@@ -729,7 +1164,7 @@ mod tests {
     async fn test_clean_alerts_removes_nonexistent_games() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let mut controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         let alert1 = create_test_alert(
             "!room1:example.com",
@@ -746,26 +1181,34 @@ mod tests {
             false,
         );
 
-        controller.add_alert("game1", &alert1).await;
-        controller.add_alert("game2", &alert2).await;
+        controller.add_alert("main:game1", &alert1).await;
+        controller.add_alert("main:game2", &alert2).await;
 
+        let synced_servers = HashSet::from(["main".to_string()]);
         // Create games map with only game1
         let mut games_map = HashMap::new();
-        games_map.insert("game1".to_string(), create_test_game("game1", vec![]));
+        games_map.insert(
+            "main:game1".to_string(),
+            create_test_game("main:game1", vec![]),
+        );
+
+        // game2 is kept during the grace period of a poll
+        controller.clean_alerts(&games_map, &synced_servers).await;
+        assert!(controller.get_alerts_map().await.contains_key("main:game2"));
 
-        controller.clean_alerts(&games_map).await;
+        controller.clean_alerts(&games_map, &synced_servers).await;
 
         let alerts_map = controller.get_alerts_map().await;
         assert_eq!(alerts_map.len(), 1);
-        assert!(alerts_map.contains_key("game1"));
-        assert!(!alerts_map.contains_key("game2"));
+        assert!(alerts_map.contains_key("main:game1"));
+        assert!(!alerts_map.contains_key("main:game2"));
     }
 
     #[tokio::test]
-    async fn test_clean_alerts_keeps_existing_games() {
+    async fn test_clean_alerts_resets_missing_polls_of_reappearing_games() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let mut controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         let alert = create_test_alert(
             "!room1:example.com",
@@ -774,23 +1217,32 @@ mod tests {
             60,
             false,
         );
-        controller.add_alert("game1", &alert).await;
+        controller.add_alert("main:game1", &alert).await;
 
         let mut games_map = HashMap::new();
-        games_map.insert("game1".to_string(), create_test_game("game1", vec![]));
+        games_map.insert(
+            "main:game1".to_string(),
+            create_test_game("main:game1", vec![]),
+        );
 
-        controller.clean_alerts(&games_map).await;
+        let synced_servers = HashSet::from(["main".to_string()]);
+        // game1 is missing from every other poll
+        controller
+            .clean_alerts(&HashMap::new(), &synced_servers)
+            .await;
+        controller.clean_alerts(&games_map, &synced_servers).await;
+        controller
+            .clean_alerts(&HashMap::new(), &synced_servers)
+            .await;
 
-        let alerts_map = controller.get_alerts_map().await;
-        assert_eq!(alerts_map.len(), 1);
-        assert!(alerts_map.contains_key("game1"));
+        assert!(controller.get_alerts_map().await.contains_key("main:game1"));
     }
 
     #[tokio::test]
-    async fn test_get_alerts_to_fire_triggers_for_waited_player() {
+    async fn test_clean_alerts_keeps_games_of_servers_not_synced() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let mut controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         let alert = create_test_alert(
             "!room1:example.com",
@@ -799,106 +1251,119 @@ mod tests {
             60,
             false,
         );
-        controller.add_alert("game1", &alert).await;
+        controller.add_alert("friends:game1", &alert).await;
+
+        // The server of the game never answered, only the other one did
+        let synced_servers = HashSet::from(["main".to_string()]);
+        for _ in 0..3 {
+            controller
+                .clean_alerts(&HashMap::new(), &synced_servers)
+                .await;
+        }
 
-        let mut games_map = HashMap::new();
-        games_map.insert(
-            "game1".to_string(),
-            create_test_game("game1", vec!["player1"]),
+        assert!(
+            controller
+                .get_alerts_map()
+                .await
+                .contains_key("friends:game1")
         );
-
-        let alerts_to_fire = controller.get_alerts_to_fire(&games_map).await;
-
-        assert_eq!(alerts_to_fire.len(), 1);
-        assert_eq!(alerts_to_fire[0].0, "game1");
-        assert_eq!(alerts_to_fire[0].1.player_id, "player1");
     }
 
     #[tokio::test]
-    async fn test_get_alerts_to_fire_does_not_trigger_for_non_waited_player() {
+    async fn test_remove_ended_games_alerts() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let mut controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
-        let alert = create_test_alert(
+        let alert1 = create_test_alert(
             "!room1:example.com",
             "player1",
             "@user1:example.com",
             60,
             false,
         );
-        controller.add_alert("game1", &alert).await;
-
-        let mut games_map = HashMap::new();
-        games_map.insert(
-            "game1".to_string(),
-            create_test_game("game1", vec!["player2"]),
+        let alert2 = create_test_alert(
+            "!room1:example.com",
+            "player2",
+            "@user2:example.com",
+            60,
+            false,
         );
+        controller.add_alert("main:game1", &alert1).await;
+        controller.add_alert("main:game2", &alert2).await;
 
-        let alerts_to_fire = controller.get_alerts_to_fire(&games_map).await;
+        // No grace period for a game confirmed ended
+        controller
+            .remove_ended_games_alerts(&HashSet::from(["main:game2".to_string()]))
+            .await;
 
-        assert_eq!(alerts_to_fire.len(), 0);
+        let alerts_map = controller.get_alerts_map().await;
+        assert_eq!(alerts_map.len(), 1);
+        assert!(alerts_map.contains_key("main:game1"));
     }
 
     #[tokio::test]
-    async fn test_get_alerts_to_fire_does_not_trigger_already_notified() {
+    async fn test_clean_alerts_keeps_existing_games() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let mut controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         let alert = create_test_alert(
             "!room1:example.com",
             "player1",
             "@user1:example.com",
             60,
-            true,
+            false,
         );
-        controller.add_alert("game1", &alert).await;
+        controller.add_alert("main:game1", &alert).await;
 
         let mut games_map = HashMap::new();
         games_map.insert(
-            "game1".to_string(),
-            create_test_game("game1", vec!["player1"]),
+            "main:game1".to_string(),
+            create_test_game("main:game1", vec![]),
         );
+        let synced_servers = HashSet::from(["main".to_string()]);
 
-        let alerts_to_fire = controller.get_alerts_to_fire(&games_map).await;
+        controller.clean_alerts(&games_map, &synced_servers).await;
 
-        assert_eq!(alerts_to_fire.len(), 0);
+        let alerts_map = controller.get_alerts_map().await;
+        assert_eq!(alerts_map.len(), 1);
+        assert!(alerts_map.contains_key("main:game1"));
     }
 
     #[tokio::test]
-    async fn test_get_alerts_to_fire_resets_notified_when_turn_ends() {
+    async fn test_get_alerts_to_fire_triggers_for_waited_player() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let mut controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         let alert = create_test_alert(
             "!room1:example.com",
             "player1",
             "@user1:example.com",
             60,
-            true,
+            false,
         );
         controller.add_alert("game1", &alert).await;
 
         let mut games_map = HashMap::new();
         games_map.insert(
             "game1".to_string(),
-            create_test_game("game1", vec!["player2"]),
+            create_test_game("game1", vec!["player1"]),
         );
 
-        controller.get_alerts_to_fire(&games_map).await;
+        let alerts_to_fire = controller.get_alerts_to_fire(&games_map, &[]).await;
 
-        let alerts_map = controller.get_alerts_map().await;
-        let updated_alert = alerts_map.get("game1").unwrap().iter().next().unwrap();
-        assert!(!updated_alert.notified);
+        assert_eq!(alerts_to_fire.len(), 1);
+        assert_eq!(alerts_to_fire[0].0, "game1");
+        assert_eq!(alerts_to_fire[0].1.player_id, "player1");
     }
 
     #[tokio::test]
-    async fn test_get_alerts_to_fire_marks_as_notified() {
+    async fn test_get_alerts_to_fire_triggers_on_turn_started() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let mut controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         let alert = create_test_alert(
             "!room1:example.com",
@@ -909,13 +1374,181 @@ mod tests {
         );
         controller.add_alert("game1", &alert).await;
 
-        let mut games_map = HashMap::new();
-        games_map.insert(
+        let mut games_map = HashMap::from([(
             "game1".to_string(),
-            create_test_game("game1", vec!["player1"]),
+            create_test_game("game1", vec!["player2"]),
+        )]);
+        assert!(
+            controller
+                .get_alerts_to_fire(&games_map, &[])
+                .await
+                .is_empty()
         );
 
-        controller.get_alerts_to_fire(&games_map).await;
+        // The turn starts without any event: the game isn't checked again
+        games_map.insert(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player1"]),
+        );
+        assert!(
+            controller
+                .get_alerts_to_fire(&games_map, &[])
+                .await
+                .is_empty()
+        );
+
+        let alerts_to_fire = controller
+            .get_alerts_to_fire(&games_map, &[turn_started("game1", "player1")])
+            .await;
+
+        assert_eq!(alerts_to_fire.len(), 1);
+        assert_eq!(alerts_to_fire[0].1.player_id, "player1");
+    }
+
+    #[tokio::test]
+    async fn test_get_alerts_to_fire_respects_phases() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        let mut alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            60,
+            false,
+        );
+        alert.phases = vec![Phase::Action, Phase::Production];
+        controller.add_alert("game1", &alert).await;
+
+        // Everyone is waited for during the drafting
+        let mut game = create_test_game("game1", vec!["player1", "player2"]);
+        game.phase = Phase::Drafting;
+        let mut games_map = HashMap::from([("game1".to_string(), game)]);
+
+        assert!(
+            controller
+                .get_alerts_to_fire(&games_map, &[])
+                .await
+                .is_empty()
+        );
+
+        games_map.get_mut("game1").unwrap().phase = Phase::Action;
+        let game_events = vec![GameEvent::PhaseChanged {
+            game_id: "game1".to_string(),
+            previous_phase: Phase::Drafting,
+            phase: Phase::Action,
+        }];
+        let alerts_to_fire = controller
+            .get_alerts_to_fire(&games_map, &game_events)
+            .await;
+
+        assert_eq!(alerts_to_fire.len(), 1);
+        assert_eq!(alerts_to_fire[0].1.player_id, "player1");
+    }
+
+    #[tokio::test]
+    async fn test_get_alerts_to_fire_does_not_trigger_for_non_waited_player() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        let alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            60,
+            false,
+        );
+        controller.add_alert("game1", &alert).await;
+
+        let mut games_map = HashMap::new();
+        games_map.insert(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player2"]),
+        );
+
+        let alerts_to_fire = controller.get_alerts_to_fire(&games_map, &[]).await;
+
+        assert_eq!(alerts_to_fire.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_alerts_to_fire_does_not_trigger_already_notified() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        let alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            60,
+            true,
+        );
+        controller.add_alert("game1", &alert).await;
+
+        let mut games_map = HashMap::new();
+        games_map.insert(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player1"]),
+        );
+
+        let alerts_to_fire = controller.get_alerts_to_fire(&games_map, &[]).await;
+
+        assert_eq!(alerts_to_fire.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_alerts_to_fire_resets_notified_when_turn_ends() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        let alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            60,
+            true,
+        );
+        controller.add_alert("game1", &alert).await;
+
+        let mut games_map = HashMap::new();
+        games_map.insert(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player2"]),
+        );
+
+        controller.get_alerts_to_fire(&games_map, &[]).await;
+
+        let alerts_map = controller.get_alerts_map().await;
+        let updated_alert = alerts_map.get("game1").unwrap().iter().next().unwrap();
+        assert!(!updated_alert.notified);
+    }
+
+    #[tokio::test]
+    async fn test_get_alerts_to_fire_marks_as_notified() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        let alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            60,
+            false,
+        );
+        controller.add_alert("game1", &alert).await;
+
+        let mut games_map = HashMap::new();
+        games_map.insert(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player1"]),
+        );
+
+        controller.get_alerts_to_fire(&games_map, &[]).await;
 
         let alerts_map = controller.get_alerts_map().await;
         let updated_alert = alerts_map.get("game1").unwrap().iter().next().unwrap();
@@ -926,7 +1559,7 @@ mod tests {
     async fn test_update_alerts_fires_callback() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let mut controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         // Use a very short delay for testing (1 second)
         let alert = create_test_alert(
@@ -948,7 +1581,8 @@ mod tests {
         let counter_clone = Arc::clone(&counter);
 
         controller
-            .update_alerts(&games_map, move |_alert| {
+            .update_alerts(&games_map, &[], move |_alert, game_id| {
+                assert_eq!(game_id, "game1");
                 counter_clone.fetch_add(1, Ordering::SeqCst);
             })
             .await;
@@ -960,10 +1594,10 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_update_alerts_cleans_nonexistent_games() {
+    async fn test_update_alerts_keeps_missing_games() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let mut controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         let alert1 = create_test_alert(
             "!room1:example.com",
@@ -986,18 +1620,19 @@ mod tests {
         let mut games_map = HashMap::new();
         games_map.insert("game1".to_string(), create_test_game("game1", vec![]));
 
-        controller.update_alerts(&games_map, |_| {}).await;
+        // Only the polls clean the alerts
+        controller.update_alerts(&games_map, &[], |_, _| {}).await;
+        controller.update_alerts(&games_map, &[], |_, _| {}).await;
 
         let alerts_map = controller.get_alerts_map().await;
-        assert_eq!(alerts_map.len(), 1);
-        assert!(alerts_map.contains_key("game1"));
+        assert_eq!(alerts_map.len(), 2);
     }
 
     #[tokio::test]
     async fn test_fire_alert_spawns_delayed_task() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let mut controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         let alert = create_test_alert(
             "!room1:example.com",
@@ -1011,7 +1646,7 @@ mod tests {
         let counter = Arc::new(AtomicUsize::new(0));
         let counter_clone = Arc::clone(&counter);
 
-        controller.fire_alert(alerts_to_fire, move |_alert| {
+        controller.fire_alert(alerts_to_fire, move |_alert, _game| {
             counter_clone.fetch_add(1, Ordering::SeqCst);
         });
 
@@ -1028,7 +1663,7 @@ mod tests {
     async fn test_fire_alert_aborts_existing_task() {
         let temp_file = NamedTempFile::new().unwrap();
         let path = temp_file.path().to_str().unwrap().to_string();
-        let mut controller = AlertController::new(path).await;
+        let mut controller = create_test_controller(path).await;
 
         let alert = create_test_alert(
             "!room1:example.com",
@@ -1046,16 +1681,517 @@ mod tests {
         let counter_clone1 = Arc::clone(&counter);
         let counter_clone2 = Arc::clone(&counter);
 
-        controller.fire_alert(alerts_to_fire1, move |_alert| {
+        controller.fire_alert(alerts_to_fire1, move |_alert, _game| {
             counter_clone1.fetch_add(1, Ordering::SeqCst);
         });
 
         // Fire again immediately - should abort first task
-        controller.fire_alert(alerts_to_fire2, move |_alert| {
+        controller.fire_alert(alerts_to_fire2, move |_alert, _game| {
             counter_clone2.fetch_add(1, Ordering::SeqCst);
         });
 
         // Only one task handle should exist
         assert_eq!(controller.thread_handles_map.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_get_alerts_to_fire_records_schedule() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        let alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            60,
+            false,
+        );
+        controller.add_alert("game1", &alert).await;
+
+        let mut games_map = HashMap::new();
+        games_map.insert(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player1"]),
+        );
+
+        let alerts_to_fire = controller.get_alerts_to_fire(&games_map, &[]).await;
+
+        let alerts_map = controller.get_alerts_map().await;
+        let updated_alert = alerts_map.get("game1").unwrap().iter().next().unwrap();
+        let turn_started_at = updated_alert.turn_started_at.unwrap();
+        assert_eq!(updated_alert.notify_at, Some(turn_started_at + 60 * 60));
+        assert_eq!(alerts_to_fire[0].1.notify_at, updated_alert.notify_at);
+    }
+
+    #[tokio::test]
+    async fn test_get_alerts_to_fire_resets_schedule_when_turn_ends() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        let mut alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            60,
+            true,
+        );
+        alert.turn_started_at = Some(1_000);
+        alert.notify_at = Some(4_600);
+        controller.add_alert("game1", &alert).await;
+
+        let mut games_map = HashMap::new();
+        games_map.insert(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player2"]),
+        );
+
+        controller.get_alerts_to_fire(&games_map, &[]).await;
+
+        let alerts_map = controller.get_alerts_map().await;
+        let updated_alert = alerts_map.get("game1").unwrap().iter().next().unwrap();
+        assert!(!updated_alert.notified);
+        assert!(updated_alert.turn_started_at.is_none());
+        assert!(updated_alert.notify_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_alerts_to_fire_rearms_pending_notification() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        // Notified but still due: the bot stopped during the delay
+        let mut alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            60,
+            true,
+        );
+        alert.turn_started_at = Some(1_000);
+        alert.notify_at = Some(4_600);
+        controller.add_alert("game1", &alert).await;
+
+        let mut games_map = HashMap::new();
+        games_map.insert(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player1"]),
+        );
+
+        let alerts_to_fire = controller.get_alerts_to_fire(&games_map, &[]).await;
+
+        assert_eq!(alerts_to_fire.len(), 1);
+        // The original schedule is kept
+        assert_eq!(alerts_to_fire[0].1.turn_started_at, Some(1_000));
+        assert_eq!(alerts_to_fire[0].1.notify_at, Some(4_600));
+    }
+
+    #[tokio::test]
+    async fn test_get_alerts_to_fire_does_not_rearm_running_notification() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        let alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            60,
+            false,
+        );
+        controller.add_alert("game1", &alert).await;
+
+        let mut games_map = HashMap::new();
+        games_map.insert(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player1"]),
+        );
+
+        controller.update_alerts(&games_map, &[], |_, _| {}).await;
+        let alerts_to_fire = controller
+            .get_alerts_to_fire(&games_map, &[turn_started("game1", "player1")])
+            .await;
+
+        assert!(alerts_to_fire.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_alerts_fires_overdue_notification_after_restart() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+
+        // Persist an alert whose notification was due while the bot was stopped
+        let mut alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            60,
+            true,
+        );
+        let now = get_timestamp();
+        alert.turn_started_at = Some(now - 2 * 60 * 60);
+        alert.notify_at = Some(now - 60 * 60);
+
+        let alert_loader = AlertLoader::new(path.clone());
+        let mut alerts_map = HashMap::new();
+        alerts_map.insert("game1".to_string(), HashSet::from([alert]));
+        alert_loader.persist_alerts_map(&alerts_map).await;
+
+        let mut controller = create_test_controller(path).await;
+
+        let mut games_map = HashMap::new();
+        games_map.insert(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player1"]),
+        );
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        let counter_clone = Arc::clone(&counter);
+
+        // The first synchronization discovers the game
+        let game_events = vec![GameEvent::GameAdded {
+            game_id: "game1".to_string(),
+        }];
+        controller
+            .update_alerts(&games_map, &game_events, move |_alert, _game| {
+                counter_clone.fetch_add(1, Ordering::SeqCst);
+            })
+            .await;
+
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+        // The notification is not pending anymore
+        let alerts_map = controller.get_alerts_map().await;
+        let updated_alert = alerts_map.get("game1").unwrap().iter().next().unwrap();
+        assert!(updated_alert.notified);
+        assert!(updated_alert.notify_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_update_alerts_does_not_resume_sent_notification() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        // Notified and already sent before the restart
+        let mut alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            0,
+            true,
+        );
+        alert.turn_started_at = Some(get_timestamp());
+        controller.add_alert("game1", &alert).await;
+
+        let mut games_map = HashMap::new();
+        games_map.insert(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player1"]),
+        );
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        let counter_clone = Arc::clone(&counter);
+
+        controller
+            .update_alerts(&games_map, &[], move |_alert, _game| {
+                counter_clone.fetch_add(1, Ordering::SeqCst);
+            })
+            .await;
+
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(counter.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_fire_alert_persists_sent_notification() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path.clone()).await;
+
+        let mut alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            0,
+            true,
+        );
+        alert.notify_at = Some(get_timestamp());
+        controller.add_alert("game1", &alert).await;
+
+        controller.fire_alert(vec![("game1".to_string(), alert)], |_, _| {});
+
+        sleep(Duration::from_millis(100)).await;
+
+        // The sent notification is persisted without waiting for the persistence task,
+        // so it isn't resumed after a restart
+        let alerts_map = AlertLoader::new(path).load().await;
+        let persisted_alert = alerts_map.get("game1").unwrap().iter().next().unwrap();
+        assert_eq!(persisted_alert.notifications_sent, 1);
+        assert!(persisted_alert.notify_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fire_alert_schedules_next_reminder() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        let mut alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            0,
+            true,
+        );
+        alert.notify_at = Some(get_timestamp());
+        alert.repeat_interval = Some(30);
+        controller.add_alert("game1", &alert).await;
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        let counter_clone = Arc::clone(&counter);
+
+        controller.fire_alert(
+            vec![("game1".to_string(), alert.clone())],
+            move |alert, _game| {
+                assert!(!alert.is_reminder());
+                counter_clone.fetch_add(1, Ordering::SeqCst);
+            },
+        );
+
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+        // The next reminder is scheduled and saved
+        let alerts_map = controller.get_alerts_map().await;
+        let updated_alert = alerts_map.get("game1").unwrap().iter().next().unwrap();
+        assert_eq!(updated_alert.notifications_sent, 1);
+        assert!(updated_alert.notify_at.unwrap() >= get_timestamp() + 29 * 60);
+        assert!(
+            !controller
+                .thread_handles_map
+                .get(&alert)
+                .unwrap()
+                .is_finished()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fire_alert_stops_after_max_reminders() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        // First notification already sent, the only reminder is due
+        let mut alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            0,
+            true,
+        );
+        alert.notify_at = Some(get_timestamp());
+        alert.repeat_interval = Some(30);
+        alert.max_reminders = Some(1);
+        alert.notifications_sent = 1;
+        controller.add_alert("game1", &alert).await;
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        let counter_clone = Arc::clone(&counter);
+
+        controller.fire_alert(
+            vec![("game1".to_string(), alert.clone())],
+            move |alert, _game| {
+                assert!(alert.is_reminder());
+                counter_clone.fetch_add(1, Ordering::SeqCst);
+            },
+        );
+
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+        let alerts_map = controller.get_alerts_map().await;
+        let updated_alert = alerts_map.get("game1").unwrap().iter().next().unwrap();
+        assert_eq!(updated_alert.notifications_sent, 2);
+        assert!(updated_alert.notify_at.is_none());
+        assert!(
+            controller
+                .thread_handles_map
+                .get(&alert)
+                .unwrap()
+                .is_finished()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_alerts_stops_reminders_when_turn_ends() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let mut controller = create_test_controller(path).await;
+
+        let mut alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            0,
+            false,
+        );
+        alert.repeat_interval = Some(30);
+        controller.add_alert("game1", &alert).await;
+
+        let mut games_map = HashMap::new();
+        games_map.insert(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player1"]),
+        );
+        controller.update_alerts(&games_map, &[], |_, _| {}).await;
+
+        sleep(Duration::from_millis(100)).await;
+
+        // The reminder task is waiting for the next reminder
+        assert!(controller.thread_handles_map.contains_key(&alert));
+
+        // The player plays
+        games_map.insert(
+            "game1".to_string(),
+            create_test_game("game1", vec!["player2"]),
+        );
+        let game_events = vec![
+            GameEvent::TurnEnded {
+                game_id: "game1".to_string(),
+                player_id: "player1".to_string(),
+            },
+            turn_started("game1", "player2"),
+        ];
+        controller
+            .update_alerts(&games_map, &game_events, |_, _| {})
+            .await;
+
+        assert!(!controller.thread_handles_map.contains_key(&alert));
+
+        let alerts_map = controller.get_alerts_map().await;
+        let updated_alert = alerts_map.get("game1").unwrap().iter().next().unwrap();
+        assert!(!updated_alert.notified);
+        assert_eq!(updated_alert.notifications_sent, 0);
+        assert!(updated_alert.notify_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fire_alert_defers_notification_during_quiet_hours() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let settings_file = NamedTempFile::new().unwrap();
+        let settings_path = settings_file.path().to_str().unwrap().to_string();
+
+        // Quiet hours from one hour ago to one hour from now
+        let now = chrono::Utc::now().time();
+        let window = format!(
+            "{}-{}",
+            (now - chrono::TimeDelta::hours(1)).format("%H:%M"),
+            (now + chrono::TimeDelta::hours(1)).format("%H:%M")
+        );
+        let settings_controller = Arc::new(SettingsController::new(settings_path).await);
+        settings_controller
+            .update_user_settings(
+                "@user1:example.com",
+                &UserSettings {
+                    quiet_hours: QuietHours::parse(&window, "UTC").ok(),
+                },
+            )
+            .await;
+
+        let mut controller = AlertController::new(path, settings_controller, 1).await;
+
+        let mut alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            0,
+            true,
+        );
+        alert.notify_at = Some(get_timestamp());
+        controller.add_alert("game1", &alert).await;
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        let counter_clone = Arc::clone(&counter);
+
+        controller.fire_alert(
+            vec![("game1".to_string(), alert.clone())],
+            move |_alert, _game| {
+                counter_clone.fetch_add(1, Ordering::SeqCst);
+            },
+        );
+
+        sleep(Duration::from_millis(100)).await;
+
+        // The notification is not sent but deferred to the end of the quiet hours
+        assert_eq!(counter.load(Ordering::SeqCst), 0);
+
+        let alerts_map = controller.get_alerts_map().await;
+        let updated_alert = alerts_map.get("game1").unwrap().iter().next().unwrap();
+        let notify_at = updated_alert.notify_at.unwrap();
+        assert!(notify_at > get_timestamp() + 58 * 60);
+        assert!(notify_at <= get_timestamp() + 60 * 60);
+        assert_eq!(updated_alert.notifications_sent, 0);
+        assert!(
+            !controller
+                .thread_handles_map
+                .get(&alert)
+                .unwrap()
+                .is_finished()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fire_alert_sends_notification_outside_quiet_hours() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let settings_file = NamedTempFile::new().unwrap();
+        let settings_path = settings_file.path().to_str().unwrap().to_string();
+
+        // Quiet hours starting in one hour
+        let now = chrono::Utc::now().time();
+        let window = format!(
+            "{}-{}",
+            (now + chrono::TimeDelta::hours(1)).format("%H:%M"),
+            (now + chrono::TimeDelta::hours(2)).format("%H:%M")
+        );
+        let settings_controller = Arc::new(SettingsController::new(settings_path).await);
+        settings_controller
+            .update_user_settings(
+                "@user1:example.com",
+                &UserSettings {
+                    quiet_hours: QuietHours::parse(&window, "UTC").ok(),
+                },
+            )
+            .await;
+
+        let mut controller = AlertController::new(path, settings_controller, 1).await;
+
+        let alert = create_test_alert(
+            "!room1:example.com",
+            "player1",
+            "@user1:example.com",
+            0,
+            false,
+        );
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        let counter_clone = Arc::clone(&counter);
+
+        controller.fire_alert(vec![("game1".to_string(), alert)], move |_alert, _game| {
+            counter_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
 }
@@ -162,6 +162,13 @@ mod tests {
             notified: false,
             delay: 60,
             player_url: format!("https://example.com/player?id={}", player_id),
+            turn_started_at: None,
+            notify_at: None,
+            repeat_interval: None,
+            max_reminders: None,
+            notifications_sent: 0,
+            phases: Vec::new(),
+            time_of_day: None,
         }
     }
 
@@ -227,6 +234,13 @@ mod tests {
             notified: true,
             delay: 120,
             player_url: "https://example.com/player?id=player1".to_string(),
+            turn_started_at: Some(1_000),
+            notify_at: Some(8_200),
+            repeat_interval: None,
+            max_reminders: None,
+            notifications_sent: 0,
+            phases: Vec::new(),
+            time_of_day: None,
         };
         alerts.insert(alert);
         alerts_map.insert("game1".to_string(), alerts);
@@ -245,6 +259,35 @@ mod tests {
             loaded_alert.player_url,
             "https://example.com/player?id=player1"
         );
+        assert_eq!(loaded_alert.turn_started_at, Some(1_000));
+        assert_eq!(loaded_alert.notify_at, Some(8_200));
+    }
+
+    #[tokio::test]
+    async fn test_load_alerts_without_schedule() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+
+        // Alerts persisted before the scheduling timestamps were introduced
+        let json = r#"{
+            "game1": [{
+                "room_id": "!room1:example.com",
+                "player_id": "player1",
+                "user_id": "@user1:example.com",
+                "notified": true,
+                "delay": 60,
+                "player_url": "https://example.com/player?id=player1"
+            }]
+        }"#;
+        fs::write(&path, json).await.unwrap();
+
+        let loader = AlertLoader::new(path);
+        let loaded_map = loader.load().await;
+        let loaded_alert = loaded_map.get("game1").unwrap().iter().next().unwrap();
+
+        assert!(loaded_alert.notified);
+        assert!(loaded_alert.turn_started_at.is_none());
+        assert!(loaded_alert.notify_at.is_none());
     }
 
     #[tokio::test]
@@ -1,9 +1,10 @@
 //! Alert management system for Terraforming Mars game notifications.
 //!
 //! This module provides a complete alert system that notifies Matrix users when it's
-//! their turn in a Terraforming Mars game. The system consists of three main components:
+//! their turn in a Terraforming Mars game. The system consists of these components:
 //!
 //! - [`Alert`]: Represents a single user notification preference
+//! - [`TimeOfDay`]: Time of the notifications of the alerts notifying at a given time
 //! - [`AlertController`]: Manages alert lifecycle, notification scheduling, and persistence
 //! - [`AlertLoader`]: Handles loading and saving alerts to disk
 //!
@@ -17,11 +18,13 @@
 //!
 //! ```no_run
 //! use miou::alerts::{Alert, AlertController};
-//! use std::collections::HashMap;
+//! use miou::settings::SettingsController;
+//! use std::sync::Arc;
 //!
 //! # async fn example() {
 //! // Initialize the alert controller
-//! let mut controller = AlertController::new("alerts.json".to_string()).await;
+//! let settings_controller = Arc::new(SettingsController::new("settings.json".to_string()).await);
+//! let mut controller = AlertController::new("alerts.json".to_string(), settings_controller, 3).await;
 //!
 //! // Start periodic persistence
 //! controller.start_persistence_task();
@@ -34,6 +37,13 @@
 //!     notified: false,
 //!     delay: 60,
 //!     player_url: "https://example.com/player?id=player123".to_string(),
+//!     turn_started_at: None,
+//!     notify_at: None,
+//!     repeat_interval: None,
+//!     max_reminders: None,
+//!     notifications_sent: 0,
+//!     phases: Vec::new(),
+//!     time_of_day: None,
 //! };
 //! controller.add_alert("game_id", &alert).await;
 //!
@@ -45,6 +55,7 @@
 mod alert;
 mod alert_controller;
 mod alert_loader;
+mod time_of_day;
 
 pub use crate::alerts::alert_loader::AlertLoader;
-pub use crate::alerts::{alert::Alert, alert_controller::AlertController};
+pub use crate::alerts::{alert::Alert, alert_controller::AlertController, time_of_day::TimeOfDay};
@@ -0,0 +1,107 @@
+//! Time of day of a notification.
+//!
+//! This module provides the [`TimeOfDay`] struct for the alerts notifying at a
+//! given time, like `@09:00`, instead of after a delay.
+
+use crate::utils::resolve_local;
+use chrono::{DateTime, NaiveTime};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// Local time of day in a timezone.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::alerts::TimeOfDay;
+/// # use chrono::NaiveTime;
+/// let time_of_day = TimeOfDay {
+///     time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+///     timezone: chrono_tz::UTC,
+/// };
+///
+/// // 1970-01-01 08:00 UTC is followed by 09:00 the same day
+/// assert_eq!(time_of_day.get_next_after(8 * 60 * 60), Some(9 * 60 * 60));
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimeOfDay {
+    /// Local time.
+    pub time: NaiveTime,
+    /// Timezone of the local time.
+    pub timezone: Tz,
+}
+
+impl TimeOfDay {
+    /// Returns the next occurrence of the time of day.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - Unix timestamp (in seconds) after which the time is looked for
+    ///
+    /// # Returns
+    ///
+    /// The Unix timestamp (in seconds) of the first occurrence of the time of day
+    /// after `timestamp`, the next day if it has already passed on the day of
+    /// `timestamp`. A time skipped when the clocks move forward occurs an hour
+    /// later.
+    pub fn get_next_after(&self, timestamp: u64) -> Option<u64> {
+        let local = DateTime::from_timestamp(timestamp as i64, 0)?.with_timezone(&self.timezone);
+        let date = match local.time() < self.time {
+            true => local.date_naive(),
+            false => local.date_naive().succ_opt()?,
+        };
+
+        resolve_local(self.timezone, date.and_time(self.time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::get_local_timestamp;
+
+    fn create_time_of_day(hour: u32, timezone: Tz) -> TimeOfDay {
+        TimeOfDay {
+            time: NaiveTime::from_hms_opt(hour, 0, 0).unwrap(),
+            timezone,
+        }
+    }
+
+    #[test]
+    fn test_get_next_after_same_day() {
+        let paris = chrono_tz::Europe::Paris;
+        let time_of_day = create_time_of_day(9, paris);
+
+        assert_eq!(
+            time_of_day.get_next_after(get_local_timestamp(paris, "2024-03-10 07:30")),
+            Some(get_local_timestamp(paris, "2024-03-10 09:00"))
+        );
+    }
+
+    #[test]
+    fn test_get_next_after_next_day() {
+        let paris = chrono_tz::Europe::Paris;
+        let time_of_day = create_time_of_day(9, paris);
+
+        assert_eq!(
+            time_of_day.get_next_after(get_local_timestamp(paris, "2024-03-10 09:00")),
+            Some(get_local_timestamp(paris, "2024-03-11 09:00"))
+        );
+        assert_eq!(
+            time_of_day.get_next_after(get_local_timestamp(paris, "2024-03-10 22:00")),
+            Some(get_local_timestamp(paris, "2024-03-11 09:00"))
+        );
+    }
+
+    #[test]
+    fn test_get_next_after_skipped_time() {
+        let paris = chrono_tz::Europe::Paris;
+        // 02:00 doesn't exist on the day the clocks move forward in Paris
+        let time_of_day = create_time_of_day(2, paris);
+
+        assert_eq!(
+            time_of_day.get_next_after(get_local_timestamp(paris, "2024-03-30 22:00")),
+            Some(get_local_timestamp(paris, "2024-03-31 03:00"))
+        );
+    }
+}
@@ -15,9 +15,11 @@
 //!
 //! The bot operates with three main concurrent tasks:
 //!
-//! 1. **TMars Sync Task**: Periodically polls the TMars game server for state updates,
-//!    checks registered alerts against current game state, and triggers notifications
-//!    when players' turns arrive.
+//! 1. **TMars Sync Task**: Periodically polls the TMars game servers for state updates,
+//!    checks registered alerts against current game state, triggers notifications
+//!    when players' turns arrive, and announces turn changes in rooms watching a game.
+//!    When a game ends, its final scores are announced in the rooms with alerts or
+//!    watches on it. The turns of the players are recorded for the statistics.
 //!
 //! 2. **Matrix Sync Task**: Continuously listens for Matrix messages, parses user
 //!    commands, executes them, and sends responses back to Matrix rooms.
@@ -35,7 +37,13 @@
 //!
 //! - `register` - Register an alert for a player in a game
 //! - `unregister` - Remove alerts for a game
+//! - `watch` - Announce the turn changes of a game in a room
+//! - `unwatch` - Stop announcing the turn changes of a game in a room
 //! - `list` - List all registered alerts
+//! - `game` - Show the details of a game
+//! - `settings` - Display or update user preferences like quiet hours
+//! - `server` - Display or update the server of the game IDs in a room
+//! - `stats` - Display the turn times of the players
 //! - `help` - Display help information
 //!
 //! # Example
@@ -47,28 +55,36 @@
 //! # async fn run() -> Result<(), anyhow::Error> {
 //! let config = Config::load("config.yaml")?;
 //! let args = Args::parse();
+//! let avatar_bytes = include_bytes!("../assets/miou.png");
 //!
 //! // Create and start the bot
-//! let bot = Bot::new(config, args).await?;
+//! let bot = Bot::new(config, args, avatar_bytes).await?;
 //! bot.start().await; // Runs indefinitely
 //! # Ok(())
 //! # }
 //! ```
 
-use log::info;
-use tokio::time;
+use log::{error, info, warn};
+use tokio::{sync::broadcast, time};
 
 use crate::{
     Args,
     alerts::{Alert, AlertController},
     commands::{CommandContext, CommandParseError, Commander},
     config::Config,
+    history::HistoryController,
     matrix::{MatrixClient, UserCredentials},
-    tmars::{TMarsRequester, TMarsSync},
-    utils::get_path,
+    settings::SettingsController,
+    tmars::{Game, GameEvent, ServerHealth, TMarsRequester, TMarsSync},
+    utils::{get_path, get_timestamp},
+    watches::WatchController,
 };
-use std::{sync::Arc, time::Duration};
-use tokio::sync::Mutex;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::{Mutex, RwLock};
 
 /// Context for processing a Matrix message.
 ///
@@ -83,12 +99,26 @@ struct MessageContext {
     sender_id: String,
     /// The Matrix event ID of the message
     event_id: String,
+    /// Whether the message is sent in a direct chat with the bot
+    is_direct: bool,
+    /// Text of the mention of the bot starting the message
+    mention: Option<String>,
     /// Thread-safe reference to the Matrix client
     matrix_client: Arc<MatrixClient>,
-    /// Thread-safe reference to the TMars sync service
-    tmars_sync: Arc<Mutex<TMarsSync<TMarsRequester>>>,
+    /// Thread-safe reference to the games of every server, as of the last sync
+    games: Arc<RwLock<HashMap<String, Game>>>,
+    /// Server of the game IDs without a server name in the rooms which didn't choose one
+    default_server: String,
+    /// Names of all the configured servers
+    servers: Vec<String>,
     /// Thread-safe reference to the alert controller for managing notifications
     alert_controller: Arc<Mutex<AlertController>>,
+    /// Thread-safe reference to the settings controller for managing user preferences
+    settings_controller: Arc<SettingsController>,
+    /// Thread-safe reference to the watch controller for managing room watches
+    watch_controller: Arc<WatchController>,
+    /// Thread-safe reference to the history controller for the turn statistics
+    history_controller: Arc<HistoryController>,
     /// Thread-safe reference to the command handler
     commander: Arc<Commander>,
 }
@@ -98,7 +128,7 @@ struct MessageContext {
 /// The `Bot` orchestrates the complete lifecycle of the Miou bot, managing three primary
 /// responsibilities:
 ///
-/// 1. **Game Synchronization** - Periodically polls the TMars server for game state updates
+/// 1. **Game Synchronization** - Periodically polls the TMars servers for game state updates
 /// 2. **Message Processing** - Listens to Matrix rooms and processes user commands
 /// 3. **Alert Management** - Tracks user notification preferences and triggers delayed notifications
 ///
@@ -107,8 +137,9 @@ struct MessageContext {
 /// The bot operates with three concurrent async tasks:
 ///
 /// - **TMars Sync Task**: Runs on a timer (configured by `polling_interval`), fetching
-///   game states from the TMars server and updating the alert controller with current
-///   game state to trigger notifications
+///   game states from every TMars server and updating the alert controller with current
+///   game state to trigger notifications. In between, it checks which games changed
+///   every `check_interval` if configured
 /// - **Matrix Sync Task**: Continuously listens for Matrix messages, parses commands,
 ///   executes them, and sends responses back to users
 /// - **Alert Persistence Task**: Runs on a timer, periodically saving the alerts map
@@ -125,40 +156,52 @@ struct MessageContext {
 ///
 /// # Thread Safety
 ///
-/// All shared state (`matrix_client`, `tmars_sync`, `alert_controller`, `commander`) is
+/// All shared state (`matrix_client`, `games`, `alert_controller`, `commander`) is
 /// wrapped in `Arc` for safe sharing across async tasks. Mutable state uses `Mutex` for
 /// interior mutability, ensuring thread-safe concurrent access.
 ///
+/// The TMars synchronization services are owned by the TMars sync task, which
+/// publishes the games in `games` after each sync. The commands and notifications
+/// read them there, without waiting for the requests to the servers.
+///
 /// # Examples
 ///
 /// ```no_run
 /// # use miou::bot::Bot;
-/// # use miou::config::{Config, TMars, Matrix};
-/// # #[derive(Debug)]
-/// # struct Args {
-/// #     config: String,
-/// #     session_path: String,
-/// # }
+/// # use miou::config::{Config, Matrix, TMars, TMarsRequests, TMarsServer};
+/// # use std::collections::BTreeMap;
+/// # use miou::Args;
 /// # async fn example() -> Result<(), anyhow::Error> {
 /// let config = Config {
 ///     tmars: TMars {
-///         url: "https://tmars.example.com".to_string(),
-///         server_id: "server1".to_string(),
 ///         polling_interval: 120,
+///         check_interval: None,
+///         requests: TMarsRequests::default(),
+///         alerts_grace_polls: None,
+///         default_server: None,
+///         servers: BTreeMap::from([(
+///             "main".to_string(),
+///             TMarsServer {
+///                 url: "https://tmars.example.com".to_string(),
+///                 server_id: "server1".to_string(),
+///             },
+///         )]),
 ///     },
 ///     matrix: Matrix {
 ///         user_id: "@bot:example.com".to_string(),
 ///         password: "secret".to_string(),
 ///         passphrase: "passphrase".to_string(),
+///         command_prefix: None,
 ///     },
 /// };
 ///
 /// let args = Args {
 ///     config: "config.yaml".to_string(),
-///     session_path: "./session".to_string(),
+///     data: "./data".to_string(),
 /// };
+/// let avatar_bytes = include_bytes!("../assets/miou.png");
 ///
-/// let bot = Bot::new(config, args).await?;
+/// let bot = Bot::new(config, args, avatar_bytes).await?;
 /// bot.start().await; // Runs indefinitely
 /// # Ok(())
 /// # }
@@ -172,17 +215,35 @@ pub struct Bot {
     /// - Sending turn notifications
     matrix_client: Arc<MatrixClient>,
 
-    /// TMars synchronization service for fetching game states.
+    /// TMars synchronization services for fetching game states, one per server.
     ///
-    /// Wrapped in `Mutex` because it maintains internal state (last fetched games)
-    /// that is updated during each sync operation.
-    tmars_sync: Arc<Mutex<TMarsSync<TMarsRequester>>>,
+    /// Moved into the TMars sync task when the bot starts, which is the only one
+    /// updating them.
+    tmars_syncs: Vec<TMarsSync<TMarsRequester>>,
+
+    /// Games of all the servers, as of the last sync or check.
+    ///
+    /// Written by the TMars sync task once all the servers are synchronized, and
+    /// read by the commands and the notifications.
+    games: Arc<RwLock<HashMap<String, Game>>>,
+
+    /// Server of the game IDs without a server name in the rooms which didn't
+    /// choose one.
+    default_server: String,
+
+    /// Names of all the configured servers.
+    servers: Vec<String>,
 
-    /// Polling interval in seconds for syncing with the TMars server.
+    /// Polling interval in seconds for syncing with the TMars servers.
     ///
     /// Determines how frequently the bot checks for game state updates.
     polling_interval: u64,
 
+    /// Interval in seconds of the checks for changed games between two syncs.
+    ///
+    /// Checks are disabled when `None`.
+    check_interval: Option<u64>,
+
     /// Alert controller for managing game notifications.
     ///
     /// Wrapped in `Mutex` because it maintains internal state including:
@@ -193,6 +254,24 @@ pub struct Bot {
     /// These are updated during alert registration/removal and when notifications fire.
     alert_controller: Arc<Mutex<AlertController>>,
 
+    /// Settings controller for managing user preferences.
+    ///
+    /// Shared with the alert controller, which defers notifications falling into
+    /// the quiet hours of a user. Uses internal locking, so no `Mutex` is needed.
+    settings_controller: Arc<SettingsController>,
+
+    /// Watch controller for announcing turn changes in rooms.
+    ///
+    /// Keeps the games seen during the previous sync to detect changes. Uses
+    /// internal locking, so no `Mutex` is needed.
+    watch_controller: Arc<WatchController>,
+
+    /// History controller for recording the turns of the players.
+    ///
+    /// Keeps the ongoing turns to detect when they end. Uses internal locking,
+    /// so no `Mutex` is needed.
+    history_controller: Arc<HistoryController>,
+
     /// Command parser and executor.
     ///
     /// Handles parsing Matrix messages into structured commands and routing
@@ -204,21 +283,28 @@ impl Bot {
     /// Creates a new Bot instance from configuration and command line arguments.
     ///
     /// This constructor initializes all bot components including the Matrix client,
-    /// TMars requester, and command parser. It performs the Matrix login if no valid
-    /// session exists, or restores the previous session if available.
+    /// a TMars requester per server, and command parser. It performs the Matrix login
+    /// if no valid session exists, or restores the previous session if available.
+    ///
+    /// Alerts and watches persisted before the support of multiple servers refer to
+    /// games by their bare IDs, they are moved to the games of the default server.
     ///
     /// # Arguments
     ///
     /// * `config` - YAML configuration loaded from file containing:
-    ///   - `tmars.url`: Base URL of the TMars server
-    ///   - `tmars.server_id`: TMars server identifier
     ///   - `tmars.polling_interval`: Seconds between TMars sync operations
+    ///   - `tmars.check_interval`: Seconds between checks for changed games, if any
+    ///   - `tmars.alerts_grace_polls`: Polls a game can be missing before its alerts, watches and ongoing turns are removed
+    ///   - `tmars.default_server`: Server of the rooms which didn't choose one
+    ///   - `tmars.servers`: URL and identifier of each TMars server, by name
     ///   - `matrix.user_id`: Matrix bot account ID (e.g., `@bot:example.com`)
     ///   - `matrix.password`: Matrix account password
     ///   - `matrix.passphrase`: E2EE recovery passphrase
+    ///   - `matrix.command_prefix`: Prefix of the commands in the rooms which didn't choose one
     ///
     /// * `args` - Command line arguments containing:
-    ///   - `data_path`: Directory path for storing Matrix session data and SQLite database
+    ///   - `data`: Directory path for storing Matrix session data and SQLite database
+    /// * `avatar_bytes` - PNG image data set as the bot's avatar if it has none
     ///
     /// # Returns
     ///
@@ -236,27 +322,39 @@ impl Bot {
     ///
     /// ```no_run
     /// # use miou::bot::Bot;
-    /// # use miou::config::{Config, TMars, Matrix};
+    /// # use miou::config::{Config, Matrix, TMars, TMarsRequests, TMarsServer};
+    /// # use std::collections::BTreeMap;
     /// # use miou::Args;
     /// # async fn example() -> Result<(), anyhow::Error> {
     /// # let args = Args {
     /// #     config: "config.yaml".to_string(),
-    /// #     data_path: "./data".to_string(),
+    /// #     data: "./data".to_string(),
     /// # };
+    /// # let avatar_bytes = include_bytes!("../assets/miou.png");
     /// let config = Config {
     ///     tmars: TMars {
-    ///         url: "https://tmars.example.com".to_string(),
-    ///         server_id: "server1".to_string(),
     ///         polling_interval: 120,
+    ///         check_interval: None,
+    ///         requests: TMarsRequests::default(),
+    ///         alerts_grace_polls: None,
+    ///         default_server: None,
+    ///         servers: BTreeMap::from([(
+    ///             "main".to_string(),
+    ///             TMarsServer {
+    ///                 url: "https://tmars.example.com".to_string(),
+    ///                 server_id: "server1".to_string(),
+    ///             },
+    ///         )]),
     ///     },
     ///     matrix: Matrix {
     ///         user_id: "@bot:example.com".to_string(),
     ///         password: "secret".to_string(),
     ///         passphrase: "passphrase".to_string(),
+    ///         command_prefix: None,
     ///     },
     /// };
     ///
-    /// let bot = Bot::new(config, args).await?;
+    /// let bot = Bot::new(config, args, avatar_bytes).await?;
     /// # Ok(())
     /// # }
     /// ```
@@ -265,9 +363,25 @@ impl Bot {
         args: Args,
         avatar_bytes: &[u8],
     ) -> Result<Self, anyhow::Error> {
-        // Create tmars services
-        let tmars_requester = TMarsRequester::new(&config.tmars.url, &config.tmars.server_id);
-        let tmars_sync = Arc::new(Mutex::new(TMarsSync::new(tmars_requester)));
+        // Create tmars services, one per server
+        let default_server = config.tmars.get_default_server().to_owned();
+        let servers: Vec<String> = config.tmars.servers.keys().cloned().collect();
+        let tmars_syncs = config
+            .tmars
+            .servers
+            .iter()
+            .map(|(server_name, server)| {
+                let tmars_requester =
+                    TMarsRequester::new(&server.url, &server.server_id, &config.tmars.requests);
+                TMarsSync::new(server_name, tmars_requester)
+            })
+            .collect();
+
+        // Create the commander before the matrix credentials are moved to the client
+        let commander = Arc::new(Commander::new(
+            config.matrix.get_command_prefix().to_owned(),
+            config.matrix.user_id.clone(),
+        ));
 
         // Create matrix client
         let matrix_client = Arc::new(
@@ -283,17 +397,54 @@ impl Bot {
             .await?,
         );
 
+        let settings_controller =
+            Arc::new(SettingsController::new(get_path(&args.data, "settings")).await);
+
         let alert_controller = Arc::new(Mutex::new(
-            AlertController::new(get_path(&args.data, "alerts")).await,
+            AlertController::new(
+                get_path(&args.data, "alerts"),
+                Arc::clone(&settings_controller),
+                config.tmars.get_alerts_grace_polls(),
+            )
+            .await,
         ));
 
-        let commander = Arc::new(Commander::new());
+        let watch_controller = Arc::new(
+            WatchController::new(
+                get_path(&args.data, "watches"),
+                config.tmars.get_alerts_grace_polls(),
+            )
+            .await,
+        );
+
+        // Alerts and watches persisted with a single server refer to its games
+        alert_controller
+            .lock()
+            .await
+            .namespace_game_ids(&default_server)
+            .await;
+        watch_controller.namespace_game_ids(&default_server).await;
+
+        let history_controller = Arc::new(
+            HistoryController::new(
+                get_path(&args.data, "history"),
+                config.tmars.get_alerts_grace_polls(),
+            )
+            .await,
+        );
 
         Ok(Bot {
             matrix_client,
-            tmars_sync,
+            tmars_syncs,
+            games: Arc::new(RwLock::new(HashMap::new())),
+            default_server,
+            servers,
             polling_interval: config.tmars.polling_interval,
+            check_interval: config.tmars.check_interval,
             alert_controller,
+            settings_controller,
+            watch_controller,
+            history_controller,
             commander,
         })
     }
@@ -305,7 +456,8 @@ impl Bot {
     /// 1. **TMars Sync Task** (background):
     ///    - Runs every `polling_interval` seconds
     ///    - Fetches current game states from TMars server
-    ///    - Cleans up alerts for ended games
+    ///    - Checks which games changed every `check_interval` seconds in between
+    ///    - Cleans up alerts, watches and ongoing turns for ended games, and for missing games after a grace period
     ///    - Identifies which players need notifications
     ///    - Spawns delayed notification tasks
     ///
@@ -329,33 +481,45 @@ impl Bot {
     ///
     /// ```no_run
     /// # use miou::bot::Bot;
-    /// # use miou::config::{Config, TMars, Matrix};
+    /// # use miou::config::{Config, Matrix, TMars, TMarsRequests, TMarsServer};
+    /// # use std::collections::BTreeMap;
     /// # use miou::Args;
     /// # async fn example() -> Result<(), anyhow::Error> {
     /// # let config = Config {
     /// #     tmars: TMars {
-    /// #         url: "https://tmars.example.com".to_string(),
-    /// #         server_id: "server1".to_string(),
     /// #         polling_interval: 120,
+    /// #         check_interval: None,
+    /// #         requests: TMarsRequests::default(),
+    /// #         alerts_grace_polls: None,
+    /// #         default_server: None,
+    /// #         servers: BTreeMap::from([(
+    /// #             "main".to_string(),
+    /// #             TMarsServer {
+    /// #                 url: "https://tmars.example.com".to_string(),
+    /// #                 server_id: "server1".to_string(),
+    /// #             },
+    /// #         )]),
     /// #     },
     /// #     matrix: Matrix {
     /// #         user_id: "@bot:example.com".to_string(),
     /// #         password: "secret".to_string(),
     /// #         passphrase: "passphrase".to_string(),
+    /// #         command_prefix: None,
     /// #     },
     /// # };
     /// # let args = Args {
     /// #     config: "config.yaml".to_string(),
-    /// #     data_path: "./data".to_string(),
+    /// #     data: "./data".to_string(),
     /// # };
-    /// let bot = Bot::new(config, args).await?;
+    /// # let avatar_bytes = include_bytes!("../assets/miou.png");
+    /// let bot = Bot::new(config, args, avatar_bytes).await?;
     /// bot.start().await; // Runs until process termination
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn start(self) {
+    pub async fn start(mut self) {
         let matrix_client_for_spawn = Arc::clone(&self.matrix_client);
-        let tmars_sync = Arc::clone(&self.tmars_sync);
+        let tmars_syncs = std::mem::take(&mut self.tmars_syncs);
         let alert_controller = Arc::clone(&self.alert_controller);
         let polling_interval = self.polling_interval;
 
@@ -364,31 +528,49 @@ impl Bot {
         // Start tmars sync in a separate task to not block matrix sync
         self.start_tmars_sync_task(
             matrix_client_for_spawn,
-            tmars_sync,
+            tmars_syncs,
             Arc::clone(&alert_controller),
+            Arc::clone(&self.watch_controller),
+            Arc::clone(&self.history_controller),
             polling_interval,
         );
 
         // Clone references for the message handler
         let matrix_client_for_handler = Arc::clone(&self.matrix_client);
-        let tmars_sync_ref = Arc::clone(&self.tmars_sync);
+        let games = Arc::clone(&self.games);
+        let default_server = self.default_server.clone();
+        let servers = self.servers.clone();
         let commander = Arc::clone(&self.commander);
+        let settings_controller = Arc::clone(&self.settings_controller);
+        let watch_controller = Arc::clone(&self.watch_controller);
+        let history_controller = Arc::clone(&self.history_controller);
 
         // Create message handler closure
-        let on_message =
-            move |body: String, room_id: String, sender_id: String, event_id: String| {
-                let ctx = MessageContext {
-                    body,
-                    room_id,
-                    sender_id,
-                    event_id,
-                    matrix_client: Arc::clone(&matrix_client_for_handler),
-                    tmars_sync: Arc::clone(&tmars_sync_ref),
-                    commander: Arc::clone(&commander),
-                    alert_controller: Arc::clone(&alert_controller),
-                };
-                Self::handle_matrix_message(ctx)
+        let on_message = move |body: String,
+                               room_id: String,
+                               sender_id: String,
+                               event_id: String,
+                               is_direct: bool,
+                               mention: Option<String>| {
+            let ctx = MessageContext {
+                body,
+                room_id,
+                sender_id,
+                event_id,
+                is_direct,
+                mention,
+                matrix_client: Arc::clone(&matrix_client_for_handler),
+                games: Arc::clone(&games),
+                default_server: default_server.clone(),
+                servers: servers.clone(),
+                commander: Arc::clone(&commander),
+                alert_controller: Arc::clone(&alert_controller),
+                settings_controller: Arc::clone(&settings_controller),
+                watch_controller: Arc::clone(&watch_controller),
+                history_controller: Arc::clone(&history_controller),
             };
+            Self::handle_matrix_message(ctx)
+        };
 
         // Start matrix sync
         self.matrix_client.sync(on_message).await.unwrap();
@@ -396,25 +578,39 @@ impl Bot {
 
     /// Starts the TMars synchronization task in the background.
     ///
-    /// This task periodically polls the TMars server for game state updates,
+    /// This task periodically polls the TMars servers for game state updates,
     /// manages alerts, and triggers notifications. The task runs independently
     /// and will continue until the process is terminated.
     ///
     /// # Arguments
     ///
     /// * `matrix_client` - Thread-safe reference to the Matrix client for sending notifications
-    /// * `tmars_sync` - Thread-safe reference to the TMars synchronization service
+    /// * `tmars_syncs` - The TMars synchronization services, owned by the task
     /// * `alert_controller` - Thread-safe reference to the alert controller
+    /// * `watch_controller` - Thread-safe reference to the watch controller
+    /// * `history_controller` - Thread-safe reference to the history controller
     /// * `polling_interval` - Number of seconds between sync operations
     ///
     /// # Behavior
     ///
     /// On each sync cycle:
-    /// 1. Fetches current game states from the TMars server
-    /// 2. Updates the alert controller with current game state
-    /// 3. The controller identifies which players need notifications
-    /// 4. Spawns delayed notification tasks for each alert
-    /// 5. Sends Matrix mentions to users when delays expire
+    /// 1. Fetches current game states from every TMars server, one after the other,
+    ///    or only the changed games when the cycle is a check between two syncs
+    /// 2. Publishes the games of all the servers, read by the commands and the
+    ///    notifications
+    /// 3. Announces the final scores of ended games, before their alerts and watches
+    ///    are cleaned up
+    /// 4. Updates the alert controller with current game state
+    /// 5. The controller identifies which players need notifications
+    /// 6. Spawns delayed notification tasks for each alert
+    /// 7. Sends Matrix mentions to users when delays expire
+    /// 8. Announces in the rooms watching a game the turn changes received as game events
+    /// 9. Records the turns started and ended since the previous sync
+    ///
+    /// A server failing to answer keeps its games as they were during its last
+    /// synchronization, and is retried with an exponential backoff, see [`ServerHealth`].
+    /// Its outage is announced to all rooms, and so is its recovery once it answers
+    /// again.
     ///
     /// # Note
     ///
@@ -423,44 +619,184 @@ impl Bot {
     fn start_tmars_sync_task(
         &self,
         matrix_client: Arc<MatrixClient>,
-        tmars_sync: Arc<Mutex<TMarsSync<TMarsRequester>>>,
+        mut tmars_syncs: Vec<TMarsSync<TMarsRequester>>,
         alert_controller: Arc<Mutex<AlertController>>,
+        watch_controller: Arc<WatchController>,
+        history_controller: Arc<HistoryController>,
         polling_interval: u64,
     ) {
+        let check_interval = self.check_interval;
+        let settings_controller = Arc::clone(&self.settings_controller);
+        let games = Arc::clone(&self.games);
+
         tokio::spawn(async move {
             info!(
                 "syncing with tmars server every {} seconds",
                 polling_interval
             );
-            let mut interval = time::interval(Duration::from_secs(polling_interval));
+            let tick_interval = match check_interval {
+                Some(check_interval) => {
+                    info!("checking changed games every {} seconds", check_interval);
+                    check_interval
+                }
+                None => polling_interval,
+            };
+            // Number of ticks between two syncs, the ticks in between only check
+            let ticks_per_sync = (polling_interval / tick_interval).max(1);
+            let mut tick_count: u64 = 0;
+            let mut interval = time::interval(Duration::from_secs(tick_interval));
+            let mut game_events_receivers = Vec::new();
+            let mut server_healths = Vec::new();
+            for tmars_sync in tmars_syncs.iter() {
+                game_events_receivers.push(tmars_sync.subscribe());
+                server_healths.push(ServerHealth::new(Duration::from_secs(tick_interval)));
+            }
 
             loop {
-                interval.tick().await;
+                let now = interval.tick().await;
+                let is_sync_tick = tick_count.is_multiple_of(ticks_per_sync);
+                tick_count += 1;
 
-                // Perform sync
-                // If an error occurs, log it, notify all rooms, and stop the sync task
-                if tmars_sync.lock().await.sync().await.is_err() {
-                    log::error!("stop tmars sync task due to error");
-                    matrix_client
-                        .send_to_all(&Commander::get_access_error_message())
-                        .await;
-                    break;
+                let mut game_events = Vec::new();
+                // Servers which listed their games, the checks don't
+                let mut synced_servers = HashSet::new();
+                for ((tmars_sync, game_events_receiver), server_health) in tmars_syncs
+                    .iter_mut()
+                    .zip(&mut game_events_receivers)
+                    .zip(&mut server_healths)
+                {
+                    let server_name = tmars_sync.get_server_name().to_owned();
+                    if !server_health.is_ready(now) {
+                        continue;
+                    }
+
+                    // A failing server is retried with a sync, not a check
+                    if !is_sync_tick && !server_health.is_failing() {
+                        tmars_sync.check().await;
+                        game_events.extend(Self::receive_game_events(game_events_receiver));
+                        continue;
+                    }
+
+                    // Perform sync
+                    // If an error occurs, keep the games and retry later, the outage is
+                    // announced to all rooms once it lasts
+                    match tmars_sync.sync().await {
+                        Ok(()) => {
+                            synced_servers.insert(server_name.clone());
+                            if server_health.record_success() {
+                                info!("tmars server {} answers again", server_name);
+                                matrix_client
+                                    .send_to_all(&Commander::get_server_back_message(&server_name))
+                                    .await;
+                            }
+                        }
+                        Err(e) => {
+                            error!("failed to sync tmars server {}: {}", server_name, e);
+                            if server_health.record_failure(&e, now) {
+                                matrix_client
+                                    .send_to_all(&Commander::get_sync_error_message(
+                                        &server_name,
+                                        &e,
+                                    ))
+                                    .await;
+                            }
+                            continue;
+                        }
+                    }
+
+                    game_events.extend(Self::receive_game_events(game_events_receiver));
                 }
 
-                let games_map = tmars_sync.lock().await.get_games();
+                let games_map = Self::get_games(&tmars_syncs);
+                *games.write().await = games_map.clone();
+
+                Self::announce_game_ends(
+                    &matrix_client,
+                    &alert_controller,
+                    &watch_controller,
+                    &game_events,
+                )
+                .await;
+
+                // The alerts and watches of the ended games are removed right away,
+                // those of the missing games only after a grace period
+                let ended_game_ids: HashSet<String> = game_events
+                    .iter()
+                    .filter_map(|game_event| match game_event {
+                        GameEvent::GameEnded { game_id, .. } => Some(game_id.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                {
+                    let mut alert_controller = alert_controller.lock().await;
+                    alert_controller
+                        .remove_ended_games_alerts(&ended_game_ids)
+                        .await;
+                    alert_controller
+                        .clean_alerts(&games_map, &synced_servers)
+                        .await;
+                }
+                watch_controller
+                    .remove_ended_games_watches(&ended_game_ids)
+                    .await;
+                watch_controller
+                    .clean_watches(&games_map, &synced_servers)
+                    .await;
 
                 let on_alert_to_fire = {
                     let matrix_client = Arc::clone(&matrix_client);
-                    move |alert: Alert| {
+                    let settings_controller = Arc::clone(&settings_controller);
+                    let games = Arc::clone(&games);
+                    move |alert: Alert, game_id: &str| {
                         let matrix_client = Arc::clone(&matrix_client);
+                        let settings_controller = Arc::clone(&settings_controller);
+                        let games = Arc::clone(&games);
+                        let game_id = game_id.to_owned();
                         tokio::spawn(async move {
+                            // The notification may be sent long after the turn started, the
+                            // context of the turn is the current state of the game
+                            let Some(game) = games.read().await.get(&game_id).cloned() else {
+                                info!(
+                                    "game {} is gone, not notifying user {}",
+                                    game_id, alert.user_id
+                                );
+                                return;
+                            };
+                            if !game.waited_players.contains(&alert.player_id)
+                                || !alert.fires_in(&game.phase)
+                            {
+                                info!(
+                                    "turn of player {} in game {} is over, not notifying user {}",
+                                    alert.player_id, game_id, alert.user_id
+                                );
+                                return;
+                            }
+
+                            // The context of the turn is formatted with the template of the
+                            // room if it has one
+                            let template = settings_controller
+                                .get_room_settings(&alert.room_id)
+                                .await
+                                .template;
+                            let message = match alert.is_reminder() {
+                                true => Commander::get_player_reminder_message(
+                                    &alert,
+                                    &game,
+                                    template.as_ref(),
+                                    get_timestamp(),
+                                ),
+                                false => Commander::get_player_turn_message(
+                                    &alert,
+                                    &game,
+                                    template.as_ref(),
+                                    get_timestamp(),
+                                ),
+                            };
                             let _ = matrix_client
                                 .send_mention(
                                     &alert.room_id,
-                                    &Commander::get_player_turn_message(
-                                        alert.user_id.as_str(),
-                                        alert.player_url.as_str(),
-                                    ),
+                                    &message.body,
+                                    &message.html_body,
                                     &alert.user_id,
                                 )
                                 .await;
@@ -470,31 +806,149 @@ impl Bot {
                 alert_controller
                     .lock()
                     .await
-                    .update_alerts(&games_map, on_alert_to_fire)
+                    .update_alerts(&games_map, &game_events, on_alert_to_fire)
+                    .await;
+
+                for (room_id, turn_change) in watch_controller
+                    .update_watches(&games_map, &game_events)
+                    .await
+                {
+                    matrix_client
+                        .send_message(&room_id, &Commander::get_turn_change_message(&turn_change))
+                        .await;
+                }
+
+                // The ongoing turns of the removed games are dropped right away, those
+                // of the missing games only after a grace period
+                let removed_game_ids: HashSet<String> = game_events
+                    .iter()
+                    .filter_map(|game_event| match game_event {
+                        GameEvent::GameEnded { game_id, .. }
+                        | GameEvent::GameRemoved { game_id } => Some(game_id.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                history_controller
+                    .update_history(&games_map, &removed_game_ids, &synced_servers)
                     .await;
             }
         });
     }
 
+    /// Returns the synchronized games of all the servers.
+    ///
+    /// # Arguments
+    ///
+    /// * `tmars_syncs` - The TMars synchronization services, one per server
+    ///
+    /// # Returns
+    ///
+    /// A [`HashMap`] mapping the namespaced game IDs to their [`Game`].
+    fn get_games(tmars_syncs: &[TMarsSync<TMarsRequester>]) -> HashMap<String, Game> {
+        let mut games_map = HashMap::new();
+        for tmars_sync in tmars_syncs {
+            games_map.extend(tmars_sync.get_games());
+        }
+        games_map
+    }
+
+    /// Collects the game events emitted since the last call.
+    ///
+    /// Events dropped because the receiver didn't keep up are logged and skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_events_receiver` - Receiver subscribed to the TMars synchronization
+    ///
+    /// # Returns
+    ///
+    /// The pending game events, in the order they were emitted.
+    fn receive_game_events(
+        game_events_receiver: &mut broadcast::Receiver<GameEvent>,
+    ) -> Vec<GameEvent> {
+        let mut game_events = Vec::new();
+
+        loop {
+            match game_events_receiver.try_recv() {
+                Ok(game_event) => game_events.push(game_event),
+                Err(broadcast::error::TryRecvError::Lagged(count)) => {
+                    warn!("{} game events were dropped", count);
+                }
+                Err(_) => break,
+            }
+        }
+
+        game_events
+    }
+
+    /// Announces the final scores of the ended games.
+    ///
+    /// The announcement is sent once to every room with alerts or watches on the game.
+    ///
+    /// # Arguments
+    ///
+    /// * `matrix_client` - Matrix client for sending the announcements
+    /// * `alert_controller` - Alert controller, for the rooms with alerts on the game
+    /// * `watch_controller` - Watch controller, for the rooms watching the game
+    /// * `game_events` - Events emitted by the last synchronization
+    async fn announce_game_ends(
+        matrix_client: &MatrixClient,
+        alert_controller: &Mutex<AlertController>,
+        watch_controller: &WatchController,
+        game_events: &[GameEvent],
+    ) {
+        for game_event in game_events {
+            let GameEvent::GameEnded { game_id, scores } = game_event else {
+                continue;
+            };
+
+            let mut room_ids = watch_controller.get_room_ids(game_id).await;
+            if let Some(alerts) = alert_controller
+                .lock()
+                .await
+                .get_alerts_map()
+                .await
+                .get(game_id)
+            {
+                room_ids.extend(alerts.iter().map(|alert| alert.room_id.clone()));
+            }
+
+            info!(
+                "game {} ended, announcing in {} rooms",
+                game_id,
+                room_ids.len()
+            );
+            let message = Commander::get_game_end_message(game_id, scores);
+            for room_id in room_ids {
+                matrix_client.send_message(&room_id, &message).await;
+            }
+        }
+    }
+
     /// Handles an incoming Matrix message and processes it as a command.
     ///
     /// This method implements the complete command processing flow:
-    /// 1. Parse the message body to identify the command
+    /// 1. Parse the message body to identify the command, with the prefix of the room
     /// 2. Silently ignore if not a command or for a different bot
     /// 3. Send error response if command syntax is invalid
     /// 4. Create execution context with current game/alert state
     /// 5. Execute the command and get result
     /// 6. Update alerts via the alert controller (add/remove as needed)
-    /// 7. Send success response to user
+    /// 7. Update user settings, room settings and room watches if needed
+    /// 8. Send success response to user
     ///
     /// # Arguments
     ///
     /// * `ctx` - The message context containing:
     ///   - `body`: The message text to parse
-    ///   - `room_id`, `sender_id`, `event_id`: Matrix message metadata
+    ///   - `room_id`, `sender_id`, `event_id`, `is_direct`, `mention`: Matrix message metadata
     ///   - `matrix_client`: For sending responses
-    ///   - `tmars_sync`: For accessing current game state
+    ///   - `games`: For accessing the games of every server, as of the last sync
+    ///   - `default_server`, `servers`: For resolving the game IDs of the commands
     ///   - `alert_controller`: For managing alerts
+    ///   - `settings_controller`: For reading and updating user preferences
+    ///   - `watch_controller`: For managing room watches
+    ///   - `history_controller`: For reading the recorded turns
     ///   - `commander`: For parsing and executing commands
     ///
     /// # Behavior
@@ -507,8 +961,18 @@ impl Bot {
     /// the controller's background persistence task.
     fn handle_matrix_message(ctx: MessageContext) {
         tokio::spawn(async move {
+            let room_settings = ctx
+                .settings_controller
+                .get_room_settings(&ctx.room_id)
+                .await;
+
             // Parse body to extract command
-            let result = match ctx.commander.parse(&ctx.body) {
+            let result = match ctx.commander.parse(
+                &ctx.body,
+                room_settings.prefix.as_deref(),
+                ctx.is_direct,
+                ctx.mention.as_deref(),
+            ) {
                 Ok(result) => result,
                 Err(e) => match e {
                     // Return silently if the command is not for the bot
@@ -523,19 +987,30 @@ impl Bot {
                 },
             };
 
+            // Use the server chosen by the room, if it is still configured
+            let default_server = match &room_settings.server {
+                Some(server) if ctx.servers.contains(server) => server.clone(),
+                _ => ctx.default_server.clone(),
+            };
+
             let command_context = CommandContext {
                 room_id: ctx.room_id.clone(),
                 user_id: ctx.sender_id.clone(),
-                games_map: ctx.tmars_sync.lock().await.get_games(),
+                games_map: ctx.games.read().await.clone(),
                 alerts_map: ctx.alert_controller.lock().await.get_alerts_map().await,
+                user_settings: ctx
+                    .settings_controller
+                    .get_user_settings(&ctx.sender_id)
+                    .await,
+                room_settings,
+                default_server,
+                default_prefix: ctx.commander.get_prefix().to_owned(),
+                servers: ctx.servers.clone(),
+                turns_map: ctx.history_controller.get_turns_map().await,
             };
 
             // Parse command with context
-            let command_result = match ctx.commander.parse_command(&result, &command_context).await
-            {
-                Some(command_result) => command_result,
-                None => return,
-            };
+            let command_result = ctx.commander.parse_command(&result, &command_context).await;
 
             // Update alerts map based on command result
             if let Some((game_id, alert)) = command_result.alert_to_add {
@@ -552,6 +1027,22 @@ impl Bot {
                     .remove_alerts(&game_id, &room_id, &user_id)
                     .await;
             }
+            if let Some((user_id, user_settings)) = command_result.user_settings_to_update {
+                ctx.settings_controller
+                    .update_user_settings(&user_id, &user_settings)
+                    .await;
+            }
+            if let Some((room_id, room_settings)) = command_result.room_settings_to_update {
+                ctx.settings_controller
+                    .update_room_settings(&room_id, &room_settings)
+                    .await;
+            }
+            if let Some((game_id, room_id)) = command_result.watch_to_add {
+                ctx.watch_controller.add_watch(&game_id, &room_id).await;
+            }
+            if let Some((game_id, room_id)) = command_result.watch_to_remove {
+                ctx.watch_controller.remove_watch(&game_id, &room_id).await;
+            }
 
             // Send response back to matrix room
             ctx.matrix_client
@@ -6,14 +6,20 @@
 //! - User ID: Only shows alerts belonging to the requesting user
 //! - Room ID: Only shows alerts registered in the current room
 //!
-//! Player IDs in alerts are resolved to human-readable names using the games map.
+//! Player IDs in alerts are resolved to human-readable names using the games map,
+//! followed by the phases of the alerts restricted to some phases.
 
 use std::collections::HashMap;
 
+use futures::{FutureExt, future::BoxFuture};
 use log::debug;
 
 use crate::{
-    commands::{CommandContext, CommandResult, markdown_response::format_alerts},
+    commands::{
+        CommandContext, CommandResult,
+        markdown_response::{format_alert_player, format_alerts},
+        registry::{ArgumentError, BotCommand, Example, Usage},
+    },
     tmars::Game,
 };
 
@@ -37,6 +43,12 @@ pub fn handle_alerts(context: &CommandContext) -> CommandResult {
         user_id,
         games_map,
         alerts_map,
+        user_settings: _,
+        room_settings: _,
+        default_server: _,
+        default_prefix: _,
+        servers: _,
+        turns_map: _,
     } = context;
 
     let mut filtered_alerts: Vec<(String, Vec<String>)> = Vec::new();
@@ -53,7 +65,7 @@ pub fn handle_alerts(context: &CommandContext) -> CommandResult {
                 && alert.room_id == room_id.clone()
                 && let Some(name) = search_player_name(games_map, game_id, alert.player_id.as_str())
             {
-                player_names.push(name);
+                player_names.push(format_alert_player(&name, &alert.phases));
             }
         }
 
@@ -65,8 +77,7 @@ pub fn handle_alerts(context: &CommandContext) -> CommandResult {
 
     let result = CommandResult {
         response: format_alerts(&filtered_alerts),
-        alert_to_add: None,
-        alerts_to_remove: None,
+        ..Default::default()
     };
 
     debug!("alerts command result {:?}", result);
@@ -93,12 +104,46 @@ fn search_player_name(
         })
 }
 
+/// The `alerts` command.
+pub struct AlertsCommand;
+
+impl BotCommand for AlertsCommand {
+    type Arguments = ();
+
+    fn name(&self) -> &'static str {
+        "alerts"
+    }
+
+    fn usages(&self) -> &'static [Usage] {
+        &[Usage {
+            arguments: "",
+            description: "list your registered alerts",
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            arguments: "",
+            description: "list your alerts in this room",
+        }]
+    }
+
+    fn parse(&self, _: &[&str], _: &HashMap<String, String>) -> Result<(), ArgumentError> {
+        Ok(())
+    }
+
+    fn handle<'a>(&self, context: &'a CommandContext, _: &'a ()) -> BoxFuture<'a, CommandResult> {
+        futures::future::ready(handle_alerts(context)).boxed()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
 
     use crate::{
         alerts::Alert,
+        settings::{RoomSettings, UserSettings},
         tmars::{Game, Phase, Player},
     };
 
@@ -126,6 +171,12 @@ mod tests {
             alerts_map,
             room_id: room_id.to_string(),
             user_id: user_id.to_string(),
+            user_settings: UserSettings::default(),
+            room_settings: RoomSettings::default(),
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["main".to_string()],
+            turns_map: HashMap::new(),
         }
     }
 
@@ -134,6 +185,7 @@ mod tests {
             id: id.to_string(),
             phase: Phase::Action,
             spectator_id: format!("spectator_{}", id),
+            spectator_url: "http://example.com/spectator".to_string(),
             players: players
                 .iter()
                 .map(|(id, name, color)| Player {
@@ -141,9 +193,12 @@ mod tests {
                     name: name.to_string(),
                     color: color.to_string(),
                     url: "http://alice.example.com".to_string(),
+                    terraform_rating: None,
+                    waiting_since: None,
                 })
                 .collect(),
             waited_players: HashSet::new(),
+            progress: None,
         }
     }
 
@@ -155,6 +210,13 @@ mod tests {
             player_url: "http://alice.example.com".to_string(),
             notified: false,
             delay,
+            turn_started_at: None,
+            notify_at: None,
+            repeat_interval: None,
+            max_reminders: None,
+            notifications_sent: 0,
+            phases: Vec::new(),
+            time_of_day: None,
         }
     }
 
@@ -0,0 +1,263 @@
+//! Game command handler.
+//!
+//! Displays the full state of a single Terraforming Mars game: its phase and
+//! progress, every player with who is being waited for and since when, the link to
+//! the spectator page, and the alerts of the current room targeting the game.
+//!
+//! # Validation
+//!
+//! The game must exist in the active games map.
+
+use std::collections::HashMap;
+
+use futures::{FutureExt, future::BoxFuture};
+use log::debug;
+
+use crate::{
+    commands::{
+        CommandContext, CommandResult,
+        markdown_response::{format_game, format_game_not_found},
+        registry::{
+            ArgumentError, ArgumentHelp, ArgumentSchema, BotCommand, Example, GAME_ID, Usage,
+        },
+        suggest_game_id,
+    },
+    utils::get_timestamp,
+};
+
+/// Shows the detailed state of a game.
+///
+/// Alerts are resolved to (user ID, player name) pairs and limited to the current
+/// room, sorted by user then player for a stable output.
+///
+/// # Returns
+///
+/// The detailed state of the game, or an error message if the game doesn't exist.
+pub async fn handle_game(context: &CommandContext, game_id: &str) -> CommandResult {
+    debug!("handling game command: {}", game_id);
+
+    let game_id = context.resolve_game_id(game_id);
+
+    let CommandContext {
+        room_id,
+        user_id: _,
+        games_map,
+        alerts_map,
+        user_settings: _,
+        room_settings: _,
+        default_server: _,
+        default_prefix: _,
+        servers: _,
+        turns_map: _,
+    } = context;
+
+    let response = match games_map.get(&game_id) {
+        Some(game) => {
+            let mut room_alerts: Vec<(String, String)> = alerts_map
+                .get(&game_id)
+                .into_iter()
+                .flatten()
+                .filter(|alert| alert.room_id == *room_id)
+                .filter_map(|alert| {
+                    game.players
+                        .iter()
+                        .find(|player| player.id == alert.player_id)
+                        .map(|player| (alert.user_id.clone(), player.name.clone()))
+                })
+                .collect();
+            room_alerts.sort();
+
+            format_game(game, &room_alerts, get_timestamp())
+        }
+        None => format_game_not_found(&game_id, suggest_game_id(games_map, &game_id).as_deref()),
+    };
+
+    let result = CommandResult {
+        response,
+        ..Default::default()
+    };
+
+    debug!("game command result {:?}", result);
+
+    result
+}
+
+/// The `game` command.
+pub struct GameCommand;
+
+impl BotCommand for GameCommand {
+    /// ID of the game, as written in the message
+    type Arguments = String;
+
+    fn name(&self) -> &'static str {
+        "game"
+    }
+
+    fn usages(&self) -> &'static [Usage] {
+        &[Usage {
+            arguments: "<game_id>",
+            description: "show the details of a game",
+        }]
+    }
+
+    fn schema(&self) -> ArgumentSchema {
+        ArgumentSchema {
+            min_arguments: 1,
+            max_arguments: Some(1),
+            options: &[],
+        }
+    }
+
+    fn arguments(&self) -> &'static [ArgumentHelp] {
+        &[GAME_ID]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                arguments: "game123",
+                description: "show the players and the phase of game123 on the server of this room",
+            },
+            Example {
+                arguments: "friends:game123",
+                description: "show game123 of the `friends` server",
+            },
+        ]
+    }
+
+    fn parse(
+        &self,
+        arguments: &[&str],
+        _: &HashMap<String, String>,
+    ) -> Result<String, ArgumentError> {
+        Ok(arguments[0].to_owned())
+    }
+
+    fn handle<'a>(
+        &self,
+        context: &'a CommandContext,
+        game_id: &'a String,
+    ) -> BoxFuture<'a, CommandResult> {
+        handle_game(context, game_id).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use super::*;
+    use crate::{
+        alerts::Alert,
+        settings::{RoomSettings, UserSettings},
+        tmars::{Game, Phase, Player},
+    };
+
+    fn create_test_alert(room_id: &str, player_id: &str, user_id: &str) -> Alert {
+        Alert {
+            room_id: room_id.to_string(),
+            player_id: player_id.to_string(),
+            user_id: user_id.to_string(),
+            notified: false,
+            delay: 10,
+            player_url: format!("http://example.com/{}", player_id),
+            turn_started_at: None,
+            notify_at: None,
+            repeat_interval: None,
+            max_reminders: None,
+            notifications_sent: 0,
+            phases: Vec::new(),
+            time_of_day: None,
+        }
+    }
+
+    fn create_test_context() -> CommandContext {
+        let game = Game {
+            id: "main:game123".to_string(),
+            phase: Phase::Action,
+            spectator_id: "spec123".to_string(),
+            spectator_url: "http://example.com/spectator?id=spec123".to_string(),
+            players: vec![
+                Player {
+                    id: "player1".to_string(),
+                    color: "red".to_string(),
+                    name: "Alice".to_string(),
+                    url: "http://example.com/player1".to_string(),
+                    terraform_rating: None,
+                    waiting_since: None,
+                },
+                Player {
+                    id: "player2".to_string(),
+                    color: "blue".to_string(),
+                    name: "Bob".to_string(),
+                    url: "http://example.com/player2".to_string(),
+                    terraform_rating: None,
+                    waiting_since: None,
+                },
+            ],
+            waited_players: HashSet::new(),
+            progress: None,
+        };
+
+        CommandContext {
+            games_map: HashMap::from([(game.id.clone(), game)]),
+            alerts_map: HashMap::from([(
+                "main:game123".to_string(),
+                HashSet::from([
+                    create_test_alert("!test_room:matrix.org", "player2", "@bob:matrix.org"),
+                    create_test_alert("!test_room:matrix.org", "player1", "@alice:matrix.org"),
+                    create_test_alert("!other_room:matrix.org", "player1", "@carol:matrix.org"),
+                ]),
+            )]),
+            room_id: "!test_room:matrix.org".to_string(),
+            user_id: "@test_user:matrix.org".to_string(),
+            user_settings: UserSettings::default(),
+            room_settings: RoomSettings::default(),
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["main".to_string()],
+            turns_map: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_game_successful() {
+        let context = create_test_context();
+        let result = handle_game(&context, "game123").await;
+
+        // Only the alerts of the current room are listed
+        let room_alerts = vec![
+            ("@alice:matrix.org".to_string(), "Alice".to_string()),
+            ("@bob:matrix.org".to_string(), "Bob".to_string()),
+        ];
+        assert_eq!(
+            result.response,
+            format_game(
+                context.games_map.get("main:game123").unwrap(),
+                &room_alerts,
+                get_timestamp()
+            )
+        );
+        assert!(result.alert_to_add.is_none());
+        assert!(result.watch_to_add.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handle_game_not_found() {
+        let context = create_test_context();
+        let result = handle_game(&context, "unknown").await;
+
+        assert_eq!(result.response, format_game_not_found("main:unknown", None));
+    }
+
+    #[tokio::test]
+    async fn test_handle_game_not_found_with_suggestion() {
+        let context = create_test_context();
+        let result = handle_game(&context, "game12").await;
+
+        assert_eq!(
+            result.response,
+            format_game_not_found("main:game12", Some("main:game123"))
+        );
+    }
+}
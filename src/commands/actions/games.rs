@@ -7,9 +7,16 @@
 //!
 //! This is a read-only command that queries the games map from the context.
 
+use std::collections::HashMap;
+
+use futures::{FutureExt, future::BoxFuture};
 use log::debug;
 
-use crate::commands::{CommandContext, CommandResult, markdown_response::format_games};
+use crate::commands::{
+    CommandContext, CommandResult,
+    markdown_response::format_games,
+    registry::{ArgumentError, BotCommand, Example, Usage},
+};
 
 /// Lists all active games with their details.
 ///
@@ -20,8 +27,7 @@ pub fn handle_games(context: &CommandContext) -> CommandResult {
 
     let result = CommandResult {
         response: format_games(&context.games_map.values().cloned().collect::<Vec<_>>()),
-        alert_to_add: None,
-        alerts_to_remove: None,
+        ..Default::default()
     };
 
     debug!("games command result {:?}", result);
@@ -29,11 +35,47 @@ pub fn handle_games(context: &CommandContext) -> CommandResult {
     result
 }
 
+/// The `games` command.
+pub struct GamesCommand;
+
+impl BotCommand for GamesCommand {
+    type Arguments = ();
+
+    fn name(&self) -> &'static str {
+        "games"
+    }
+
+    fn usages(&self) -> &'static [Usage] {
+        &[Usage {
+            arguments: "",
+            description: "list all the ongoing games",
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            arguments: "",
+            description: "list the games of all the servers",
+        }]
+    }
+
+    fn parse(&self, _: &[&str], _: &HashMap<String, String>) -> Result<(), ArgumentError> {
+        Ok(())
+    }
+
+    fn handle<'a>(&self, context: &'a CommandContext, _: &'a ()) -> BoxFuture<'a, CommandResult> {
+        futures::future::ready(handle_games(context)).boxed()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use crate::tmars::{Game, Phase, Player};
+    use crate::{
+        settings::{RoomSettings, UserSettings},
+        tmars::{Game, Phase, Player},
+    };
 
     use super::*;
 
@@ -48,6 +90,12 @@ mod tests {
             alerts_map: HashMap::new(),
             room_id: "!test_room:matrix.org".to_string(),
             user_id: "@test_user:matrix.org".to_string(),
+            user_settings: UserSettings::default(),
+            room_settings: RoomSettings::default(),
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["main".to_string()],
+            turns_map: HashMap::new(),
         }
     }
 
@@ -66,6 +114,7 @@ mod tests {
             id: id.to_string(),
             phase,
             spectator_id: format!("spectator_{}", id),
+            spectator_url: "http://example.com/spectator".to_string(),
             players: players
                 .iter()
                 .map(|(id, name, color)| Player {
@@ -73,9 +122,12 @@ mod tests {
                     name: name.to_string(),
                     color: color.to_string(),
                     url: "http://alice.example.com".to_string(),
+                    terraform_rating: None,
+                    waiting_since: None,
                 })
                 .collect(),
             waited_players,
+            progress: None,
         }
     }
 
@@ -2,24 +2,126 @@
 //!
 //! Displays comprehensive help information including all available commands,
 //! their syntax, and a brief description of the bot's alert functionality.
+//! Followed by the name of a command, displays the usages of this command with
+//! the constraints of its arguments and examples.
 //!
-//! This is a stateless command that always returns the same help message.
+//! This is a stateless command that always returns the same help message for
+//! the same arguments.
 
+use std::collections::HashMap;
+
+use futures::{FutureExt, future::BoxFuture};
 use log::debug;
 
-use crate::commands::{CommandResult, markdown_response::format_help};
+use crate::commands::{
+    CommandContext, CommandResult,
+    markdown_response::{format_command_help, format_help},
+    registry::{
+        ArgumentError, ArgumentHelp, ArgumentSchema, BotCommand, COMMANDS, Example, Usage,
+        find_command,
+    },
+};
 
 /// Returns formatted help information about available commands.
 ///
-/// Generates a Markdown-formatted message listing all bot commands with syntax
-/// and usage information. This command is read-only and doesn't modify any state.
-pub fn handle_help() -> CommandResult {
-    debug!("handling help command");
+/// Generates a Markdown-formatted message listing all the commands of the
+/// [`COMMANDS`] registry with their syntax and usage information, or the detailed
+/// help of a single command. This command is read-only and doesn't modify any
+/// state.
+///
+/// # Arguments
+///
+/// * `prefix` - Prefix of the commands in the room, shown in the usages
+/// * `command_name` - Name of the command to describe, `None` for all the commands
+pub fn handle_help(prefix: &str, command_name: Option<&str>) -> CommandResult {
+    debug!("handling help command for {:?}", command_name);
 
     CommandResult {
-        response: format_help(),
-        alert_to_add: None,
-        alerts_to_remove: None,
+        response: match command_name.and_then(find_command) {
+            Some(command) => format_command_help(prefix, command),
+            None => format_help(prefix, COMMANDS),
+        },
+        ..Default::default()
+    }
+}
+
+/// The `help` command.
+pub struct HelpCommand;
+
+impl BotCommand for HelpCommand {
+    /// Name of the command to describe, `None` for all the commands
+    type Arguments = Option<String>;
+
+    fn name(&self) -> &'static str {
+        "help"
+    }
+
+    fn usages(&self) -> &'static [Usage] {
+        &[
+            Usage {
+                arguments: "",
+                description: "show this help message",
+            },
+            Usage {
+                arguments: "<command>",
+                description: "show the usages, arguments and examples of a command",
+            },
+        ]
+    }
+
+    fn arguments(&self) -> &'static [ArgumentHelp] {
+        &[ArgumentHelp {
+            name: "command",
+            description: "name or alias of a command",
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                arguments: "",
+                description: "list all the commands",
+            },
+            Example {
+                arguments: "register",
+                description: "show how to register an alert",
+            },
+        ]
+    }
+
+    fn schema(&self) -> ArgumentSchema {
+        ArgumentSchema {
+            min_arguments: 0,
+            max_arguments: Some(1),
+            options: &[],
+        }
+    }
+
+    fn parse(
+        &self,
+        arguments: &[&str],
+        _: &HashMap<String, String>,
+    ) -> Result<Option<String>, ArgumentError> {
+        let Some(name) = arguments.first() else {
+            return Ok(None);
+        };
+
+        // The aliases are resolved to show the help under the name of the command
+        find_command(name)
+            .map(|command| Some(command.name().to_owned()))
+            .ok_or_else(|| ArgumentError::InvalidValue {
+                argument: "command",
+                value: name.to_string(),
+                reason: "is unknown",
+            })
+    }
+
+    fn handle<'a>(
+        &self,
+        context: &'a CommandContext,
+        command_name: &'a Option<String>,
+    ) -> BoxFuture<'a, CommandResult> {
+        futures::future::ready(handle_help(context.get_prefix(), command_name.as_deref())).boxed()
     }
 }
 
@@ -29,11 +131,21 @@ mod tests {
 
     #[test]
     fn test_handle_help() {
-        let result = handle_help();
+        let result = handle_help("!miou", None);
 
         // Verify it returns a CommandResult
         assert!(result.alert_to_add.is_none());
         assert!(result.alerts_to_remove.is_none());
-        assert!(!result.response.is_empty());
+        assert_eq!(result.response, format_help("!miou", COMMANDS));
+    }
+
+    #[test]
+    fn test_handle_help_command() {
+        let result = handle_help("!mi", Some("register"));
+
+        assert_eq!(
+            result.response,
+            format_command_help("!mi", find_command("register").unwrap())
+        );
     }
 }
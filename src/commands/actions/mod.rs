@@ -4,6 +4,10 @@
 //! [`CommandContext`](crate::commands::CommandContext), processes the command,
 //! and returns a [`CommandResult`](crate::commands::CommandResult).
 //!
+//! Each module also implements the [`BotCommand`](crate::commands::registry::BotCommand)
+//! of its command: its name, usages and arguments, the type its arguments are parsed
+//! into and which handler executes them.
+//!
 //! # Handler Pattern
 //!
 //! Handlers follow a consistent pattern:
@@ -11,26 +15,55 @@
 //! 2. Validate and process the command
 //! 3. Return a result with Markdown response and optional state changes
 //!
-//! # Available Handlers
+//! # Available Commands
 //!
-//! - [`handle_help`] - Display help information
-//! - [`handle_games`] - List ongoing games with players
-//! - [`handle_alerts`] - Show user's alert subscriptions
-//! - [`handle_register`] - Register new turn notification alert
-//! - [`handle_unregister`] - Remove alert subscriptions for a game
+//! - [`HelpCommand`] - Display help information
+//! - [`GamesCommand`] - List ongoing games with players
+//! - [`GameCommand`] - Show the detailed state of a game
+//! - [`AlertsCommand`] - Show user's alert subscriptions
+//! - [`RegisterCommand`] - Register new turn notification alert
+//! - [`UnregisterCommand`] - Remove alert subscriptions for a game
+//! - [`WatchCommand`] - Announce the turn changes of a game in the room
+//! - [`UnwatchCommand`] - Stop announcing the turn changes of a game in the room
+//! - [`SettingsCommand`] - Display or update user settings
+//! - [`ServerCommand`] - Display or update the server of the room
+//! - [`StatsCommand`] - Show the turn statistics of a game or of all games
+//! - [`TemplateCommand`] - Display or update the notification template of the room
+//! - [`PrefixCommand`] - Display or update the prefix of the commands in the room
 //!
 //! # State Changes
 //!
 //! Handlers don't modify state directly. Instead, they return state change requests
-//! via `alert_to_add` or `alerts_to_remove` in the [`CommandResult`](crate::commands::CommandResult).
+//! via `alert_to_add`, `alerts_to_remove`, `user_settings_to_update`,
+//! `room_settings_to_update`, `watch_to_add` or `watch_to_remove` in the
+//! [`CommandResult`](crate::commands::CommandResult).
 
 mod alerts;
+mod game;
 mod games;
 mod help;
+mod prefix;
 mod register;
+mod server;
+mod settings;
+mod stats;
+mod template;
 mod unregister;
+mod unwatch;
+mod watch;
+
+pub use crate::commands::actions::{
+    alerts::AlertsCommand, game::GameCommand, games::GamesCommand, help::HelpCommand,
+    prefix::PrefixCommand, register::RegisterCommand, server::ServerCommand,
+    settings::SettingsCommand, stats::StatsCommand, template::TemplateCommand,
+    unregister::UnregisterCommand, unwatch::UnwatchCommand, watch::WatchCommand,
+};
 
+// The arguments are only built by their commands, outside of the tests
+#[cfg(test)]
 pub use crate::commands::actions::{
-    alerts::handle_alerts, games::handle_games, help::handle_help, register::handle_register,
-    unregister::handle_unregister,
+    prefix::PrefixArguments,
+    register::{Delay, RegisterArguments},
+    settings::SettingsArguments,
+    template::TemplateArguments,
 };
@@ -0,0 +1,287 @@
+//! Room prefix command handler.
+//!
+//! Displays and updates the prefix of the commands in the current Matrix room.
+//! The prefix of the room replaces the prefix of the bot configuration, letting
+//! two bots share a room or the users shorten the commands. The commands
+//! starting with a mention of the bot are accepted whatever the prefix.
+//!
+//! # Validation
+//!
+//! The prefix must not be empty, longer than 32 characters nor contain whitespace.
+
+use std::collections::HashMap;
+
+use futures::{FutureExt, future::BoxFuture};
+use log::debug;
+
+use crate::{
+    commands::{
+        CommandContext, CommandResult,
+        markdown_response::{
+            format_invalid_prefix, format_prefix, format_prefix_reset, format_successful_prefix,
+        },
+        registry::{ArgumentError, ArgumentHelp, ArgumentSchema, BotCommand, Example, Usage},
+    },
+    settings::RoomSettings,
+};
+
+/// Maximum number of characters of the prefix of a room.
+const MAX_PREFIX_LENGTH: usize = 32;
+
+/// Action of the `prefix` command.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PrefixArguments {
+    /// Show the prefix of the room
+    Show,
+    /// Set the prefix of the room, not validated yet
+    Set(String),
+    /// Restore the default prefix of the room
+    Reset,
+}
+
+/// Displays or updates the prefix of the commands in the current room.
+///
+/// Updates are not applied directly, they are returned in `room_settings_to_update`
+/// and saved by the caller.
+///
+/// # Returns
+///
+/// The prefix of the room, a success message with the room settings to save, or an
+/// error message if the prefix is invalid.
+pub async fn handle_prefix(context: &CommandContext, arguments: &PrefixArguments) -> CommandResult {
+    debug!("handling prefix command: {:?}", arguments);
+
+    let CommandContext {
+        room_id,
+        user_id: _,
+        games_map: _,
+        alerts_map: _,
+        user_settings: _,
+        room_settings,
+        default_server: _,
+        default_prefix,
+        servers: _,
+        turns_map: _,
+    } = context;
+
+    let result = match arguments {
+        PrefixArguments::Show => CommandResult {
+            response: format_prefix(context.get_prefix()),
+            ..Default::default()
+        },
+        PrefixArguments::Set(prefix) => match is_valid_prefix(prefix) {
+            true => CommandResult {
+                response: format_successful_prefix(prefix),
+                room_settings_to_update: Some((
+                    room_id.clone(),
+                    with_prefix(room_settings, Some(prefix.clone())),
+                )),
+                ..Default::default()
+            },
+            false => CommandResult {
+                response: format_invalid_prefix(),
+                ..Default::default()
+            },
+        },
+        PrefixArguments::Reset => CommandResult {
+            response: format_prefix_reset(default_prefix),
+            room_settings_to_update: Some((room_id.clone(), with_prefix(room_settings, None))),
+            ..Default::default()
+        },
+    };
+
+    debug!("prefix command result {:?}", result);
+
+    result
+}
+
+/// Returns whether a prefix can be set to a room.
+fn is_valid_prefix(prefix: &str) -> bool {
+    let length = prefix.chars().count();
+    length > 0 && length <= MAX_PREFIX_LENGTH && !prefix.contains(char::is_whitespace)
+}
+
+/// Returns a copy of the room settings with the given prefix.
+fn with_prefix(room_settings: &RoomSettings, prefix: Option<String>) -> RoomSettings {
+    let mut room_settings = room_settings.clone();
+    room_settings.prefix = prefix;
+    room_settings
+}
+
+/// The `prefix` command.
+pub struct PrefixCommand;
+
+impl BotCommand for PrefixCommand {
+    type Arguments = PrefixArguments;
+
+    fn name(&self) -> &'static str {
+        "prefix"
+    }
+
+    fn usages(&self) -> &'static [Usage] {
+        &[
+            Usage {
+                arguments: "",
+                description: "show the prefix of the commands in this room",
+            },
+            Usage {
+                arguments: "<prefix>",
+                description: "set the prefix of the commands in this room",
+            },
+            Usage {
+                arguments: "reset",
+                description: "restore the default prefix of the commands in this room",
+            },
+        ]
+    }
+
+    fn schema(&self) -> ArgumentSchema {
+        ArgumentSchema {
+            min_arguments: 0,
+            max_arguments: Some(1),
+            options: &[],
+        }
+    }
+
+    fn arguments(&self) -> &'static [ArgumentHelp] {
+        &[ArgumentHelp {
+            name: "prefix",
+            description: "up to 32 characters without spaces, the commands starting with a mention of the bot are accepted whatever the prefix",
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                arguments: "",
+                description: "show the prefix of this room",
+            },
+            Example {
+                arguments: "!mi",
+                description: "type `!mi games` instead of the default prefix in this room",
+            },
+            Example {
+                arguments: "reset",
+                description: "use the default prefix in this room",
+            },
+        ]
+    }
+
+    fn parse(
+        &self,
+        arguments: &[&str],
+        _: &HashMap<String, String>,
+    ) -> Result<PrefixArguments, ArgumentError> {
+        Ok(match arguments.first() {
+            None => PrefixArguments::Show,
+            Some(&"reset") => PrefixArguments::Reset,
+            Some(prefix) => PrefixArguments::Set(prefix.to_string()),
+        })
+    }
+
+    fn handle<'a>(
+        &self,
+        context: &'a CommandContext,
+        arguments: &'a PrefixArguments,
+    ) -> BoxFuture<'a, CommandResult> {
+        handle_prefix(context, arguments).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::UserSettings;
+
+    fn create_test_context(room_settings: RoomSettings) -> CommandContext {
+        CommandContext {
+            games_map: HashMap::new(),
+            alerts_map: HashMap::new(),
+            room_id: "!test_room:matrix.org".to_string(),
+            user_id: "@test_user:matrix.org".to_string(),
+            user_settings: UserSettings::default(),
+            room_settings,
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["main".to_string()],
+            turns_map: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_prefix_display() {
+        let context = create_test_context(RoomSettings::default());
+
+        let result = handle_prefix(&context, &PrefixArguments::Show).await;
+
+        assert_eq!(result.response, format_prefix("!miou"));
+        assert!(result.room_settings_to_update.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handle_prefix_display_room_prefix() {
+        let context = create_test_context(RoomSettings {
+            prefix: Some("!mi".to_string()),
+            ..RoomSettings::default()
+        });
+
+        let result = handle_prefix(&context, &PrefixArguments::Show).await;
+
+        assert_eq!(result.response, format_prefix("!mi"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_set_prefix() {
+        let context = create_test_context(RoomSettings {
+            server: Some("friends".to_string()),
+            ..RoomSettings::default()
+        });
+        let arguments = PrefixArguments::Set("!mi".to_string());
+
+        let result = handle_prefix(&context, &arguments).await;
+
+        assert_eq!(result.response, format_successful_prefix("!mi"));
+        // The other settings of the room are kept
+        assert_eq!(
+            result.room_settings_to_update,
+            Some((
+                "!test_room:matrix.org".to_string(),
+                RoomSettings {
+                    server: Some("friends".to_string()),
+                    template: None,
+                    prefix: Some("!mi".to_string()),
+                },
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_set_invalid_prefix() {
+        let context = create_test_context(RoomSettings::default());
+
+        for prefix in ["", "! mi", &"!".repeat(33)] {
+            let arguments = PrefixArguments::Set(prefix.to_string());
+
+            let result = handle_prefix(&context, &arguments).await;
+
+            assert_eq!(result.response, format_invalid_prefix(), "{}", prefix);
+            assert!(result.room_settings_to_update.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_reset_prefix() {
+        let context = create_test_context(RoomSettings {
+            prefix: Some("!mi".to_string()),
+            ..RoomSettings::default()
+        });
+
+        let result = handle_prefix(&context, &PrefixArguments::Reset).await;
+
+        assert_eq!(result.response, format_prefix_reset("!miou"));
+        assert_eq!(
+            result.room_settings_to_update,
+            Some(("!test_room:matrix.org".to_string(), RoomSettings::default()))
+        );
+    }
+}
@@ -1,46 +1,94 @@
 //! Player registration command handler.
 //!
 //! Registers users to receive Matrix notifications when their turn arrives in
-//! Terraforming Mars games. The bot will send a mention after the specified delay.
+//! Terraforming Mars games. The bot will send a mention after the specified delay,
+//! or at the specified time of day, then optionally a reminder at a fixed interval
+//! while the player hasn't played.
+//! The alert can be restricted to some phases of the game, e.g. to skip the
+//! drafting where everyone is waited for at once.
 //!
 //! # Validation
 //!
-//! The handler validates three constraints:
-//! - **Delay**: Must be between 1 and 10,080 minutes (1 week)
+//! The handler validates these constraints:
+//! - **Delay**: Must be between 1 and 10,080 minutes (1 week), a time of day is
+//!   always valid and uses the timezone of the user's quiet hours, or UTC
+//! - **Repeat interval**: If set, must be between 1 and 10,080 minutes (1 week)
+//! - **Max reminders**: If set, must be at least 1
 //! - **Game**: Must exist in the active games map
-//! - **Player**: Must be a member of the specified game (case-sensitive)
+//! - **Player**: Must be a member of the specified game, the case of the name
+//!   only matters if several players have the same name in another case
 //!
 //! # Errors
 //!
-//! Returns user-friendly error messages for invalid delay, non-existent games,
-//! or players not found in the specified game.
+//! Returns user-friendly error messages for invalid delay or reminders settings,
+//! non-existent games, or players not found in the specified game. The game and
+//! the player closest to a mistyped one are suggested.
 
 use std::collections::HashMap;
 
+use chrono::NaiveTime;
+use futures::{FutureExt, future::BoxFuture};
 use log::debug;
 
 use crate::{
-    alerts::Alert,
+    alerts::{Alert, TimeOfDay},
     commands::{
         CommandContext, CommandResult,
-        command::Command,
         markdown_response::{
-            format_game_not_found, format_invalid_delay, format_player_not_found,
-            format_successful_register,
+            format_game_not_found, format_invalid_delay, format_invalid_max_reminders,
+            format_invalid_repeat_interval, format_player_not_found, format_successful_register,
+            format_successful_register_at,
         },
+        registry::{
+            ArgumentError, ArgumentHelp, ArgumentSchema, BotCommand, Example, GAME_ID, Usage,
+        },
+        suggest_game_id,
     },
-    tmars::Game,
+    settings::UserSettings,
+    tmars::{Game, Phase},
+    utils::find_closest,
 };
 
+/// Delay before the first notification of an alert.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Delay {
+    /// Number of minutes after the turn is detected, not validated yet
+    Minutes(u64),
+    /// Local time of day, in the timezone of the user
+    At(NaiveTime),
+}
+
+/// Arguments of the `register` command.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RegisterArguments {
+    /// Game ID, as written in the message
+    pub game_id: String,
+    /// Player name
+    pub player_name: String,
+    /// Delay before the first notification
+    pub delay: Delay,
+    /// Interval in minutes between reminders, if the alert repeats
+    pub repeat_interval: Option<u64>,
+    /// Maximum number of reminders sent after the first notification
+    pub max_reminders: Option<u32>,
+    /// Phases in which the alert fires, every phase if empty
+    pub phases: Vec<Phase>,
+}
+
 /// Errors that can occur during player registration.
 #[derive(Debug)]
 enum RegisterError {
     /// The specified delay is invalid (must be between 1 minute and 1 week).
     InvalidDelay,
-    /// The specified game ID does not exist.
-    GameNotFound,
-    /// The specified player name was not found in the game.
-    PlayerNotFound,
+    /// The specified repeat interval is invalid (must be between 1 minute and 1 week).
+    InvalidRepeatInterval,
+    /// The specified max reminders is invalid (must be at least 1).
+    InvalidMaxReminders,
+    /// The specified game ID does not exist, with the closest game ID if any.
+    GameNotFound(Option<String>),
+    /// The specified player name was not found in the game, with the closest
+    /// player name if any.
+    PlayerNotFound(Option<String>),
 }
 
 /// Formats a registration error into a human-readable message.
@@ -63,7 +111,7 @@ enum RegisterError {
 /// ```
 /// # use miou::commands::register::{format_register_error, RegisterError};
 /// let message = format_register_error(
-///     RegisterError::GameNotFound,
+///     RegisterError::GameNotFound(None),
 ///     "game123".to_string(),
 ///     "Alice".to_string()
 /// );
@@ -71,75 +119,149 @@ enum RegisterError {
 fn format_register_error(error: RegisterError, game_id: &str, player_name: &str) -> String {
     match error {
         RegisterError::InvalidDelay => format_invalid_delay(),
-        RegisterError::GameNotFound => format_game_not_found(game_id),
-        RegisterError::PlayerNotFound => format_player_not_found(player_name, game_id),
+        RegisterError::InvalidRepeatInterval => format_invalid_repeat_interval(),
+        RegisterError::InvalidMaxReminders => format_invalid_max_reminders(),
+        RegisterError::GameNotFound(suggestion) => {
+            format_game_not_found(game_id, suggestion.as_deref())
+        }
+        RegisterError::PlayerNotFound(suggestion) => {
+            format_player_not_found(player_name, game_id, suggestion.as_deref())
+        }
+    }
+}
+
+/// Validates the reminders settings of a registration command.
+///
+/// # Returns
+///
+/// - `Ok(())`: If the repeat interval is between 1 and 10,080 minutes (1 week)
+///   and the max reminders is at least 1, or if they are not set
+/// - `Err(RegisterError)`: Specific error for invalid repeat interval or max reminders
+fn validate_reminders(
+    repeat_interval: Option<u64>,
+    max_reminders: Option<u32>,
+) -> Result<(), RegisterError> {
+    let week_minutes = 7 * 24 * 60;
+
+    if repeat_interval.is_some_and(|interval| interval == 0 || interval > week_minutes) {
+        debug!("invalid repeat interval: {:?}", repeat_interval);
+        return Err(RegisterError::InvalidRepeatInterval);
+    }
+
+    if max_reminders == Some(0) {
+        debug!("invalid max reminders: {:?}", max_reminders);
+        return Err(RegisterError::InvalidMaxReminders);
     }
+
+    Ok(())
 }
 
 /// Validates a registration command and retrieves the player ID.
 ///
 /// Performs validation in order:
-/// 1. Delay must be between 1 and 10,080 minutes (1 week)
+/// 1. Delay in minutes must be between 1 and 10,080 (1 week)
 /// 2. Game must exist in the games map
-/// 3. Player name must match a player in that game
+/// 3. Player name must match a player in that game, ignoring the case if no
+///    player has exactly this name
 ///
 /// # Returns
 ///
 /// - `Ok(String)`: The player ID if all validations pass
 /// - `Err(RegisterError)`: Specific error for invalid delay, game not found, or player not found
 fn validate_and_get_player(
-    (game_id, player_name, delay): (String, String, u64),
+    (game_id, player_name, delay): (String, String, Delay),
     games: &HashMap<String, Game>,
 ) -> Result<(String, String), RegisterError> {
     // Validate delay (must be between 1 minute and 1 week)
     let week_minutes = 7 * 24 * 60;
 
-    if delay == 0 || delay > week_minutes {
-        debug!("invalid delay: {}", delay);
+    if let Delay::Minutes(minutes) = delay
+        && (minutes == 0 || minutes > week_minutes)
+    {
+        debug!("invalid delay: {:?}", delay);
         return Err(RegisterError::InvalidDelay);
     }
 
     let game = match games.get(&game_id) {
         None => {
             debug!("game {} not found", game_id);
-            return Err(RegisterError::GameNotFound);
+            return Err(RegisterError::GameNotFound(suggest_game_id(
+                games, &game_id,
+            )));
         }
         Some(g) => g,
     };
 
-    let player = match game.players.iter().find(|p| p.name == player_name) {
+    let player = game
+        .players
+        .iter()
+        .find(|p| p.name == player_name)
+        .or_else(|| {
+            let lowercase_name = player_name.to_lowercase();
+            game.players
+                .iter()
+                .find(|p| p.name.to_lowercase() == lowercase_name)
+        });
+
+    let player = match player {
         None => {
             debug!("player {} not found in game {}", player_name, game_id);
-            return Err(RegisterError::PlayerNotFound);
+            let suggestion =
+                find_closest(&player_name, game.players.iter().map(|p| p.name.as_str()));
+            return Err(RegisterError::PlayerNotFound(suggestion.map(str::to_owned)));
         }
         Some(p) => (p.id.clone(), p.url.clone()),
     };
 
     debug!(
-        "registered player {} ({}) for game {} with delay {} minutes",
+        "registered player {} ({}) for game {} with delay {:?}",
         player_name, player.0, game_id, delay
     );
 
     Ok(player)
 }
 
-/// Registers a user for turn notifications in a game.
-///
-/// Extracts command parameters, validates them, and returns a `CommandResult` with
-/// either a success message and alert to add, or an error message.
+/// Returns the time of day of a notification in the timezone of the user.
 ///
-/// # Returns
+/// The timezone of the user is the one of their quiet hours, UTC if they have none.
+fn get_time_of_day(time: NaiveTime, user_settings: &UserSettings) -> TimeOfDay {
+    TimeOfDay {
+        time,
+        timezone: user_settings
+            .quiet_hours
+            .as_ref()
+            .map_or(chrono_tz::UTC, |quiet_hours| quiet_hours.timezone),
+    }
+}
+
+/// Registers a user for turn notifications in a game.
 ///
-/// - `Some(CommandResult)`: Always returns a result (success or error message)
-/// - `None`: Only if the command is not a `Register` variant
-pub async fn handle_register(context: &CommandContext, command: &Command) -> Option<CommandResult> {
-    debug!("handling register command: {:?}", command);
-
-    let (game_id, player_name, delay) = match command {
-        Command::Register(game_id, player_name, delay) => {
-            (game_id.clone(), player_name.clone(), delay)
-        }
-        _ => return None,
+/// Validates the arguments and returns a `CommandResult` with either a success
+/// message and alert to add, or an error message.
+pub async fn handle_register(
+    context: &CommandContext,
+    arguments: &RegisterArguments,
+) -> CommandResult {
+    debug!("handling register command: {:?}", arguments);
+
+    let (game_id, player_name, delay, repeat_interval, max_reminders, phases) = {
+        let RegisterArguments {
+            game_id,
+            player_name,
+            delay,
+            repeat_interval,
+            max_reminders,
+            phases,
+        } = arguments;
+
+        (
+            context.resolve_game_id(game_id),
+            player_name.clone(),
+            delay,
+            *repeat_interval,
+            *max_reminders,
+            phases.clone(),
+        )
     };
 
     let CommandContext {
@@ -147,23 +269,38 @@ pub async fn handle_register(context: &CommandContext, command: &Command) -> Opt
         user_id,
         games_map,
         alerts_map: _,
+        user_settings,
+        room_settings: _,
+        default_server: _,
+        default_prefix: _,
+        servers: _,
+        turns_map: _,
     } = context;
 
-    let (player_id, player_url) =
-        match validate_and_get_player((game_id.clone(), player_name.clone(), *delay), games_map) {
-            Err(e) => {
-                return Some(CommandResult {
-                    response: format_register_error(e, game_id.as_str(), player_name.as_str()),
-                    alerts_to_remove: None,
-                    alert_to_add: None,
-                });
-            }
-            Ok(id) => id,
-        };
+    let validation = validate_reminders(repeat_interval, max_reminders).and_then(|_| {
+        validate_and_get_player((game_id.clone(), player_name.clone(), *delay), games_map)
+    });
+
+    let (player_id, player_url) = match validation {
+        Err(e) => {
+            return CommandResult {
+                response: format_register_error(e, game_id.as_str(), player_name.as_str()),
+                ..Default::default()
+            };
+        }
+        Ok(id) => id,
+    };
+
+    let (delay, time_of_day) = match delay {
+        Delay::Minutes(minutes) => (*minutes, None),
+        Delay::At(time) => (0, Some(get_time_of_day(*time, user_settings))),
+    };
 
     let result = CommandResult {
-        response: format_successful_register(),
-        alerts_to_remove: None,
+        response: match &time_of_day {
+            Some(time_of_day) => format_successful_register_at(time_of_day),
+            None => format_successful_register(),
+        },
         alert_to_add: Some((
             game_id,
             Alert {
@@ -171,22 +308,257 @@ pub async fn handle_register(context: &CommandContext, command: &Command) -> Opt
                 room_id: room_id.clone(),
                 user_id: user_id.clone(),
                 notified: false,
-                delay: *delay,
+                delay,
                 player_url,
+                turn_started_at: None,
+                notify_at: None,
+                repeat_interval,
+                max_reminders,
+                notifications_sent: 0,
+                phases,
+                time_of_day,
             },
         )),
+        ..Default::default()
     };
 
     debug!("register command result {:?}", result);
 
-    Some(result)
+    result
+}
+
+/// The `register` command.
+pub struct RegisterCommand;
+
+impl BotCommand for RegisterCommand {
+    type Arguments = RegisterArguments;
+
+    fn name(&self) -> &'static str {
+        "register"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["reg"]
+    }
+
+    fn usages(&self) -> &'static [Usage] {
+        &[Usage {
+            arguments: "<game_id> <player_name> <delay> [repeat_in_minutes] [max_reminders] [--phases <phase,...>]",
+            description: "register a new alert",
+        }]
+    }
+
+    fn arguments(&self) -> &'static [ArgumentHelp] {
+        &[
+            GAME_ID,
+            ArgumentHelp {
+                name: "player_name",
+                description: "name of the player in the game, quoted if it contains spaces",
+            },
+            ArgumentHelp {
+                name: "delay",
+                description: "minutes like `90` or a duration like `30m`, `2h` or `1d12h`, between 1 minute and 1 week, or a time of day like `@09:00` in the timezone of your quiet hours, UTC without quiet hours",
+            },
+            ArgumentHelp {
+                name: "repeat_in_minutes",
+                description: "optional, minutes between the reminders while the player hasn't played, between 1 minute and 1 week",
+            },
+            ArgumentHelp {
+                name: "max_reminders",
+                description: "optional, maximum number of reminders per turn, at least 1",
+            },
+            ArgumentHelp {
+                name: "--phases",
+                description: "optional, comma-separated phases in which the alert fires: `initialDrafting`, `preludes`, `ceos`, `research`, `drafting`, `action`, `production`, `solar`, `intergeneration` or `end`",
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                arguments: "game123 Alice 60",
+                description: "notify an hour after the turn of Alice starts",
+            },
+            Example {
+                arguments: "game123 \"Alice Smith\" 1d12h",
+                description: "notify a day and a half after the turn of Alice Smith starts",
+            },
+            Example {
+                arguments: "game123 Alice @09:00",
+                description: "notify at 09:00 if it's still the turn of Alice",
+            },
+            Example {
+                arguments: "game123 Alice 10 30 3 --phases action,production",
+                description: "notify after 10 minutes then every 30 minutes, 3 times at most, only in the action and production phases",
+            },
+        ]
+    }
+
+    fn schema(&self) -> ArgumentSchema {
+        // The game id, player name and delay, then optionally the repeat interval
+        // and the max number of reminders
+        ArgumentSchema {
+            min_arguments: 3,
+            max_arguments: Some(5),
+            options: &["phases"],
+        }
+    }
+
+    fn parse(
+        &self,
+        arguments: &[&str],
+        options: &HashMap<String, String>,
+    ) -> Result<RegisterArguments, ArgumentError> {
+        let game_id = arguments[0].to_owned();
+        let player_name = arguments[1].to_owned();
+        let delay = parse_delay(arguments[2]).ok_or_else(|| ArgumentError::InvalidValue {
+            argument: "delay",
+            value: arguments[2].to_owned(),
+            reason: match arguments[2].starts_with('@') {
+                true => "is not a time of day like `@09:00`",
+                false => "is not a number of minutes nor a duration like `2h`",
+            },
+        })?;
+        let repeat_interval = match arguments.get(3) {
+            None => None,
+            Some(repeat_interval) => Some(parse_number("repeat_in_minutes", repeat_interval)?),
+        };
+        let max_reminders = match arguments.get(4) {
+            None => None,
+            Some(max_reminders) => Some(parse_number("max_reminders", max_reminders)?),
+        };
+        let phases = match options.get("phases") {
+            None => Vec::new(),
+            Some(phases) => parse_phases(phases)?,
+        };
+
+        debug!(
+            "Parsed register command - game_id: {}, player_name: {}, delay: {:?}, repeat_interval: {:?}, max_reminders: {:?}, phases: {:?}",
+            game_id, player_name, delay, repeat_interval, max_reminders, phases
+        );
+
+        Ok(RegisterArguments {
+            game_id,
+            player_name,
+            delay,
+            repeat_interval,
+            max_reminders,
+            phases,
+        })
+    }
+
+    fn handle<'a>(
+        &self,
+        context: &'a CommandContext,
+        arguments: &'a RegisterArguments,
+    ) -> BoxFuture<'a, CommandResult> {
+        handle_register(context, arguments).boxed()
+    }
+}
+
+/// Parses a number argument of a register command.
+///
+/// # Errors
+///
+/// [`ArgumentError::InvalidValue`] naming the argument if the value is not a number.
+fn parse_number<T: std::str::FromStr>(
+    argument: &'static str,
+    value: &str,
+) -> Result<T, ArgumentError> {
+    value.parse::<T>().map_err(|_| ArgumentError::InvalidValue {
+        argument,
+        value: value.to_owned(),
+        reason: "is not a number",
+    })
+}
+
+/// Parses the delay of a register command.
+///
+/// The delay is a number of minutes (`90`), a duration made of days, hours and
+/// minutes (`30m`, `2h`, `1d12h`), or a time of day (`@09:00`).
+fn parse_delay(delay: &str) -> Option<Delay> {
+    if let Some(time) = delay.strip_prefix('@') {
+        return NaiveTime::parse_from_str(time, "%H:%M").ok().map(Delay::At);
+    }
+
+    if let Ok(minutes) = delay.parse::<u64>() {
+        return Some(Delay::Minutes(minutes));
+    }
+
+    parse_duration(delay).map(Delay::Minutes)
+}
+
+/// Parses a duration like `1d12h` into minutes.
+///
+/// Each unit (`d`, `h` or `m`) is given at most once, in this order.
+fn parse_duration(duration: &str) -> Option<u64> {
+    let mut units = [('d', 24 * 60), ('h', 60), ('m', 1)].into_iter();
+    let mut remaining = duration;
+    let mut minutes: u64 = 0;
+
+    if remaining.is_empty() {
+        return None;
+    }
+
+    while !remaining.is_empty() {
+        // A number without unit is invalid
+        let value_end = remaining.find(|c: char| !c.is_ascii_digit())?;
+        let value = remaining[..value_end].parse::<u64>().ok()?;
+        let unit = remaining[value_end..].chars().next()?;
+        let (_, unit_minutes) = units.find(|(name, _)| *name == unit)?;
+
+        minutes = minutes.checked_add(value.checked_mul(unit_minutes)?)?;
+        remaining = &remaining[value_end + unit.len_utf8()..];
+    }
+
+    Some(minutes)
+}
+
+/// Parses a comma-separated list of phase names, e.g. `action,production`.
+///
+/// The list must name at least one phase, and only phases known to the bot.
+///
+/// # Errors
+///
+/// [`ArgumentError::InvalidValue`] with the first unknown phase, or with the whole
+/// list if it names no phase.
+fn parse_phases(phases: &str) -> Result<Vec<Phase>, ArgumentError> {
+    let mut parsed_phases = Vec::new();
+
+    for name in phases.split(',').filter(|name| !name.is_empty()) {
+        let phase = Phase::from_name(name);
+        if matches!(phase, Phase::Unknown(_)) {
+            debug!("unknown phase {} in register command", name);
+            return Err(ArgumentError::InvalidValue {
+                argument: "phase",
+                value: name.to_owned(),
+                reason: "is unknown",
+            });
+        }
+        if !parsed_phases.contains(&phase) {
+            parsed_phases.push(phase);
+        }
+    }
+
+    match parsed_phases.is_empty() {
+        true => Err(ArgumentError::InvalidValue {
+            argument: "--phases",
+            value: phases.to_owned(),
+            reason: "names no phase",
+        }),
+        false => Ok(parsed_phases),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
 
-    use crate::tmars::{Phase, Player};
+    use crate::{
+        settings::{QuietHours, RoomSettings},
+        tmars::{Phase, Player},
+    };
 
     use super::*;
 
@@ -201,24 +573,91 @@ mod tests {
     #[test]
     fn test_format_register_error_game_not_found() {
         assert_eq!(
-            format_register_error(RegisterError::GameNotFound, "game_id", "player_name"),
-            format_game_not_found("game_id")
+            format_register_error(
+                RegisterError::GameNotFound(Some("main:game_id2".to_string())),
+                "main:game_id",
+                "player_name"
+            ),
+            format_game_not_found("main:game_id", Some("main:game_id2"))
         );
     }
 
     #[test]
     fn test_format_register_error_player_not_found() {
         assert_eq!(
-            format_register_error(RegisterError::PlayerNotFound, "game_id", "player_name"),
-            format_player_not_found("player_name", "game_id")
+            format_register_error(
+                RegisterError::PlayerNotFound(None),
+                "game_id",
+                "player_name"
+            ),
+            format_player_not_found("player_name", "game_id", None)
+        );
+    }
+
+    #[test]
+    fn test_format_register_error_invalid_repeat_interval() {
+        assert_eq!(
+            format_register_error(
+                RegisterError::InvalidRepeatInterval,
+                "game_id",
+                "player_name"
+            ),
+            format_invalid_repeat_interval()
+        );
+    }
+
+    #[test]
+    fn test_format_register_error_invalid_max_reminders() {
+        assert_eq!(
+            format_register_error(RegisterError::InvalidMaxReminders, "game_id", "player_name"),
+            format_invalid_max_reminders()
         );
     }
 
+    #[test]
+    fn test_validate_reminders_not_set() {
+        assert!(validate_reminders(None, None).is_ok());
+    }
+
+    #[test]
+    fn test_validate_reminders() {
+        assert!(validate_reminders(Some(30), Some(3)).is_ok());
+        assert!(validate_reminders(Some(30), None).is_ok());
+    }
+
+    #[test]
+    fn test_validate_reminders_repeat_interval_0() {
+        assert!(matches!(
+            validate_reminders(Some(0), None),
+            Err(RegisterError::InvalidRepeatInterval)
+        ));
+    }
+
+    #[test]
+    fn test_validate_reminders_repeat_interval_2_weeks() {
+        assert!(matches!(
+            validate_reminders(Some(2 * 7 * 24 * 60), None),
+            Err(RegisterError::InvalidRepeatInterval)
+        ));
+    }
+
+    #[test]
+    fn test_validate_reminders_max_reminders_0() {
+        assert!(matches!(
+            validate_reminders(Some(30), Some(0)),
+            Err(RegisterError::InvalidMaxReminders)
+        ));
+    }
+
     #[test]
     fn test_validate_and_get_player_delay_0() {
         assert!(matches!(
             validate_and_get_player(
-                ("game_id".to_string(), "player_name".to_string(), 0),
+                (
+                    "game_id".to_string(),
+                    "player_name".to_string(),
+                    Delay::Minutes(0)
+                ),
                 &HashMap::new()
             ),
             Err(RegisterError::InvalidDelay)
@@ -231,7 +670,11 @@ mod tests {
         let weeks = 2 * 7 * 24 * 60;
         assert!(matches!(
             validate_and_get_player(
-                ("game_id".to_string(), "player_name".to_string(), weeks),
+                (
+                    "game_id".to_string(),
+                    "player_name".to_string(),
+                    Delay::Minutes(weeks)
+                ),
                 &HashMap::new()
             ),
             Err(RegisterError::InvalidDelay)
@@ -242,10 +685,14 @@ mod tests {
     fn test_validate_and_get_player_game_not_found() {
         assert!(matches!(
             validate_and_get_player(
-                ("game_id".to_string(), "player_name".to_string(), 1),
+                (
+                    "game_id".to_string(),
+                    "player_name".to_string(),
+                    Delay::Minutes(1)
+                ),
                 &HashMap::new()
             ),
-            Err(RegisterError::GameNotFound)
+            Err(RegisterError::GameNotFound(None))
         ));
     }
 
@@ -256,16 +703,22 @@ mod tests {
             id: game_id.to_owned(),
             phase: Phase::Research,
             spectator_id: "spectator_id".to_string(),
+            spectator_url: "http://example.com/spectator".to_string(),
             players: Vec::new(),
             waited_players: HashSet::new(),
+            progress: None,
         };
 
         assert!(matches!(
             validate_and_get_player(
-                (game_id.to_owned(), "player_name".to_string(), 1),
+                (
+                    game_id.to_owned(),
+                    "player_name".to_string(),
+                    Delay::Minutes(1)
+                ),
                 &HashMap::from([(game_id, game)])
             ),
-            Err(RegisterError::PlayerNotFound)
+            Err(RegisterError::PlayerNotFound(None))
         ));
     }
 
@@ -280,18 +733,22 @@ mod tests {
             color: "red".to_string(),
             name: player_name.to_owned(),
             url: "http://example.com/player".to_string(),
+            terraform_rating: None,
+            waiting_since: None,
         };
         let game = Game {
             id: game_id.to_owned(),
             phase: Phase::Research,
             spectator_id: "spectator_id".to_string(),
+            spectator_url: "http://example.com/spectator".to_string(),
             players: Vec::from([player]),
             waited_players: HashSet::new(),
+            progress: None,
         };
 
         assert_eq!(
             validate_and_get_player(
-                (game_id.to_owned(), player_name, 1),
+                (game_id.to_owned(), player_name, Delay::Minutes(1)),
                 &HashMap::from([(game_id, game)])
             )
             .unwrap()
@@ -312,6 +769,12 @@ mod tests {
             alerts_map: HashMap::new(),
             room_id: "!test_room:matrix.org".to_string(),
             user_id: "@test_user:matrix.org".to_string(),
+            user_settings: UserSettings::default(),
+            room_settings: RoomSettings::default(),
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["main".to_string()],
+            turns_map: HashMap::new(),
         }
     }
 
@@ -321,6 +784,7 @@ mod tests {
             id: id.to_string(),
             phase: Phase::Action,
             spectator_id: format!("spectator_{}", id),
+            spectator_url: "http://example.com/spectator".to_string(),
             players: players
                 .iter()
                 .map(|(id, name, color)| Player {
@@ -328,45 +792,177 @@ mod tests {
                     name: name.to_string(),
                     color: color.to_string(),
                     url: "http://example.com/player".to_string(),
+                    terraform_rating: None,
+                    waiting_since: None,
                 })
                 .collect(),
             waited_players: HashSet::new(),
+            progress: None,
         }
     }
 
     #[tokio::test]
     async fn test_handle_register_successful() {
-        let game = create_test_game("game1", vec![("player1", "Alice", "red")]);
+        let game = create_test_game("main:game1", vec![("player1", "Alice", "red")]);
         let context = create_test_context(vec![game]);
-        let command = Command::Register("game1".to_string(), "Alice".to_string(), 60);
+        let arguments = RegisterArguments {
+            game_id: "game1".to_string(),
+            player_name: "Alice".to_string(),
+            delay: Delay::Minutes(60),
+            repeat_interval: None,
+            max_reminders: None,
+            phases: Vec::new(),
+        };
 
-        let result = handle_register(&context, &command).await;
+        let result = handle_register(&context, &arguments).await;
 
-        assert!(result.is_some());
-        let result = result.unwrap();
         assert_eq!(result.response, format_successful_register());
         assert!(result.alert_to_add.is_some());
         assert!(result.alerts_to_remove.is_none());
 
         let (game_id, alert) = result.alert_to_add.unwrap();
-        assert_eq!(game_id, "game1");
+        assert_eq!(game_id, "main:game1");
         assert_eq!(alert.player_id, "player1");
         assert_eq!(alert.user_id, "@test_user:matrix.org");
         assert_eq!(alert.room_id, "!test_room:matrix.org");
         assert_eq!(alert.delay, 60);
+        assert!(alert.repeat_interval.is_none());
+        assert!(alert.max_reminders.is_none());
+        assert!(alert.phases.is_empty());
         assert!(!alert.notified);
     }
 
+    #[tokio::test]
+    async fn test_handle_register_with_phases() {
+        let game = create_test_game("main:game1", vec![("player1", "Alice", "red")]);
+        let context = create_test_context(vec![game]);
+        let arguments = RegisterArguments {
+            game_id: "game1".to_string(),
+            player_name: "Alice".to_string(),
+            delay: Delay::Minutes(60),
+            repeat_interval: None,
+            max_reminders: None,
+            phases: vec![Phase::Action],
+        };
+
+        let result = handle_register(&context, &arguments).await;
+
+        let (_, alert) = result.alert_to_add.unwrap();
+        assert_eq!(alert.phases, vec![Phase::Action]);
+    }
+
+    #[tokio::test]
+    async fn test_handle_register_namespaced_game_id() {
+        let game = create_test_game("friends:game1", vec![("player1", "Alice", "red")]);
+        let context = create_test_context(vec![game]);
+        let arguments = RegisterArguments {
+            game_id: "friends:game1".to_string(),
+            player_name: "Alice".to_string(),
+            delay: Delay::Minutes(60),
+            repeat_interval: None,
+            max_reminders: None,
+            phases: Vec::new(),
+        };
+
+        let result = handle_register(&context, &arguments).await;
+
+        assert_eq!(result.response, format_successful_register());
+        let (game_id, _) = result.alert_to_add.unwrap();
+        assert_eq!(game_id, "friends:game1");
+    }
+
+    #[tokio::test]
+    async fn test_handle_register_with_reminders() {
+        let game = create_test_game("main:game1", vec![("player1", "Alice", "red")]);
+        let context = create_test_context(vec![game]);
+        let arguments = RegisterArguments {
+            game_id: "game1".to_string(),
+            player_name: "Alice".to_string(),
+            delay: Delay::Minutes(60),
+            repeat_interval: Some(30),
+            max_reminders: Some(3),
+            phases: Vec::new(),
+        };
+
+        let result = handle_register(&context, &arguments).await;
+
+        assert_eq!(result.response, format_successful_register());
+        let (_, alert) = result.alert_to_add.unwrap();
+        assert_eq!(alert.repeat_interval, Some(30));
+        assert_eq!(alert.max_reminders, Some(3));
+        assert_eq!(alert.notifications_sent, 0);
+    }
+
+    #[tokio::test]
+    async fn test_handle_register_invalid_repeat_interval() {
+        let game = create_test_game("main:game1", vec![("player1", "Alice", "red")]);
+        let context = create_test_context(vec![game]);
+        let arguments = RegisterArguments {
+            game_id: "game1".to_string(),
+            player_name: "Alice".to_string(),
+            delay: Delay::Minutes(60),
+            repeat_interval: Some(0),
+            max_reminders: None,
+            phases: Vec::new(),
+        };
+
+        let result = handle_register(&context, &arguments).await;
+
+        assert_eq!(result.response, format_invalid_repeat_interval());
+        assert!(result.alert_to_add.is_none());
+        assert!(result.alerts_to_remove.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handle_register_at_time_of_day() {
+        let game = create_test_game("main:game1", vec![("player1", "Alice", "red")]);
+        let mut context = create_test_context(vec![game]);
+        context.user_settings.quiet_hours = QuietHours::parse("23:00-08:00", "Europe/Paris").ok();
+        let arguments = RegisterArguments {
+            game_id: "game1".to_string(),
+            player_name: "Alice".to_string(),
+            delay: Delay::At(NaiveTime::from_hms_opt(9, 0, 0).unwrap()),
+            repeat_interval: None,
+            max_reminders: None,
+            phases: Vec::new(),
+        };
+
+        let result = handle_register(&context, &arguments).await;
+
+        // The time of day is in the timezone of the user
+        let time_of_day = TimeOfDay {
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            timezone: chrono_tz::Europe::Paris,
+        };
+        assert_eq!(result.response, format_successful_register_at(&time_of_day));
+        let (_, alert) = result.alert_to_add.unwrap();
+        assert_eq!(alert.time_of_day, Some(time_of_day));
+    }
+
+    #[test]
+    fn test_get_time_of_day_without_quiet_hours() {
+        let time = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+
+        let time_of_day = get_time_of_day(time, &UserSettings::default());
+
+        assert_eq!(time_of_day.timezone, chrono_tz::UTC);
+    }
+
     #[tokio::test]
     async fn test_handle_register_invalid_delay_zero() {
-        let game = create_test_game("game1", vec![("player1", "Alice", "red")]);
+        let game = create_test_game("main:game1", vec![("player1", "Alice", "red")]);
         let context = create_test_context(vec![game]);
-        let command = Command::Register("game1".to_string(), "Alice".to_string(), 0);
+        let arguments = RegisterArguments {
+            game_id: "game1".to_string(),
+            player_name: "Alice".to_string(),
+            delay: Delay::Minutes(0),
+            repeat_interval: None,
+            max_reminders: None,
+            phases: Vec::new(),
+        };
 
-        let result = handle_register(&context, &command).await;
+        let result = handle_register(&context, &arguments).await;
 
-        assert!(result.is_some());
-        let result = result.unwrap();
         assert_eq!(result.response, format_invalid_delay());
         assert!(result.alert_to_add.is_none());
         assert!(result.alerts_to_remove.is_none());
@@ -375,40 +971,94 @@ mod tests {
     #[tokio::test]
     async fn test_handle_register_game_not_found() {
         let context = create_test_context(vec![]);
-        let command = Command::Register("game999".to_string(), "Alice".to_string(), 60);
+        let arguments = RegisterArguments {
+            game_id: "game999".to_string(),
+            player_name: "Alice".to_string(),
+            delay: Delay::Minutes(60),
+            repeat_interval: None,
+            max_reminders: None,
+            phases: Vec::new(),
+        };
 
-        let result = handle_register(&context, &command).await;
+        let result = handle_register(&context, &arguments).await;
 
-        assert!(result.is_some());
-        let result = result.unwrap();
-        assert_eq!(result.response, format_game_not_found("game999"));
+        assert_eq!(result.response, format_game_not_found("main:game999", None));
         assert!(result.alert_to_add.is_none());
         assert!(result.alerts_to_remove.is_none());
     }
 
     #[tokio::test]
     async fn test_handle_register_player_not_found() {
-        let game = create_test_game("game1", vec![("player1", "Alice", "red")]);
+        let game = create_test_game("main:game1", vec![("player1", "Alice", "red")]);
         let context = create_test_context(vec![game]);
-        let command = Command::Register("game1".to_string(), "Bob".to_string(), 60);
+        let arguments = RegisterArguments {
+            game_id: "game1".to_string(),
+            player_name: "Bob".to_string(),
+            delay: Delay::Minutes(60),
+            repeat_interval: None,
+            max_reminders: None,
+            phases: Vec::new(),
+        };
 
-        let result = handle_register(&context, &command).await;
+        let result = handle_register(&context, &arguments).await;
 
-        assert!(result.is_some());
-        let result = result.unwrap();
-        assert_eq!(result.response, format_player_not_found("Bob", "game1"));
+        assert_eq!(
+            result.response,
+            format_player_not_found("Bob", "main:game1", None)
+        );
         assert!(result.alert_to_add.is_none());
         assert!(result.alerts_to_remove.is_none());
     }
 
     #[tokio::test]
-    async fn test_handle_register_wrong_command_type() {
-        let game = create_test_game("game1", vec![("player1", "Alice", "red")]);
+    async fn test_handle_register_suggestions() {
+        let game = create_test_game("main:game1", vec![("player1", "Alice", "red")]);
         let context = create_test_context(vec![game]);
-        let command = Command::Help;
+        let mut arguments = RegisterArguments {
+            game_id: "gme1".to_string(),
+            player_name: "Alice".to_string(),
+            delay: Delay::Minutes(60),
+            repeat_interval: None,
+            max_reminders: None,
+            phases: Vec::new(),
+        };
 
-        let result = handle_register(&context, &command).await;
+        let result = handle_register(&context, &arguments).await;
+        assert_eq!(
+            result.response,
+            format_game_not_found("main:gme1", Some("main:game1"))
+        );
+
+        arguments.game_id = "game1".to_string();
+        arguments.player_name = "Alise".to_string();
+        let result = handle_register(&context, &arguments).await;
+        assert_eq!(
+            result.response,
+            format_player_not_found("Alise", "main:game1", Some("Alice"))
+        );
+    }
 
-        assert!(result.is_none());
+    #[tokio::test]
+    async fn test_handle_register_case_insensitive_player_name() {
+        let game = create_test_game(
+            "main:game1",
+            vec![("player1", "Alice", "red"), ("player2", "alice", "blue")],
+        );
+        let context = create_test_context(vec![game]);
+
+        for (player_name, player_id) in [("ALICE", "player1"), ("alice", "player2")] {
+            let arguments = RegisterArguments {
+                game_id: "game1".to_string(),
+                player_name: player_name.to_string(),
+                delay: Delay::Minutes(60),
+                repeat_interval: None,
+                max_reminders: None,
+                phases: Vec::new(),
+            };
+
+            let result = handle_register(&context, &arguments).await;
+            let (_, alert) = result.alert_to_add.unwrap();
+            assert_eq!(alert.player_id, player_id, "{}", player_name);
+        }
     }
 }
@@ -0,0 +1,217 @@
+//! Room server command handler.
+//!
+//! Displays and updates the server of the game IDs without a server name in the
+//! current Matrix room. Game IDs are namespaced by server (e.g. `main:g123`), the
+//! server of the room lets its users keep using the bare game IDs.
+//!
+//! # Validation
+//!
+//! The server must be one of the servers of the configuration.
+
+use std::collections::HashMap;
+
+use futures::{FutureExt, future::BoxFuture};
+use log::debug;
+
+use crate::{
+    commands::{
+        CommandContext, CommandResult,
+        markdown_response::{format_server, format_successful_server, format_unknown_server},
+        registry::{ArgumentError, ArgumentHelp, ArgumentSchema, BotCommand, Example, Usage},
+    },
+    settings::RoomSettings,
+};
+
+/// Displays or updates the server of the current room.
+///
+/// Updates are not applied directly, they are returned in `room_settings_to_update`
+/// and saved by the caller.
+///
+/// # Returns
+///
+/// The server of the room if no server is given, a success message with the room
+/// settings to save, or an error message if the server is unknown.
+pub async fn handle_server(context: &CommandContext, server: Option<&str>) -> CommandResult {
+    debug!("handling server command: {:?}", server);
+
+    let CommandContext {
+        room_id,
+        user_id: _,
+        games_map: _,
+        alerts_map: _,
+        user_settings: _,
+        room_settings,
+        default_server,
+        default_prefix: _,
+        servers,
+        turns_map: _,
+    } = context;
+
+    let result = match server {
+        None => CommandResult {
+            response: format_server(default_server, servers),
+            ..Default::default()
+        },
+        Some(server) => match servers.iter().any(|name| name == server) {
+            true => CommandResult {
+                response: format_successful_server(server),
+                room_settings_to_update: Some((
+                    room_id.clone(),
+                    with_server(room_settings, server),
+                )),
+                ..Default::default()
+            },
+            false => CommandResult {
+                response: format_unknown_server(server, servers),
+                ..Default::default()
+            },
+        },
+    };
+
+    debug!("server command result {:?}", result);
+
+    result
+}
+
+/// Returns a copy of the room settings with the given server.
+fn with_server(room_settings: &RoomSettings, server: &str) -> RoomSettings {
+    let mut room_settings = room_settings.clone();
+    room_settings.server = Some(server.to_owned());
+    room_settings
+}
+
+/// The `server` command.
+pub struct ServerCommand;
+
+impl BotCommand for ServerCommand {
+    /// Server to set, `None` to show the server of the room
+    type Arguments = Option<String>;
+
+    fn name(&self) -> &'static str {
+        "server"
+    }
+
+    fn usages(&self) -> &'static [Usage] {
+        &[
+            Usage {
+                arguments: "",
+                description: "show the server of the game IDs without a server name in this room",
+            },
+            Usage {
+                arguments: "<server_name>",
+                description: "set the server of the game IDs without a server name in this room",
+            },
+        ]
+    }
+
+    fn schema(&self) -> ArgumentSchema {
+        ArgumentSchema {
+            min_arguments: 0,
+            max_arguments: Some(1),
+            options: &[],
+        }
+    }
+
+    fn arguments(&self) -> &'static [ArgumentHelp] {
+        &[ArgumentHelp {
+            name: "server_name",
+            description: "name of a server of the bot, listed by `server`",
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                arguments: "",
+                description: "show the server of this room and the servers of the bot",
+            },
+            Example {
+                arguments: "friends",
+                description: "read `game123` as `friends:game123` in this room",
+            },
+        ]
+    }
+
+    fn parse(
+        &self,
+        arguments: &[&str],
+        _: &HashMap<String, String>,
+    ) -> Result<Option<String>, ArgumentError> {
+        Ok(arguments.first().map(|server| server.to_string()))
+    }
+
+    fn handle<'a>(
+        &self,
+        context: &'a CommandContext,
+        server: &'a Option<String>,
+    ) -> BoxFuture<'a, CommandResult> {
+        handle_server(context, server.as_deref()).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::settings::{NotificationTemplate, UserSettings};
+
+    fn create_test_context() -> CommandContext {
+        CommandContext {
+            games_map: HashMap::new(),
+            alerts_map: HashMap::new(),
+            room_id: "!test_room:matrix.org".to_string(),
+            user_id: "@test_user:matrix.org".to_string(),
+            user_settings: UserSettings::default(),
+            room_settings: RoomSettings::default(),
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["friends".to_string(), "main".to_string()],
+            turns_map: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_server_display() {
+        let context = create_test_context();
+
+        let result = handle_server(&context, None).await;
+
+        assert_eq!(result.response, format_server("main", &context.servers));
+        assert!(result.room_settings_to_update.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handle_set_server() {
+        let mut context = create_test_context();
+        context.room_settings.template = NotificationTemplate::parse("{player}, play!").ok();
+
+        let result = handle_server(&context, Some("friends")).await;
+
+        assert_eq!(result.response, format_successful_server("friends"));
+        // The other settings of the room are kept
+        assert_eq!(
+            result.room_settings_to_update,
+            Some((
+                "!test_room:matrix.org".to_string(),
+                RoomSettings {
+                    server: Some("friends".to_string()),
+                    template: NotificationTemplate::parse("{player}, play!").ok(),
+                    prefix: None,
+                },
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_set_unknown_server() {
+        let context = create_test_context();
+        let result = handle_server(&context, Some("other")).await;
+
+        assert_eq!(
+            result.response,
+            format_unknown_server("other", &context.servers)
+        );
+        assert!(result.room_settings_to_update.is_none());
+    }
+}
@@ -0,0 +1,321 @@
+//! User settings command handler.
+//!
+//! Displays and updates the preferences of the requesting user.
+//!
+//! # Quiet Hours
+//!
+//! Quiet hours are a daily window in the user's timezone during which the bot
+//! doesn't mention the user. Notifications falling into this window are deferred
+//! to its end.
+//!
+//! # Validation
+//!
+//! The handler validates these constraints:
+//! - **Window**: Must be formatted as `HH:MM-HH:MM` and not be empty
+//! - **Timezone**: Must be a known IANA timezone name (e.g. `Europe/Paris`)
+
+use std::collections::HashMap;
+
+use futures::{FutureExt, future::BoxFuture};
+use log::debug;
+
+use crate::{
+    commands::{
+        CommandContext, CommandResult,
+        markdown_response::{
+            format_invalid_quiet_hours, format_quiet_hours_disabled, format_settings,
+            format_successful_quiet_hours, format_unknown_timezone,
+        },
+        registry::{ArgumentError, ArgumentHelp, ArgumentSchema, BotCommand, Example, Usage},
+    },
+    settings::{QuietHours, QuietHoursError, UserSettings},
+};
+
+/// Action of the `settings` command.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SettingsArguments {
+    /// Show the settings of the user
+    Show,
+    /// Set the quiet hours of the user, not validated yet
+    SetQuietHours {
+        /// Window of the quiet hours, e.g. `23:00-08:00`
+        window: String,
+        /// IANA name of the timezone of the window, e.g. `Europe/Paris`
+        timezone: String,
+    },
+    /// Disable the quiet hours of the user
+    DisableQuietHours,
+}
+
+/// Displays or updates the settings of the requesting user.
+///
+/// Updates are not applied directly, they are returned in `user_settings_to_update`
+/// and saved by the caller.
+///
+/// # Returns
+///
+/// The settings, a success message with the settings to save, or an error message
+/// if the quiet hours are invalid.
+pub async fn handle_settings(
+    context: &CommandContext,
+    arguments: &SettingsArguments,
+) -> CommandResult {
+    debug!("handling settings command: {:?}", arguments);
+
+    let CommandContext {
+        room_id: _,
+        user_id,
+        games_map: _,
+        alerts_map: _,
+        user_settings,
+        room_settings: _,
+        default_server: _,
+        default_prefix: _,
+        servers: _,
+        turns_map: _,
+    } = context;
+
+    let result = match arguments {
+        SettingsArguments::Show => CommandResult {
+            response: format_settings(user_settings.quiet_hours.as_ref()),
+            ..Default::default()
+        },
+        SettingsArguments::SetQuietHours { window, timezone } => {
+            match QuietHours::parse(window, timezone) {
+                Ok(quiet_hours) => CommandResult {
+                    response: format_successful_quiet_hours(&quiet_hours),
+                    user_settings_to_update: Some((
+                        user_id.clone(),
+                        with_quiet_hours(user_settings, Some(quiet_hours)),
+                    )),
+                    ..Default::default()
+                },
+                Err(e) => CommandResult {
+                    response: format_quiet_hours_error(e, timezone),
+                    ..Default::default()
+                },
+            }
+        }
+        SettingsArguments::DisableQuietHours => CommandResult {
+            response: format_quiet_hours_disabled(),
+            user_settings_to_update: Some((user_id.clone(), with_quiet_hours(user_settings, None))),
+            ..Default::default()
+        },
+    };
+
+    debug!("settings command result {:?}", result);
+
+    result
+}
+
+/// Returns a copy of the user settings with the given quiet hours.
+fn with_quiet_hours(user_settings: &UserSettings, quiet_hours: Option<QuietHours>) -> UserSettings {
+    let mut user_settings = user_settings.clone();
+    user_settings.quiet_hours = quiet_hours;
+    user_settings
+}
+
+/// Formats a quiet hours error into a user-friendly message.
+fn format_quiet_hours_error(error: QuietHoursError, timezone: &str) -> String {
+    match error {
+        QuietHoursError::InvalidWindow => format_invalid_quiet_hours(),
+        QuietHoursError::UnknownTimezone => format_unknown_timezone(timezone),
+    }
+}
+
+/// The `settings` command.
+pub struct SettingsCommand;
+
+impl BotCommand for SettingsCommand {
+    type Arguments = SettingsArguments;
+
+    fn name(&self) -> &'static str {
+        "settings"
+    }
+
+    fn usages(&self) -> &'static [Usage] {
+        &[
+            Usage {
+                arguments: "",
+                description: "show your settings",
+            },
+            Usage {
+                arguments: "quiet <start>-<end> <timezone>",
+                description: "set your quiet hours, e.g. `settings quiet 23:00-08:00 Europe/Paris`",
+            },
+            Usage {
+                arguments: "quiet off",
+                description: "disable your quiet hours",
+            },
+        ]
+    }
+
+    fn schema(&self) -> ArgumentSchema {
+        ArgumentSchema {
+            min_arguments: 0,
+            max_arguments: Some(3),
+            options: &[],
+        }
+    }
+
+    fn arguments(&self) -> &'static [ArgumentHelp] {
+        &[
+            ArgumentHelp {
+                name: "<start>-<end>",
+                description: "times formatted as `HH:MM`, the quiet hours can span midnight like `23:00-08:00`",
+            },
+            ArgumentHelp {
+                name: "timezone",
+                description: "IANA name of the timezone of the quiet hours, e.g. `Europe/Paris` or `UTC`",
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                arguments: "",
+                description: "show your quiet hours",
+            },
+            Example {
+                arguments: "quiet 23:00-08:00 Europe/Paris",
+                description: "hold your notifications from 23:00 to 08:00, Paris time",
+            },
+            Example {
+                arguments: "quiet off",
+                description: "send your notifications at any time",
+            },
+        ]
+    }
+
+    fn parse(
+        &self,
+        arguments: &[&str],
+        _: &HashMap<String, String>,
+    ) -> Result<SettingsArguments, ArgumentError> {
+        match arguments {
+            [] => Ok(SettingsArguments::Show),
+            ["quiet", "off"] => Ok(SettingsArguments::DisableQuietHours),
+            ["quiet", window, timezone] => Ok(SettingsArguments::SetQuietHours {
+                window: window.to_string(),
+                timezone: timezone.to_string(),
+            }),
+            ["quiet", ..] => Err(ArgumentError::MissingArguments),
+            [setting, ..] => Err(ArgumentError::InvalidValue {
+                argument: "setting",
+                value: setting.to_string(),
+                reason: "is unknown, expected `quiet`",
+            }),
+        }
+    }
+
+    fn handle<'a>(
+        &self,
+        context: &'a CommandContext,
+        arguments: &'a SettingsArguments,
+    ) -> BoxFuture<'a, CommandResult> {
+        handle_settings(context, arguments).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::settings::RoomSettings;
+
+    fn create_test_context(user_settings: UserSettings) -> CommandContext {
+        CommandContext {
+            games_map: HashMap::new(),
+            alerts_map: HashMap::new(),
+            room_id: "!test_room:matrix.org".to_string(),
+            user_id: "@test_user:matrix.org".to_string(),
+            user_settings,
+            room_settings: RoomSettings::default(),
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["main".to_string()],
+            turns_map: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_settings_display() {
+        let quiet_hours = QuietHours::parse("23:00-08:00", "Europe/Paris").unwrap();
+        let context = create_test_context(UserSettings {
+            quiet_hours: Some(quiet_hours.clone()),
+        });
+
+        let result = handle_settings(&context, &SettingsArguments::Show).await;
+
+        assert_eq!(result.response, format_settings(Some(&quiet_hours)));
+        assert!(result.user_settings_to_update.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handle_settings_set_quiet_hours() {
+        let context = create_test_context(UserSettings::default());
+        let arguments = SettingsArguments::SetQuietHours {
+            window: "23:00-08:00".to_string(),
+            timezone: "Europe/Paris".to_string(),
+        };
+
+        let result = handle_settings(&context, &arguments).await;
+
+        let quiet_hours = QuietHours::parse("23:00-08:00", "Europe/Paris").unwrap();
+        assert_eq!(result.response, format_successful_quiet_hours(&quiet_hours));
+        assert!(result.alert_to_add.is_none());
+        assert!(result.alerts_to_remove.is_none());
+
+        let (user_id, user_settings) = result.user_settings_to_update.unwrap();
+        assert_eq!(user_id, "@test_user:matrix.org");
+        assert_eq!(user_settings.quiet_hours, Some(quiet_hours));
+    }
+
+    #[tokio::test]
+    async fn test_handle_settings_set_quiet_hours_invalid_window() {
+        let context = create_test_context(UserSettings::default());
+        let arguments = SettingsArguments::SetQuietHours {
+            window: "night".to_string(),
+            timezone: "Europe/Paris".to_string(),
+        };
+
+        let result = handle_settings(&context, &arguments).await;
+
+        assert_eq!(result.response, format_invalid_quiet_hours());
+        assert!(result.user_settings_to_update.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handle_settings_set_quiet_hours_unknown_timezone() {
+        let context = create_test_context(UserSettings::default());
+        let arguments = SettingsArguments::SetQuietHours {
+            window: "23:00-08:00".to_string(),
+            timezone: "Mars/Olympus_Mons".to_string(),
+        };
+
+        let result = handle_settings(&context, &arguments).await;
+
+        assert_eq!(
+            result.response,
+            format_unknown_timezone("Mars/Olympus_Mons")
+        );
+        assert!(result.user_settings_to_update.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handle_settings_disable_quiet_hours() {
+        let context = create_test_context(UserSettings {
+            quiet_hours: QuietHours::parse("23:00-08:00", "Europe/Paris").ok(),
+        });
+
+        let result = handle_settings(&context, &SettingsArguments::DisableQuietHours).await;
+
+        assert_eq!(result.response, format_quiet_hours_disabled());
+
+        let (user_id, user_settings) = result.user_settings_to_update.unwrap();
+        assert_eq!(user_id, "@test_user:matrix.org");
+        assert_eq!(user_settings.quiet_hours, None);
+    }
+}
@@ -0,0 +1,206 @@
+//! Stats command handler.
+//!
+//! Displays the response-time statistics of the players: the average and median
+//! duration of their turns, and the slowest turns. The statistics cover a single
+//! game, or all the games recorded since the bot follows them.
+//!
+//! # Validation
+//!
+//! The game doesn't have to be ongoing, the turns of ended games are kept.
+
+use std::collections::HashMap;
+
+use futures::{FutureExt, future::BoxFuture};
+use log::debug;
+
+use crate::{
+    commands::{
+        CommandContext, CommandResult,
+        markdown_response::format_stats,
+        registry::{ArgumentError, ArgumentHelp, ArgumentSchema, BotCommand, Example, Usage},
+    },
+    history::TurnStats,
+};
+
+/// Shows the turn statistics of a game or of all games.
+///
+/// # Returns
+///
+/// The statistics, or a message if no turn was recorded.
+pub async fn handle_stats(context: &CommandContext, game_id: Option<&str>) -> CommandResult {
+    debug!("handling stats command: {:?}", game_id);
+
+    let game_id = game_id.map(|game_id| context.resolve_game_id(game_id));
+
+    let CommandContext {
+        room_id: _,
+        user_id: _,
+        games_map: _,
+        alerts_map: _,
+        user_settings: _,
+        room_settings: _,
+        default_server: _,
+        default_prefix: _,
+        servers: _,
+        turns_map,
+    } = context;
+
+    let turn_stats = match &game_id {
+        Some(game_id) => TurnStats::from_turns_map(&HashMap::from([(
+            game_id.clone(),
+            turns_map.get(game_id).cloned().unwrap_or_default(),
+        )])),
+        None => TurnStats::from_turns_map(turns_map),
+    };
+
+    let result = CommandResult {
+        response: format_stats(game_id.as_deref(), &turn_stats),
+        ..Default::default()
+    };
+
+    debug!("stats command result {:?}", result);
+
+    result
+}
+
+/// The `stats` command.
+pub struct StatsCommand;
+
+impl BotCommand for StatsCommand {
+    /// ID of the game, as written in the message, `None` for all the games
+    type Arguments = Option<String>;
+
+    fn name(&self) -> &'static str {
+        "stats"
+    }
+
+    fn usages(&self) -> &'static [Usage] {
+        &[Usage {
+            arguments: "[game_id]",
+            description: "show the turn times of the players of a game, or of all games",
+        }]
+    }
+
+    fn schema(&self) -> ArgumentSchema {
+        ArgumentSchema {
+            min_arguments: 0,
+            max_arguments: Some(1),
+            options: &[],
+        }
+    }
+
+    fn arguments(&self) -> &'static [ArgumentHelp] {
+        &[ArgumentHelp {
+            name: "game_id",
+            description: "optional, ID of the game, prefixed with its server name like `main:game123` when it's not the server of the room",
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                arguments: "",
+                description: "show the turn times of the players of all the games",
+            },
+            Example {
+                arguments: "game123",
+                description: "show the turn times of the players of game123",
+            },
+        ]
+    }
+
+    fn parse(
+        &self,
+        arguments: &[&str],
+        _: &HashMap<String, String>,
+    ) -> Result<Option<String>, ArgumentError> {
+        Ok(arguments.first().map(|game_id| game_id.to_string()))
+    }
+
+    fn handle<'a>(
+        &self,
+        context: &'a CommandContext,
+        game_id: &'a Option<String>,
+    ) -> BoxFuture<'a, CommandResult> {
+        handle_stats(context, game_id.as_deref()).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        history::Turn,
+        settings::{RoomSettings, UserSettings},
+    };
+
+    fn create_test_turn(player_name: &str, duration: u64) -> Turn {
+        Turn {
+            player_id: player_name.to_lowercase(),
+            player_name: player_name.to_string(),
+            started_at: 1000,
+            ended_at: 1000 + duration,
+        }
+    }
+
+    fn create_test_context() -> CommandContext {
+        CommandContext {
+            games_map: HashMap::new(),
+            alerts_map: HashMap::new(),
+            room_id: "!test_room:matrix.org".to_string(),
+            user_id: "@test_user:matrix.org".to_string(),
+            user_settings: UserSettings::default(),
+            room_settings: RoomSettings::default(),
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["main".to_string()],
+            turns_map: HashMap::from([
+                (
+                    "main:game123".to_string(),
+                    vec![create_test_turn("Alice", 600)],
+                ),
+                (
+                    "main:game456".to_string(),
+                    vec![create_test_turn("Bob", 1200)],
+                ),
+            ]),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_stats_of_game() {
+        let context = create_test_context();
+        let result = handle_stats(&context, Some("game123")).await;
+
+        let turn_stats = TurnStats::from_turns_map(&HashMap::from([(
+            "main:game123".to_string(),
+            vec![create_test_turn("Alice", 600)],
+        )]));
+        assert_eq!(
+            result.response,
+            format_stats(Some("main:game123"), &turn_stats)
+        );
+        assert!(result.alert_to_add.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handle_stats_of_all_games() {
+        let context = create_test_context();
+
+        let result = handle_stats(&context, None).await;
+
+        let turn_stats = TurnStats::from_turns_map(&context.turns_map);
+        assert_eq!(result.response, format_stats(None, &turn_stats));
+    }
+
+    #[tokio::test]
+    async fn test_handle_stats_of_game_without_turns() {
+        let context = create_test_context();
+        let result = handle_stats(&context, Some("unknown")).await;
+
+        assert_eq!(
+            result.response,
+            "No turns recorded yet for game **main:unknown**."
+        );
+    }
+}
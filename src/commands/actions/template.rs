@@ -0,0 +1,302 @@
+//! Room template command handler.
+//!
+//! Displays and updates the template of the turn notifications of the alerts of
+//! the current Matrix room. The template replaces the default text of the
+//! notifications, its placeholders are replaced by the context of the turn.
+//!
+//! # Validation
+//!
+//! The template must not be empty nor longer than 1000 characters, and its
+//! placeholders must be known to the bot.
+
+use std::collections::HashMap;
+
+use futures::{FutureExt, future::BoxFuture};
+use log::debug;
+
+use crate::{
+    commands::{
+        CommandContext, CommandResult,
+        markdown_response::{
+            format_invalid_template_length, format_successful_template, format_template,
+            format_template_reset, format_unclosed_placeholder, format_unknown_placeholder,
+        },
+        registry::{ArgumentError, ArgumentHelp, ArgumentSchema, BotCommand, Example, Usage},
+    },
+    settings::{NotificationTemplate, RoomSettings, TemplateError},
+};
+
+/// Action of the `template` command.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TemplateArguments {
+    /// Show the template of the room
+    Show,
+    /// Set the template of the room, not parsed yet
+    Set(String),
+    /// Restore the default notifications of the room
+    Reset,
+}
+
+/// Displays or updates the template of the current room.
+///
+/// Updates are not applied directly, they are returned in `room_settings_to_update`
+/// and saved by the caller.
+///
+/// # Returns
+///
+/// The template of the room, a success message with the room settings to save, or
+/// an error message if the template is invalid.
+pub async fn handle_template(
+    context: &CommandContext,
+    arguments: &TemplateArguments,
+) -> CommandResult {
+    debug!("handling template command: {:?}", arguments);
+
+    let CommandContext {
+        room_id,
+        user_id: _,
+        games_map: _,
+        alerts_map: _,
+        user_settings: _,
+        room_settings,
+        default_server: _,
+        default_prefix: _,
+        servers: _,
+        turns_map: _,
+    } = context;
+
+    let result = match arguments {
+        TemplateArguments::Show => CommandResult {
+            response: format_template(context.get_prefix(), room_settings.template.as_ref()),
+            ..Default::default()
+        },
+        TemplateArguments::Set(template) => match NotificationTemplate::parse(template) {
+            Ok(template) => CommandResult {
+                response: format_successful_template(&template),
+                room_settings_to_update: Some((
+                    room_id.clone(),
+                    with_template(room_settings, Some(template)),
+                )),
+                ..Default::default()
+            },
+            Err(e) => CommandResult {
+                response: format_template_error(e),
+                ..Default::default()
+            },
+        },
+        TemplateArguments::Reset => CommandResult {
+            response: format_template_reset(),
+            room_settings_to_update: Some((room_id.clone(), with_template(room_settings, None))),
+            ..Default::default()
+        },
+    };
+
+    debug!("template command result {:?}", result);
+
+    result
+}
+
+/// Returns a copy of the room settings with the given template.
+fn with_template(
+    room_settings: &RoomSettings,
+    template: Option<NotificationTemplate>,
+) -> RoomSettings {
+    let mut room_settings = room_settings.clone();
+    room_settings.template = template;
+    room_settings
+}
+
+/// Formats a template error into a user-friendly message.
+fn format_template_error(error: TemplateError) -> String {
+    match error {
+        TemplateError::InvalidLength => format_invalid_template_length(),
+        TemplateError::UnclosedPlaceholder => format_unclosed_placeholder(),
+        TemplateError::UnknownPlaceholder(name) => format_unknown_placeholder(&name),
+    }
+}
+
+/// The `template` command.
+pub struct TemplateCommand;
+
+impl BotCommand for TemplateCommand {
+    type Arguments = TemplateArguments;
+
+    fn name(&self) -> &'static str {
+        "template"
+    }
+
+    fn usages(&self) -> &'static [Usage] {
+        &[
+            Usage {
+                arguments: "",
+                description: "show the template of the turn notifications of this room",
+            },
+            Usage {
+                arguments: "set \"<template>\"",
+                description: "set the template of the turn notifications of this room, e.g. `template set \"{player}, wake up! {url}\"`",
+            },
+            Usage {
+                arguments: "reset",
+                description: "restore the default turn notifications of this room",
+            },
+        ]
+    }
+
+    fn schema(&self) -> ArgumentSchema {
+        // An unquoted template is made of several words
+        ArgumentSchema {
+            min_arguments: 0,
+            max_arguments: None,
+            options: &[],
+        }
+    }
+
+    fn arguments(&self) -> &'static [ArgumentHelp] {
+        &[ArgumentHelp {
+            name: "template",
+            description: "text of the notifications, up to 1000 characters, with the placeholders `{user}`, `{player}`, `{game}`, `{phase}`, `{url}` and `{waited_for}`",
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                arguments: "",
+                description: "show the template of this room",
+            },
+            Example {
+                arguments: "set \"{player}, wake up! {url}\"",
+                description: "notify with the name of the player and the link to the game",
+            },
+            Example {
+                arguments: "reset",
+                description: "notify with the default message",
+            },
+        ]
+    }
+
+    fn parse(
+        &self,
+        arguments: &[&str],
+        _: &HashMap<String, String>,
+    ) -> Result<TemplateArguments, ArgumentError> {
+        match arguments {
+            [] => Ok(TemplateArguments::Show),
+            ["reset"] => Ok(TemplateArguments::Reset),
+            ["reset", argument, ..] => Err(ArgumentError::UnexpectedArgument(argument.to_string())),
+            ["set", template @ ..] => {
+                // The template can be quoted to keep its spaces, or given as several words
+                let template = template.join(" ");
+
+                match template.is_empty() {
+                    true => Err(ArgumentError::MissingArguments),
+                    false => Ok(TemplateArguments::Set(template)),
+                }
+            }
+            [action, ..] => Err(ArgumentError::InvalidValue {
+                argument: "action",
+                value: action.to_string(),
+                reason: "is unknown, expected `set` or `reset`",
+            }),
+        }
+    }
+
+    fn handle<'a>(
+        &self,
+        context: &'a CommandContext,
+        arguments: &'a TemplateArguments,
+    ) -> BoxFuture<'a, CommandResult> {
+        handle_template(context, arguments).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::UserSettings;
+
+    fn create_test_context(room_settings: RoomSettings) -> CommandContext {
+        CommandContext {
+            games_map: HashMap::new(),
+            alerts_map: HashMap::new(),
+            room_id: "!test_room:matrix.org".to_string(),
+            user_id: "@test_user:matrix.org".to_string(),
+            user_settings: UserSettings::default(),
+            room_settings,
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["friends".to_string(), "main".to_string()],
+            turns_map: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_template_display() {
+        let template = NotificationTemplate::parse("{player}, play!").unwrap();
+        let context = create_test_context(RoomSettings {
+            server: None,
+            template: Some(template.clone()),
+            prefix: None,
+        });
+
+        let result = handle_template(&context, &TemplateArguments::Show).await;
+
+        assert_eq!(result.response, format_template("!miou", Some(&template)));
+        assert!(result.room_settings_to_update.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handle_set_template() {
+        let context = create_test_context(RoomSettings {
+            server: Some("friends".to_string()),
+            template: None,
+            prefix: Some("!mi".to_string()),
+        });
+        let arguments = TemplateArguments::Set("{player}, play!".to_string());
+
+        let result = handle_template(&context, &arguments).await;
+
+        let template = NotificationTemplate::parse("{player}, play!").unwrap();
+        assert_eq!(result.response, format_successful_template(&template));
+        // The other settings of the room are kept
+        assert_eq!(
+            result.room_settings_to_update,
+            Some((
+                "!test_room:matrix.org".to_string(),
+                RoomSettings {
+                    server: Some("friends".to_string()),
+                    template: Some(template),
+                    prefix: Some("!mi".to_string()),
+                },
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_set_invalid_template() {
+        let context = create_test_context(RoomSettings::default());
+        let arguments = TemplateArguments::Set("{player}, play {gaem}".to_string());
+
+        let result = handle_template(&context, &arguments).await;
+
+        assert_eq!(result.response, format_unknown_placeholder("gaem"));
+        assert!(result.room_settings_to_update.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handle_reset_template() {
+        let context = create_test_context(RoomSettings {
+            server: None,
+            template: NotificationTemplate::parse("{player}, play!").ok(),
+            prefix: None,
+        });
+
+        let result = handle_template(&context, &TemplateArguments::Reset).await;
+
+        assert_eq!(result.response, format_template_reset());
+        assert_eq!(
+            result.room_settings_to_update,
+            Some(("!test_room:matrix.org".to_string(), RoomSettings::default()))
+        );
+    }
+}
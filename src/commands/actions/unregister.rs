@@ -16,55 +16,118 @@
 //! - Always returns success (even if no alerts existed)
 //! - Doesn't affect alerts in other rooms or other users' alerts
 
+use std::collections::HashMap;
+
+use futures::{FutureExt, future::BoxFuture};
 use log::debug;
 
 use crate::commands::{
-    CommandContext, CommandResult, command::Command,
+    CommandContext, CommandResult,
     markdown_response::format_successful_unregister,
+    registry::{ArgumentError, ArgumentHelp, ArgumentSchema, BotCommand, Example, GAME_ID, Usage},
 };
 
 /// Removes all alerts for a game in the current room for the requesting user.
 ///
-/// Extracts the game ID from the command and returns a `CommandResult` indicating
-/// which alerts should be removed. The actual removal is performed by the caller.
+/// Returns a `CommandResult` indicating which alerts of the game should be
+/// removed. The actual removal is performed by the caller.
 ///
 /// # Returns
 ///
-/// - `Some(CommandResult)`: Success message with `alerts_to_remove` containing (game_id, room_id, user_id)
-/// - `None`: Only if the command is not an `Unregister` variant
-pub async fn handle_unregister(
-    context: &CommandContext,
-    command: &Command,
-) -> Option<CommandResult> {
-    debug!("handling unregister command: {:?}", command);
-
-    let game_id = match command {
-        Command::Unregister(game_id) => game_id.clone(),
-        _ => return None,
-    };
+/// Success message with `alerts_to_remove` containing (game_id, room_id, user_id).
+pub async fn handle_unregister(context: &CommandContext, game_id: &str) -> CommandResult {
+    debug!("handling unregister command: {}", game_id);
+
+    let game_id = context.resolve_game_id(game_id);
 
     let CommandContext {
         room_id,
         user_id,
         games_map: _,
         alerts_map: _,
+        user_settings: _,
+        room_settings: _,
+        default_server: _,
+        default_prefix: _,
+        servers: _,
+        turns_map: _,
     } = context;
 
     let result = CommandResult {
         response: format_successful_unregister(),
-        alert_to_add: None,
         alerts_to_remove: Some((game_id, room_id.clone(), user_id.clone())),
+        ..Default::default()
     };
 
     debug!("unregister command result {:?}", result);
 
-    Some(result)
+    result
+}
+
+/// The `unregister` command.
+pub struct UnregisterCommand;
+
+impl BotCommand for UnregisterCommand {
+    /// ID of the game, as written in the message
+    type Arguments = String;
+
+    fn name(&self) -> &'static str {
+        "unregister"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["unreg"]
+    }
+
+    fn usages(&self) -> &'static [Usage] {
+        &[Usage {
+            arguments: "<game_id>",
+            description: "unregister an alert",
+        }]
+    }
+
+    fn schema(&self) -> ArgumentSchema {
+        ArgumentSchema {
+            min_arguments: 1,
+            max_arguments: Some(1),
+            options: &[],
+        }
+    }
+
+    fn arguments(&self) -> &'static [ArgumentHelp] {
+        &[GAME_ID]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            arguments: "game123",
+            description: "remove your alerts for game123 in this room",
+        }]
+    }
+
+    fn parse(
+        &self,
+        arguments: &[&str],
+        _: &HashMap<String, String>,
+    ) -> Result<String, ArgumentError> {
+        Ok(arguments[0].to_owned())
+    }
+
+    fn handle<'a>(
+        &self,
+        context: &'a CommandContext,
+        game_id: &'a String,
+    ) -> BoxFuture<'a, CommandResult> {
+        handle_unregister(context, game_id).boxed()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
+    use crate::settings::{RoomSettings, UserSettings};
+
     use super::*;
 
     fn create_test_context() -> CommandContext {
@@ -73,35 +136,27 @@ mod tests {
             alerts_map: HashMap::new(),
             room_id: "!test_room:matrix.org".to_string(),
             user_id: "@test_user:matrix.org".to_string(),
+            user_settings: UserSettings::default(),
+            room_settings: RoomSettings::default(),
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["main".to_string()],
+            turns_map: HashMap::new(),
         }
     }
 
     #[tokio::test]
     async fn test_handle_unregister_successful() {
         let context = create_test_context();
-        let command = Command::Unregister("game123".to_string());
+        let result = handle_unregister(&context, "game123").await;
 
-        let result = handle_unregister(&context, &command).await;
-
-        assert!(result.is_some());
-        let result = result.unwrap();
         assert_eq!(result.response, format_successful_unregister());
         assert!(result.alert_to_add.is_none());
         assert!(result.alerts_to_remove.is_some());
 
         let (game_id, room_id, user_id) = result.alerts_to_remove.unwrap();
-        assert_eq!(game_id, "game123");
+        assert_eq!(game_id, "main:game123");
         assert_eq!(room_id, "!test_room:matrix.org");
         assert_eq!(user_id, "@test_user:matrix.org");
     }
-
-    #[tokio::test]
-    async fn test_handle_unregister_wrong_command_type_help() {
-        let context = create_test_context();
-        let command = Command::Help;
-
-        let result = handle_unregister(&context, &command).await;
-
-        assert!(result.is_none());
-    }
 }
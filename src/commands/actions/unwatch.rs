@@ -0,0 +1,138 @@
+//! Game unwatch command handler.
+//!
+//! Stops announcing the turn changes of a Terraforming Mars game in the current
+//! Matrix room.
+//!
+//! # Behavior
+//!
+//! - Always returns success (even if the game wasn't watched)
+//! - Doesn't affect the watches of other rooms
+
+use std::collections::HashMap;
+
+use futures::{FutureExt, future::BoxFuture};
+use log::debug;
+
+use crate::commands::{
+    CommandContext, CommandResult,
+    markdown_response::format_successful_unwatch,
+    registry::{ArgumentError, ArgumentHelp, ArgumentSchema, BotCommand, Example, GAME_ID, Usage},
+};
+
+/// Stops watching a game in the current room.
+///
+/// Returns a `CommandResult` indicating which watch should be removed. The actual
+/// removal is performed by the caller.
+///
+/// # Returns
+///
+/// Success message with `watch_to_remove` containing (game_id, room_id).
+pub async fn handle_unwatch(context: &CommandContext, game_id: &str) -> CommandResult {
+    debug!("handling unwatch command: {}", game_id);
+
+    let game_id = context.resolve_game_id(game_id);
+
+    let result = CommandResult {
+        response: format_successful_unwatch(&game_id),
+        watch_to_remove: Some((game_id, context.room_id.clone())),
+        ..Default::default()
+    };
+
+    debug!("unwatch command result {:?}", result);
+
+    result
+}
+
+/// The `unwatch` command.
+pub struct UnwatchCommand;
+
+impl BotCommand for UnwatchCommand {
+    /// ID of the game, as written in the message
+    type Arguments = String;
+
+    fn name(&self) -> &'static str {
+        "unwatch"
+    }
+
+    fn usages(&self) -> &'static [Usage] {
+        &[Usage {
+            arguments: "<game_id>",
+            description: "stop announcing the turn changes of a game in this room",
+        }]
+    }
+
+    fn schema(&self) -> ArgumentSchema {
+        ArgumentSchema {
+            min_arguments: 1,
+            max_arguments: Some(1),
+            options: &[],
+        }
+    }
+
+    fn arguments(&self) -> &'static [ArgumentHelp] {
+        &[GAME_ID]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            arguments: "game123",
+            description: "stop announcing the turn changes of game123 in this room",
+        }]
+    }
+
+    fn parse(
+        &self,
+        arguments: &[&str],
+        _: &HashMap<String, String>,
+    ) -> Result<String, ArgumentError> {
+        Ok(arguments[0].to_owned())
+    }
+
+    fn handle<'a>(
+        &self,
+        context: &'a CommandContext,
+        game_id: &'a String,
+    ) -> BoxFuture<'a, CommandResult> {
+        handle_unwatch(context, game_id).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::settings::{RoomSettings, UserSettings};
+
+    use super::*;
+
+    fn create_test_context() -> CommandContext {
+        CommandContext {
+            games_map: HashMap::new(),
+            alerts_map: HashMap::new(),
+            room_id: "!test_room:matrix.org".to_string(),
+            user_id: "@test_user:matrix.org".to_string(),
+            user_settings: UserSettings::default(),
+            room_settings: RoomSettings::default(),
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["main".to_string()],
+            turns_map: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_unwatch_successful() {
+        let context = create_test_context();
+        let result = handle_unwatch(&context, "game123").await;
+
+        assert_eq!(result.response, format_successful_unwatch("main:game123"));
+        assert!(result.watch_to_add.is_none());
+        assert_eq!(
+            result.watch_to_remove,
+            Some((
+                "main:game123".to_string(),
+                "!test_room:matrix.org".to_string()
+            ))
+        );
+    }
+}
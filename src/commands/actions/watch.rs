@@ -0,0 +1,192 @@
+//! Game watch command handler.
+//!
+//! Binds a Terraforming Mars game to the current Matrix room, so that every turn
+//! change of the game is announced in the room.
+//!
+//! # Validation
+//!
+//! The game must exist in the active games map.
+
+use std::collections::HashMap;
+
+use futures::{FutureExt, future::BoxFuture};
+use log::debug;
+
+use crate::commands::{
+    CommandContext, CommandResult,
+    markdown_response::{format_game_not_found, format_successful_watch},
+    registry::{ArgumentError, ArgumentHelp, ArgumentSchema, BotCommand, Example, GAME_ID, Usage},
+    suggest_game_id,
+};
+
+/// Watches a game in the current room.
+///
+/// Returns a `CommandResult` indicating which game should be watched. The actual
+/// watch is added by the caller.
+///
+/// # Returns
+///
+/// Success message with `watch_to_add` containing (game_id, room_id), or an error
+/// message if the game doesn't exist.
+pub async fn handle_watch(context: &CommandContext, game_id: &str) -> CommandResult {
+    debug!("handling watch command: {}", game_id);
+
+    let game_id = context.resolve_game_id(game_id);
+
+    let CommandContext {
+        room_id,
+        user_id: _,
+        games_map,
+        alerts_map: _,
+        user_settings: _,
+        room_settings: _,
+        default_server: _,
+        default_prefix: _,
+        servers: _,
+        turns_map: _,
+    } = context;
+
+    let result = match games_map.contains_key(&game_id) {
+        true => CommandResult {
+            response: format_successful_watch(&game_id),
+            watch_to_add: Some((game_id, room_id.clone())),
+            ..Default::default()
+        },
+        false => CommandResult {
+            response: format_game_not_found(
+                &game_id,
+                suggest_game_id(games_map, &game_id).as_deref(),
+            ),
+            ..Default::default()
+        },
+    };
+
+    debug!("watch command result {:?}", result);
+
+    result
+}
+
+/// The `watch` command.
+pub struct WatchCommand;
+
+impl BotCommand for WatchCommand {
+    /// ID of the game, as written in the message
+    type Arguments = String;
+
+    fn name(&self) -> &'static str {
+        "watch"
+    }
+
+    fn usages(&self) -> &'static [Usage] {
+        &[Usage {
+            arguments: "<game_id>",
+            description: "announce every turn change of a game in this room",
+        }]
+    }
+
+    fn schema(&self) -> ArgumentSchema {
+        ArgumentSchema {
+            min_arguments: 1,
+            max_arguments: Some(1),
+            options: &[],
+        }
+    }
+
+    fn arguments(&self) -> &'static [ArgumentHelp] {
+        &[GAME_ID]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                arguments: "game123",
+                description: "announce the turn changes of game123 in this room",
+            },
+            Example {
+                arguments: "friends:game123",
+                description: "announce the turn changes of game123 of the `friends` server",
+            },
+        ]
+    }
+
+    fn parse(
+        &self,
+        arguments: &[&str],
+        _: &HashMap<String, String>,
+    ) -> Result<String, ArgumentError> {
+        Ok(arguments[0].to_owned())
+    }
+
+    fn handle<'a>(
+        &self,
+        context: &'a CommandContext,
+        game_id: &'a String,
+    ) -> BoxFuture<'a, CommandResult> {
+        handle_watch(context, game_id).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use crate::{
+        settings::{RoomSettings, UserSettings},
+        tmars::{Game, Phase},
+    };
+
+    use super::*;
+
+    fn create_test_context() -> CommandContext {
+        let game = Game {
+            id: "main:game123".to_string(),
+            phase: Phase::Action,
+            spectator_id: "spec123".to_string(),
+            spectator_url: "http://example.com/spectator".to_string(),
+            players: vec![],
+            waited_players: HashSet::new(),
+            progress: None,
+        };
+
+        CommandContext {
+            games_map: HashMap::from([(game.id.clone(), game)]),
+            alerts_map: HashMap::new(),
+            room_id: "!test_room:matrix.org".to_string(),
+            user_id: "@test_user:matrix.org".to_string(),
+            user_settings: UserSettings::default(),
+            room_settings: RoomSettings::default(),
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["main".to_string()],
+            turns_map: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_watch_successful() {
+        let context = create_test_context();
+        let result = handle_watch(&context, "game123").await;
+
+        assert_eq!(result.response, format_successful_watch("main:game123"));
+        assert_eq!(
+            result.watch_to_add,
+            Some((
+                "main:game123".to_string(),
+                "!test_room:matrix.org".to_string()
+            ))
+        );
+        assert!(result.watch_to_remove.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_handle_watch_game_not_found() {
+        let context = create_test_context();
+        let result = handle_watch(&context, "nonexistent").await;
+
+        assert_eq!(
+            result.response,
+            format_game_not_found("main:nonexistent", None)
+        );
+        assert!(result.watch_to_add.is_none());
+    }
+}
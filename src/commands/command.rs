@@ -1,42 +1,136 @@
 //! Command parsing and handling.
 //!
 //! This module provides command parsing functionality for the bot, converting
-//! Matrix message text into structured [`Command`] enums that can be processed
-//! by the application.
+//! Matrix message text into a [`Command`] parsed by one of the commands of the
+//! [`COMMANDS`](crate::commands::registry::COMMANDS) registry, which executes it.
 
-use command_parser::{Command as ParserCommand, Parser};
+#[cfg(test)]
+use std::any::Any;
+use std::fmt;
+
+use futures::future::BoxFuture;
 use log::debug;
 
-use crate::commands::markdown_response::{
-    format_invalid_register, format_invalid_unregister, format_unknown_command,
+use crate::commands::{
+    CommandContext, CommandResult,
+    actions::HelpCommand,
+    markdown_response::{format_invalid_arguments, format_syntax_error, format_unknown_command},
+    registry::{ArgumentError, BotCommand, find_command, suggest_command},
+    tokenizer::{SyntaxError, tokenize},
 };
 
 /// Represents a parsed bot command.
 ///
-/// Commands are parsed from Matrix message text and represent the various
-/// operations users can perform with the bot.
-#[derive(Debug, Hash, PartialEq, Eq)]
-pub enum Command {
-    /// Display help information
-    Help,
-    /// List all ongoing games
-    Games,
-    /// Register for game alerts
-    ///
-    /// # Fields
+/// A command holds the arguments parsed by one of the commands of the
+/// [`COMMANDS`](crate::commands::registry::COMMANDS) registry, and is executed by
+/// this same command.
+pub struct Command {
+    /// Name of the command which parsed the arguments
+    name: &'static str,
+    /// Arguments of the command, with the command executing them
+    parsed: Box<dyn ParsedArguments>,
+}
+
+/// Arguments parsed by a command, whatever their type.
+trait ParsedArguments: fmt::Debug + Send + Sync {
+    /// Executes the command which parsed the arguments.
+    fn handle<'a>(&'a self, context: &'a CommandContext) -> BoxFuture<'a, CommandResult>;
+
+    /// Returns the arguments, to be downcast to the arguments of the command.
+    #[cfg(test)]
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+/// Arguments parsed by the command `T`.
+struct Parsed<T: BotCommand> {
+    /// The command which parsed the arguments
+    command: &'static T,
+    /// The parsed arguments
+    arguments: T::Arguments,
+}
+
+impl<T: BotCommand> fmt::Debug for Parsed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.arguments.fmt(f)
+    }
+}
+
+impl<T: BotCommand> ParsedArguments for Parsed<T> {
+    fn handle<'a>(&'a self, context: &'a CommandContext) -> BoxFuture<'a, CommandResult> {
+        self.command.handle(context, &self.arguments)
+    }
+
+    #[cfg(test)]
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        Box::new(self.arguments)
+    }
+}
+
+impl fmt::Debug for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}({:?})", self.name(), self.parsed)
+    }
+}
+
+/// How the messages address their commands to the bot.
+///
+/// A message is a command of the bot when it starts with the prefix of the room,
+/// e.g. `!miou games`, when it starts with a mention of the bot, e.g.
+/// `@miou:example.com games` or `Miou: games` when the client sends a pill, or when
+/// it is sent in a direct chat with the bot, where the prefix is optional, e.g. `games`.
+#[derive(Clone, Copy, Debug)]
+pub struct CommandAddress<'a> {
+    /// Prefix of the commands, e.g. `!miou`
+    pub prefix: &'a str,
+    /// Matrix user ID of the bot
+    pub user_id: &'a str,
+    /// Text of the mention of the bot starting the message, e.g. the display name
+    /// of the bot in a pill
+    pub mention: Option<&'a str>,
+    /// Whether the message is sent in a direct chat with the bot
+    pub is_direct: bool,
+}
+
+impl CommandAddress<'_> {
+    /// Returns the position of the first character after the address of the command.
     ///
-    /// * `String` - Game ID
-    /// * `String` - Player name
-    /// * `u32` - Delay in minutes
-    Register(String, String, u64),
-    /// Unregister from game alerts
+    /// The mention of the bot may be followed by a colon, e.g. `@miou:example.com: games`
+    /// or `Miou: games`.
     ///
-    /// # Fields
+    /// # Returns
     ///
-    /// * `String` - Game ID
-    Unregister(String),
-    /// List user's registered alerts
-    Alerts,
+    /// The position in characters, `None` if the message is not addressed to the bot.
+    fn find_command_start(&self, body: &str) -> Option<usize> {
+        let message = body.trim_start();
+        let rest = strip_address(message, self.prefix)
+            .or_else(|| strip_mention(message, self.user_id))
+            .or_else(|| strip_mention(message, self.mention?));
+
+        match rest {
+            Some(rest) => Some(body.chars().count() - rest.chars().count()),
+            None if self.is_direct => Some(0),
+            None => None,
+        }
+    }
+}
+
+/// Returns the rest of a message starting with the given address.
+///
+/// The address must be followed by whitespace or end the message, `!mioux` is not
+/// addressed to `!miou`.
+fn strip_address<'a>(message: &'a str, address: &str) -> Option<&'a str> {
+    let rest = message.strip_prefix(address)?;
+    match rest.chars().next() {
+        Some(c) if !c.is_whitespace() => None,
+        _ => Some(rest),
+    }
+}
+
+/// Returns the rest of a message starting with a mention of the bot, optionally
+/// followed by a colon.
+fn strip_mention<'a>(message: &'a str, mention: &str) -> Option<&'a str> {
+    let rest = message.strip_prefix(mention)?;
+    strip_address(rest, ":").or_else(|| strip_address(rest, ""))
 }
 
 /// Errors that can occur during command parsing.
@@ -44,25 +138,49 @@ pub enum Command {
 pub enum CommandParsingError {
     /// The message could not be parsed as a command
     UnableToParse,
-    /// The command is not for this bot (wrong prefix)
+    /// The command is for another bot (prefix of another bot)
     NotMiou,
+    /// The command has invalid quotes, escapes or options
+    InvalidSyntax(SyntaxError),
     /// The command is not recognized
-    Unknown,
-    /// The register command has invalid syntax or arguments
-    InvalidRegister,
-    /// The unregister command has invalid syntax or arguments
-    InvalidUnRegister,
+    ///
+    /// # Fields
+    ///
+    /// * `String` - Name of the command as typed
+    Unknown(String),
+    /// The arguments or options don't match any form of the command
+    ///
+    /// # Fields
+    ///
+    /// * `&'static str` - Name of the command
+    /// * `ArgumentError` - The wrong argument or option
+    InvalidArguments(&'static str, ArgumentError),
 }
 
 impl Command {
+    /// Creates the command executing the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command which parsed the arguments
+    /// * `arguments` - The parsed arguments
+    pub fn new<T: BotCommand>(command: &'static T, arguments: T::Arguments) -> Self {
+        Command {
+            name: BotCommand::name(command),
+            parsed: Box::new(Parsed { command, arguments }),
+        }
+    }
+
     /// Parses a message string into a Command.
     ///
     /// This method attempts to parse a Matrix message body into a structured
-    /// command. It handles the bot prefix check and validates command syntax.
+    /// command. It checks that the message is addressed to the bot, finds the
+    /// command in the [`COMMANDS`](crate::commands::registry::COMMANDS) registry
+    /// and lets it parse its arguments.
     ///
     /// # Arguments
     ///
-    /// * `parser` - The command parser instance configured for the bot
+    /// * `address` - How the commands are addressed to the bot in the room of the message
     /// * `body` - The message text to parse
     ///
     /// # Returns
@@ -73,97 +191,88 @@ impl Command {
     /// # Errors
     ///
     /// Returns an error if:
-    /// - The message is not a command format - [`CommandParsingError::UnableToParse`]
-    /// - The command is for a different bot - [`CommandParsingError::NotMiou`]
+    /// - The message is not addressed to the bot - [`CommandParsingError::UnableToParse`]
+    /// - The command is for a different bot, e.g. `!other_bot` - [`CommandParsingError::NotMiou`]
+    /// - The command has invalid quotes, escapes or options - [`CommandParsingError::InvalidSyntax`]
     /// - The command is not recognized - [`CommandParsingError::Unknown`]
-    /// - Register command has invalid arguments - [`CommandParsingError::InvalidRegister`]
-    /// - Unregister command has invalid arguments - [`CommandParsingError::InvalidUnRegister`]
+    /// - The command has invalid arguments or options - [`CommandParsingError::InvalidArguments`]
     ///
     /// # Examples
     ///
     /// ```
-    /// # use command_parser::Parser;
-    /// # use miou::commands::command::Command;
-    /// let parser = Parser::new('!', '-')
-    ///     .command("miou")
-    ///     .subcommand("help")
-    ///     .build();
-    /// let result = Command::parse(&parser, "!miou help".to_string());
+    /// # use miou::commands::command::{Command, CommandAddress};
+    /// let address = CommandAddress {
+    ///     prefix: "!miou",
+    ///     user_id: "@miou:example.com",
+    ///     mention: None,
+    ///     is_direct: false,
+    /// };
+    /// let result = Command::parse(&address, "!miou register game123 \"Alice Smith\" 60");
+    /// assert!(result.is_ok());
+    /// let result = Command::parse(&address, "@miou:example.com: games");
     /// assert!(result.is_ok());
     /// ```
-    pub fn parse(parser: &Parser, body: &str) -> Result<Self, CommandParsingError> {
-        // For an unknown reason the parser ignores the last word, so we add a dummy word at the end
-        let body = body.to_string() + " dummy";
-
-        // This is normal to fails if the message is not a command
-        let command = match parser.parse(&body) {
-            Ok(cmd) => cmd,
-            Err(_) => return Err(CommandParsingError::UnableToParse),
+    pub fn parse(address: &CommandAddress, body: &str) -> Result<Self, CommandParsingError> {
+        // Ignore the messages that are not for the bot, before reporting their syntax errors
+        let Some(start) = address.find_command_start(body) else {
+            let prefix_start = address.prefix.chars().next();
+            return match body.trim_start().chars().next() {
+                Some(c) if prefix_start == Some(c) => Err(CommandParsingError::NotMiou),
+                // This is normal to fails if the message is not a command
+                _ => Err(CommandParsingError::UnableToParse),
+            };
         };
 
-        // Ignore commands that are not for the bot
-        if command.name != "miou" {
-            return Err(CommandParsingError::NotMiou);
-        }
+        let command = tokenize(body, start).map_err(CommandParsingError::InvalidSyntax)?;
 
         debug!("Parsing command: {:?}", command);
 
         // If no arguments, return help
-        if command.arguments.is_empty() {
-            return Ok(Command::Help);
-        }
-
-        match command.arguments[0].as_str() {
-            "help" => Ok(Command::Help),
-            "games" => Ok(Command::Games),
-            "register" => {
-                let (game_id, player_name, delay) = Self::parse_register(&command)?;
-                Ok(Command::Register(game_id, player_name, delay))
-            }
-            "alerts" => Ok(Command::Alerts),
-            "unregister" => Ok(Command::Unregister(Self::parse_unregister(&command)?)),
-            _ => Err(CommandParsingError::Unknown),
-        }
-    }
-
-    fn parse_register(
-        command: &ParserCommand,
-    ) -> Result<(String, String, u64), CommandParsingError> {
-        debug!("Parsing register command: {:?}", command);
-
-        // 4 arguments: register, game id, player name and delay
-        if command.arguments.len() < 4 {
-            return Err(CommandParsingError::InvalidRegister);
-        }
-
-        let game_id = command.arguments[1].clone();
-        let player_name = command.arguments[2].clone();
-        let delay = match command.arguments[3].parse::<u64>() {
-            Ok(delay) => delay,
-            Err(_) => return Err(CommandParsingError::InvalidRegister),
+        let Some((name, arguments)) = command.arguments.split_first() else {
+            return Ok(Command::new(&HelpCommand, None));
         };
 
-        debug!(
-            "Parsed register command - game_id: {}, player_name: {}, delay: {}",
-            game_id, player_name, delay
-        );
+        let bot_command =
+            find_command(name).ok_or_else(|| CommandParsingError::Unknown(name.clone()))?;
+        let arguments: Vec<&str> = arguments.iter().map(String::as_str).collect();
 
-        Ok((game_id, player_name, delay))
+        bot_command
+            .schema()
+            .check(&arguments, &command.options)
+            .and_then(|_| bot_command.parse_command(&arguments, &command.options))
+            .map_err(|error| {
+                debug!("invalid arguments of {} command: {:?}", name, error);
+                CommandParsingError::InvalidArguments(bot_command.name(), error)
+            })
     }
 
-    fn parse_unregister(command: &ParserCommand) -> Result<String, CommandParsingError> {
-        debug!("Parsing unregister command: {:?}", command);
-
-        // 2 arguments: unregister and game id
-        if command.arguments.len() < 2 {
-            return Err(CommandParsingError::InvalidUnRegister);
-        }
-
-        let game_id = command.arguments[1].clone();
+    /// Returns the name of the command of the
+    /// [`COMMANDS`](crate::commands::registry::COMMANDS) registry which executes it.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
 
-        debug!("Parsed unregister command - game_id: {}", game_id);
+    /// Executes the command with the arguments it parsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - Runtime context of the command
+    ///
+    /// # Returns
+    ///
+    /// The result of the command.
+    pub fn handle<'a>(&'a self, context: &'a CommandContext) -> BoxFuture<'a, CommandResult> {
+        self.parsed.handle(context)
+    }
 
-        Ok(game_id)
+    /// Returns the arguments of the command, if they are of type `T`.
+    #[cfg(test)]
+    pub fn into_arguments<T: 'static>(self) -> Option<T> {
+        self.parsed
+            .into_any()
+            .downcast()
+            .ok()
+            .map(|arguments| *arguments)
     }
 }
 
@@ -175,6 +284,7 @@ impl Command {
 ///
 /// # Arguments
 ///
+/// * `prefix` - The prefix of the commands in the room, shown in the usages
 /// * `error` - The command error to format
 ///
 /// # Returns
@@ -186,156 +296,994 @@ impl Command {
 ///
 /// ```
 /// # use miou::commands::command::{format_command_error, CommandParsingError};
-/// let error = CommandParsingError::Unknown;
-/// let message = format_command_error(error);
+/// let error = CommandParsingError::Unknown("regster".to_string());
+/// let message = format_command_error("!miou", error);
 /// assert!(message.is_some());
 /// ```
-pub fn format_command_error(error: CommandParsingError) -> Option<String> {
+pub fn format_command_error(prefix: &str, error: CommandParsingError) -> Option<String> {
     match error {
-        CommandParsingError::Unknown => Some(format_unknown_command()),
-        CommandParsingError::InvalidRegister => Some(format_invalid_register()),
-        CommandParsingError::InvalidUnRegister => Some(format_invalid_unregister()),
+        CommandParsingError::InvalidSyntax(error) => Some(format_syntax_error(prefix, &error)),
+        CommandParsingError::Unknown(name) => {
+            Some(format_unknown_command(prefix, suggest_command(&name)))
+        }
+        CommandParsingError::InvalidArguments(name, error) => find_command(name).map(|command| {
+            format_invalid_arguments(prefix, command.name(), command.usages(), &error)
+        }),
         _ => None,
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use chrono::NaiveTime;
+
     use super::*;
+    use crate::{
+        commands::actions::{
+            Delay, PrefixArguments, RegisterArguments, SettingsArguments, TemplateArguments,
+        },
+        tmars::Phase,
+    };
+
+    fn create_address() -> CommandAddress<'static> {
+        CommandAddress {
+            prefix: "!miou",
+            user_id: "@miou:example.com",
+            mention: None,
+            is_direct: false,
+        }
+    }
 
-    fn create_parser() -> Parser {
-        Parser::new('!', '-')
+    /// Parses a message into the name of its command and its arguments of type `T`.
+    fn parse_arguments<T: 'static>(
+        address: &CommandAddress,
+        body: &str,
+    ) -> Result<(&'static str, T), CommandParsingError> {
+        Command::parse(address, body).map(|command| {
+            let name = command.name();
+            let arguments = command.into_arguments().expect("arguments of another type");
+            (name, arguments)
+        })
     }
 
     #[test]
     fn test_parse_help_command() {
-        let parser = create_parser();
-        let result = Command::parse(&parser, "!miou help");
-        assert!(matches!(result, Ok(Command::Help)));
+        let address = create_address();
+        let result = parse_arguments::<Option<String>>(&address, "!miou help");
+        assert!(matches!(result, Ok(("help", None))));
+    }
+
+    #[test]
+    fn test_parse_help_command_with_command() {
+        let address = create_address();
+        let result = parse_arguments::<Option<String>>(&address, "!miou help reg");
+        assert!(matches!(
+            result,
+            Ok(("help", Some(name))) if name == "register"
+        ));
+    }
+
+    #[test]
+    fn test_parse_help_command_invalid() {
+        let address = create_address();
+
+        let result = Command::parse(&address, "!miou help dance");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments(
+                "help",
+                ArgumentError::InvalidValue { argument: "command", value, .. }
+            )) if value == "dance"
+        ));
+
+        let result = Command::parse(&address, "!miou help register games");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments(
+                "help",
+                ArgumentError::UnexpectedArgument(_)
+            ))
+        ));
     }
 
     #[test]
     fn test_parse_help_command_no_args() {
-        let parser = create_parser();
-        let result = Command::parse(&parser, "!miou");
-        assert!(matches!(result, Ok(Command::Help)));
+        let address = create_address();
+        let result = parse_arguments::<Option<String>>(&address, "!miou");
+        assert!(matches!(result, Ok(("help", None))));
     }
 
     #[test]
     fn test_parse_games_command() {
-        let parser = create_parser();
-        let result = Command::parse(&parser, "!miou games");
-        assert!(matches!(result, Ok(Command::Games)));
+        let address = create_address();
+        let result = parse_arguments::<()>(&address, "!miou games");
+        assert!(matches!(result, Ok(("games", ()))));
     }
 
     #[test]
     fn test_parse_alerts_command() {
-        let parser = create_parser();
-        let result = Command::parse(&parser, "!miou alerts");
-        assert!(matches!(result, Ok(Command::Alerts)));
+        let address = create_address();
+        let result = parse_arguments::<()>(&address, "!miou alerts");
+        assert!(matches!(result, Ok(("alerts", ()))));
     }
 
     #[test]
     fn test_parse_register_command() {
-        let parser = create_parser();
-        let result = Command::parse(&parser, "!miou register game123 Alice 60");
+        let address = create_address();
+        let result =
+            parse_arguments::<RegisterArguments>(&address, "!miou register game123 Alice 60");
         assert!(matches!(
             result,
-            Ok(Command::Register(game_id, player_name, delay))
-            if game_id == "game123" && player_name == "Alice" && delay == 60
+            Ok(("register", RegisterArguments { game_id, player_name, delay, repeat_interval: None, max_reminders: None, phases }))
+            if game_id == "game123" && player_name == "Alice" && delay == Delay::Minutes(60) && phases.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_parse_register_command_with_phases() {
+        let address = create_address();
+        let result = parse_arguments::<RegisterArguments>(
+            &address,
+            "!miou register game123 Alice 60 --phases action,Production 30",
+        );
+        assert!(matches!(
+            result,
+            Ok(("register", RegisterArguments { repeat_interval: Some(30), phases, .. }))
+            if phases == vec![Phase::Action, Phase::Production]
+        ));
+    }
+
+    #[test]
+    fn test_parse_register_command_invalid_phases() {
+        let address = create_address();
+
+        for (body, expected_error) in [
+            (
+                "!miou register game123 Alice 60 --phases action,auction",
+                ArgumentError::InvalidValue {
+                    argument: "phase",
+                    value: "auction".to_string(),
+                    reason: "is unknown",
+                },
+            ),
+            (
+                "!miou register game123 Alice 60 --phases ,",
+                ArgumentError::InvalidValue {
+                    argument: "--phases",
+                    value: ",".to_string(),
+                    reason: "names no phase",
+                },
+            ),
+            (
+                "!miou register game123 Alice 60 --players Bob",
+                ArgumentError::UnknownOption("players".to_string()),
+            ),
+        ] {
+            let result = Command::parse(&address, body);
+            assert!(matches!(
+                result,
+                Err(CommandParsingError::InvalidArguments("register", error))
+                if error == expected_error
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_register_command_with_quoted_player_name() {
+        let address = create_address();
+        let result = parse_arguments::<RegisterArguments>(
+            &address,
+            "!miou register game123 \"Alice Smith\" 60 30",
+        );
+        assert!(matches!(
+            result,
+            Ok(("register", RegisterArguments { player_name, delay, repeat_interval: Some(30), .. }))
+            if player_name == "Alice Smith" && delay == Delay::Minutes(60)
+        ));
+    }
+
+    #[test]
+    fn test_parse_register_command_with_escaped_player_name() {
+        let address = create_address();
+        let result = parse_arguments::<RegisterArguments>(
+            &address,
+            "!miou register game123 Alice\\ Smith 60",
+        );
+        assert!(matches!(
+            result,
+            Ok(("register", RegisterArguments { player_name, .. })) if player_name == "Alice Smith"
+        ));
+    }
+
+    #[test]
+    fn test_parse_register_command_missing_phases_value() {
+        let address = create_address();
+        let result = Command::parse(&address, "!miou register game123 Alice 60 --phases");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidSyntax(SyntaxError::MissingOptionValue { name, position: 32 }))
+            if name == "phases"
+        ));
+    }
+
+    #[test]
+    fn test_parse_register_command_with_repeat_interval() {
+        let address = create_address();
+        let result =
+            parse_arguments::<RegisterArguments>(&address, "!miou register game123 Alice 60 30");
+        assert!(matches!(
+            result,
+            Ok((
+                "register",
+                RegisterArguments {
+                    repeat_interval: Some(30),
+                    max_reminders: None,
+                    ..
+                }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_parse_register_command_with_max_reminders() {
+        let address = create_address();
+        let result =
+            parse_arguments::<RegisterArguments>(&address, "!miou register game123 Alice 60 30 3");
+        assert!(matches!(
+            result,
+            Ok((
+                "register",
+                RegisterArguments {
+                    repeat_interval: Some(30),
+                    max_reminders: Some(3),
+                    ..
+                }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_parse_register_command_invalid_repeat_interval() {
+        let address = create_address();
+        let result = Command::parse(&address, "!miou register game123 Alice 60 often");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments(
+                "register",
+                ArgumentError::InvalidValue { argument: "repeat_in_minutes", value, .. }
+            )) if value == "often"
+        ));
+    }
+
+    #[test]
+    fn test_parse_register_command_invalid_max_reminders() {
+        let address = create_address();
+        let result = Command::parse(&address, "!miou register game123 Alice 60 30 many");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments(
+                "register",
+                ArgumentError::InvalidValue { argument: "max_reminders", value, .. }
+            )) if value == "many"
+        ));
+    }
+
+    #[test]
+    fn test_parse_register_command_too_many_args() {
+        let address = create_address();
+        let result = Command::parse(&address, "!miou register game123 Alice 60 30 3 extra");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments(
+                "register",
+                ArgumentError::UnexpectedArgument(argument)
+            )) if argument == "extra"
         ));
     }
 
     #[test]
     fn test_parse_register_command_invalid_missing_args() {
-        let parser = create_parser();
-        let result = Command::parse(&parser, "!miou register game123");
-        assert!(matches!(result, Err(CommandParsingError::InvalidRegister)));
+        let address = create_address();
+        let result = Command::parse(&address, "!miou register game123");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments(
+                "register",
+                ArgumentError::MissingArguments
+            ))
+        ));
     }
 
     #[test]
     fn test_parse_register_command_invalid_delay() {
-        let parser = create_parser();
-        let result = Command::parse(&parser, "!miou register game123 Alice invalid");
-        assert!(matches!(result, Err(CommandParsingError::InvalidRegister)));
+        let address = create_address();
+        let result = Command::parse(&address, "!miou register game123 Alice invalid");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments(
+                "register",
+                ArgumentError::InvalidValue { argument: "delay", value, .. }
+            )) if value == "invalid"
+        ));
+    }
+
+    #[test]
+    fn test_parse_register_command_with_duration() {
+        let address = create_address();
+
+        for (delay, minutes) in [("30m", 30), ("2h", 120), ("1d12h", 2160), ("1h30m", 90)] {
+            let body = format!("!miou register game123 Alice {} 30", delay);
+            let result = parse_arguments::<RegisterArguments>(&address, &body);
+            assert!(matches!(
+                result,
+                Ok(("register", RegisterArguments { delay, repeat_interval: Some(30), .. }))
+                if delay == Delay::Minutes(minutes)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_register_command_with_time_of_day() {
+        let address = create_address();
+        let result =
+            parse_arguments::<RegisterArguments>(&address, "!miou register game123 Alice @09:00");
+        assert!(matches!(
+            result,
+            Ok(("register", RegisterArguments { delay, .. }))
+            if delay == Delay::At(NaiveTime::from_hms_opt(9, 0, 0).unwrap())
+        ));
+    }
+
+    #[test]
+    fn test_parse_register_command_invalid_duration() {
+        let address = create_address();
+
+        for (delay, expected_reason) in [
+            (
+                "2h1d",
+                "is not a number of minutes nor a duration like `2h`",
+            ),
+            (
+                "1h1h",
+                "is not a number of minutes nor a duration like `2h`",
+            ),
+            ("m", "is not a number of minutes nor a duration like `2h`"),
+            ("2x", "is not a number of minutes nor a duration like `2h`"),
+            (
+                "1h30",
+                "is not a number of minutes nor a duration like `2h`",
+            ),
+            ("@25:00", "is not a time of day like `@09:00`"),
+            ("@9am", "is not a time of day like `@09:00`"),
+        ] {
+            let body = format!("!miou register game123 Alice {}", delay);
+            let result = Command::parse(&address, &body);
+            assert!(
+                matches!(
+                    result,
+                    Err(CommandParsingError::InvalidArguments(
+                        "register",
+                        ArgumentError::InvalidValue { argument: "delay", value, reason }
+                    )) if value == delay && reason == expected_reason
+                ),
+                "{}",
+                delay
+            );
+        }
     }
 
     #[test]
     fn test_parse_unregister_command() {
-        let parser = create_parser();
-        let result = Command::parse(&parser, "!miou unregister game123");
+        let address = create_address();
+        let result = parse_arguments::<String>(&address, "!miou unregister game123");
         assert!(matches!(
             result,
-            Ok(Command::Unregister(game_id)) if game_id == "game123"
+            Ok(("unregister", game_id)) if game_id == "game123"
         ));
     }
 
     #[test]
     fn test_parse_unregister_command_invalid_missing_args() {
-        let parser = create_parser();
-        let result = Command::parse(&parser, "!miou unregister");
+        let address = create_address();
+        let result = Command::parse(&address, "!miou unregister");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments("unregister", _))
+        ));
+    }
+
+    #[test]
+    fn test_parse_watch_command() {
+        let address = create_address();
+        let result = parse_arguments::<String>(&address, "!miou watch game123");
+        assert!(matches!(
+            result,
+            Ok(("watch", game_id)) if game_id == "game123"
+        ));
+    }
+
+    #[test]
+    fn test_parse_watch_command_invalid_missing_args() {
+        let address = create_address();
+        let result = Command::parse(&address, "!miou watch");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments("watch", _))
+        ));
+    }
+
+    #[test]
+    fn test_parse_watch_command_invalid_too_many_args() {
+        let address = create_address();
+        let result = Command::parse(&address, "!miou watch game123 game456");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments("watch", _))
+        ));
+    }
+
+    #[test]
+    fn test_parse_unwatch_command() {
+        let address = create_address();
+        let result = parse_arguments::<String>(&address, "!miou unwatch game123");
+        assert!(matches!(
+            result,
+            Ok(("unwatch", game_id)) if game_id == "game123"
+        ));
+    }
+
+    #[test]
+    fn test_parse_unwatch_command_invalid_missing_args() {
+        let address = create_address();
+        let result = Command::parse(&address, "!miou unwatch");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments("unwatch", _))
+        ));
+    }
+
+    #[test]
+    fn test_parse_game_command() {
+        let address = create_address();
+        let result = parse_arguments::<String>(&address, "!miou game game123");
+        assert!(matches!(
+            result,
+            Ok(("game", game_id)) if game_id == "game123"
+        ));
+    }
+
+    #[test]
+    fn test_parse_game_command_invalid_missing_args() {
+        let address = create_address();
+        let result = Command::parse(&address, "!miou game");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments("game", _))
+        ));
+    }
+
+    #[test]
+    fn test_parse_server_command() {
+        let address = create_address();
+        let result = parse_arguments::<Option<String>>(&address, "!miou server");
+        assert!(matches!(result, Ok(("server", None))));
+    }
+
+    #[test]
+    fn test_parse_set_server_command() {
+        let address = create_address();
+        let result = parse_arguments::<Option<String>>(&address, "!miou server friends");
+        assert!(matches!(
+            result,
+            Ok(("server", Some(server))) if server == "friends"
+        ));
+    }
+
+    #[test]
+    fn test_parse_server_command_invalid_too_many_args() {
+        let address = create_address();
+        let result = Command::parse(&address, "!miou server friends main");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments("server", _))
+        ));
+    }
+
+    #[test]
+    fn test_parse_stats_command() {
+        let address = create_address();
+        let result = parse_arguments::<Option<String>>(&address, "!miou stats");
+        assert!(matches!(result, Ok(("stats", None))));
+    }
+
+    #[test]
+    fn test_parse_stats_command_with_game_id() {
+        let address = create_address();
+        let result = parse_arguments::<Option<String>>(&address, "!miou stats game123");
+        assert!(matches!(
+            result,
+            Ok(("stats", Some(game_id))) if game_id == "game123"
+        ));
+    }
+
+    #[test]
+    fn test_parse_stats_command_invalid_too_many_args() {
+        let address = create_address();
+        let result = Command::parse(&address, "!miou stats game123 Alice");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments("stats", _))
+        ));
+    }
+
+    #[test]
+    fn test_parse_template_command() {
+        let address = create_address();
+        assert!(matches!(
+            parse_arguments::<TemplateArguments>(&address, "!miou template"),
+            Ok(("template", TemplateArguments::Show))
+        ));
+        assert!(matches!(
+            parse_arguments::<TemplateArguments>(&address, "!miou template reset"),
+            Ok(("template", TemplateArguments::Reset))
+        ));
+    }
+
+    #[test]
+    fn test_parse_set_template_command() {
+        let address = create_address();
+        let result = parse_arguments::<TemplateArguments>(
+            &address,
+            "!miou template set \"{user}:  wake up {player} --now, {url}\"",
+        );
+        assert!(matches!(
+            result,
+            Ok(("template", TemplateArguments::Set(template))) if template == "{user}:  wake up {player} --now, {url}"
+        ));
+    }
+
+    #[test]
+    fn test_parse_set_template_command_without_quotes() {
+        let address = create_address();
+        let result =
+            parse_arguments::<TemplateArguments>(&address, "!miou template set {player}, play!");
+        assert!(matches!(
+            result,
+            Ok(("template", TemplateArguments::Set(template))) if template == "{player}, play!"
+        ));
+    }
+
+    #[test]
+    fn test_parse_template_command_invalid() {
+        let address = create_address();
+
+        for (body, expected_error) in [
+            ("!miou template set", ArgumentError::MissingArguments),
+            ("!miou template set \"\"", ArgumentError::MissingArguments),
+            (
+                "!miou template reset now",
+                ArgumentError::UnexpectedArgument("now".to_string()),
+            ),
+            (
+                "!miou template show",
+                ArgumentError::InvalidValue {
+                    argument: "action",
+                    value: "show".to_string(),
+                    reason: "is unknown, expected `set` or `reset`",
+                },
+            ),
+        ] {
+            let result = Command::parse(&address, body);
+            assert!(matches!(
+                result,
+                Err(CommandParsingError::InvalidArguments("template", error))
+                if error == expected_error
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_prefix_command() {
+        let address = create_address();
+        assert!(matches!(
+            parse_arguments::<PrefixArguments>(&address, "!miou prefix"),
+            Ok(("prefix", PrefixArguments::Show))
+        ));
+        assert!(matches!(
+            parse_arguments::<PrefixArguments>(&address, "!miou prefix !mi"),
+            Ok(("prefix", PrefixArguments::Set(prefix))) if prefix == "!mi"
+        ));
+        assert!(matches!(
+            parse_arguments::<PrefixArguments>(&address, "!miou prefix reset"),
+            Ok(("prefix", PrefixArguments::Reset))
+        ));
+        assert!(matches!(
+            Command::parse(&address, "!miou prefix !mi !miou"),
+            Err(CommandParsingError::InvalidArguments(
+                "prefix",
+                ArgumentError::UnexpectedArgument(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_parse_settings_command() {
+        let address = create_address();
+        let result = parse_arguments::<SettingsArguments>(&address, "!miou settings");
+        assert!(matches!(result, Ok(("settings", SettingsArguments::Show))));
+    }
+
+    #[test]
+    fn test_parse_settings_quiet_command() {
+        let address = create_address();
+        let result = parse_arguments::<SettingsArguments>(
+            &address,
+            "!miou settings quiet 23:00-08:00 Europe/Paris",
+        );
+        assert!(matches!(
+            result,
+            Ok(("settings", SettingsArguments::SetQuietHours { window, timezone }))
+            if window == "23:00-08:00" && timezone == "Europe/Paris"
+        ));
+    }
+
+    #[test]
+    fn test_parse_settings_quiet_off_command() {
+        let address = create_address();
+        let result = parse_arguments::<SettingsArguments>(&address, "!miou settings quiet off");
+        assert!(matches!(
+            result,
+            Ok(("settings", SettingsArguments::DisableQuietHours))
+        ));
+    }
+
+    #[test]
+    fn test_parse_settings_command_invalid_missing_timezone() {
+        let address = create_address();
+        let result = Command::parse(&address, "!miou settings quiet 23:00-08:00");
         assert!(matches!(
             result,
-            Err(CommandParsingError::InvalidUnRegister)
+            Err(CommandParsingError::InvalidArguments(
+                "settings",
+                ArgumentError::MissingArguments
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_parse_settings_command_invalid_unknown_setting() {
+        let address = create_address();
+        let result = Command::parse(&address, "!miou settings loud on");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments(
+                "settings",
+                ArgumentError::InvalidValue { argument: "setting", value, .. }
+            )) if value == "loud"
+        ));
+    }
+
+    #[test]
+    fn test_parse_command_alias() {
+        let address = create_address();
+        let result = parse_arguments::<RegisterArguments>(&address, "!miou reg game123 Alice 60");
+        assert!(matches!(
+            result,
+            Ok(("register", RegisterArguments { game_id, player_name, .. }))
+            if game_id == "game123" && player_name == "Alice"
+        ));
+    }
+
+    #[test]
+    fn test_parse_command_unknown_option() {
+        let address = create_address();
+        let result = Command::parse(&address, "!miou games --server friends");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidArguments(
+                "games",
+                ArgumentError::UnknownOption(option)
+            )) if option == "server"
         ));
     }
 
     #[test]
     fn test_parse_unknown_command() {
-        let parser = create_parser();
-        let result = Command::parse(&parser, "!miou unknown");
-        assert!(matches!(result, Err(CommandParsingError::Unknown)));
+        let address = create_address();
+        let result = Command::parse(&address, "!miou unknown");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::Unknown(name)) if name == "unknown"
+        ));
     }
 
     #[test]
     fn test_parse_not_miou_command() {
-        let parser = create_parser();
-        let result = Command::parse(&parser, "!other_bot help");
+        let address = create_address();
+        let result = Command::parse(&address, "!other_bot help");
+        assert!(matches!(result, Err(CommandParsingError::NotMiou)));
+    }
+
+    #[test]
+    fn test_parse_not_miou_command_invalid_syntax() {
+        let address = create_address();
+        let result = Command::parse(&address, "!other_bot say \"hello");
         assert!(matches!(result, Err(CommandParsingError::NotMiou)));
     }
 
+    #[test]
+    fn test_parse_invalid_syntax() {
+        let address = create_address();
+        let result = Command::parse(&address, "!miou register game123 \"Alice 60");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidSyntax(
+                SyntaxError::UnclosedQuote(23)
+            ))
+        ));
+    }
+
     #[test]
     fn test_parse_unable_to_parse() {
-        let parser = create_parser();
-        let result = Command::parse(&parser, "This is not a command");
+        let address = create_address();
+        let result = Command::parse(&address, "This is not a command");
+        assert!(matches!(result, Err(CommandParsingError::UnableToParse)));
+    }
+
+    #[test]
+    fn test_parse_custom_prefix() {
+        let address = CommandAddress {
+            prefix: "!mi",
+            ..create_address()
+        };
+
+        assert!(matches!(
+            parse_arguments::<()>(&address, "!mi games"),
+            Ok(("games", ()))
+        ));
+        assert!(matches!(
+            Command::parse(&address, "!miou games"),
+            Err(CommandParsingError::NotMiou)
+        ));
+    }
+
+    #[test]
+    fn test_parse_prefix_followed_by_text() {
+        let address = create_address();
+        let result = Command::parse(&address, "!mioux games");
+        assert!(matches!(result, Err(CommandParsingError::NotMiou)));
+    }
+
+    #[test]
+    fn test_parse_mention() {
+        let address = create_address();
+
+        for body in [
+            "@miou:example.com games",
+            "@miou:example.com: games",
+            "  @miou:example.com:\tgames",
+        ] {
+            let result = parse_arguments::<()>(&address, body);
+            assert!(matches!(result, Ok(("games", ()))), "{}", body);
+        }
+
+        let result = parse_arguments::<Option<String>>(&address, "@miou:example.com:");
+        assert!(matches!(result, Ok(("help", None))));
+
+        let result = Command::parse(&address, "@miou:example.community games");
         assert!(matches!(result, Err(CommandParsingError::UnableToParse)));
     }
 
+    #[test]
+    fn test_parse_mention_pill() {
+        // The plain text body of a pill is the display name of the bot
+        let address = CommandAddress {
+            mention: Some("Miou"),
+            ..create_address()
+        };
+
+        for body in ["Miou: games", "Miou games", "@miou:example.com games"] {
+            let result = parse_arguments::<()>(&address, body);
+            assert!(matches!(result, Ok(("games", ()))), "{}", body);
+        }
+
+        let result = Command::parse(&address, "Miouu: games");
+        assert!(matches!(result, Err(CommandParsingError::UnableToParse)));
+
+        // Without a pill, the display name is not a mention
+        let address = create_address();
+        let result = Command::parse(&address, "Miou: games");
+        assert!(matches!(result, Err(CommandParsingError::UnableToParse)));
+    }
+
+    #[test]
+    fn test_parse_mention_invalid_syntax() {
+        let address = create_address();
+        let result = Command::parse(&address, "@miou:example.com: register \"Alice");
+        assert!(matches!(
+            result,
+            Err(CommandParsingError::InvalidSyntax(
+                SyntaxError::UnclosedQuote(28)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_parse_direct_message() {
+        let address = CommandAddress {
+            is_direct: true,
+            ..create_address()
+        };
+
+        assert!(matches!(
+            parse_arguments::<()>(&address, "games"),
+            Ok(("games", ()))
+        ));
+        assert!(matches!(
+            parse_arguments::<()>(&address, "!miou games"),
+            Ok(("games", ()))
+        ));
+        assert!(matches!(
+            parse_arguments::<()>(&address, "@miou:example.com games"),
+            Ok(("games", ()))
+        ));
+        assert!(matches!(
+            Command::parse(&address, "hello"),
+            Err(CommandParsingError::Unknown(name)) if name == "hello"
+        ));
+    }
+
     #[test]
     fn test_format_command_error_unknown() {
-        let error = CommandParsingError::Unknown;
-        let result = format_command_error(error);
+        let error = CommandParsingError::Unknown("dance".to_string());
+        let result = format_command_error("!miou", error);
         assert!(result.is_some());
         assert!(result.unwrap().contains("Unknown command"));
     }
 
+    #[test]
+    fn test_format_command_error_unknown_with_suggestion() {
+        let result =
+            format_command_error("!miou", CommandParsingError::Unknown("regster".to_string()));
+        assert_eq!(
+            result.unwrap(),
+            "Unknown command. Did you mean `!miou register`? Type `!miou help` for more information."
+        );
+    }
+
     #[test]
     fn test_format_command_error_invalid_register() {
-        let error = CommandParsingError::InvalidRegister;
-        let result = format_command_error(error);
-        assert!(result.is_some());
-        assert!(result.unwrap().contains("Invalid register"));
+        let result = format_command_error(
+            "!miou",
+            CommandParsingError::InvalidArguments(
+                "register",
+                ArgumentError::InvalidValue {
+                    argument: "delay",
+                    value: "abc".to_string(),
+                    reason: "is not a number of minutes nor a duration like `2h`",
+                },
+            ),
+        );
+        assert_eq!(
+            result.unwrap(),
+            "Invalid register command: delay `abc` is not a number of minutes nor a duration like `2h`. Usage: `!miou register <game_id> <player_name> <delay> [repeat_in_minutes] [max_reminders] [--phases <phase,...>]`"
+        );
     }
 
     #[test]
     fn test_format_command_error_invalid_unregister() {
-        let error = CommandParsingError::InvalidUnRegister;
-        let result = format_command_error(error);
-        assert!(result.is_some());
-        assert!(result.unwrap().contains("Invalid unregister"));
+        let result = format_command_error(
+            "!miou",
+            CommandParsingError::InvalidArguments("unregister", ArgumentError::MissingArguments),
+        );
+        assert_eq!(
+            result.unwrap(),
+            "Invalid unregister command: missing arguments. Usage: `!miou unregister <game_id>`"
+        );
+    }
+
+    #[test]
+    fn test_format_command_error_invalid_settings() {
+        let result = format_command_error(
+            "!miou",
+            CommandParsingError::InvalidArguments(
+                "settings",
+                ArgumentError::InvalidValue {
+                    argument: "setting",
+                    value: "loud".to_string(),
+                    reason: "is unknown, expected `quiet`",
+                },
+            ),
+        );
+        assert_eq!(
+            result.unwrap(),
+            "Invalid settings command: setting `loud` is unknown, expected `quiet`. Usage: `!miou settings`, `!miou settings quiet <start>-<end> <timezone>` or `!miou settings quiet off`"
+        );
+    }
+
+    #[test]
+    fn test_format_command_error_invalid_watch() {
+        let result = format_command_error(
+            "!miou",
+            CommandParsingError::InvalidArguments(
+                "watch",
+                ArgumentError::UnexpectedArgument("game456".to_string()),
+            ),
+        );
+        assert_eq!(
+            result.unwrap(),
+            "Invalid watch command: unexpected argument `game456`. Usage: `!miou watch <game_id>`"
+        );
+    }
+
+    #[test]
+    fn test_format_command_error_invalid_unwatch() {
+        let result = format_command_error(
+            "!miou",
+            CommandParsingError::InvalidArguments("unwatch", ArgumentError::MissingArguments),
+        );
+        assert_eq!(
+            result.unwrap(),
+            "Invalid unwatch command: missing arguments. Usage: `!miou unwatch <game_id>`"
+        );
+    }
+
+    #[test]
+    fn test_format_command_error_invalid_server() {
+        let result = format_command_error(
+            "!miou",
+            CommandParsingError::InvalidArguments(
+                "server",
+                ArgumentError::UnknownOption("name".to_string()),
+            ),
+        );
+        assert_eq!(
+            result.unwrap(),
+            "Invalid server command: unknown option `--name`. Usage: `!miou server` or `!miou server <server_name>`"
+        );
+    }
+
+    #[test]
+    fn test_format_command_error_invalid_stats() {
+        let result = format_command_error(
+            "!miou",
+            CommandParsingError::InvalidArguments("stats", ArgumentError::MissingArguments),
+        );
+        assert!(result.unwrap().contains("Invalid stats"));
+    }
+
+    #[test]
+    fn test_format_command_error_invalid_game() {
+        let result = format_command_error(
+            "!miou",
+            CommandParsingError::InvalidArguments("game", ArgumentError::MissingArguments),
+        );
+        assert_eq!(
+            result.unwrap(),
+            "Invalid game command: missing arguments. Usage: `!miou game <game_id>`"
+        );
+    }
+
+    #[test]
+    fn test_format_command_error_invalid_syntax() {
+        let result = format_command_error(
+            "!miou",
+            CommandParsingError::InvalidSyntax(SyntaxError::UnclosedQuote(23)),
+        );
+        assert!(result.unwrap().contains("column 24"));
     }
 
     #[test]
     fn test_format_command_error_unable_to_parse() {
         let error = CommandParsingError::UnableToParse;
-        let result = format_command_error(error);
+        let result = format_command_error("!miou", error);
         assert!(result.is_none());
     }
 
     #[test]
     fn test_format_command_error_not_miou() {
         let error = CommandParsingError::NotMiou;
-        let result = format_command_error(error);
+        let result = format_command_error("!miou", error);
         assert!(result.is_none());
     }
 }
@@ -21,13 +21,14 @@
 //!
 //! ```no_run
 //! # use miou::commands::{Commander, CommandContext};
+//! # use miou::settings::{RoomSettings, UserSettings};
 //! # use std::collections::HashMap;
 //! # async fn example() {
-//! let commander = Commander::new();
+//! let commander = Commander::new("!miou".to_string(), "@miou:example.com".to_string());
 //!
 //! // Parse a message
 //! let message = "!miou help".to_string();
-//! let command = commander.parse(message).unwrap();
+//! let command = commander.parse(&message, None, false, None).unwrap();
 //!
 //! // Execute the command
 //! let context = CommandContext {
@@ -35,19 +36,33 @@
 //!     alerts_map: HashMap::new(),
 //!     room_id: "!room:example.com".to_string(),
 //!     user_id: "@user:example.com".to_string(),
+//!     user_settings: UserSettings::default(),
+//!     room_settings: RoomSettings::default(),
+//!     default_server: "main".to_string(),
+//!     default_prefix: "!miou".to_string(),
+//!     servers: vec!["main".to_string()],
+//!     turns_map: HashMap::new(),
 //! };
 //! let result = commander.parse_command(&command, &context).await;
 //! # }
 //! ```
 
-use command_parser::Parser;
-
+use crate::alerts::Alert;
 use crate::commands::{
     CommandContext, CommandParseError, CommandResult,
-    actions::{handle_alerts, handle_games, handle_help, handle_register, handle_unregister},
-    command::{Command, format_command_error},
-    markdown_response::{format_access_error, format_player_turn},
+    command::{Command, CommandAddress, format_command_error},
+    html_response::{
+        HtmlMessage, format_player_turn, format_player_turn_reminder, format_templated_player_turn,
+        format_templated_player_turn_reminder,
+    },
+    markdown_response::{
+        format_access_error, format_game_end, format_server_back, format_server_unavailable,
+        format_turn_change,
+    },
 };
+use crate::settings::NotificationTemplate;
+use crate::tmars::{Game, PlayerScore, SyncError};
+use crate::watches::TurnChange;
 
 /// Command orchestrator for parsing and executing bot commands.
 ///
@@ -59,26 +74,28 @@ use crate::commands::{
 ///
 /// # Command Prefix
 ///
-/// All commands must start with the `!miou` prefix. Messages without this prefix
-/// are silently ignored (returning [`CommandParseError::NotForBot`]).
+/// Commands must start with the prefix of the room, `!miou` by default, or with a
+/// mention of the bot. In a direct chat with the bot, they need neither. Other
+/// messages are silently ignored (returning [`CommandParseError::NotForBot`]).
 ///
 /// # Supported Commands
 ///
-/// - `help` - Display help information
-/// - `games` - List all ongoing games
-/// - `alerts` - List user's registered alerts
-/// - `register <game_id> <player_name> <delay>` - Register for turn notifications
-/// - `unregister <game_id>` - Stop receiving notifications
+/// The commands are the ones of the [`COMMANDS`](crate::commands::registry::COMMANDS)
+/// registry, listed by the `help` command.
 pub struct Commander {
-    /// Command parser for processing user commands
-    parser: Parser,
+    /// Prefix of the commands in the rooms which didn't choose one
+    prefix: String,
+    /// Matrix user ID of the bot, mentioned to address it the commands
+    user_id: String,
 }
 
 impl Commander {
-    /// Creates a new Commander instance with a configured command parser.
+    /// Creates a new Commander instance.
+    ///
+    /// # Arguments
     ///
-    /// The parser is configured to recognize commands starting with `!` as the command
-    /// prefix and `-` as the option prefix.
+    /// * `prefix` - Prefix of the commands in the rooms which didn't choose one, e.g. `!miou`
+    /// * `user_id` - Matrix user ID of the bot
     ///
     /// # Returns
     ///
@@ -88,23 +105,31 @@ impl Commander {
     ///
     /// ```
     /// # use miou::commands::Commander;
-    /// let commander = Commander::new();
+    /// let commander = Commander::new("!miou".to_string(), "@miou:example.com".to_string());
     /// ```
-    pub fn new() -> Self {
-        let parser = Parser::new('!', '-');
-        Commander { parser }
+    pub fn new(prefix: String, user_id: String) -> Self {
+        Commander { prefix, user_id }
+    }
+
+    /// Returns the prefix of the commands in the rooms which didn't choose one.
+    pub fn get_prefix(&self) -> &str {
+        &self.prefix
     }
 
     /// Parses a Matrix message body into a structured command.
     ///
     /// This method validates that the message is:
-    /// 1. A valid command format (starts with `!`)
-    /// 2. Directed at this bot (uses `miou` as the command name)
-    /// 3. Contains valid syntax and arguments
+    /// 1. Addressed to this bot: starting with the prefix of the room or a mention
+    ///    of the bot, or sent in a direct chat with the bot
+    /// 2. Contains valid syntax and arguments
     ///
     /// # Arguments
     ///
     /// * `body` - The raw message text from Matrix
+    /// * `room_prefix` - Prefix of the commands chosen by the room, `None` for the default prefix
+    /// * `is_direct` - Whether the message is sent in a direct chat with the bot
+    /// * `mention` - Text of the mention of the bot starting the message, e.g. the display
+    ///   name of the bot when the client sends a pill
     ///
     /// # Returns
     ///
@@ -122,29 +147,46 @@ impl Commander {
     ///
     /// ```
     /// # use miou::commands::Commander;
-    /// let commander = Commander::new();
+    /// let commander = Commander::new("!miou".to_string(), "@miou:example.com".to_string());
     ///
     /// // Valid command
-    /// let result = commander.parse("!miou help".to_string());
+    /// let result = commander.parse("!miou help", None, false, None);
+    /// assert!(result.is_ok());
+    ///
+    /// // Mention of the bot
+    /// let result = commander.parse("@miou:example.com: help", None, false, None);
     /// assert!(result.is_ok());
     ///
     /// // Not a command
-    /// let result = commander.parse("Hello, world!".to_string());
+    /// let result = commander.parse("Hello, world!", None, false, None);
     /// assert!(result.is_err());
     ///
     /// // Wrong bot
-    /// let result = commander.parse("!other_bot help".to_string());
+    /// let result = commander.parse("!other_bot help", None, false, None);
     /// assert!(result.is_err());
     /// ```
-    pub fn parse(&self, body: &str) -> Result<Command, CommandParseError> {
-        let parse_result = Command::parse(&self.parser, body);
+    pub fn parse(
+        &self,
+        body: &str,
+        room_prefix: Option<&str>,
+        is_direct: bool,
+        mention: Option<&str>,
+    ) -> Result<Command, CommandParseError> {
+        let prefix = room_prefix.unwrap_or(&self.prefix);
+        let address = CommandAddress {
+            prefix,
+            user_id: &self.user_id,
+            mention,
+            is_direct,
+        };
+        let parse_result = Command::parse(&address, body);
 
         // Raise an error message if the command is invalid
         if parse_result.is_err() {
             let error = parse_result.err().unwrap();
             // Return silently if the command is not for the bot
             // Otherwise, send an error message
-            if let Some(message) = format_command_error(error) {
+            if let Some(message) = format_command_error(prefix, error) {
                 return Err(CommandParseError::InvalidCommand(message));
             }
             return Err(CommandParseError::NotForBot);
@@ -155,8 +197,8 @@ impl Commander {
 
     /// Executes a parsed command and returns the result.
     ///
-    /// This method routes commands to their appropriate handlers and collects the
-    /// results.
+    /// This method runs the handler of the command with the arguments it parsed and
+    /// collects the results.
     ///
     /// # Arguments
     ///
@@ -169,103 +211,219 @@ impl Commander {
     ///
     /// # Returns
     ///
-    /// * `Some(CommandResult)` - Command executed successfully with a result
-    /// * `None` - Command handler rejected the command (invalid command type)
-    ///
-    /// # Command Handlers
-    ///
-    /// - [`Command::Help`] → [`handle_help`]
-    /// - [`Command::Games`] → [`handle_games`]
-    /// - [`Command::Alerts`] → [`handle_alerts`]
-    /// - [`Command::Register`] → [`handle_register`]
-    /// - [`Command::Unregister`] → [`handle_unregister`]
+    /// The response of the command with the state changes to apply.
     ///
     /// # Examples
     ///
     /// ```no_run
     /// # use miou::commands::{Commander, Command, CommandContext};
+    /// # use miou::settings::UserSettings;
     /// # use std::collections::HashMap;
     /// # async fn example() {
-    /// let commander = Commander::new();
-    /// let command = Command::Help;
+    /// let commander = Commander::new("!miou".to_string(), "@miou:example.com".to_string());
+    /// let command = commander.parse("!miou help", None, false, None).unwrap();
     /// let context = CommandContext {
     ///     games_map: HashMap::new(),
     ///     alerts_map: HashMap::new(),
     ///     room_id: "!room:example.com".to_string(),
     ///     user_id: "@user:example.com".to_string(),
+    ///     user_settings: UserSettings::default(),
+    ///     room_settings: RoomSettings::default(),
+    ///     default_server: "main".to_string(),
+    ///     default_prefix: "!miou".to_string(),
+    ///     servers: vec!["main".to_string()],
+    ///     turns_map: HashMap::new(),
     /// };
     ///
-    /// if let Some(result) = commander.parse_command(&command, &context).await {
-    ///     println!("Response: {}", result.response);
-    /// }
+    /// let result = commander.parse_command(&command, &context).await;
+    /// println!("Response: {}", result.response);
     /// # }
     /// ```
     pub async fn parse_command(
         &self,
         command: &Command,
         context: &CommandContext,
-    ) -> Option<CommandResult> {
-        let result = match command {
-            Command::Help => handle_help(),
-            Command::Register(_, _, _) => match handle_register(context, command).await {
-                Some(result) => result,
-                None => return None,
-            },
-            Command::Unregister(_) => match handle_unregister(context, command).await {
-                Some(result) => result,
-                None => return None,
-            },
-            Command::Games => handle_games(context),
-            Command::Alerts => handle_alerts(context),
-        };
-
-        Some(result)
+    ) -> CommandResult {
+        command.handle(context).await
     }
 
     /// Generates a formatted notification message for a player's turn.
     ///
     /// This method creates a user-friendly message to notify players that it's their
-    /// turn in a game. The message is formatted as Markdown for display in Matrix.
+    /// turn in a game, with the context of the turn. The message is formatted as HTML
+    /// for display in Matrix, with a plain text fallback.
+    ///
+    /// The context comes from the game as it is when the message is sent, not as it
+    /// was when the turn was detected, as the notification may be sent long after.
+    ///
+    /// # Arguments
+    ///
+    /// * `alert` - The alert of the player
+    /// * `game` - The current state of the game of the player, when the message is sent
+    /// * `template` - The template of the room of the alert, `None` for the default text
+    /// * `now` - Unix timestamp (in seconds) used to compute how long the turn has been open
+    ///
+    /// # Returns
+    ///
+    /// The turn notification message.
+    pub fn get_player_turn_message(
+        alert: &Alert,
+        game: &Game,
+        template: Option<&NotificationTemplate>,
+        now: u64,
+    ) -> HtmlMessage {
+        match template {
+            Some(template) => format_templated_player_turn(
+                template,
+                &alert.user_id,
+                &alert.player_id,
+                &alert.player_url,
+                game,
+            ),
+            None => format_player_turn(
+                &alert.user_id,
+                &alert.player_id,
+                &alert.player_url,
+                game,
+                now,
+            ),
+        }
+    }
+
+    /// Generates a formatted reminder message for a player who hasn't played yet.
+    ///
+    /// Sent instead of the turn notification for the reminders of a repeating alert.
+    ///
+    /// # Arguments
+    ///
+    /// * `alert` - The alert of the player
+    /// * `game` - The current state of the game of the player, when the message is sent
+    /// * `template` - The template of the room of the alert, `None` for the default text
+    /// * `now` - Unix timestamp (in seconds) used to compute how long the turn has been open
+    ///
+    /// # Returns
+    ///
+    /// The reminder message.
+    pub fn get_player_reminder_message(
+        alert: &Alert,
+        game: &Game,
+        template: Option<&NotificationTemplate>,
+        now: u64,
+    ) -> HtmlMessage {
+        match template {
+            Some(template) => format_templated_player_turn_reminder(
+                template,
+                &alert.user_id,
+                &alert.player_id,
+                &alert.player_url,
+                game,
+            ),
+            None => format_player_turn_reminder(
+                &alert.user_id,
+                &alert.player_id,
+                &alert.player_url,
+                game,
+                now,
+            ),
+        }
+    }
+
+    /// Generates the announcement of a turn change in a watched game.
+    ///
+    /// # Arguments
+    ///
+    /// * `turn_change` - The change of the waited players or of the phase
+    ///
+    /// # Returns
+    ///
+    /// A formatted string naming the players who are now up and the phase.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use miou::commands::Commander;
+    /// # use miou::tmars::Phase;
+    /// # use miou::watches::TurnChange;
+    /// let turn_change = TurnChange {
+    ///     game_id: "game123".to_string(),
+    ///     phase: Phase::Action,
+    ///     waited_player_names: vec!["Bob".to_string()],
+    /// };
+    /// let message = Commander::get_turn_change_message(&turn_change);
+    /// assert!(message.contains("Bob"));
+    /// ```
+    pub fn get_turn_change_message(turn_change: &TurnChange) -> String {
+        format_turn_change(turn_change)
+    }
+
+    /// Generates the announcement of the end of a game with its final scores.
     ///
     /// # Arguments
     ///
-    /// * `user_id` - The Matrix user ID of the player
-    /// * `player_url` - The URL to the player's game page
+    /// * `game_id` - The ID of the ended game
+    /// * `scores` - The final scores, from the highest to the lowest
     ///
     /// # Returns
     ///
-    /// A formatted string containing the turn notification message.
+    /// A formatted string ranking the players by victory points.
     ///
     /// # Examples
     ///
     /// ```
     /// # use miou::commands::Commander;
-    /// let message = Commander::get_player_turn_message("https://example.com/player?id=p123".to_string());
-    /// assert!(message.contains("example.com"));
+    /// # use miou::tmars::PlayerScore;
+    /// let scores = vec![PlayerScore {
+    ///     name: "Bob".to_string(),
+    ///     victory_points: 85,
+    /// }];
+    /// let message = Commander::get_game_end_message("game123", &scores);
+    /// assert!(message.contains("Bob"));
     /// ```
-    pub fn get_player_turn_message(user_id: &str, player_url: &str) -> String {
-        format_player_turn(user_id, player_url)
+    pub fn get_game_end_message(game_id: &str, scores: &[PlayerScore]) -> String {
+        format_game_end(game_id, scores)
     }
 
-    /// Generates a formatted error message for API access errors.
+    /// Generates a formatted error message for a failing synchronization.
     ///
     /// This method creates a user-friendly error message to display when the bot
     /// encounters authorization or authentication issues while accessing the
-    /// Terraforming Mars API.
+    /// Terraforming Mars API, or can't reach it.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - Name of the failing server
+    /// * `error` - The error of the synchronization
     ///
     /// # Returns
     ///
-    /// A formatted string containing the access error message.
+    /// A formatted string containing the error message.
     ///
     /// # Examples
     ///
     /// ```
     /// # use miou::commands::Commander;
-    /// let message = Commander::get_access_error_message();
+    /// # use miou::tmars::SyncError;
+    /// let message = Commander::get_sync_error_message("main", &SyncError::Access);
     /// assert!(message.contains("unauthorized"));
     /// ```
-    pub fn get_access_error_message() -> String {
-        format_access_error()
+    pub fn get_sync_error_message(server_name: &str, error: &SyncError) -> String {
+        match error {
+            SyncError::Access => format_access_error(server_name),
+            _ => format_server_unavailable(server_name),
+        }
+    }
+
+    /// Generates a formatted message announcing that a server answers again.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - Name of the recovered server
+    ///
+    /// # Returns
+    ///
+    /// A formatted string containing the recovery message.
+    pub fn get_server_back_message(server_name: &str) -> String {
+        format_server_back(server_name)
     }
 }
 
@@ -274,8 +432,16 @@ mod tests {
     use std::collections::{HashMap, HashSet};
 
     use super::*;
-    use crate::alerts::Alert;
-    use crate::tmars::{Game, Phase, Player};
+    use crate::commands::actions::{
+        AlertsCommand, Delay, GameCommand, GamesCommand, HelpCommand, RegisterArguments,
+        RegisterCommand, SettingsArguments, SettingsCommand, UnregisterCommand, UnwatchCommand,
+    };
+    use crate::settings::{RoomSettings, UserSettings};
+    use crate::tmars::Phase;
+
+    fn create_commander() -> Commander {
+        Commander::new("!miou".to_string(), "@miou:example.com".to_string())
+    }
 
     fn create_test_context() -> CommandContext {
         CommandContext {
@@ -283,88 +449,75 @@ mod tests {
             alerts_map: HashMap::new(),
             room_id: "!room:example.com".to_string(),
             user_id: "@user:example.com".to_string(),
+            user_settings: UserSettings::default(),
+            room_settings: RoomSettings::default(),
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["main".to_string()],
+            turns_map: HashMap::new(),
         }
     }
 
     fn create_test_game(id: &str) -> Game {
-        Game {
-            id: id.to_string(),
-            phase: Phase::Action,
-            spectator_id: "spec123".to_string(),
-            players: vec![
-                Player {
-                    id: "player1".to_string(),
-                    name: "Alice".to_string(),
-                    color: "red".to_string(),
-                    url: "http://example.com/player1".to_string(),
-                },
-                Player {
-                    id: "player2".to_string(),
-                    name: "Bob".to_string(),
-                    color: "blue".to_string(),
-                    url: "http://example.com/player2".to_string(),
-                },
-            ],
-            waited_players: HashSet::new(),
-        }
+        Game::test(id, Phase::Action, &[])
     }
 
     #[test]
     fn test_parse_valid_help_command() {
-        let commander = Commander::new();
-        let result = commander.parse("!miou help");
-        assert!(result.is_ok());
-        assert!(matches!(result.unwrap(), Command::Help));
+        let commander = create_commander();
+        let result = commander.parse("!miou help", None, false, None).unwrap();
+        assert_eq!(result.name(), "help");
+        assert_eq!(result.into_arguments::<Option<String>>(), Some(None));
     }
 
     #[test]
     fn test_parse_valid_games_command() {
-        let commander = Commander::new();
-        let result = commander.parse("!miou games");
-        assert!(result.is_ok());
-        assert!(matches!(result.unwrap(), Command::Games));
+        let commander = create_commander();
+        let result = commander.parse("!miou games", None, false, None).unwrap();
+        assert_eq!(result.name(), "games");
     }
 
     #[test]
     fn test_parse_valid_alerts_command() {
-        let commander = Commander::new();
-        let result = commander.parse("!miou alerts");
-        assert!(result.is_ok());
-        assert!(matches!(result.unwrap(), Command::Alerts));
+        let commander = create_commander();
+        let result = commander.parse("!miou alerts", None, false, None).unwrap();
+        assert_eq!(result.name(), "alerts");
     }
 
     #[test]
     fn test_parse_valid_register_command() {
-        let commander = Commander::new();
-        let result = commander.parse("!miou register game123 Alice 60");
-        assert!(result.is_ok());
-        match result.unwrap() {
-            Command::Register(game_id, player_name, delay) => {
-                assert_eq!(game_id, "game123");
-                assert_eq!(player_name, "Alice");
-                assert_eq!(delay, 60);
-            }
-            _ => panic!("Expected Register command"),
-        }
+        let commander = create_commander();
+        let result = commander
+            .parse("!miou register game123 Alice 60", None, false, None)
+            .unwrap();
+        let RegisterArguments {
+            game_id,
+            player_name,
+            delay,
+            ..
+        } = result.into_arguments().unwrap();
+        assert_eq!(game_id, "game123");
+        assert_eq!(player_name, "Alice");
+        assert_eq!(delay, Delay::Minutes(60));
     }
 
     #[test]
     fn test_parse_valid_unregister_command() {
-        let commander = Commander::new();
-        let result = commander.parse("!miou unregister game123");
-        assert!(result.is_ok());
-        match result.unwrap() {
-            Command::Unregister(game_id) => {
-                assert_eq!(game_id, "game123");
-            }
-            _ => panic!("Expected Unregister command"),
-        }
+        let commander = create_commander();
+        let result = commander
+            .parse("!miou unregister game123", None, false, None)
+            .unwrap();
+        assert_eq!(result.name(), "unregister");
+        assert_eq!(
+            result.into_arguments::<String>(),
+            Some("game123".to_string())
+        );
     }
 
     #[test]
     fn test_parse_invalid_command_returns_error() {
-        let commander = Commander::new();
-        let result = commander.parse("!miou unknown_command");
+        let commander = create_commander();
+        let result = commander.parse("!miou unknown_command", None, false, None);
         assert!(result.is_err());
         match result.err().unwrap() {
             CommandParseError::InvalidCommand(msg) => {
@@ -376,8 +529,8 @@ mod tests {
 
     #[test]
     fn test_parse_not_for_bot() {
-        let commander = Commander::new();
-        let result = commander.parse("!other_bot help");
+        let commander = create_commander();
+        let result = commander.parse("!other_bot help", None, false, None);
         assert!(result.is_err());
         assert!(matches!(
             result.err().unwrap(),
@@ -385,10 +538,56 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_room_prefix() {
+        let commander = create_commander();
+
+        let result = commander.parse("!mi games", Some("!mi"), false, None);
+        assert!(result.is_ok_and(|command| command.name() == "games"));
+
+        // The default prefix is replaced by the prefix of the room
+        let result = commander.parse("!miou games", Some("!mi"), false, None);
+        assert!(matches!(result, Err(CommandParseError::NotForBot)));
+    }
+
+    #[test]
+    fn test_parse_room_prefix_in_error() {
+        let commander = create_commander();
+        let result = commander.parse("!mi unknown_command", Some("!mi"), false, None);
+        match result.err().unwrap() {
+            CommandParseError::InvalidCommand(msg) => {
+                assert!(msg.contains("Type `!mi help`"));
+            }
+            _ => panic!("Expected InvalidCommand error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_mention() {
+        let commander = create_commander();
+        let result = commander.parse("@miou:example.com: games", Some("!mi"), false, None);
+        assert!(result.is_ok_and(|command| command.name() == "games"));
+
+        // Pill of the bot, the body starts with its display name
+        let result = commander.parse("Miou: games", Some("!mi"), false, Some("Miou"));
+        assert!(result.is_ok_and(|command| command.name() == "games"));
+    }
+
+    #[test]
+    fn test_parse_direct_message() {
+        let commander = create_commander();
+
+        let result = commander.parse("games", None, true, None);
+        assert!(result.is_ok_and(|command| command.name() == "games"));
+
+        let result = commander.parse("games", None, false, None);
+        assert!(matches!(result, Err(CommandParseError::NotForBot)));
+    }
+
     #[test]
     fn test_parse_not_a_command() {
-        let commander = Commander::new();
-        let result = commander.parse("This is just a regular message");
+        let commander = create_commander();
+        let result = commander.parse("This is just a regular message", None, false, None);
         assert!(result.is_err());
         assert!(matches!(
             result.err().unwrap(),
@@ -398,8 +597,8 @@ mod tests {
 
     #[test]
     fn test_parse_invalid_register_missing_args() {
-        let commander = Commander::new();
-        let result = commander.parse("!miou register game123");
+        let commander = create_commander();
+        let result = commander.parse("!miou register game123", None, false, None);
         assert!(result.is_err());
         match result.err().unwrap() {
             CommandParseError::InvalidCommand(msg) => {
@@ -411,8 +610,8 @@ mod tests {
 
     #[test]
     fn test_parse_invalid_register_bad_delay() {
-        let commander = Commander::new();
-        let result = commander.parse("!miou register game123 Alice invalid");
+        let commander = create_commander();
+        let result = commander.parse("!miou register game123 Alice invalid", None, false, None);
         assert!(result.is_err());
         match result.err().unwrap() {
             CommandParseError::InvalidCommand(msg) => {
@@ -424,8 +623,8 @@ mod tests {
 
     #[test]
     fn test_parse_invalid_unregister_missing_args() {
-        let commander = Commander::new();
-        let result = commander.parse("!miou unregister");
+        let commander = create_commander();
+        let result = commander.parse("!miou unregister", None, false, None);
         assert!(result.is_err());
         match result.err().unwrap() {
             CommandParseError::InvalidCommand(msg) => {
@@ -437,62 +636,57 @@ mod tests {
 
     #[test]
     fn test_parse_empty_command() {
-        let commander = Commander::new();
-        let result = commander.parse("!miou");
-        assert!(result.is_ok());
-        assert!(matches!(result.unwrap(), Command::Help));
+        let commander = create_commander();
+        let result = commander.parse("!miou", None, false, None).unwrap();
+        assert_eq!(result.name(), "help");
+        assert_eq!(result.into_arguments::<Option<String>>(), Some(None));
     }
 
     #[test]
     fn test_parse_register_with_numeric_player_name() {
-        let commander = Commander::new();
-        let result = commander.parse("!miou register game123 123 60");
-        assert!(result.is_ok());
-        match result.unwrap() {
-            Command::Register(game_id, player_name, delay) => {
-                assert_eq!(game_id, "game123");
-                assert_eq!(player_name, "123");
-                assert_eq!(delay, 60);
-            }
-            _ => panic!("Expected Register command"),
-        }
+        let commander = create_commander();
+        let result = commander
+            .parse("!miou register game123 123 60", None, false, None)
+            .unwrap();
+        let RegisterArguments {
+            game_id,
+            player_name,
+            delay,
+            ..
+        } = result.into_arguments().unwrap();
+        assert_eq!(game_id, "game123");
+        assert_eq!(player_name, "123");
+        assert_eq!(delay, Delay::Minutes(60));
     }
 
     #[test]
     fn test_parse_register_with_zero_delay() {
-        let commander = Commander::new();
-        let result = commander.parse("!miou register game123 Alice 0");
-        assert!(result.is_ok());
-        match result.unwrap() {
-            Command::Register(_game_id, _player_name, delay) => {
-                assert_eq!(delay, 0);
-            }
-            _ => panic!("Expected Register command"),
-        }
+        let commander = create_commander();
+        let result = commander
+            .parse("!miou register game123 Alice 0", None, false, None)
+            .unwrap();
+        let RegisterArguments { delay, .. } = result.into_arguments().unwrap();
+        assert_eq!(delay, Delay::Minutes(0));
     }
 
     #[test]
     fn test_parse_register_with_large_delay() {
-        let commander = Commander::new();
-        let result = commander.parse("!miou register game123 Alice 999999");
-        assert!(result.is_ok());
-        match result.unwrap() {
-            Command::Register(_game_id, _player_name, delay) => {
-                assert_eq!(delay, 999999);
-            }
-            _ => panic!("Expected Register command"),
-        }
+        let commander = create_commander();
+        let result = commander
+            .parse("!miou register game123 Alice 999999", None, false, None)
+            .unwrap();
+        let RegisterArguments { delay, .. } = result.into_arguments().unwrap();
+        assert_eq!(delay, Delay::Minutes(999999));
     }
 
     #[tokio::test]
     async fn test_parse_command_help() {
-        let commander = Commander::new();
+        let commander = create_commander();
         let context = create_test_context();
-        let command = Command::Help;
+        let command = Command::new(&HelpCommand, None);
 
         let result = commander.parse_command(&command, &context).await;
-        assert!(result.is_some());
-        let cmd_result = result.unwrap();
+        let cmd_result = result;
         assert!(!cmd_result.response.is_empty());
         assert!(cmd_result.alert_to_add.is_none());
         assert!(cmd_result.alerts_to_remove.is_none());
@@ -500,24 +694,39 @@ mod tests {
 
     #[tokio::test]
     async fn test_parse_command_games() {
-        let commander = Commander::new();
+        let commander = create_commander();
         let mut context = create_test_context();
         context
             .games_map
             .insert("game1".to_string(), create_test_game("game1"));
-        let command = Command::Games;
+        let command = Command::new(&GamesCommand, ());
 
         let result = commander.parse_command(&command, &context).await;
-        assert!(result.is_some());
-        let cmd_result = result.unwrap();
+        let cmd_result = result;
         assert!(!cmd_result.response.is_empty());
         assert!(cmd_result.alert_to_add.is_none());
         assert!(cmd_result.alerts_to_remove.is_none());
     }
 
+    #[tokio::test]
+    async fn test_parse_command_game() {
+        let commander = create_commander();
+        let mut context = create_test_context();
+        context
+            .games_map
+            .insert("game1".to_string(), create_test_game("game1"));
+        let command = Command::new(&GameCommand, "game1".to_string());
+
+        let result = commander.parse_command(&command, &context).await;
+        let cmd_result = result;
+        assert!(cmd_result.response.contains("game1"));
+        assert!(cmd_result.alert_to_add.is_none());
+        assert!(cmd_result.watch_to_add.is_none());
+    }
+
     #[tokio::test]
     async fn test_parse_command_alerts() {
-        let commander = Commander::new();
+        let commander = create_commander();
         let mut context = create_test_context();
         let alert = Alert {
             room_id: "!room:example.com".to_string(),
@@ -526,16 +735,22 @@ mod tests {
             player_url: "http://alice.example.com".to_string(),
             notified: false,
             delay: 60,
+            turn_started_at: None,
+            notify_at: None,
+            repeat_interval: None,
+            max_reminders: None,
+            notifications_sent: 0,
+            phases: Vec::new(),
+            time_of_day: None,
         };
         let mut alerts_set = HashSet::new();
         alerts_set.insert(alert);
         context.alerts_map.insert("game1".to_string(), alerts_set);
 
-        let command = Command::Alerts;
+        let command = Command::new(&AlertsCommand, ());
 
         let result = commander.parse_command(&command, &context).await;
-        assert!(result.is_some());
-        let cmd_result = result.unwrap();
+        let cmd_result = result;
         assert!(!cmd_result.response.is_empty());
         assert!(cmd_result.alert_to_add.is_none());
         assert!(cmd_result.alerts_to_remove.is_none());
@@ -543,14 +758,23 @@ mod tests {
 
     #[tokio::test]
     async fn test_parse_command_register_game_not_found() {
-        let commander = Commander::new();
+        let commander = create_commander();
         let context = create_test_context();
-        let command = Command::Register("nonexistent".to_string(), "Alice".to_string(), 60);
+        let command = Command::new(
+            &RegisterCommand,
+            RegisterArguments {
+                game_id: "nonexistent".to_string(),
+                player_name: "Alice".to_string(),
+                delay: Delay::Minutes(60),
+                repeat_interval: None,
+                max_reminders: None,
+                phases: Vec::new(),
+            },
+        );
 
         let result = commander.parse_command(&command, &context).await;
-        // Should return Some with error message when game is not found
-        assert!(result.is_some());
-        let cmd_result = result.unwrap();
+        // Should return an error message when game is not found
+        let cmd_result = result;
         assert!(!cmd_result.response.is_empty());
         assert!(cmd_result.alert_to_add.is_none());
         assert!(cmd_result.alerts_to_remove.is_none());
@@ -558,32 +782,173 @@ mod tests {
 
     #[tokio::test]
     async fn test_parse_command_unregister_no_alerts() {
-        let commander = Commander::new();
+        let commander = create_commander();
         let context = create_test_context();
-        let command = Command::Unregister("game123".to_string());
+        let command = Command::new(&UnregisterCommand, "game123".to_string());
 
         let result = commander.parse_command(&command, &context).await;
         // Should return Some with success message even when there are no alerts
-        assert!(result.is_some());
-        let cmd_result = result.unwrap();
+        let cmd_result = result;
         assert!(!cmd_result.response.is_empty());
         assert!(cmd_result.alert_to_add.is_none());
         assert!(cmd_result.alerts_to_remove.is_some());
     }
 
+    #[tokio::test]
+    async fn test_parse_command_unwatch() {
+        let commander = create_commander();
+        let context = create_test_context();
+        let command = Command::new(&UnwatchCommand, "game123".to_string());
+
+        let result = commander.parse_command(&command, &context).await;
+        let cmd_result = result;
+        assert!(!cmd_result.response.is_empty());
+        assert!(cmd_result.watch_to_add.is_none());
+        assert!(cmd_result.watch_to_remove.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_parse_command_settings() {
+        let commander = create_commander();
+        let context = create_test_context();
+        let command = Command::new(
+            &SettingsCommand,
+            SettingsArguments::SetQuietHours {
+                window: "23:00-08:00".to_string(),
+                timezone: "Europe/Paris".to_string(),
+            },
+        );
+
+        let result = commander.parse_command(&command, &context).await;
+        let cmd_result = result;
+        assert!(!cmd_result.response.is_empty());
+        assert!(cmd_result.alert_to_add.is_none());
+        assert!(cmd_result.alerts_to_remove.is_none());
+        assert!(cmd_result.user_settings_to_update.is_some());
+    }
+
+    fn create_test_alert() -> Alert {
+        Alert {
+            room_id: "!room:example.com".to_string(),
+            player_id: "player1".to_string(),
+            user_id: "@alice:example.com".to_string(),
+            player_url: "http://example.com/player1".to_string(),
+            notified: true,
+            delay: 60,
+            turn_started_at: Some(1000),
+            notify_at: None,
+            repeat_interval: None,
+            max_reminders: None,
+            notifications_sent: 1,
+            phases: Vec::new(),
+            time_of_day: None,
+        }
+    }
+
     #[test]
     fn test_get_player_turn_message() {
+        let message = Commander::get_player_turn_message(
+            &create_test_alert(),
+            &create_test_game("main:game1"),
+            None,
+            1000,
+        );
+
         assert_eq!(
-            Commander::get_player_turn_message("@alice:example.com", "http://example.com/player1"),
-            "@alice:example.com: it's your turn to play: [http://example.com/player1](http://example.com/player1)."
-        )
+            message.body,
+            "@alice:example.com: it's your turn to play as **Alice** in game **main:game1**: [http://example.com/player1](http://example.com/player1). Action phase."
+        );
+        assert!(
+            message
+                .html_body
+                .contains("<font color=\"#d32f2f\">Alice</font>")
+        );
+    }
+
+    #[test]
+    fn test_get_player_reminder_message() {
+        let message = Commander::get_player_reminder_message(
+            &create_test_alert(),
+            &create_test_game("main:game1"),
+            None,
+            1000,
+        );
+
+        assert!(message.body.contains("still your turn"));
+        assert!(message.html_body.contains("still your turn"));
+    }
+
+    #[test]
+    fn test_get_player_messages_with_template() {
+        let template = NotificationTemplate::parse("{player}, play in {game}!").unwrap();
+
+        let message = Commander::get_player_turn_message(
+            &create_test_alert(),
+            &create_test_game("main:game1"),
+            Some(&template),
+            1000,
+        );
+        assert_eq!(message.body, "Alice, play in main:game1!");
+
+        let message = Commander::get_player_reminder_message(
+            &create_test_alert(),
+            &create_test_game("main:game1"),
+            Some(&template),
+            1000,
+        );
+        assert_eq!(message.body, "Reminder: Alice, play in main:game1!");
+    }
+
+    #[test]
+    fn test_get_turn_change_message() {
+        let turn_change = TurnChange {
+            game_id: "game1".to_string(),
+            phase: Phase::Action,
+            waited_player_names: vec!["Bob".to_string()],
+        };
+
+        assert_eq!(
+            Commander::get_turn_change_message(&turn_change),
+            "**game1**: Bob is now up (Action phase)."
+        );
+    }
+
+    #[test]
+    fn test_get_game_end_message() {
+        let scores = vec![
+            PlayerScore {
+                name: "Bob".to_string(),
+                victory_points: 85,
+            },
+            PlayerScore {
+                name: "Alice".to_string(),
+                victory_points: 72,
+            },
+        ];
+
+        assert_eq!(
+            Commander::get_game_end_message("game1", &scores),
+            "**game1** is over! Final scores:\n\n1. **Bob**: 85 VP\n2. Alice: 72 VP"
+        );
+    }
+
+    #[test]
+    fn test_get_sync_error_message() {
+        assert_eq!(
+            Commander::get_sync_error_message("main", &SyncError::Access),
+            "Error: unauthorized access to the terraforming mars API of the **main** server"
+        );
+        assert_eq!(
+            Commander::get_sync_error_message("main", &SyncError::Timeout),
+            format_server_unavailable("main")
+        );
     }
 
     #[test]
-    fn test_get_access_error_message() {
+    fn test_get_server_back_message() {
         assert_eq!(
-            Commander::get_access_error_message(),
-            "Error: unauthorized access to the terraforming mars API"
+            Commander::get_server_back_message("main"),
+            format_server_back("main")
         );
     }
 }
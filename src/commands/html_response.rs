@@ -0,0 +1,482 @@
+//! HTML response formatters for the notifications.
+//!
+//! This module provides functions to format the turn notifications in HTML, for
+//! the Matrix clients able to display them: the players are shown in their color
+//! in the game. The plain text fallback of each notification is formatted by the
+//! matching function of [`markdown_response`](crate::commands::markdown_response),
+//! or from the template of the room.
+
+use crate::{
+    commands::markdown_response::{
+        format_phase_and_progress, format_player_turn as format_player_turn_md,
+        format_player_turn_reminder as format_player_turn_reminder_md, format_waiting_time,
+        get_other_waited_players,
+    },
+    settings::{NotificationTemplate, Placeholder},
+    tmars::{Game, Player},
+};
+
+/// A message with its HTML body and the plain text fallback of the body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlMessage {
+    /// Plain text body, displayed by the clients without HTML support
+    pub body: String,
+    /// HTML body
+    pub html_body: String,
+}
+
+/// Formats the HTML notification of a player's turn.
+///
+/// Gives the context of the turn like
+/// [`markdown_response::format_player_turn`](crate::commands::markdown_response::format_player_turn),
+/// with the player and the other waited players in their color.
+///
+/// # Arguments
+///
+/// * `user_id` - The Matrix user ID of the player
+/// * `player_id` - The ID of the player in the game
+/// * `player_url` - The URL to the player's game page on the Terraforming Mars server
+/// * `game` - The current state of the game of the player, when the message is sent
+/// * `now` - Unix timestamp (in seconds) used to compute how long the turn has been open
+///
+/// # Returns
+///
+/// The notification with its HTML body and plain text fallback.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::html_response::format_player_turn;
+/// # use miou::tmars::{Game, Phase};
+/// # use std::collections::HashSet;
+/// let game = Game {
+///     id: "main:game123".to_string(),
+///     phase: Phase::Action,
+///     spectator_id: "spec123".to_string(),
+///     spectator_url: "https://example.com/spectator?id=spec123".to_string(),
+///     players: vec![],
+///     waited_players: HashSet::new(),
+///     progress: None,
+/// };
+/// let message = format_player_turn("@alice:example.com", "p123", "https://example.com/player?id=p123", &game, 0);
+/// assert!(message.html_body.contains("<b>main:game123</b>"));
+/// ```
+pub fn format_player_turn(
+    user_id: &str,
+    player_id: &str,
+    player_url: &str,
+    game: &Game,
+    now: u64,
+) -> HtmlMessage {
+    HtmlMessage {
+        body: format_player_turn_md(user_id, player_id, player_url, game, now),
+        html_body: format_turn_notification(
+            "it's your turn to play",
+            user_id,
+            player_id,
+            player_url,
+            game,
+            now,
+        ),
+    }
+}
+
+/// Formats the HTML reminder for a player who hasn't played yet.
+///
+/// # Arguments
+///
+/// * `user_id` - The Matrix user ID of the player
+/// * `player_id` - The ID of the player in the game
+/// * `player_url` - The URL to the player's game page on the Terraforming Mars server
+/// * `game` - The current state of the game of the player, when the message is sent
+/// * `now` - Unix timestamp (in seconds) used to compute how long the turn has been open
+///
+/// # Returns
+///
+/// The reminder with its HTML body and plain text fallback.
+pub fn format_player_turn_reminder(
+    user_id: &str,
+    player_id: &str,
+    player_url: &str,
+    game: &Game,
+    now: u64,
+) -> HtmlMessage {
+    HtmlMessage {
+        body: format_player_turn_reminder_md(user_id, player_id, player_url, game, now),
+        html_body: format_turn_notification(
+            "reminder, it's still your turn to play",
+            user_id,
+            player_id,
+            player_url,
+            game,
+            now,
+        ),
+    }
+}
+
+/// Formats the notification of a player's turn from the template of the room.
+///
+/// The placeholders of the template are replaced by the context of the turn, the
+/// players are shown in their color and the URL as a link in the HTML body.
+///
+/// # Arguments
+///
+/// * `template` - The template of the room of the alert
+/// * `user_id` - The Matrix user ID of the player
+/// * `player_id` - The ID of the player in the game
+/// * `player_url` - The URL to the player's game page on the Terraforming Mars server
+/// * `game` - The current state of the game of the player, when the message is sent
+///
+/// # Returns
+///
+/// The notification with its HTML body and plain text fallback.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::html_response::format_templated_player_turn;
+/// # use miou::settings::NotificationTemplate;
+/// # use miou::tmars::{Game, Phase};
+/// # use std::collections::HashSet;
+/// let game = Game {
+///     id: "main:game123".to_string(),
+///     phase: Phase::Action,
+///     spectator_id: "spec123".to_string(),
+///     spectator_url: "https://example.com/spectator?id=spec123".to_string(),
+///     players: vec![],
+///     waited_players: HashSet::new(),
+///     progress: None,
+/// };
+/// let template = NotificationTemplate::parse("Play in {game}!").unwrap();
+/// let message = format_templated_player_turn(&template, "@alice:example.com", "p123", "https://example.com/player?id=p123", &game);
+/// assert_eq!(message.body, "Play in main:game123!");
+/// ```
+pub fn format_templated_player_turn(
+    template: &NotificationTemplate,
+    user_id: &str,
+    player_id: &str,
+    player_url: &str,
+    game: &Game,
+) -> HtmlMessage {
+    format_templated_notification("", template, user_id, player_id, player_url, game)
+}
+
+/// Formats the reminder for a player who hasn't played yet from the template of the room.
+///
+/// The reminder is the notification of the template, prefixed with `Reminder: `.
+///
+/// # Arguments
+///
+/// * `template` - The template of the room of the alert
+/// * `user_id` - The Matrix user ID of the player
+/// * `player_id` - The ID of the player in the game
+/// * `player_url` - The URL to the player's game page on the Terraforming Mars server
+/// * `game` - The current state of the game of the player, when the message is sent
+///
+/// # Returns
+///
+/// The reminder with its HTML body and plain text fallback.
+pub fn format_templated_player_turn_reminder(
+    template: &NotificationTemplate,
+    user_id: &str,
+    player_id: &str,
+    player_url: &str,
+    game: &Game,
+) -> HtmlMessage {
+    format_templated_notification("Reminder: ", template, user_id, player_id, player_url, game)
+}
+
+/// Renders the template of a notification in plain text and in HTML, after the given prefix.
+fn format_templated_notification(
+    prefix: &str,
+    template: &NotificationTemplate,
+    user_id: &str,
+    player_id: &str,
+    player_url: &str,
+    game: &Game,
+) -> HtmlMessage {
+    let player = game.players.iter().find(|player| player.id == player_id);
+    let other_waited_players = get_other_waited_players(game, player_id);
+
+    let body = template.render(
+        |text| text.to_owned(),
+        |placeholder| match placeholder {
+            Placeholder::User => user_id.to_owned(),
+            Placeholder::Player => player.map_or(player_id, |player| &player.name).to_owned(),
+            Placeholder::Game => game.id.clone(),
+            Placeholder::Phase => game.phase.to_string(),
+            Placeholder::Url => player_url.to_owned(),
+            Placeholder::WaitedFor => match other_waited_players.is_empty() {
+                true => "nobody else".to_owned(),
+                false => other_waited_players
+                    .iter()
+                    .map(|player| player.name.as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", "),
+            },
+        },
+    );
+    let html_body = template.render(
+        |text| escape_html(text).replace('\n', "<br>"),
+        |placeholder| match placeholder {
+            Placeholder::User => escape_html(user_id),
+            Placeholder::Player => match player {
+                Some(player) => format_player(player),
+                None => escape_html(player_id),
+            },
+            Placeholder::Game => escape_html(&game.id),
+            Placeholder::Phase => escape_html(&game.phase.to_string()),
+            Placeholder::Url => format!(
+                "<a href=\"{}\">{}</a>",
+                escape_html(player_url),
+                escape_html(player_url)
+            ),
+            Placeholder::WaitedFor => match other_waited_players.is_empty() {
+                true => "nobody else".to_owned(),
+                false => other_waited_players
+                    .iter()
+                    .map(|player| format_player(player))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            },
+        },
+    );
+
+    HtmlMessage {
+        body: format!("{}{}", prefix, body),
+        html_body: format!("{}{}", prefix, html_body),
+    }
+}
+
+/// Formats the HTML body of a turn notification starting with the given introduction.
+fn format_turn_notification(
+    introduction: &str,
+    user_id: &str,
+    player_id: &str,
+    player_url: &str,
+    game: &Game,
+    now: u64,
+) -> String {
+    let player = game.players.iter().find(|player| player.id == player_id);
+    let player_html = match player {
+        Some(player) => format!(" as <b>{}</b>", format_player(player)),
+        None => String::new(),
+    };
+
+    let mut context_html = vec![escape_html(&format_phase_and_progress(game))];
+    if let Some(waiting_since) = player.and_then(|player| player.waiting_since) {
+        context_html.push(format!(
+            "Turn open for {}",
+            format_waiting_time(now.saturating_sub(waiting_since))
+        ));
+    }
+    let other_waited_players = get_other_waited_players(game, player_id);
+    if !other_waited_players.is_empty() {
+        context_html.push(format!(
+            "Also waiting for {}",
+            other_waited_players
+                .into_iter()
+                .map(format_player)
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+    }
+
+    format!(
+        "{}: {}{} in game <b>{}</b>: <a href=\"{}\">{}</a>.<br>{}.",
+        escape_html(user_id),
+        introduction,
+        player_html,
+        escape_html(&game.id),
+        escape_html(player_url),
+        escape_html(player_url),
+        context_html.join(". ")
+    )
+}
+
+/// Formats the name of a player in their color, if it can be displayed.
+fn format_player(player: &Player) -> String {
+    match get_color_code(&player.color) {
+        Some(color_code) => format!(
+            "<font color=\"{}\">{}</font>",
+            color_code,
+            escape_html(&player.name)
+        ),
+        None => escape_html(&player.name),
+    }
+}
+
+/// Returns the hexadecimal code of a player color of the Terraforming Mars server.
+///
+/// # Returns
+///
+/// `None` for the neutral player and the colors unknown to this bot.
+fn get_color_code(color: &str) -> Option<&'static str> {
+    match color {
+        "red" => Some("#d32f2f"),
+        "green" => Some("#388e3c"),
+        "yellow" => Some("#fbc02d"),
+        "blue" => Some("#1976d2"),
+        "black" => Some("#616161"),
+        "purple" => Some("#7b1fa2"),
+        "orange" => Some("#f57c00"),
+        "pink" => Some("#ec407a"),
+        "bronze" => Some("#cd7f32"),
+        _ => None,
+    }
+}
+
+/// Escapes the characters with a special meaning in HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::tmars::{GameProgress, Phase};
+
+    fn create_player(id: &str, color: &str, name: &str, waiting_since: Option<u64>) -> Player {
+        Player {
+            waiting_since,
+            ..Player::test(id, color, name)
+        }
+    }
+
+    /// Alice and Bob are waited for in an action phase, Alice for 1h 5min at 4900.
+    fn create_test_game() -> Game {
+        Game {
+            players: vec![
+                create_player("player-id1", "red", "Alice", Some(1000)),
+                create_player("player-id2", "neutral", "Bob & co", Some(1000)),
+                create_player("player-id3", "green", "Charlie", None),
+            ],
+            progress: Some(GameProgress {
+                generation: 5,
+                temperature: -12,
+                oxygen: 6,
+                oceans: 4,
+                venus: None,
+            }),
+            ..Game::test(
+                "main:game-id1",
+                Phase::Action,
+                &["player-id1", "player-id2"],
+            )
+        }
+    }
+
+    #[test]
+    fn test_format_player_turn() {
+        let game = create_test_game();
+
+        let message = format_player_turn(
+            "@alice:example.com",
+            "player-id1",
+            "http://example.com/player-id1",
+            &game,
+            4900,
+        );
+
+        assert_eq!(
+            message.html_body,
+            "@alice:example.com: it's your turn to play as <b><font color=\"#d32f2f\">Alice</font></b> in game <b>main:game-id1</b>: \
+            <a href=\"http://example.com/player-id1\">http://example.com/player-id1</a>.<br>\
+            Action phase, generation 5, temperature -12°C, oxygen 6%, oceans 4/9. Turn open for 1h 5min. Also waiting for Bob &amp; co."
+        );
+        assert!(message.body.starts_with(
+            "@alice:example.com: it's your turn to play as **Alice** in game **main:game-id1**"
+        ));
+    }
+
+    #[test]
+    fn test_format_player_turn_reminder() {
+        let game = create_test_game();
+
+        let message = format_player_turn_reminder(
+            "@alice:example.com",
+            "player-id1",
+            "http://example.com/player-id1",
+            &game,
+            4900,
+        );
+
+        assert!(
+            message
+                .html_body
+                .starts_with("@alice:example.com: reminder, it's still your turn to play as")
+        );
+        assert!(
+            message
+                .body
+                .starts_with("@alice:example.com: reminder, it's still your turn to play as")
+        );
+    }
+
+    #[test]
+    fn test_format_templated_player_turn() {
+        let game = create_test_game();
+        let template = NotificationTemplate::parse(
+            "{user} <{player}> {game}, {phase}:\n{url} (+{waited_for})",
+        )
+        .unwrap();
+
+        let message = format_templated_player_turn(
+            &template,
+            "@alice:example.com",
+            "player-id1",
+            "http://example.com/player-id1",
+            &game,
+        );
+
+        assert_eq!(
+            message.body,
+            "@alice:example.com <Alice> main:game-id1, Action:\nhttp://example.com/player-id1 (+Bob & co)"
+        );
+        assert_eq!(
+            message.html_body,
+            "@alice:example.com &lt;<font color=\"#d32f2f\">Alice</font>&gt; main:game-id1, Action:<br>\
+            <a href=\"http://example.com/player-id1\">http://example.com/player-id1</a> (+Bob &amp; co)"
+        );
+    }
+
+    #[test]
+    fn test_format_templated_player_turn_reminder() {
+        let game = create_test_game();
+        let template = NotificationTemplate::parse("{player}, waiting for {waited_for}").unwrap();
+
+        let message = format_templated_player_turn_reminder(
+            &template,
+            "@bob:example.com",
+            "player-id3",
+            "http://example.com/player-id3",
+            &game,
+        );
+
+        assert_eq!(
+            message.body,
+            "Reminder: Charlie, waiting for Alice, Bob & co"
+        );
+        assert!(
+            message
+                .html_body
+                .starts_with("Reminder: <font color=\"#388e3c\">Charlie</font>, waiting for")
+        );
+    }
+
+    #[test]
+    fn test_format_player() {
+        assert_eq!(
+            format_player(&create_player("p1", "blue", "<Bob>", None)),
+            "<font color=\"#1976d2\">&lt;Bob&gt;</font>"
+        );
+        assert_eq!(
+            format_player(&create_player("p1", "neutral", "Bob", None)),
+            "Bob"
+        );
+    }
+}
@@ -4,13 +4,28 @@
 //! for display in Matrix chat rooms. All responses are designed to be user-friendly
 //! and informative.
 
-use crate::tmars::Game;
+use crate::{
+    alerts::TimeOfDay,
+    commands::{
+        registry::{ArgumentError, RegisteredCommand, Usage},
+        tokenizer::SyntaxError,
+    },
+    history::TurnStats,
+    settings::{NotificationTemplate, Placeholder, QuietHours},
+    tmars::{Game, GameProgress, Phase, Player, PlayerScore},
+    watches::TurnChange,
+};
 
 /// Formats the help message showing available bot commands.
 ///
 /// Returns a comprehensive help message listing all available commands,
 /// their usage syntax, and a brief description of the bot's functionality.
 ///
+/// # Arguments
+///
+/// * `prefix` - The prefix of the commands in the room
+/// * `commands` - The commands of the bot, in the order of the help
+///
 /// # Returns
 ///
 /// A Markdown-formatted string containing the help message.
@@ -19,29 +34,134 @@ use crate::tmars::Game;
 ///
 /// ```
 /// # use miou::commands::markdown_response::format_help;
-/// let help = format_help();
+/// # use miou::commands::registry::COMMANDS;
+/// let help = format_help("!miou", COMMANDS);
 /// assert!(help.contains("Commands:"));
 /// ```
-pub fn format_help() -> String {
-    let body = format!(
-        "Commands:\n\
-        - `games`: list all the ongoing games\n\
-        - `alerts`: list your registered alerts\n\
-        - `register <game_id> <player_name> <delay_in_minutes>`: register a new alert\n\
-        - `unregister <game_id>`: unregister an alert\n\
-        - `help`: show this help message\n\n\
-        Alert sends a mention to the registered user when their turn to play arrives, following the delay set in the register argument.\n\
+pub fn format_help(prefix: &str, commands: &[&dyn RegisteredCommand]) -> String {
+    let mut body = "Commands:\n".to_owned();
+
+    for command in commands {
+        for (index, usage) in command.usages().iter().enumerate() {
+            body.push_str(&format!(
+                "- `{}`: {}",
+                format_usage(command.name(), usage.arguments),
+                usage.description
+            ));
+            // The aliases are given once, after the first form of the command
+            if index == 0 && !command.aliases().is_empty() {
+                body.push_str(&format!(" (alias: {})", format_aliases(command.aliases())));
+            }
+            body += "\n";
+        }
+    }
+
+    body.push_str(&format!(
+        "\n\
+        Commands start with `{}` or with a mention of the bot, and need neither in a direct chat with the bot. \
+        Quote the arguments containing spaces, e.g. `register game123 \"Alice Smith\" 60`, or escape their spaces with a backslash. \
+        Alert sends a mention to the registered user when their turn to play arrives, following the delay set in the register argument: \
+        minutes like `90`, a duration like `30m`, `2h` or `1d12h`, or a time of day like `@09:00` in the timezone of your quiet hours. \
+        With a repeat interval, a reminder is sent every `repeat_in_minutes` until the player plays, up to `max_reminders` times. \
+        With `--phases`, e.g. `--phases action,production`, the alert only fires in these phases of the game. \
+        Notifications falling into your quiet hours are sent when they end. \
+        A template replaces the text of the turn notifications of the room, with the placeholders \
+        `{{user}}`, `{{player}}`, `{{game}}`, `{{phase}}`, `{{url}}` and `{{waited_for}}`.\n\
         > miou({}) is a free open source terraforming mars bot. Source code is available on [Github](https://github.com/florianduros/miou).",
+        prefix,
         env!("CARGO_PKG_VERSION"),
-    );
+    ));
+
+    body
+}
+
+/// Formats the detailed help of a command.
+///
+/// Lists the forms of the command, its aliases, the constraints of its arguments
+/// and examples.
+///
+/// # Arguments
+///
+/// * `prefix` - The prefix of the commands in the room
+/// * `command` - The command to describe
+///
+/// # Returns
+///
+/// A Markdown-formatted string containing the help of the command.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_command_help;
+/// # use miou::commands::registry::find_command;
+/// let help = format_command_help("!miou", find_command("register").unwrap());
+/// assert!(help.starts_with("Command `register`"));
+/// ```
+pub fn format_command_help(prefix: &str, command: &dyn RegisteredCommand) -> String {
+    let mut body = format!("Command `{}`", command.name());
+    if !command.aliases().is_empty() {
+        body.push_str(&format!(" (alias: {})", format_aliases(command.aliases())));
+    }
+    body += ":\n";
+
+    for usage in command.usages() {
+        body.push_str(&format!(
+            "- `{} {}`: {}\n",
+            prefix,
+            format_usage(command.name(), usage.arguments),
+            usage.description
+        ));
+    }
+
+    if !command.arguments().is_empty() {
+        body += "\nArguments:\n";
+        for argument in command.arguments() {
+            body.push_str(&format!(
+                "- `{}`: {}\n",
+                argument.name, argument.description
+            ));
+        }
+    }
+
+    body += "\nExamples:\n";
+    for example in command.examples() {
+        body.push_str(&format!(
+            "- `{} {}`: {}\n",
+            prefix,
+            format_usage(command.name(), example.arguments),
+            example.description
+        ));
+    }
+
+    body
+}
+
+/// Formats the aliases of a command, e.g. `` `reg`, `r` ``.
+fn format_aliases(aliases: &[&str]) -> String {
+    aliases
+        .iter()
+        .map(|alias| format!("`{}`", alias))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
 
-    body.to_owned()
+/// Formats a form of a command, e.g. `register <game_id> <player_name> <delay>`.
+fn format_usage(name: &str, arguments: &str) -> String {
+    match arguments.is_empty() {
+        true => name.to_owned(),
+        false => format!("{} {}", name, arguments),
+    }
 }
 
 /// Formats a response for an unknown command.
 ///
-/// Returns a helpful message indicating the command was not recognized
-/// and suggests using the help command.
+/// Returns a helpful message indicating the command was not recognized,
+/// suggesting the closest command if any, and using the help command.
+///
+/// # Arguments
+///
+/// * `prefix` - The prefix of the commands in the room
+/// * `suggestion` - The command closest to the mistyped one, if any
 ///
 /// # Returns
 ///
@@ -51,17 +171,72 @@ pub fn format_help() -> String {
 ///
 /// ```
 /// # use miou::commands::markdown_response::format_unknown_command;
-/// let msg = format_unknown_command();
-/// assert!(msg.contains("Unknown command"));
+/// let msg = format_unknown_command("!miou", Some("register"));
+/// assert!(msg.contains("Did you mean `!miou register`?"));
+/// ```
+pub fn format_unknown_command(prefix: &str, suggestion: Option<&str>) -> String {
+    match suggestion {
+        Some(command) => format!(
+            "Unknown command. Did you mean `{} {}`? Type `{} help` for more information.",
+            prefix, command, prefix
+        ),
+        None => format!(
+            "Unknown command. Type `{} help` for more information.",
+            prefix
+        ),
+    }
+}
+
+/// Formats an error response for a command with invalid quotes, escapes or options.
+///
+/// # Arguments
+///
+/// * `prefix` - The prefix of the commands in the room
+/// * `error` - The syntax error, with its position in the message
+///
+/// # Returns
+///
+/// A Markdown-formatted error message giving the column of the error, starting at 1.
+///
+/// # Examples
+///
 /// ```
-pub fn format_unknown_command() -> String {
-    "Unknown command. Type `!miou help` for more information.".to_owned()
+/// # use miou::commands::markdown_response::format_syntax_error;
+/// # use miou::commands::tokenizer::SyntaxError;
+/// let msg = format_syntax_error("!miou", &SyntaxError::UnclosedQuote(23));
+/// assert!(msg.contains("column 24"));
+/// ```
+pub fn format_syntax_error(prefix: &str, error: &SyntaxError) -> String {
+    let error = match error {
+        SyntaxError::UnclosedQuote(position) => {
+            format!("the quote at column {} is not closed", position + 1)
+        }
+        SyntaxError::TrailingEscape(position) => {
+            format!("the backslash at column {} escapes nothing", position + 1)
+        }
+        SyntaxError::MissingOptionValue { name, position } => format!(
+            "the option `--{}` at column {} has no value",
+            name,
+            position + 1
+        ),
+        SyntaxError::DuplicateOption { name, position } => format!(
+            "the option `--{}` at column {} is already given",
+            name,
+            position + 1
+        ),
+    };
+
+    format!(
+        "Invalid command, {}. Type `{} help` for more information.",
+        error, prefix
+    )
 }
 
 /// Formats a list of ongoing games.
 ///
-/// Displays all ongoing games with their IDs, current phase, and player lists.
-/// Players who are currently being waited for are marked with an hourglass emoji (⏳).
+/// Displays all ongoing games with their IDs, current phase, player lists and progress.
+/// Players who are currently being waited for are marked with an hourglass emoji (⏳),
+/// and their terraform rating is shown when known.
 ///
 /// # Arguments
 ///
@@ -93,17 +268,30 @@ pub fn format_games(games: &[Game]) -> String {
                 .players
                 .iter()
                 // Indicate waited players with an hourglass emoji
-                .map(|p| match game.waited_players.contains(&p.id) {
-                    true => format!("{}(⏳)", p.name.clone()),
-                    false => p.name.clone(),
-                })
+                .map(
+                    |p| match (game.waited_players.contains(&p.id), p.terraform_rating) {
+                        (true, Some(tr)) => format!("{}(TR {}, ⏳)", p.name, tr),
+                        (true, None) => format!("{}(⏳)", p.name),
+                        (false, Some(tr)) => format!("{}(TR {})", p.name, tr),
+                        (false, None) => p.name.clone(),
+                    },
+                )
                 .collect::<Vec<String>>()
                 .join(", ");
 
-            format!(
-                "- **{}**({:?}), **players**: {}",
-                game.id, game.phase, players
-            )
+            match &game.progress {
+                Some(progress) => format!(
+                    "- **{}**({}), **players**: {}, **progress**: {}",
+                    game.id,
+                    game.phase,
+                    players,
+                    format_game_progress(progress)
+                ),
+                None => format!(
+                    "- **{}**({}), **players**: {}",
+                    game.id, game.phase, players
+                ),
+            }
         })
         .collect::<Vec<String>>()
         .join("\n");
@@ -111,39 +299,212 @@ pub fn format_games(games: &[Game]) -> String {
     format!("Games: \n\n {}", games_md)
 }
 
-/// Formats an error response for invalid register command syntax.
+/// Formats the detailed state of a game.
+///
+/// Shows the phase and progress of the game, a link to its spectator page, every
+/// player with their color and terraform rating, who is being waited for and for
+/// how long, and the alerts of the current room targeting the game.
+///
+/// # Arguments
+///
+/// * `game` - The game to display
+/// * `room_alerts` - The (user ID, player name) pairs of the alerts of the room
+/// * `now` - Unix timestamp (in seconds) used to compute the waiting times
 ///
 /// # Returns
 ///
-/// A Markdown-formatted string with the correct register command usage.
+/// A Markdown-formatted string with the detailed state of the game.
 ///
 /// # Examples
 ///
 /// ```
-/// # use miou::commands::markdown_response::format_invalid_register;
-/// let msg = format_invalid_register();
-/// assert!(msg.contains("Usage:"));
+/// # use miou::commands::markdown_response::format_game;
+/// # use miou::tmars::{Game, Phase};
+/// # use std::collections::HashSet;
+/// let game = Game {
+///     id: "game123".to_string(),
+///     phase: Phase::Action,
+///     spectator_id: "spec123".to_string(),
+///     spectator_url: "https://example.com/spectator?id=spec123".to_string(),
+///     players: vec![],
+///     waited_players: HashSet::new(),
+///     progress: None,
+/// };
+/// let msg = format_game(&game, &[], 0);
+/// assert!(msg.contains("spectator"));
 /// ```
-pub fn format_invalid_register() -> String {
-    "Invalid register command. Usage: `!miou register <game_id> <player_name> <delay_in_minutes>`"
-        .to_owned()
+pub fn format_game(game: &Game, room_alerts: &[(String, String)], now: u64) -> String {
+    let progress_md = match &game.progress {
+        Some(progress) => format!(", {}", format_game_progress(progress)),
+        None => String::new(),
+    };
+
+    let players_md = game
+        .players
+        .iter()
+        .map(|player| {
+            let details = match player.terraform_rating {
+                Some(tr) => format!("{}, TR {}", player.color, tr),
+                None => player.color.clone(),
+            };
+
+            match (
+                game.waited_players.contains(&player.id),
+                player.waiting_since,
+            ) {
+                (true, Some(waiting_since)) => format!(
+                    "- **{}** ({}): ⏳ waited for {}",
+                    player.name,
+                    details,
+                    format_waiting_time(now.saturating_sub(waiting_since))
+                ),
+                (true, None) => format!("- **{}** ({}): ⏳", player.name, details),
+                (false, _) => format!("- **{}** ({})", player.name, details),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let alerts_md = match room_alerts.is_empty() {
+        true => "No alerts in this room.".to_owned(),
+        false => format!(
+            "Alerts in this room:\n\n{}",
+            room_alerts
+                .iter()
+                .map(|(user_id, player_name)| format!("- {} for {}", user_id, player_name))
+                .collect::<Vec<String>>()
+                .join("\n")
+        ),
+    };
+
+    format!(
+        "Game **{}**: {} phase{}.\n\nSpectator page: [{}]({})\n\nPlayers:\n\n{}\n\n{}",
+        game.id,
+        game.phase,
+        progress_md,
+        game.spectator_url,
+        game.spectator_url,
+        players_md,
+        alerts_md
+    )
+}
+
+/// Formats a waiting time in seconds, with a precision of one minute.
+pub fn format_waiting_time(seconds: u64) -> String {
+    let minutes = seconds / 60;
+    let hours = minutes / 60;
+    let days = hours / 24;
+
+    match (days, hours, minutes) {
+        (0, 0, 0) => "less than a minute".to_owned(),
+        (0, 0, minutes) => format!("{}min", minutes),
+        (0, hours, minutes) => format!("{}h {}min", hours, minutes % 60),
+        (days, hours, _) => format!("{}d {}h", days, hours % 24),
+    }
+}
+
+/// Formats the generation and global parameters of a game.
+///
+/// The Venus scale is only shown for games played with the Venus Next expansion.
+///
+/// # Arguments
+///
+/// * `progress` - The progress of the game
+///
+/// # Returns
+///
+/// A plain string listing the generation and the global parameters.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_game_progress;
+/// # use miou::tmars::GameProgress;
+/// let progress = GameProgress {
+///     generation: 5,
+///     temperature: -12,
+///     oxygen: 6,
+///     oceans: 4,
+///     venus: None,
+/// };
+/// assert!(format_game_progress(&progress).contains("generation 5"));
+/// ```
+pub fn format_game_progress(progress: &GameProgress) -> String {
+    let progress_md = format!(
+        "generation {}, temperature {}°C, oxygen {}%, oceans {}/9",
+        progress.generation, progress.temperature, progress.oxygen, progress.oceans
+    );
+
+    match progress.venus {
+        Some(venus) => format!("{}, Venus {}%", progress_md, venus),
+        None => progress_md,
+    }
 }
 
-/// Formats an error response for invalid unregister command syntax.
+/// Formats an error response for a command with invalid arguments or options.
+///
+/// # Arguments
+///
+/// * `prefix` - The prefix of the commands in the room
+/// * `name` - The name of the command
+/// * `usages` - The forms of the command
+/// * `error` - The wrong argument or option
 ///
 /// # Returns
 ///
-/// A Markdown-formatted string with the correct unregister command usage.
+/// A Markdown-formatted string pointing at the wrong argument, followed by the
+/// correct usages of the command.
 ///
 /// # Examples
 ///
 /// ```
-/// # use miou::commands::markdown_response::format_invalid_unregister;
-/// let msg = format_invalid_unregister();
-/// assert!(msg.contains("Usage:"));
+/// # use miou::commands::markdown_response::format_invalid_arguments;
+/// # use miou::commands::registry::{ArgumentError, Usage};
+/// let usages = [Usage { arguments: "<game_id>", description: "watch a game" }];
+/// let msg = format_invalid_arguments("!miou", "watch", &usages, &ArgumentError::MissingArguments);
+/// assert_eq!(
+///     msg,
+///     "Invalid watch command: missing arguments. Usage: `!miou watch <game_id>`"
+/// );
 /// ```
-pub fn format_invalid_unregister() -> String {
-    "Invalid unregister command. Usage: `!miou unregister <game_id>`".to_owned()
+pub fn format_invalid_arguments(
+    prefix: &str,
+    name: &str,
+    usages: &[Usage],
+    error: &ArgumentError,
+) -> String {
+    let mut usages: Vec<String> = usages
+        .iter()
+        .map(|usage| format!("`{} {}`", prefix, format_usage(name, usage.arguments)))
+        .collect();
+    let last_usage = usages.pop().unwrap_or_default();
+    let usages = match usages.is_empty() {
+        true => last_usage,
+        false => format!("{} or {}", usages.join(", "), last_usage),
+    };
+
+    format!(
+        "Invalid {} command: {}. Usage: {}",
+        name,
+        format_argument_error(error),
+        usages
+    )
+}
+
+/// Formats the wrong argument of a command, e.g. ``delay `abc` is not a number``.
+fn format_argument_error(error: &ArgumentError) -> String {
+    match error {
+        ArgumentError::MissingArguments => "missing arguments".to_owned(),
+        ArgumentError::UnexpectedArgument(argument) => {
+            format!("unexpected argument `{}`", argument)
+        }
+        ArgumentError::UnknownOption(name) => format!("unknown option `--{}`", name),
+        ArgumentError::InvalidValue {
+            argument,
+            value,
+            reason,
+        } => format!("{} `{}` {}", argument, value, reason),
+    }
 }
 
 /// Formats an error response for invalid delay values.
@@ -165,11 +526,92 @@ pub fn format_invalid_delay() -> String {
     "Invalid delay. Delay must be between 1 minutes and 1 week.".to_owned()
 }
 
+/// Formats an error response for invalid repeat interval values.
+///
+/// Returned when the repeat interval is outside the valid range (1 minute to 1 week).
+///
+/// # Returns
+///
+/// A Markdown-formatted string explaining the repeat interval constraint.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_invalid_repeat_interval;
+/// let msg = format_invalid_repeat_interval();
+/// assert!(msg.contains("Invalid repeat interval"));
+/// ```
+pub fn format_invalid_repeat_interval() -> String {
+    "Invalid repeat interval. Repeat interval must be between 1 minutes and 1 week.".to_owned()
+}
+
+/// Formats an error response for invalid max reminders values.
+///
+/// # Returns
+///
+/// A Markdown-formatted string explaining the max reminders constraint.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_invalid_max_reminders;
+/// let msg = format_invalid_max_reminders();
+/// assert!(msg.contains("Invalid max reminders"));
+/// ```
+pub fn format_invalid_max_reminders() -> String {
+    "Invalid max reminders. Max reminders must be at least 1.".to_owned()
+}
+
+/// Formats an error response for invalid quiet hours windows.
+///
+/// Returned when the window is not formatted as `HH:MM-HH:MM` or is empty.
+///
+/// # Returns
+///
+/// A Markdown-formatted string explaining the expected window format.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_invalid_quiet_hours;
+/// let msg = format_invalid_quiet_hours();
+/// assert!(msg.contains("Invalid quiet hours"));
+/// ```
+pub fn format_invalid_quiet_hours() -> String {
+    "Invalid quiet hours. Quiet hours must be formatted as `HH:MM-HH:MM`, e.g. `23:00-08:00`."
+        .to_owned()
+}
+
+/// Formats an error response for unknown timezones.
+///
+/// # Arguments
+///
+/// * `timezone` - The timezone that was not recognized
+///
+/// # Returns
+///
+/// A Markdown-formatted string indicating the timezone is unknown.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_unknown_timezone;
+/// let msg = format_unknown_timezone("Mars/Olympus_Mons");
+/// assert!(msg.contains("Mars/Olympus_Mons"));
+/// ```
+pub fn format_unknown_timezone(timezone: &str) -> String {
+    format!(
+        "Unknown timezone '{}'. Use a timezone name like `Europe/Paris` or `UTC`.",
+        timezone
+    )
+}
+
 /// Formats an error response when a game is not found.
 ///
 /// # Arguments
 ///
 /// * `game_id` - The ID of the game that was not found
+/// * `suggestion` - The ID of the game closest to the mistyped one, if any
 ///
 /// # Returns
 ///
@@ -179,11 +621,17 @@ pub fn format_invalid_delay() -> String {
 ///
 /// ```
 /// # use miou::commands::markdown_response::format_game_not_found;
-/// let msg = format_game_not_found("game123".to_string());
-/// assert!(msg.contains("game123"));
+/// let msg = format_game_not_found("main:game12", Some("main:game123"));
+/// assert!(msg.contains("Did you mean 'main:game123'?"));
 /// ```
-pub fn format_game_not_found(game_id: &str) -> String {
-    format!("Game with id '{}' not found.", game_id)
+pub fn format_game_not_found(game_id: &str, suggestion: Option<&str>) -> String {
+    match suggestion {
+        Some(suggestion) => format!(
+            "Game with id '{}' not found. Did you mean '{}'?",
+            game_id, suggestion
+        ),
+        None => format!("Game with id '{}' not found.", game_id),
+    }
 }
 
 /// Formats an error response when a player is not found in a game.
@@ -192,6 +640,7 @@ pub fn format_game_not_found(game_id: &str) -> String {
 ///
 /// * `player_name` - The name of the player that was not found
 /// * `game_id` - The ID of the game where the player was searched for
+/// * `suggestion` - The name of the player of the game closest to the mistyped one, if any
 ///
 /// # Returns
 ///
@@ -201,15 +650,26 @@ pub fn format_game_not_found(game_id: &str) -> String {
 ///
 /// ```
 /// # use miou::commands::markdown_response::format_player_not_found;
-/// let msg = format_player_not_found("Alice".to_string(), "game123".to_string());
-/// assert!(msg.contains("Alice"));
+/// let msg = format_player_not_found("Alise", "game123", Some("Alice"));
+/// assert!(msg.contains("Alise"));
 /// assert!(msg.contains("game123"));
+/// assert!(msg.contains("Did you mean 'Alice'?"));
 /// ```
-pub fn format_player_not_found(player_name: &str, game_id: &str) -> String {
-    format!(
-        "Player '{}' not found in game with id '{}'.",
-        player_name, game_id
-    )
+pub fn format_player_not_found(
+    player_name: &str,
+    game_id: &str,
+    suggestion: Option<&str>,
+) -> String {
+    match suggestion {
+        Some(suggestion) => format!(
+            "Player '{}' not found in game with id '{}'. Did you mean '{}'?",
+            player_name, game_id, suggestion
+        ),
+        None => format!(
+            "Player '{}' not found in game with id '{}'.",
+            player_name, game_id
+        ),
+    }
 }
 
 /// Formats a success response for player registration.
@@ -229,6 +689,37 @@ pub fn format_successful_register() -> String {
     "You have been registered successfully.".to_owned()
 }
 
+/// Formats a success response for a registration notifying at a time of day.
+///
+/// # Arguments
+///
+/// * `time_of_day` - The time of day of the notifications
+///
+/// # Returns
+///
+/// A Markdown-formatted string confirming the registration and its time of day.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::alerts::TimeOfDay;
+/// # use miou::commands::markdown_response::format_successful_register_at;
+/// # use chrono::NaiveTime;
+/// let time_of_day = TimeOfDay {
+///     time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+///     timezone: chrono_tz::Europe::Paris,
+/// };
+/// let msg = format_successful_register_at(&time_of_day);
+/// assert!(msg.contains("09:00 (Europe/Paris)"));
+/// ```
+pub fn format_successful_register_at(time_of_day: &TimeOfDay) -> String {
+    format!(
+        "You have been registered successfully. You will be notified at {} ({}) if it's still your turn.",
+        time_of_day.time.format("%H:%M"),
+        time_of_day.timezone
+    )
+}
+
 /// Formats a success response for player unregistration.
 ///
 /// # Returns
@@ -246,115 +737,1114 @@ pub fn format_successful_unregister() -> String {
     "You have been unregistered successfully.".to_owned()
 }
 
-/// Formats a notification message for a player's turn.
+/// Formats a success response for watching a game.
 ///
-/// Creates a message notifying the user that it's their turn to play,
-/// including the URL to access their game.
+/// # Arguments
+///
+/// * `game_id` - The ID of the watched game
+///
+/// # Returns
+///
+/// A Markdown-formatted string confirming the game is watched.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_successful_watch;
+/// let msg = format_successful_watch("game123");
+/// assert!(msg.contains("game123"));
+/// ```
+pub fn format_successful_watch(game_id: &str) -> String {
+    format!(
+        "Turn changes of game '{}' will be announced in this room.",
+        game_id
+    )
+}
+
+/// Formats a success response for unwatching a game.
 ///
 /// # Arguments
 ///
-/// * `user_id` - The Matrix user ID of the player
-/// * `player_url` - The URL to the player's game page on the Terraforming Mars server
+/// * `game_id` - The ID of the game that is no longer watched
 ///
 /// # Returns
 ///
-/// A Markdown-formatted string with the turn notification message.
+/// A Markdown-formatted string confirming the game is no longer watched.
 ///
 /// # Examples
 ///
 /// ```
-/// # use miou::commands::markdown_response::format_player_turn;
-/// let msg = format_player_turn("https://example.com/player?id=p123".to_string());
-/// assert!(msg.contains("turn to play"));
+/// # use miou::commands::markdown_response::format_successful_unwatch;
+/// let msg = format_successful_unwatch("game123");
+/// assert!(msg.contains("game123"));
 /// ```
-pub fn format_player_turn(user_id: &str, player_url: &str) -> String {
+pub fn format_successful_unwatch(game_id: &str) -> String {
     format!(
-        "{}: it's your turn to play: [{}]({}).",
-        user_id, player_url, player_url
+        "Turn changes of game '{}' won't be announced in this room anymore.",
+        game_id
     )
 }
 
-/// Formats a list of registered alerts for the user.
+/// Formats the announcement of a turn change in a watched game.
 ///
-/// Displays all alerts grouped by game ID, showing which players are being
-/// monitored in each game.
+/// # Arguments
+///
+/// * `turn_change` - The change of the waited players or of the phase
+///
+/// # Returns
+///
+/// A Markdown-formatted string naming the players who are now up and the phase.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_turn_change;
+/// # use miou::tmars::Phase;
+/// # use miou::watches::TurnChange;
+/// let turn_change = TurnChange {
+///     game_id: "game123".to_string(),
+///     phase: Phase::Action,
+///     waited_player_names: vec!["Bob".to_string()],
+/// };
+/// let msg = format_turn_change(&turn_change);
+/// assert!(msg.contains("Bob is now up"));
+/// ```
+pub fn format_turn_change(turn_change: &TurnChange) -> String {
+    let players_md = match turn_change.waited_player_names.as_slice() {
+        [] => "no player to wait for".to_owned(),
+        [player_name] => format!("{} is now up", player_name),
+        player_names => format!("{} are now up", player_names.join(", ")),
+    };
+
+    format!(
+        "**{}**: {} ({} phase).",
+        turn_change.game_id, players_md, turn_change.phase
+    )
+}
+
+/// Formats the announcement of the end of a game.
+///
+/// The winner is highlighted. Tied players share the same rank.
 ///
 /// # Arguments
 ///
-/// * `alerts` - A slice of tuples containing the game ID and a vector of player names
+/// * `game_id` - The ID of the ended game
+/// * `scores` - The final scores, from the highest to the lowest
 ///
 /// # Returns
 ///
-/// A Markdown-formatted string listing all registered alerts, or a message if no alerts exist.
+/// A Markdown-formatted string with the ranking of the players, or a notice if the
+/// scores are unavailable.
 ///
 /// # Examples
 ///
 /// ```
-/// # use miou::commands::markdown_response::format_alerts;
-/// let alerts = vec![
-///     ("game1".to_string(), vec!["Alice".to_string()]),
-/// ];
-/// let output = format_alerts(&alerts);
-/// assert!(output.contains("game1"));
+/// # use miou::commands::markdown_response::format_game_end;
+/// let msg = format_game_end("game123", &[]);
+/// assert!(msg.contains("is over"));
 /// ```
-pub fn format_alerts(alerts: &[(String, Vec<String>)]) -> String {
-    if alerts.is_empty() {
-        return "No alerts found.".to_owned();
+pub fn format_game_end(game_id: &str, scores: &[PlayerScore]) -> String {
+    if scores.is_empty() {
+        return format!("**{}** is over! The final scores are unavailable.", game_id);
     }
 
-    let alerts_md = alerts
+    let best_victory_points = scores[0].victory_points;
+    let mut rank = 0;
+    let scores_md = scores
         .iter()
-        .map(|(game_id, player_name)| format!("- {}: {}", game_id, player_name.join(", ")))
+        .enumerate()
+        .map(|(index, score)| {
+            // Tied players keep the rank of the first of them
+            if index == 0 || scores[index - 1].victory_points != score.victory_points {
+                rank = index + 1;
+            }
+
+            if score.victory_points == best_victory_points {
+                format!("{}. **{}**: {} VP", rank, score.name, score.victory_points)
+            } else {
+                format!("{}. {}: {} VP", rank, score.name, score.victory_points)
+            }
+        })
         .collect::<Vec<String>>()
         .join("\n");
 
-    format!("Registered alerts:\n\n {}", alerts_md)
+    format!("**{}** is over! Final scores:\n\n{}", game_id, scores_md)
 }
 
-/// Formats an error message for API access failures.
+/// Formats the turn statistics of a game or of all games.
 ///
-/// Returns an error message indicating that the bot encountered authorization
-/// or authentication issues while trying to access the Terraforming Mars API.
+/// Players are listed from the slowest average turn time to the fastest. The game
+/// of the slowest turns is only shown for the statistics of all games.
+///
+/// # Arguments
+///
+/// * `game_id` - The ID of the game, or `None` for the statistics of all games
+/// * `turn_stats` - The statistics of the recorded turns
 ///
 /// # Returns
 ///
-/// A Markdown-formatted string containing the access error message.
+/// A Markdown-formatted string with the turn times of each player and the slowest
+/// turns, or a message if no turn was recorded.
 ///
 /// # Examples
 ///
 /// ```
-/// # use miou::commands::markdown_response::format_access_error;
-/// let msg = format_access_error();
-/// assert!(msg.contains("unauthorized"));
-/// assert!(msg.contains("terraforming mars"));
+/// # use miou::commands::markdown_response::format_stats;
+/// # use miou::history::TurnStats;
+/// # use std::collections::HashMap;
+/// let turn_stats = TurnStats::from_turns_map(&HashMap::new());
+/// let msg = format_stats(None, &turn_stats);
+/// assert_eq!(msg, "No turns recorded yet.");
 /// ```
-pub fn format_access_error() -> String {
-    "Error: unauthorized access to the terraforming mars API".to_owned()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::tmars::{Phase, Player};
-    use std::collections::HashSet;
-
-    #[test]
-    fn test_format_help() {
-        let help = format_help();
-        assert!(help.contains("Commands:"));
-        assert!(help.contains("games"));
-        assert!(help.contains("alerts"));
-        assert!(help.contains("register"));
-        assert!(help.contains("unregister"));
-        assert!(help.contains("help"));
+pub fn format_stats(game_id: Option<&str>, turn_stats: &TurnStats) -> String {
+    if turn_stats.players.is_empty() {
+        return match game_id {
+            Some(game_id) => format!("No turns recorded yet for game **{}**.", game_id),
+            None => "No turns recorded yet.".to_owned(),
+        };
+    }
+
+    let title_md = match game_id {
+        Some(game_id) => format!("Turn statistics of game **{}**", game_id),
+        None => "Turn statistics of all games".to_owned(),
+    };
+
+    let players_md = turn_stats
+        .players
+        .iter()
+        .map(|player| {
+            format!(
+                "- **{}**: {} {}, average {}, median {}",
+                player.player_name,
+                player.turn_count,
+                if player.turn_count == 1 {
+                    "turn"
+                } else {
+                    "turns"
+                },
+                format_waiting_time(player.average_duration),
+                format_waiting_time(player.median_duration)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let slowest_turns_md = turn_stats
+        .slowest_turns
+        .iter()
+        .enumerate()
+        .map(|(index, (turn_game_id, turn))| match game_id {
+            Some(_) => format!(
+                "{}. **{}**: {}",
+                index + 1,
+                turn.player_name,
+                format_waiting_time(turn.get_duration())
+            ),
+            None => format!(
+                "{}. **{}** in {}: {}",
+                index + 1,
+                turn.player_name,
+                turn_game_id,
+                format_waiting_time(turn.get_duration())
+            ),
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!(
+        "{}:\n\nPlayers, from the slowest:\n\n{}\n\nSlowest turns:\n\n{}",
+        title_md, players_md, slowest_turns_md
+    )
+}
+
+/// Formats the settings of a user.
+///
+/// # Arguments
+///
+/// * `quiet_hours` - The quiet hours of the user, if any
+///
+/// # Returns
+///
+/// A Markdown-formatted string listing the settings of the user.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_settings;
+/// let msg = format_settings(None);
+/// assert!(msg.contains("Quiet hours"));
+/// ```
+pub fn format_settings(quiet_hours: Option<&QuietHours>) -> String {
+    let quiet_hours_md = match quiet_hours {
+        Some(quiet_hours) => quiet_hours.to_string(),
+        None => "disabled".to_owned(),
+    };
+
+    format!("Settings:\n\n - **Quiet hours**: {}", quiet_hours_md)
+}
+
+/// Formats a success response for setting quiet hours.
+///
+/// # Arguments
+///
+/// * `quiet_hours` - The new quiet hours of the user
+///
+/// # Returns
+///
+/// A Markdown-formatted string confirming the new quiet hours.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_successful_quiet_hours;
+/// # use miou::settings::QuietHours;
+/// let quiet_hours = QuietHours::parse("23:00-08:00", "Europe/Paris").unwrap();
+/// let msg = format_successful_quiet_hours(&quiet_hours);
+/// assert!(msg.contains("23:00-08:00"));
+/// ```
+pub fn format_successful_quiet_hours(quiet_hours: &QuietHours) -> String {
+    format!(
+        "Quiet hours set to {}. Notifications during quiet hours will be sent when they end.",
+        quiet_hours
+    )
+}
+
+/// Formats a success response for disabling quiet hours.
+///
+/// # Returns
+///
+/// A Markdown-formatted string confirming the quiet hours are disabled.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_quiet_hours_disabled;
+/// let msg = format_quiet_hours_disabled();
+/// assert!(msg.contains("disabled"));
+/// ```
+pub fn format_quiet_hours_disabled() -> String {
+    "Quiet hours disabled.".to_owned()
+}
+
+/// Formats the server of the game IDs without a server name in a room.
+///
+/// # Arguments
+///
+/// * `server` - Name of the server of the room
+/// * `servers` - Names of all the configured servers
+///
+/// # Returns
+///
+/// A Markdown-formatted string with the server of the room and the available servers.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_server;
+/// let msg = format_server("main", &["main".to_string(), "friends".to_string()]);
+/// assert!(msg.contains("main"));
+/// ```
+pub fn format_server(server: &str, servers: &[String]) -> String {
+    format!(
+        "Game IDs without a server name refer to the games of the **{}** server in this room. {}",
+        server,
+        format_available_servers(servers)
+    )
+}
+
+/// Formats a success response for setting the server of a room.
+///
+/// # Arguments
+///
+/// * `server` - Name of the new server of the room
+///
+/// # Returns
+///
+/// A Markdown-formatted string confirming the new server of the room.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_successful_server;
+/// let msg = format_successful_server("friends");
+/// assert!(msg.contains("friends"));
+/// ```
+pub fn format_successful_server(server: &str) -> String {
+    format!(
+        "Game IDs without a server name now refer to the games of the **{}** server in this room.",
+        server
+    )
+}
+
+/// Formats an error response when a server is not configured.
+///
+/// # Arguments
+///
+/// * `server` - Name of the unknown server
+/// * `servers` - Names of all the configured servers
+///
+/// # Returns
+///
+/// A Markdown-formatted error message with the available servers.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_unknown_server;
+/// let msg = format_unknown_server("other", &["main".to_string()]);
+/// assert!(msg.contains("other"));
+/// ```
+pub fn format_unknown_server(server: &str, servers: &[String]) -> String {
+    format!(
+        "Server **{}** not found. {}",
+        server,
+        format_available_servers(servers)
+    )
+}
+
+/// Formats the list of the configured servers.
+fn format_available_servers(servers: &[String]) -> String {
+    format!("Available servers: {}", servers.join(", "))
+}
+
+/// Formats the template of the turn notifications of a room.
+///
+/// # Arguments
+///
+/// * `prefix` - The prefix of the commands in the room
+/// * `template` - The template of the room, `None` if the room uses the default notifications
+///
+/// # Returns
+///
+/// A Markdown-formatted string with the template and the available placeholders.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_template;
+/// let msg = format_template("!miou", None);
+/// assert!(msg.contains("default"));
+/// ```
+pub fn format_template(prefix: &str, template: Option<&NotificationTemplate>) -> String {
+    match template {
+        Some(template) => format!(
+            "Turn notifications of this room use the template `{}`. {}",
+            template,
+            format_available_placeholders()
+        ),
+        None => format!(
+            "Turn notifications of this room use the default text. Set a template with `{} template set \"<template>\"`. {}",
+            prefix,
+            format_available_placeholders()
+        ),
+    }
+}
+
+/// Formats a success response for setting the template of a room.
+///
+/// # Arguments
+///
+/// * `template` - The new template of the room
+///
+/// # Returns
+///
+/// A Markdown-formatted string confirming the new template.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_successful_template;
+/// # use miou::settings::NotificationTemplate;
+/// let template = NotificationTemplate::parse("{player}, play!").unwrap();
+/// let msg = format_successful_template(&template);
+/// assert!(msg.contains("{player}, play!"));
+/// ```
+pub fn format_successful_template(template: &NotificationTemplate) -> String {
+    format!(
+        "Turn notifications of this room now use the template `{}`.",
+        template
+    )
+}
+
+/// Formats a success response for restoring the default notifications of a room.
+///
+/// # Returns
+///
+/// A Markdown-formatted string confirming the default notifications.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_template_reset;
+/// let msg = format_template_reset();
+/// assert!(msg.contains("default"));
+/// ```
+pub fn format_template_reset() -> String {
+    "Turn notifications of this room now use the default text.".to_owned()
+}
+
+/// Formats an error response for a template which is empty or too long.
+///
+/// # Returns
+///
+/// A Markdown-formatted error message with the length constraint.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_invalid_template_length;
+/// let msg = format_invalid_template_length();
+/// assert!(msg.contains("1000"));
+/// ```
+pub fn format_invalid_template_length() -> String {
+    "Invalid template. It must be between 1 and 1000 characters long.".to_owned()
+}
+
+/// Formats an error response for a template with an unclosed placeholder.
+///
+/// # Returns
+///
+/// A Markdown-formatted error message with the available placeholders.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_unclosed_placeholder;
+/// let msg = format_unclosed_placeholder();
+/// assert!(msg.contains("{player}"));
+/// ```
+pub fn format_unclosed_placeholder() -> String {
+    format!(
+        "Invalid template, a `{{` is not closed by a `}}`. {}",
+        format_available_placeholders()
+    )
+}
+
+/// Formats an error response for a template with an unknown placeholder.
+///
+/// # Arguments
+///
+/// * `name` - Name of the unknown placeholder
+///
+/// # Returns
+///
+/// A Markdown-formatted error message with the available placeholders.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_unknown_placeholder;
+/// let msg = format_unknown_placeholder("gaem");
+/// assert!(msg.contains("{gaem}"));
+/// ```
+pub fn format_unknown_placeholder(name: &str) -> String {
+    format!(
+        "Invalid template, placeholder `{{{}}}` not found. {}",
+        name,
+        format_available_placeholders()
+    )
+}
+
+/// Formats the list of the placeholders of the templates.
+fn format_available_placeholders() -> String {
+    format!(
+        "Available placeholders: {}",
+        Placeholder::ALL
+            .iter()
+            .map(|placeholder| format!("`{{{}}}`", placeholder.name()))
+            .collect::<Vec<String>>()
+            .join(", ")
+    )
+}
+
+/// Formats the prefix of the commands in a room.
+///
+/// # Arguments
+///
+/// * `prefix` - The prefix of the commands in the room
+///
+/// # Returns
+///
+/// A Markdown-formatted string with the prefix of the room.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_prefix;
+/// let msg = format_prefix("!miou");
+/// assert!(msg.contains("`!miou`"));
+/// ```
+pub fn format_prefix(prefix: &str) -> String {
+    format!(
+        "Commands of this room start with `{}` or with a mention of the bot.",
+        prefix
+    )
+}
+
+/// Formats a success response for setting the prefix of the commands in a room.
+///
+/// # Arguments
+///
+/// * `prefix` - The new prefix of the commands in the room
+///
+/// # Returns
+///
+/// A Markdown-formatted string confirming the new prefix, with an example.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_successful_prefix;
+/// let msg = format_successful_prefix("!mi");
+/// assert!(msg.contains("`!mi help`"));
+/// ```
+pub fn format_successful_prefix(prefix: &str) -> String {
+    format!(
+        "Commands of this room now start with `{}`, e.g. `{} help`.",
+        prefix, prefix
+    )
+}
+
+/// Formats a success response for restoring the default prefix of the commands in a room.
+///
+/// # Arguments
+///
+/// * `prefix` - The default prefix of the commands of the bot
+///
+/// # Returns
+///
+/// A Markdown-formatted string confirming the default prefix.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_prefix_reset;
+/// let msg = format_prefix_reset("!miou");
+/// assert!(msg.contains("default"));
+/// ```
+pub fn format_prefix_reset(prefix: &str) -> String {
+    format!(
+        "Commands of this room now start with the default prefix `{}`.",
+        prefix
+    )
+}
+
+/// Formats an error response for a prefix which is too long or contains whitespace.
+///
+/// # Returns
+///
+/// A Markdown-formatted error message with the constraints of the prefix.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_invalid_prefix;
+/// let msg = format_invalid_prefix();
+/// assert!(msg.contains("32"));
+/// ```
+pub fn format_invalid_prefix() -> String {
+    "Invalid prefix. It must be between 1 and 32 characters long, without spaces.".to_owned()
+}
+
+/// Formats a notification message for a player's turn.
+///
+/// Creates a message notifying the user that it's their turn to play, with the
+/// URL to access their game and the context of the turn: the game, its phase and
+/// progress, how long the turn has been open and who else is waited for.
+///
+/// This is the plain text fallback of the HTML notification, see
+/// [`html_response::format_player_turn`](crate::commands::html_response::format_player_turn).
+///
+/// # Arguments
+///
+/// * `user_id` - The Matrix user ID of the player
+/// * `player_id` - The ID of the player in the game
+/// * `player_url` - The URL to the player's game page on the Terraforming Mars server
+/// * `game` - The current state of the game of the player, when the message is sent
+/// * `now` - Unix timestamp (in seconds) used to compute how long the turn has been open
+///
+/// # Returns
+///
+/// A Markdown-formatted string with the turn notification message.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_player_turn;
+/// # use miou::tmars::{Game, Phase};
+/// # use std::collections::HashSet;
+/// let game = Game {
+///     id: "main:game123".to_string(),
+///     phase: Phase::Action,
+///     spectator_id: "spec123".to_string(),
+///     spectator_url: "https://example.com/spectator?id=spec123".to_string(),
+///     players: vec![],
+///     waited_players: HashSet::new(),
+///     progress: None,
+/// };
+/// let msg = format_player_turn("@alice:example.com", "p123", "https://example.com/player?id=p123", &game, 0);
+/// assert!(msg.contains("turn to play"));
+/// ```
+pub fn format_player_turn(
+    user_id: &str,
+    player_id: &str,
+    player_url: &str,
+    game: &Game,
+    now: u64,
+) -> String {
+    format_turn_notification(
+        "it's your turn to play",
+        user_id,
+        player_id,
+        player_url,
+        game,
+        now,
+    )
+}
+
+/// Formats a reminder message for a player who hasn't played yet.
+///
+/// Sent for the reminders of a repeating alert, after the first turn notification.
+/// It gives the same context as [`format_player_turn`].
+///
+/// # Arguments
+///
+/// * `user_id` - The Matrix user ID of the player
+/// * `player_id` - The ID of the player in the game
+/// * `player_url` - The URL to the player's game page on the Terraforming Mars server
+/// * `game` - The current state of the game of the player, when the message is sent
+/// * `now` - Unix timestamp (in seconds) used to compute how long the turn has been open
+///
+/// # Returns
+///
+/// A Markdown-formatted string with the reminder message.
+pub fn format_player_turn_reminder(
+    user_id: &str,
+    player_id: &str,
+    player_url: &str,
+    game: &Game,
+    now: u64,
+) -> String {
+    format_turn_notification(
+        "reminder, it's still your turn to play",
+        user_id,
+        player_id,
+        player_url,
+        game,
+        now,
+    )
+}
+
+/// Formats a turn notification starting with the given introduction.
+fn format_turn_notification(
+    introduction: &str,
+    user_id: &str,
+    player_id: &str,
+    player_url: &str,
+    game: &Game,
+    now: u64,
+) -> String {
+    let player = game.players.iter().find(|player| player.id == player_id);
+    let player_md = match player {
+        Some(player) => format!(" as **{}**", player.name),
+        None => String::new(),
+    };
+
+    let mut context_md = vec![format_phase_and_progress(game)];
+    if let Some(waiting_since) = player.and_then(|player| player.waiting_since) {
+        context_md.push(format!(
+            "Turn open for {}",
+            format_waiting_time(now.saturating_sub(waiting_since))
+        ));
+    }
+    let other_waited_players = get_other_waited_players(game, player_id);
+    if !other_waited_players.is_empty() {
+        context_md.push(format!(
+            "Also waiting for {}",
+            other_waited_players
+                .iter()
+                .map(|player| player.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        ));
+    }
+
+    format!(
+        "{}: {}{} in game **{}**: [{}]({}). {}.",
+        user_id,
+        introduction,
+        player_md,
+        game.id,
+        player_url,
+        player_url,
+        context_md.join(". ")
+    )
+}
+
+/// Formats the phase of a game, followed by its progress if known.
+///
+/// # Arguments
+///
+/// * `game` - The game to describe
+///
+/// # Returns
+///
+/// A plain string, e.g. `Action phase, generation 5, temperature -12°C, oxygen 6%, oceans 4/9`.
+pub fn format_phase_and_progress(game: &Game) -> String {
+    match &game.progress {
+        Some(progress) => format!("{} phase, {}", game.phase, format_game_progress(progress)),
+        None => format!("{} phase", game.phase),
+    }
+}
+
+/// Returns the players of a game waited for along with the given player.
+///
+/// # Arguments
+///
+/// * `game` - The game of the player
+/// * `player_id` - The ID of the player to leave out
+pub fn get_other_waited_players<'a>(game: &'a Game, player_id: &str) -> Vec<&'a Player> {
+    game.players
+        .iter()
+        .filter(|player| player.id != player_id && game.waited_players.contains(&player.id))
+        .collect()
+}
+
+/// Formats a list of registered alerts for the user.
+///
+/// Displays all alerts grouped by game ID, showing which players are being
+/// monitored in each game.
+///
+/// # Arguments
+///
+/// * `alerts` - A slice of tuples containing the game ID and a vector of player names
+///
+/// # Returns
+///
+/// A Markdown-formatted string listing all registered alerts, or a message if no alerts exist.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_alerts;
+/// let alerts = vec![
+///     ("game1".to_string(), vec!["Alice".to_string()]),
+/// ];
+/// let output = format_alerts(&alerts);
+/// assert!(output.contains("game1"));
+/// ```
+pub fn format_alerts(alerts: &[(String, Vec<String>)]) -> String {
+    if alerts.is_empty() {
+        return "No alerts found.".to_owned();
+    }
+
+    let alerts_md = alerts
+        .iter()
+        .map(|(game_id, player_name)| format!("- {}: {}", game_id, player_name.join(", ")))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!("Registered alerts:\n\n {}", alerts_md)
+}
+
+/// Formats the player of an alert, with the phases in which the alert fires.
+///
+/// # Arguments
+///
+/// * `player_name` - Name of the player targeted by the alert
+/// * `phases` - Phases in which the alert fires, every phase if empty
+///
+/// # Returns
+///
+/// The player name, followed by the phases if the alert is restricted to some.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_alert_player;
+/// # use miou::tmars::Phase;
+/// let player = format_alert_player("Alice", &[Phase::Action]);
+/// assert_eq!(player, "Alice (Action phase)");
+/// ```
+pub fn format_alert_player(player_name: &str, phases: &[Phase]) -> String {
+    match phases.len() {
+        0 => player_name.to_owned(),
+        1 => format!("{} ({} phase)", player_name, phases[0]),
+        _ => format!(
+            "{} ({} phases)",
+            player_name,
+            phases
+                .iter()
+                .map(Phase::to_string)
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Formats an error message for API access failures.
+///
+/// Returns an error message indicating that the bot encountered authorization
+/// or authentication issues while trying to access the Terraforming Mars API.
+///
+/// # Arguments
+///
+/// * `server_name` - Name of the server that denied the access
+///
+/// # Returns
+///
+/// A Markdown-formatted string containing the access error message.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_access_error;
+/// let msg = format_access_error("main");
+/// assert!(msg.contains("unauthorized"));
+/// assert!(msg.contains("terraforming mars"));
+/// ```
+pub fn format_access_error(server_name: &str) -> String {
+    format!(
+        "Error: unauthorized access to the terraforming mars API of the **{}** server",
+        server_name
+    )
+}
+
+/// Formats an error message for a server which doesn't answer.
+///
+/// # Arguments
+///
+/// * `server_name` - Name of the server that doesn't answer
+///
+/// # Returns
+///
+/// A Markdown-formatted string containing the error message.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_server_unavailable;
+/// let msg = format_server_unavailable("main");
+/// assert!(msg.contains("**main**"));
+/// ```
+pub fn format_server_unavailable(server_name: &str) -> String {
+    format!(
+        "Error: the **{}** server doesn't answer, its games are kept as they were until it answers again",
+        server_name
+    )
+}
+
+/// Formats the message announcing that a server answers again.
+///
+/// # Arguments
+///
+/// * `server_name` - Name of the server that answers again
+///
+/// # Returns
+///
+/// A Markdown-formatted string containing the recovery message.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::markdown_response::format_server_back;
+/// let msg = format_server_back("main");
+/// assert!(msg.contains("I'm back"));
+/// ```
+pub fn format_server_back(server_name: &str) -> String {
+    format!(
+        "I'm back! The **{}** server answers again, its games are synchronized",
+        server_name
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commands::registry::{COMMANDS, find_command},
+        history::Turn,
+        tmars::{Phase, Player},
+    };
+    use std::collections::{HashMap, HashSet};
+
+    fn create_test_progress(venus: Option<i32>) -> GameProgress {
+        GameProgress {
+            generation: 5,
+            temperature: -12,
+            oxygen: 6,
+            oceans: 4,
+            venus,
+        }
+    }
+
+    #[test]
+    fn test_format_help() {
+        let help = format_help("!miou", COMMANDS);
+        assert!(help.contains("Commands:"));
+        assert!(help.contains("games"));
+        assert!(help.contains("alerts"));
+        assert!(help.contains("register"));
+        assert!(help.contains("unregister"));
+        assert!(help.contains("watch"));
+        assert!(help.contains("settings"));
+        assert!(help.contains("help"));
+        assert!(help.contains(
+            "\n- `register <game_id> <player_name> <delay> [repeat_in_minutes] [max_reminders] [--phases <phase,...>]`: register a new alert (alias: `reg`)\n"
+        ));
+        assert!(help.contains("\n- `settings quiet off`: disable your quiet hours\n"));
+        assert!(help.contains("\nCommands start with `!miou` or with a mention of the bot"));
+    }
+
+    #[test]
+    fn test_format_help_lists_every_usage() {
+        let help = format_help("!miou", COMMANDS);
+
+        for command in COMMANDS {
+            for usage in command.usages() {
+                assert!(help.contains(&format!(
+                    "- `{}`",
+                    format_usage(command.name(), usage.arguments)
+                )));
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_invalid_arguments() {
+        let usages = [
+            Usage {
+                arguments: "",
+                description: "show your settings",
+            },
+            Usage {
+                arguments: "quiet <start>-<end> <timezone>",
+                description: "set your quiet hours",
+            },
+            Usage {
+                arguments: "quiet off",
+                description: "disable your quiet hours",
+            },
+        ];
+
+        assert_eq!(
+            format_invalid_arguments(
+                "!miou",
+                "settings",
+                &usages[..1],
+                &ArgumentError::MissingArguments
+            ),
+            "Invalid settings command: missing arguments. Usage: `!miou settings`",
+        );
+        assert_eq!(
+            format_invalid_arguments(
+                "!miou",
+                "settings",
+                &usages[..2],
+                &ArgumentError::MissingArguments
+            ),
+            "Invalid settings command: missing arguments. Usage: `!miou settings` or `!miou settings quiet <start>-<end> <timezone>`",
+        );
+        assert_eq!(
+            format_invalid_arguments(
+                "!miou",
+                "settings",
+                &usages,
+                &ArgumentError::MissingArguments
+            ),
+            "Invalid settings command: missing arguments. Usage: `!miou settings`, `!miou settings quiet <start>-<end> <timezone>` or `!miou settings quiet off`",
+        );
+    }
+
+    #[test]
+    fn test_format_argument_error() {
+        assert_eq!(
+            format_argument_error(&ArgumentError::MissingArguments),
+            "missing arguments"
+        );
+        assert_eq!(
+            format_argument_error(&ArgumentError::UnexpectedArgument("extra".to_string())),
+            "unexpected argument `extra`"
+        );
+        assert_eq!(
+            format_argument_error(&ArgumentError::UnknownOption("players".to_string())),
+            "unknown option `--players`"
+        );
+        assert_eq!(
+            format_argument_error(&ArgumentError::InvalidValue {
+                argument: "delay",
+                value: "abc".to_string(),
+                reason: "is not a number",
+            }),
+            "delay `abc` is not a number"
+        );
+    }
+
+    #[test]
+    fn test_format_command_help() {
+        let help = format_command_help("!miou", find_command("register").unwrap());
+
+        assert!(
+            help.starts_with("Command `register` (alias: `reg`):\n- `!miou register <game_id>")
+        );
+        assert!(help.contains("\nArguments:\n- `game_id`: "));
+        assert!(help.contains("\n- `--phases`: "));
+        assert!(help.contains("\nExamples:\n- `!miou register game123 Alice 60`: "));
+    }
+
+    #[test]
+    fn test_format_command_help_without_arguments() {
+        let help = format_command_help("!miou", find_command("games").unwrap());
+
+        assert_eq!(
+            help,
+            "Command `games`:\n\
+            - `!miou games`: list all the ongoing games\n\
+            \n\
+            Examples:\n\
+            - `!miou games`: list the games of all the servers\n"
+        );
     }
 
     #[test]
     fn test_format_unknown_command() {
         assert_eq!(
-            format_unknown_command(),
+            format_unknown_command("!miou", None),
             "Unknown command. Type `!miou help` for more information.",
         );
+        assert_eq!(
+            format_unknown_command("!miou", Some("games")),
+            "Unknown command. Did you mean `!miou games`? Type `!miou help` for more information.",
+        );
+        assert_eq!(
+            format_unknown_command("!mi", Some("games")),
+            "Unknown command. Did you mean `!mi games`? Type `!mi help` for more information.",
+        );
+    }
+
+    #[test]
+    fn test_format_command_help_with_prefix() {
+        let help = format_command_help("!mi", find_command("games").unwrap());
+
+        assert!(help.contains("\n- `!mi games`: list all the ongoing games\n"));
+        assert!(!help.contains("!miou"));
+    }
+
+    #[test]
+    fn test_format_syntax_error() {
+        assert_eq!(
+            format_syntax_error(
+                "!miou",
+                &SyntaxError::MissingOptionValue {
+                    name: "phases".to_string(),
+                    position: 32,
+                }
+            ),
+            "Invalid command, the option `--phases` at column 33 has no value. Type `!miou help` for more information.",
+        );
+        assert!(
+            format_syntax_error("!miou", &SyntaxError::TrailingEscape(11)).contains("column 12")
+        );
     }
 
     #[test]
@@ -369,33 +1859,43 @@ mod tests {
                 id: "game-id1".to_owned(),
                 phase: Phase::Research,
                 spectator_id: "spec-id1".to_owned(),
+                spectator_url: "http://example.com/spectator".to_string(),
                 players: vec![
                     Player {
                         id: "player-id1".to_owned(),
                         color: "red".to_owned(),
                         name: "Alice".to_owned(),
                         url: "http://example.com/player-id1".to_owned(),
+                        terraform_rating: None,
+                        waiting_since: None,
                     },
                     Player {
                         id: "player-id2".to_owned(),
                         color: "blue".to_owned(),
                         name: "Bob".to_owned(),
                         url: "http://example.com/player-id2".to_owned(),
+                        terraform_rating: None,
+                        waiting_since: None,
                     },
                 ],
                 waited_players: HashSet::from(["player-id2".to_owned()]),
+                progress: None,
             },
             Game {
                 id: "game-id2".to_owned(),
                 phase: Phase::Research,
                 spectator_id: "spec-id2".to_owned(),
+                spectator_url: "http://example.com/spectator".to_string(),
                 players: vec![Player {
                     id: "player-id3".to_owned(),
                     color: "red".to_owned(),
                     name: "Alice".to_owned(),
                     url: "http://example.com/player-id3".to_owned(),
+                    terraform_rating: None,
+                    waiting_since: None,
                 }],
                 waited_players: HashSet::new(),
+                progress: None,
             },
         ];
 
@@ -406,18 +1906,102 @@ mod tests {
     }
 
     #[test]
-    fn test_format_invalid_register() {
+    fn test_format_games_with_progress() {
+        let games = [Game {
+            id: "game-id1".to_owned(),
+            phase: Phase::Action,
+            spectator_id: "spec-id1".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
+            players: vec![
+                Player {
+                    id: "player-id1".to_owned(),
+                    color: "red".to_owned(),
+                    name: "Alice".to_owned(),
+                    url: "http://example.com/player-id1".to_owned(),
+                    terraform_rating: Some(32),
+                    waiting_since: None,
+                },
+                Player {
+                    id: "player-id2".to_owned(),
+                    color: "blue".to_owned(),
+                    name: "Bob".to_owned(),
+                    url: "http://example.com/player-id2".to_owned(),
+                    terraform_rating: Some(28),
+                    waiting_since: None,
+                },
+            ],
+            waited_players: HashSet::from(["player-id2".to_owned()]),
+            progress: Some(create_test_progress(Some(10))),
+        }];
+
+        assert_eq!(
+            format_games(&games),
+            "Games: \n\n - **game-id1**(Action), **players**: Alice(TR 32), Bob(TR 28, ⏳), **progress**: generation 5, temperature -12°C, oxygen 6%, oceans 4/9, Venus 10%",
+        );
+    }
+
+    #[test]
+    fn test_format_game_progress() {
+        assert_eq!(
+            format_game_progress(&create_test_progress(Some(10))),
+            "generation 5, temperature -12°C, oxygen 6%, oceans 4/9, Venus 10%",
+        );
         assert_eq!(
-            format_invalid_register(),
-            "Invalid register command. Usage: `!miou register <game_id> <player_name> <delay_in_minutes>`",
+            format_game_progress(&create_test_progress(None)),
+            "generation 5, temperature -12°C, oxygen 6%, oceans 4/9",
         );
     }
 
+    /// Alice and Bob are waited for in an action phase, Alice for 1h 5min at 4900.
+    fn create_turn_test_game() -> Game {
+        let create_player = |id: &str, color: &str, name: &str, waiting_since| Player {
+            id: id.to_owned(),
+            color: color.to_owned(),
+            name: name.to_owned(),
+            url: format!("http://example.com/{}", id),
+            terraform_rating: None,
+            waiting_since,
+        };
+
+        Game {
+            id: "main:game-id1".to_owned(),
+            phase: Phase::Action,
+            spectator_id: "spec-id1".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
+            players: vec![
+                create_player("player-id1", "red", "Alice", Some(1000)),
+                create_player("player-id2", "blue", "Bob", Some(1000)),
+                create_player("player-id3", "green", "Charlie", None),
+            ],
+            waited_players: HashSet::from(["player-id1".to_owned(), "player-id2".to_owned()]),
+            progress: Some(create_test_progress(None)),
+        }
+    }
+
     #[test]
-    fn test_format_invalid_unregister() {
+    fn test_format_player_turn_with_context() {
+        let game = create_turn_test_game();
+
         assert_eq!(
-            format_invalid_unregister(),
-            "Invalid unregister command. Usage: `!miou unregister <game_id>`",
+            format_player_turn(
+                "@alice:example.com",
+                "player-id1",
+                "http://example.com/player-id1",
+                &game,
+                4900
+            ),
+            "@alice:example.com: it's your turn to play as **Alice** in game **main:game-id1**: [http://example.com/player-id1](http://example.com/player-id1). \
+            Action phase, generation 5, temperature -12°C, oxygen 6%, oceans 4/9. Turn open for 1h 5min. Also waiting for Bob."
+        );
+        assert!(
+            format_player_turn_reminder(
+                "@alice:example.com",
+                "player-id1",
+                "http://example.com/player-id1",
+                &game,
+                4900
+            )
+            .starts_with("@alice:example.com: reminder, it's still your turn to play as **Alice**")
         );
     }
 
@@ -429,20 +2013,136 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_invalid_repeat_interval() {
+        assert_eq!(
+            format_invalid_repeat_interval(),
+            "Invalid repeat interval. Repeat interval must be between 1 minutes and 1 week.",
+        );
+    }
+
+    #[test]
+    fn test_format_invalid_max_reminders() {
+        assert_eq!(
+            format_invalid_max_reminders(),
+            "Invalid max reminders. Max reminders must be at least 1.",
+        );
+    }
+
+    #[test]
+    fn test_format_game() {
+        let game = Game {
+            id: "game-id1".to_owned(),
+            phase: Phase::Action,
+            spectator_id: "spec-id1".to_owned(),
+            spectator_url: "http://example.com/spectator?id=spec-id1".to_owned(),
+            players: vec![
+                Player {
+                    id: "player-id1".to_owned(),
+                    color: "red".to_owned(),
+                    name: "Alice".to_owned(),
+                    url: "http://example.com/player-id1".to_owned(),
+                    terraform_rating: Some(32),
+                    waiting_since: None,
+                },
+                Player {
+                    id: "player-id2".to_owned(),
+                    color: "blue".to_owned(),
+                    name: "Bob".to_owned(),
+                    url: "http://example.com/player-id2".to_owned(),
+                    terraform_rating: Some(28),
+                    waiting_since: Some(1000),
+                },
+            ],
+            waited_players: HashSet::from(["player-id2".to_owned()]),
+            progress: Some(create_test_progress(None)),
+        };
+        let room_alerts = vec![("@bob:example.com".to_owned(), "Bob".to_owned())];
+
+        assert_eq!(
+            format_game(&game, &room_alerts, 1000 + 3900),
+            "Game **game-id1**: Action phase, generation 5, temperature -12°C, oxygen 6%, oceans 4/9.\n\n\
+            Spectator page: [http://example.com/spectator?id=spec-id1](http://example.com/spectator?id=spec-id1)\n\n\
+            Players:\n\n\
+            - **Alice** (red, TR 32)\n\
+            - **Bob** (blue, TR 28): ⏳ waited for 1h 5min\n\n\
+            Alerts in this room:\n\n\
+            - @bob:example.com for Bob",
+        );
+    }
+
+    #[test]
+    fn test_format_game_without_progress_nor_alerts() {
+        let game = Game {
+            id: "game-id1".to_owned(),
+            phase: Phase::Research,
+            spectator_id: "spec-id1".to_owned(),
+            spectator_url: "http://example.com/spectator?id=spec-id1".to_owned(),
+            players: vec![Player {
+                id: "player-id1".to_owned(),
+                color: "red".to_owned(),
+                name: "Alice".to_owned(),
+                url: "http://example.com/player-id1".to_owned(),
+                terraform_rating: None,
+                waiting_since: None,
+            }],
+            waited_players: HashSet::new(),
+            progress: None,
+        };
+
+        let msg = format_game(&game, &[], 0);
+
+        assert!(msg.starts_with("Game **game-id1**: Research phase.\n\n"));
+        assert!(msg.contains("- **Alice** (red)\n\n"));
+        assert!(msg.ends_with("No alerts in this room."));
+    }
+
+    #[test]
+    fn test_format_waiting_time() {
+        assert_eq!(format_waiting_time(30), "less than a minute");
+        assert_eq!(format_waiting_time(5 * 60), "5min");
+        assert_eq!(format_waiting_time(2 * 3600 + 5 * 60), "2h 5min");
+        assert_eq!(format_waiting_time(3 * 86400 + 4 * 3600 + 5 * 60), "3d 4h");
+    }
+
+    #[test]
+    fn test_format_invalid_quiet_hours() {
+        assert_eq!(
+            format_invalid_quiet_hours(),
+            "Invalid quiet hours. Quiet hours must be formatted as `HH:MM-HH:MM`, e.g. `23:00-08:00`.",
+        );
+    }
+
+    #[test]
+    fn test_format_unknown_timezone() {
+        assert_eq!(
+            format_unknown_timezone("Mars/Olympus_Mons"),
+            "Unknown timezone 'Mars/Olympus_Mons'. Use a timezone name like `Europe/Paris` or `UTC`.",
+        );
+    }
+
     #[test]
     fn test_format_game_not_found() {
         assert_eq!(
-            format_game_not_found("game123"),
+            format_game_not_found("game123", None),
             "Game with id 'game123' not found.",
         );
+        assert_eq!(
+            format_game_not_found("main:game12", Some("main:game123")),
+            "Game with id 'main:game12' not found. Did you mean 'main:game123'?",
+        );
     }
 
     #[test]
     fn test_format_player_not_found() {
         assert_eq!(
-            format_player_not_found("Alice", "game123"),
+            format_player_not_found("Alice", "game123", None),
             "Player 'Alice' not found in game with id 'game123'.",
         );
+        assert_eq!(
+            format_player_not_found("Alise", "game123", Some("Alice")),
+            "Player 'Alise' not found in game with id 'game123'. Did you mean 'Alice'?",
+        );
     }
 
     #[test]
@@ -474,14 +2174,252 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_alert_player() {
+        assert_eq!(format_alert_player("Alice", &[]), "Alice");
+        assert_eq!(
+            format_alert_player("Alice", &[Phase::Action, Phase::Production]),
+            "Alice (Action, Production phases)"
+        );
+    }
+
     #[test]
     fn test_format_player_turn() {
+        let game = Game {
+            id: "main:game-id1".to_owned(),
+            phase: Phase::Research,
+            spectator_id: "spec-id1".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
+            players: vec![],
+            waited_players: HashSet::new(),
+            progress: None,
+        };
+
+        // Without the player nor the progress, only the phase is known
         assert_eq!(
-            format_player_turn("@alice:example.com", "http://example.com/player-id1"),
-            "@alice:example.com: it's your turn to play: [http://example.com/player-id1](http://example.com/player-id1)."
+            format_player_turn(
+                "@alice:example.com",
+                "player-id1",
+                "http://example.com/player-id1",
+                &game,
+                0
+            ),
+            "@alice:example.com: it's your turn to play in game **main:game-id1**: [http://example.com/player-id1](http://example.com/player-id1). Research phase."
         )
     }
 
+    #[test]
+    fn test_format_successful_watch() {
+        assert_eq!(
+            format_successful_watch("game123"),
+            "Turn changes of game 'game123' will be announced in this room.",
+        );
+    }
+
+    #[test]
+    fn test_format_successful_unwatch() {
+        assert_eq!(
+            format_successful_unwatch("game123"),
+            "Turn changes of game 'game123' won't be announced in this room anymore.",
+        );
+    }
+
+    #[test]
+    fn test_format_turn_change() {
+        let mut turn_change = TurnChange {
+            game_id: "game123".to_string(),
+            phase: Phase::Action,
+            waited_player_names: vec!["Bob".to_string()],
+        };
+        assert_eq!(
+            format_turn_change(&turn_change),
+            "**game123**: Bob is now up (Action phase).",
+        );
+
+        turn_change.phase = Phase::Research;
+        turn_change.waited_player_names = vec!["Alice".to_string(), "Bob".to_string()];
+        assert_eq!(
+            format_turn_change(&turn_change),
+            "**game123**: Alice, Bob are now up (Research phase).",
+        );
+
+        turn_change.phase = Phase::Production;
+        turn_change.waited_player_names = vec![];
+        assert_eq!(
+            format_turn_change(&turn_change),
+            "**game123**: no player to wait for (Production phase).",
+        );
+
+        // A phase unknown to the bot is shown with its name on the server
+        turn_change.phase = Phase::Unknown("auction".to_string());
+        assert_eq!(
+            format_turn_change(&turn_change),
+            "**game123**: no player to wait for (auction phase).",
+        );
+    }
+
+    #[test]
+    fn test_format_game_end() {
+        let scores = vec![
+            PlayerScore {
+                name: "Bob".to_string(),
+                victory_points: 85,
+            },
+            PlayerScore {
+                name: "Alice".to_string(),
+                victory_points: 72,
+            },
+            PlayerScore {
+                name: "Charlie".to_string(),
+                victory_points: 72,
+            },
+            PlayerScore {
+                name: "Diana".to_string(),
+                victory_points: 60,
+            },
+        ];
+
+        assert_eq!(
+            format_game_end("game123", &scores),
+            "**game123** is over! Final scores:\n\n1. **Bob**: 85 VP\n2. Alice: 72 VP\n2. Charlie: 72 VP\n4. Diana: 60 VP",
+        );
+    }
+
+    #[test]
+    fn test_format_game_end_tied_winners() {
+        let scores = vec![
+            PlayerScore {
+                name: "Alice".to_string(),
+                victory_points: 80,
+            },
+            PlayerScore {
+                name: "Bob".to_string(),
+                victory_points: 80,
+            },
+        ];
+
+        assert_eq!(
+            format_game_end("game123", &scores),
+            "**game123** is over! Final scores:\n\n1. **Alice**: 80 VP\n1. **Bob**: 80 VP",
+        );
+    }
+
+    #[test]
+    fn test_format_game_end_without_scores() {
+        assert_eq!(
+            format_game_end("game123", &[]),
+            "**game123** is over! The final scores are unavailable.",
+        );
+    }
+
+    fn create_test_turn_stats() -> TurnStats {
+        TurnStats::from_turns_map(&HashMap::from([(
+            "main:game123".to_string(),
+            vec![
+                Turn {
+                    player_id: "player1".to_string(),
+                    player_name: "Alice".to_string(),
+                    started_at: 0,
+                    ended_at: 120,
+                },
+                Turn {
+                    player_id: "player2".to_string(),
+                    player_name: "Bob".to_string(),
+                    started_at: 120,
+                    ended_at: 3720,
+                },
+            ],
+        )]))
+    }
+
+    #[test]
+    fn test_format_stats_of_game() {
+        assert_eq!(
+            format_stats(Some("main:game123"), &create_test_turn_stats()),
+            "Turn statistics of game **main:game123**:\n\n\
+            Players, from the slowest:\n\n\
+            - **Bob**: 1 turn, average 1h 0min, median 1h 0min\n\
+            - **Alice**: 1 turn, average 2min, median 2min\n\n\
+            Slowest turns:\n\n\
+            1. **Bob**: 1h 0min\n\
+            2. **Alice**: 2min",
+        );
+    }
+
+    #[test]
+    fn test_format_stats_of_all_games() {
+        let stats = format_stats(None, &create_test_turn_stats());
+
+        assert!(stats.starts_with("Turn statistics of all games:"));
+        assert!(stats.contains("1. **Bob** in main:game123: 1h 0min"));
+    }
+
+    #[test]
+    fn test_format_stats_without_turns() {
+        let turn_stats = TurnStats::from_turns_map(&HashMap::new());
+
+        assert_eq!(format_stats(None, &turn_stats), "No turns recorded yet.");
+        assert_eq!(
+            format_stats(Some("main:game123"), &turn_stats),
+            "No turns recorded yet for game **main:game123**.",
+        );
+    }
+
+    #[test]
+    fn test_format_settings_without_quiet_hours() {
+        assert_eq!(
+            format_settings(None),
+            "Settings:\n\n - **Quiet hours**: disabled",
+        );
+    }
+
+    #[test]
+    fn test_format_settings_with_quiet_hours() {
+        let quiet_hours = QuietHours::parse("23:00-08:00", "Europe/Paris").unwrap();
+        assert_eq!(
+            format_settings(Some(&quiet_hours)),
+            "Settings:\n\n - **Quiet hours**: 23:00-08:00 (Europe/Paris)",
+        );
+    }
+
+    #[test]
+    fn test_format_successful_quiet_hours() {
+        let quiet_hours = QuietHours::parse("23:00-08:00", "Europe/Paris").unwrap();
+        assert_eq!(
+            format_successful_quiet_hours(&quiet_hours),
+            "Quiet hours set to 23:00-08:00 (Europe/Paris). Notifications during quiet hours will be sent when they end.",
+        );
+    }
+
+    #[test]
+    fn test_format_server() {
+        assert_eq!(
+            format_server("main", &["friends".to_string(), "main".to_string()]),
+            "Game IDs without a server name refer to the games of the **main** server in this room. Available servers: friends, main",
+        );
+    }
+
+    #[test]
+    fn test_format_successful_server() {
+        assert_eq!(
+            format_successful_server("friends"),
+            "Game IDs without a server name now refer to the games of the **friends** server in this room.",
+        );
+    }
+
+    #[test]
+    fn test_format_unknown_server() {
+        assert_eq!(
+            format_unknown_server("other", &["friends".to_string(), "main".to_string()]),
+            "Server **other** not found. Available servers: friends, main",
+        );
+    }
+
+    #[test]
+    fn test_format_quiet_hours_disabled() {
+        assert_eq!(format_quiet_hours_disabled(), "Quiet hours disabled.");
+    }
+
     #[test]
     fn test_format_successful_unregister() {
         assert_eq!(
@@ -493,8 +2431,24 @@ mod tests {
     #[test]
     fn test_format_access_error() {
         assert_eq!(
-            format_access_error(),
-            "Error: unauthorized access to the terraforming mars API",
+            format_access_error("main"),
+            "Error: unauthorized access to the terraforming mars API of the **main** server",
+        );
+    }
+
+    #[test]
+    fn test_format_server_unavailable() {
+        assert_eq!(
+            format_server_unavailable("main"),
+            "Error: the **main** server doesn't answer, its games are kept as they were until it answers again",
+        );
+    }
+
+    #[test]
+    fn test_format_server_back() {
+        assert_eq!(
+            format_server_back("main"),
+            "I'm back! The **main** server answers again, its games are synchronized",
         );
     }
 }
@@ -6,11 +6,12 @@
 //! # Overview
 //!
 //! The commands module handles the entire lifecycle of bot commands:
-//! 1. **Parsing** - Converting Matrix messages into structured [`command::Command`] enums
+//! 1. **Parsing** - Converting Matrix messages into a [`command::Command`] holding the
+//!    arguments parsed by one of the commands
 //! 2. **Validation** - Ensuring commands have correct syntax and valid arguments
 //! 3. **Execution** - Routing commands to specialized handlers
 //! 4. **Response** - Formatting results as Markdown for Matrix display
-//! 5. **State Management** - Managing alert subscriptions
+//! 5. **State Management** - Managing alert subscriptions, user settings and room watches
 //!
 //! # Architecture
 //!
@@ -24,6 +25,11 @@
 //!      │
 //!      ├── parse() ────────────────────┐
 //!      │                               ▼
+//!      │                   ┌──────────────────────┐
+//!      │                   │ registry::COMMANDS   │  ← BotCommand::parse()
+//!      │                   └──────────────────────┘
+//!      │                               │
+//!      │                               ▼
 //!                          ┌──────────────────┐
 //!                          │  command::Command│
 //!                          └──────────────────┘
@@ -31,12 +37,11 @@
 //!      └── parse_command() ───────────┐
 //!                                     ▼
 //!                          ┌─────────────────────┐
-//!                          │ Action Handlers     │
-//!                          │  - handle_help      │
-//!                          │  - handle_games     │
-//!                          │  - handle_alerts    │
-//!                          │  - handle_register  │
-//!                          │  - handle_unregister│
+//!                          │ registry::COMMANDS  │  ← BotCommand::handle()
+//!                          │  - HelpCommand      │
+//!                          │  - GamesCommand     │
+//!                          │  - RegisterCommand  │
+//!                          │  - ...              │
 //!                          └─────────────────────┘
 //!                                     │
 //!                                     ▼
@@ -44,6 +49,8 @@
 //!                          │  CommandResult     │
 //!                          │  - response (MD)   │
 //!                          │  - alert changes   │
+//!                          │  - settings changes│
+//!                          │  - watch changes   │
 //!                          └────────────────────┘
 //! ```
 //!
@@ -51,15 +58,31 @@
 //!
 //! All commands follow the format: `!miou <subcommand> [args...]`
 //!
+//! The prefix `!miou` is set in the configuration and can be replaced in a room by
+//! the `prefix` command. A command can also start with a mention of the bot, e.g.
+//! `@miou:example.com games`, and needs no prefix in a direct chat with the bot.
+//!
+//! Arguments are separated by whitespace. An argument containing spaces is quoted
+//! (`"Alice Smith"`) or has its spaces escaped with a backslash (`Alice\ Smith`).
+//! Options are given as `--name value`.
+//!
 //! ## Available Commands
 //!
 //! | Command | Arguments | Description |
 //! |---------|-----------|-------------|
-//! | `help` | None | Display help information |
+//! | `help` | `[command]` | Display help information, or the detailed help of a command |
 //! | `games` | None | List all ongoing Terraforming Mars games |
 //! | `alerts` | None | List active alert registrations |
-//! | `register` | `<game_id> <player_name> <delay>` | Register for turn notifications |
+//! | `register` | `<game_id> <player_name> <delay> [repeat] [max_reminders] [--phases <phase,...>]` | Register for turn notifications |
 //! | `unregister` | `<game_id>` | Stop receiving notifications for a game |
+//! | `watch` | `<game_id>` | Announce every turn change of a game in the room |
+//! | `unwatch` | `<game_id>` | Stop announcing the turn changes of a game in the room |
+//! | `settings` | None | Display the user's settings |
+//! | `settings quiet` | `<start>-<end> <timezone>` or `off` | Set or disable quiet hours |
+//! | `server` | `[server_name]` | Display or set the server of the game IDs without a server name in the room |
+//! | `stats` | `[game_id]` | Display the turn times of the players of a game, or of all games |
+//! | `template` | `[set "<template>" \| reset]` | Display, set or reset the template of the turn notifications of the room |
+//! | `prefix` | `[<prefix> \| reset]` | Display, set or reset the prefix of the commands in the room |
 //!
 //! ## Command Details
 //!
@@ -68,13 +91,53 @@
 //! Subscribes to notifications when a player's turn arrives in a game.
 //!
 //! - **game_id**: The unique identifier of the Terraforming Mars game
-//! - **player_name**: The name of the player to monitor
-//! - **delay**: Minutes to wait before notifying (1-10080, i.e., 1 week max)
+//! - **player_name**: The name of the player to monitor, quoted if it contains spaces
+//! - **delay**: Time to wait before notifying, in minutes (`90`) or as a duration (`30m`, `2h`,
+//!   `1d12h`), 1 week max, or time of day at which to notify (`@09:00`)
+//! - **repeat** (optional): Minutes between reminders while the player hasn't played (1-10080)
+//! - **max_reminders** (optional): Maximum number of reminders per turn (at least 1)
+//! - **--phases** (optional): Comma-separated phases in which the alert fires, e.g. `action,production`
 //!
 //! ### Unregister Command
 //!
 //! Removes all alert subscriptions for a specific game in the current room.
 //!
+//! ### Watch Command
+//!
+//! Binds a game to the current room: every change of the waited players or of the
+//! phase is announced in the room, without mentioning anyone.
+//!
+//! ### Settings Command
+//!
+//! Displays or updates the preferences of the user issuing the command.
+//!
+//! - **quiet**: Daily window (e.g. `23:00-08:00`) in an IANA timezone (e.g. `Europe/Paris`)
+//!   during which notifications are deferred to the end of the window
+//!
+//! ### Stats Command
+//!
+//! Displays the average and median turn times of each player and the slowest turns,
+//! computed from the turns recorded since the bot follows the games. Players are
+//! identified by their name across games.
+//!
+//! ### Template Command
+//!
+//! Replaces the text of the turn notifications of the alerts of the room. The
+//! placeholders `{user}`, `{player}`, `{game}`, `{phase}`, `{url}` and `{waited_for}`
+//! are replaced by the context of the turn, unknown placeholders are refused.
+//!
+//! ### Prefix Command
+//!
+//! Replaces the prefix of the commands in the room, e.g. `!mi`, up to 32 characters
+//! without spaces. The commands starting with a mention of the bot are accepted
+//! whatever the prefix, to reset a forgotten prefix.
+//!
+//! ### Game IDs
+//!
+//! The bot follows several servers, so game IDs are namespaced by server, e.g.
+//! `main:g123`. Game IDs without a server name refer to the games of the server of
+//! the room, set with the `server` command, or of the default server of the bot.
+//!
 //! # Examples
 //!
 //! ## Basic Usage
@@ -82,14 +145,15 @@
 //! ```no_run
 //! # use miou::commands::Commander;
 //! # use std::collections::HashMap;
+//! # use miou::settings::{RoomSettings, UserSettings};
 //! # async fn example() {
 //! use miou::commands::{Commander, CommandContext};
 //!
-//! let commander = Commander::new();
+//! let commander = Commander::new("!miou".to_string(), "@miou:example.com".to_string());
 //!
 //! // Parse a Matrix message
 //! let message = "!miou games".to_string();
-//! match commander.parse(message) {
+//! match commander.parse(&message, None, false, None) {
 //!     Ok(command) => {
 //!         // Create execution context
 //!         let context = CommandContext {
@@ -97,12 +161,17 @@
 //!             alerts_map: HashMap::new(),
 //!             room_id: "!room:example.com".to_string(),
 //!             user_id: "@user:example.com".to_string(),
+//!             user_settings: UserSettings::default(),
+//!             room_settings: RoomSettings::default(),
+//!             default_server: "main".to_string(),
+//!             default_prefix: "!miou".to_string(),
+//!             servers: vec!["main".to_string()],
+//!             turns_map: HashMap::new(),
 //!         };
 //!
 //!         // Execute the command
-//!         if let Some(result) = commander.parse_command(&command, &context).await {
-//!             println!("Bot response: {}", result.response);
-//!         }
+//!         let result = commander.parse_command(&command, &context).await;
+//!         println!("Bot response: {}", result.response);
 //!     }
 //!     Err(e) => {
 //!         // Handle parse errors (invalid commands, wrong bot, etc.)
@@ -115,21 +184,19 @@
 //!
 //! ```
 //! # use miou::commands::Commander;
-//! # use miou::commands::command::Command;
 //!
-//! let commander = Commander::new();
+//! let commander = Commander::new("!miou".to_string(), "@miou:example.com".to_string());
 //!
 //! // Help command
-//! let cmd = commander.parse("!miou help".to_string()).unwrap();
-//! assert!(matches!(cmd, Command::Help));
-//!
-//! // Register command
-//! let cmd = commander.parse("!miou register game123 Alice 60".to_string()).unwrap();
-//! if let Command::Register(game_id, player_name, delay) = cmd {
-//!     assert_eq!(game_id, "game123");
-//!     assert_eq!(player_name, "Alice");
-//!     assert_eq!(delay, 60);
-//! }
+//! let cmd = commander.parse("!miou help", None, false, None).unwrap();
+//! assert_eq!(cmd.name(), "help");
+//!
+//! // Register command, with an alias
+//! let cmd = commander.parse("!miou reg game123 Alice 1h", None, false, None).unwrap();
+//! assert_eq!(cmd.name(), "register");
+//!
+//! // Invalid delay
+//! assert!(commander.parse("!miou register game123 Alice soon", None, false, None).is_err());
 //! ```
 //!
 //! # Error Handling
@@ -145,19 +212,31 @@
 //! # Module Organization
 //!
 //! - [`commander`] - Main orchestrator for parsing and executing commands
-//! - [`command`] - Command enum definitions and parsing logic
+//! - [`command`] - Parsed command and parsing logic
+//! - [`tokenizer`] - Splitting of the messages into words, quotes and options
+//! - [`registry`] - Commands of the bot, with their usages, arguments and handlers
 //! - [`actions`] - Individual command handler implementations
 //! - [`markdown_response`] - Response formatting utilities
+//! - [`html_response`] - HTML formatting of the turn notifications
 
 use std::collections::{HashMap, HashSet};
 
 mod actions;
 mod command;
 mod commander;
+mod html_response;
 mod markdown_response;
+mod registry;
+mod tokenizer;
 
 pub use crate::commands::commander::Commander;
-use crate::{alerts::Alert, tmars::Game};
+use crate::{
+    alerts::Alert,
+    history::Turn,
+    settings::{RoomSettings, UserSettings},
+    tmars::{GAME_ID_SEPARATOR, Game, is_namespaced_game_id, namespace_game_id},
+    utils::find_closest,
+};
 
 /// Runtime context for command execution.
 ///
@@ -170,17 +249,30 @@ use crate::{alerts::Alert, tmars::Game};
 /// * `alerts_map` - Alert subscriptions, indexed by game ID with sets of [`Alert`]s
 /// * `room_id` - Matrix room ID where the command was issued
 /// * `user_id` - Matrix user ID of the user who issued the command
+/// * `user_settings` - Settings of the user who issued the command
+/// * `room_settings` - Settings of the room where the command was issued
+/// * `default_server` - Server of the game IDs without a server name in the room
+/// * `default_prefix` - Prefix of the commands in the rooms which didn't choose one
+/// * `servers` - Names of all the configured servers
+/// * `turns_map` - Ended turns of the players, indexed by game ID with lists of [`Turn`]s
 ///
 /// # Examples
 ///
 /// ```
 /// # use miou::commands::CommandContext;
+/// # use miou::settings::{RoomSettings, UserSettings};
 /// # use std::collections::HashMap;
 /// let context = CommandContext {
 ///     games_map: HashMap::new(),
 ///     alerts_map: HashMap::new(),
 ///     room_id: "!room:example.com".to_string(),
 ///     user_id: "@user:example.com".to_string(),
+///     user_settings: UserSettings::default(),
+///     room_settings: RoomSettings::default(),
+///     default_server: "main".to_string(),
+///     default_prefix: "!miou".to_string(),
+///     servers: vec!["main".to_string()],
+///     turns_map: HashMap::new(),
 /// };
 /// ```
 #[derive(Debug)]
@@ -193,6 +285,72 @@ pub struct CommandContext {
     pub room_id: String,
     /// Matrix user ID of the command issuer
     pub user_id: String,
+    /// Settings of the command issuer
+    pub user_settings: UserSettings,
+    /// Settings of the room where the command was issued
+    pub room_settings: RoomSettings,
+    /// Server of the game IDs without a server name, chosen by the room or
+    /// the default server of the bot
+    pub default_server: String,
+    /// Prefix of the commands in the rooms which didn't choose one
+    pub default_prefix: String,
+    /// Names of all the configured servers
+    pub servers: Vec<String>,
+    /// Map of the ended turns indexed by game ID
+    pub turns_map: HashMap<String, Vec<Turn>>,
+}
+
+impl CommandContext {
+    /// Resolves a game ID given in a command to the namespaced ID of the game.
+    ///
+    /// Game IDs already namespaced (e.g. `main:g123`) are kept as is, the others
+    /// refer to a game of the default server of the room.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_id` - The game ID given in the command
+    ///
+    /// # Returns
+    ///
+    /// The namespaced game ID.
+    pub fn resolve_game_id(&self, game_id: &str) -> String {
+        match is_namespaced_game_id(game_id) {
+            true => game_id.to_owned(),
+            false => namespace_game_id(&self.default_server, game_id),
+        }
+    }
+
+    /// Returns the prefix of the commands in the room, chosen by the room or the
+    /// default prefix of the bot.
+    pub fn get_prefix(&self) -> &str {
+        self.room_settings
+            .prefix
+            .as_deref()
+            .unwrap_or(&self.default_prefix)
+    }
+}
+
+/// Returns the ID of the game closest to a game ID which was not found.
+///
+/// # Arguments
+///
+/// * `games_map` - Map of active games indexed by game ID
+/// * `game_id` - The namespaced game ID which was not found
+///
+/// # Returns
+///
+/// The namespaced ID of the closest active game of the same server, `None` if
+/// no game is close enough.
+pub fn suggest_game_id(games_map: &HashMap<String, Game>, game_id: &str) -> Option<String> {
+    // The server names are not compared, the games of another server would be
+    // suggested for a typo in a short game ID
+    let (server, game_id) = game_id.split_once(GAME_ID_SEPARATOR)?;
+    let prefix = namespace_game_id(server, "");
+    let server_game_ids = games_map
+        .keys()
+        .filter_map(|key| key.strip_prefix(prefix.as_str()));
+
+    find_closest(game_id, server_game_ids).map(|closest| namespace_game_id(server, closest))
 }
 
 /// Result of command execution.
@@ -205,13 +363,22 @@ pub struct CommandContext {
 /// * `response` - Markdown-formatted message to send to the Matrix room
 /// * `alert_to_add` - Optional alert to register: (game_id, Alert)
 /// * `alerts_to_remove` - Optional alerts to remove: (game_id, room_id, user_id)
+/// * `user_settings_to_update` - Optional settings to save: (user_id, UserSettings)
+/// * `room_settings_to_update` - Optional room settings to save: (room_id, RoomSettings)
+/// * `watch_to_add` - Optional game to watch: (game_id, room_id)
+/// * `watch_to_remove` - Optional game to stop watching: (game_id, room_id)
 ///
 /// # State Changes
 ///
 /// Command handlers don't directly modify state. Instead, they return state
-/// change requests through `alert_to_add` and `alerts_to_remove`. The caller
+/// change requests through `alert_to_add`, `alerts_to_remove`,
+/// `user_settings_to_update`, `room_settings_to_update`, `watch_to_add` and
+/// `watch_to_remove`. The caller
 /// is responsible for applying these changes.
 ///
+/// All the state changes default to `None`, so handlers only name the
+/// fields they set and fill the rest with `..Default::default()`.
+///
 /// # Examples
 ///
 /// ```
@@ -219,11 +386,10 @@ pub struct CommandContext {
 /// // Read-only command (help, games, alerts)
 /// let result = CommandResult {
 ///     response: "No ongoing games found.".to_string(),
-///     alert_to_add: None,
-///     alerts_to_remove: None,
+///     ..Default::default()
 /// };
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct CommandResult {
     /// Markdown-formatted response message
     pub response: String,
@@ -231,6 +397,14 @@ pub struct CommandResult {
     pub alert_to_add: Option<(String, Alert)>,
     /// Optional alerts to remove: (game_id, room_id, user_id)
     pub alerts_to_remove: Option<(String, String, String)>,
+    /// Optional settings to save: (user_id, UserSettings)
+    pub user_settings_to_update: Option<(String, UserSettings)>,
+    /// Optional room settings to save: (room_id, RoomSettings)
+    pub room_settings_to_update: Option<(String, RoomSettings)>,
+    /// Optional game to watch: (game_id, room_id)
+    pub watch_to_add: Option<(String, String)>,
+    /// Optional game to stop watching: (game_id, room_id)
+    pub watch_to_remove: Option<(String, String)>,
 }
 
 /// Errors that can occur during command parsing.
@@ -250,10 +424,10 @@ pub struct CommandResult {
 ///
 /// ```
 /// # use miou::commands::{Commander, CommandParseError};
-/// let commander = Commander::new();
+/// let commander = Commander::new("!miou".to_string(), "@miou:example.com".to_string());
 ///
 /// // Not a command - silent error
-/// match commander.parse("Just chatting".to_string()) {
+/// match commander.parse("Just chatting", None, false, None) {
 ///     Err(CommandParseError::NotForBot) => {
 ///         // Don't respond - not a command
 ///     }
@@ -261,7 +435,7 @@ pub struct CommandResult {
 /// }
 ///
 /// // Invalid command - send error message
-/// match commander.parse("!miou invalid_cmd".to_string()) {
+/// match commander.parse("!miou invalid_cmd", None, false, None) {
 ///     Err(CommandParseError::InvalidCommand(msg)) => {
 ///         // Send error message to user
 ///         println!("Error: {}", msg);
@@ -276,3 +450,79 @@ pub enum CommandParseError {
     /// Invalid command syntax with error message
     InvalidCommand(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmars::Phase;
+
+    #[test]
+    fn test_resolve_game_id() {
+        let context = CommandContext {
+            games_map: HashMap::new(),
+            alerts_map: HashMap::new(),
+            room_id: "!room:example.com".to_string(),
+            user_id: "@user:example.com".to_string(),
+            user_settings: UserSettings::default(),
+            room_settings: RoomSettings::default(),
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["main".to_string(), "friends".to_string()],
+            turns_map: HashMap::new(),
+        };
+
+        assert_eq!(context.resolve_game_id("game123"), "main:game123");
+        assert_eq!(
+            context.resolve_game_id("friends:game123"),
+            "friends:game123"
+        );
+    }
+
+    #[test]
+    fn test_get_prefix() {
+        let mut context = CommandContext {
+            games_map: HashMap::new(),
+            alerts_map: HashMap::new(),
+            room_id: "!room:example.com".to_string(),
+            user_id: "@user:example.com".to_string(),
+            user_settings: UserSettings::default(),
+            room_settings: RoomSettings::default(),
+            default_server: "main".to_string(),
+            default_prefix: "!miou".to_string(),
+            servers: vec!["main".to_string()],
+            turns_map: HashMap::new(),
+        };
+
+        assert_eq!(context.get_prefix(), "!miou");
+        context.room_settings.prefix = Some("!mi".to_string());
+        assert_eq!(context.get_prefix(), "!mi");
+    }
+
+    #[test]
+    fn test_suggest_game_id() {
+        let game = Game {
+            id: "game123".to_string(),
+            phase: Phase::Action,
+            spectator_id: "spectator123".to_string(),
+            spectator_url: "https://example.com/spectator".to_string(),
+            players: Vec::new(),
+            waited_players: HashSet::new(),
+            progress: None,
+        };
+        let games_map = HashMap::from([
+            ("main:game123".to_string(), game.clone()),
+            ("friends:g1".to_string(), game),
+        ]);
+
+        assert_eq!(
+            suggest_game_id(&games_map, "main:game12"),
+            Some("main:game123".to_string())
+        );
+        assert_eq!(
+            suggest_game_id(&games_map, "main:gmae123"),
+            Some("main:game123".to_string())
+        );
+        assert_eq!(suggest_game_id(&games_map, "friends:game123"), None);
+        assert_eq!(suggest_game_id(&games_map, "main:g1"), None);
+    }
+}
@@ -0,0 +1,401 @@
+//! Registry of the bot commands.
+//!
+//! This module provides the [`BotCommand`] trait, implemented by each command of
+//! the bot in its [`actions`](crate::commands::actions) module, and the
+//! [`COMMANDS`] registry through which the commands are parsed, executed and
+//! described in the help.
+//!
+//! # Adding a Command
+//!
+//! 1. Implement [`BotCommand`] next to its handler in a new actions module, with
+//!    the type of its parsed arguments
+//! 2. Add it to [`COMMANDS`], at the place it takes in the help
+
+use std::{collections::HashMap, fmt};
+
+use futures::future::BoxFuture;
+
+use crate::{
+    commands::{
+        CommandContext, CommandResult,
+        actions::{
+            AlertsCommand, GameCommand, GamesCommand, HelpCommand, PrefixCommand, RegisterCommand,
+            ServerCommand, SettingsCommand, StatsCommand, TemplateCommand, UnregisterCommand,
+            UnwatchCommand, WatchCommand,
+        },
+        command::Command,
+    },
+    utils::find_closest,
+};
+
+/// Commands of the bot, in the order of the help.
+pub static COMMANDS: &[&dyn RegisteredCommand] = &[
+    &GamesCommand,
+    &GameCommand,
+    &AlertsCommand,
+    &RegisterCommand,
+    &UnregisterCommand,
+    &WatchCommand,
+    &UnwatchCommand,
+    &SettingsCommand,
+    &ServerCommand,
+    &StatsCommand,
+    &TemplateCommand,
+    &PrefixCommand,
+    &HelpCommand,
+];
+
+/// A form of a command, shown in the help and in the usage errors.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Usage {
+    /// Arguments after the command name, e.g. `<game_id>`, empty if none
+    pub arguments: &'static str,
+    /// What the command does in this form, starting in lowercase
+    pub description: &'static str,
+}
+
+/// An argument or option of a command and its constraints, shown in the help of the command.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ArgumentHelp {
+    /// Name of the argument as written in the usages, e.g. `delay` or `--phases`
+    pub name: &'static str,
+    /// Expected value of the argument, starting in lowercase
+    pub description: &'static str,
+}
+
+/// An example of a command, shown in the help of the command.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    /// Arguments after the command name, empty if none
+    pub arguments: &'static str,
+    /// What the example does, starting in lowercase
+    pub description: &'static str,
+}
+
+/// The game ID argument, shared by the commands targeting a game.
+pub const GAME_ID: ArgumentHelp = ArgumentHelp {
+    name: "game_id",
+    description: "ID of the game, prefixed with its server name like `main:game123` when it's not the server of the room",
+};
+
+/// Errors in the arguments or options of a command.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ArgumentError {
+    /// The command needs more arguments
+    MissingArguments,
+    /// The argument follows the last argument accepted by the command
+    UnexpectedArgument(String),
+    /// The option, without the leading `--`, is not accepted by the command
+    UnknownOption(String),
+    /// The value of an argument is invalid
+    InvalidValue {
+        /// Name of the argument, e.g. `delay`
+        argument: &'static str,
+        /// Value given to the argument
+        value: String,
+        /// Why the value is invalid, e.g. `is not a number`
+        reason: &'static str,
+    },
+}
+
+/// Number of arguments and options accepted by a command.
+///
+/// The arguments are checked against the schema before the command parses them.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ArgumentSchema {
+    /// Minimum number of arguments after the command name
+    pub min_arguments: usize,
+    /// Maximum number of arguments after the command name, `None` if unlimited
+    pub max_arguments: Option<usize>,
+    /// Names of the `--name value` options, without the leading `--`
+    pub options: &'static [&'static str],
+}
+
+impl ArgumentSchema {
+    /// Schema of the commands without arguments nor options.
+    pub const NONE: ArgumentSchema = ArgumentSchema {
+        min_arguments: 0,
+        max_arguments: Some(0),
+        options: &[],
+    };
+
+    /// Checks the given arguments and options against the schema.
+    ///
+    /// # Errors
+    ///
+    /// - [`ArgumentError::MissingArguments`] if there are fewer arguments than the minimum
+    /// - [`ArgumentError::UnexpectedArgument`] with the first argument over the maximum
+    /// - [`ArgumentError::UnknownOption`] with an option which is not accepted
+    pub fn check(
+        &self,
+        arguments: &[&str],
+        options: &HashMap<String, String>,
+    ) -> Result<(), ArgumentError> {
+        if arguments.len() < self.min_arguments {
+            return Err(ArgumentError::MissingArguments);
+        }
+
+        if let Some(argument) = self.max_arguments.and_then(|max| arguments.get(max)) {
+            return Err(ArgumentError::UnexpectedArgument(argument.to_string()));
+        }
+
+        // The smallest name is reported, for the error to be the same every time
+        match options
+            .keys()
+            .filter(|name| !self.options.contains(&name.as_str()))
+            .min()
+        {
+            Some(name) => Err(ArgumentError::UnknownOption(name.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A command of the bot.
+///
+/// A command parses the words following its name, e.g. `!miou <name>`, into its
+/// [`Arguments`](BotCommand::Arguments), then executes them with its handler.
+pub trait BotCommand: Sync + 'static {
+    /// Arguments of the command, parsed from the words following its name.
+    type Arguments: fmt::Debug + Send + Sync + 'static;
+
+    /// Name of the command, e.g. `register`.
+    fn name(&self) -> &'static str;
+
+    /// Other names of the command, e.g. `reg`.
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Forms of the command, in the order of the help.
+    fn usages(&self) -> &'static [Usage];
+
+    /// Arguments and options of the command with their constraints, in the order of the usages.
+    fn arguments(&self) -> &'static [ArgumentHelp] {
+        &[]
+    }
+
+    /// Examples of the command, shown in its help.
+    fn examples(&self) -> &'static [Example];
+
+    /// Arguments and options accepted by the command.
+    fn schema(&self) -> ArgumentSchema {
+        ArgumentSchema::NONE
+    }
+
+    /// Parses the arguments of the command, already checked against its schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `arguments` - The arguments after the command name
+    /// * `options` - The values of the options by name
+    ///
+    /// # Errors
+    ///
+    /// An [`ArgumentError`] naming the invalid argument.
+    fn parse(
+        &self,
+        arguments: &[&str],
+        options: &HashMap<String, String>,
+    ) -> Result<Self::Arguments, ArgumentError>;
+
+    /// Executes the command with the arguments it parsed.
+    fn handle<'a>(
+        &self,
+        context: &'a CommandContext,
+        arguments: &'a Self::Arguments,
+    ) -> BoxFuture<'a, CommandResult>;
+}
+
+/// A command of the [`COMMANDS`] registry, whatever the type of its arguments.
+///
+/// Implemented by every [`BotCommand`].
+pub trait RegisteredCommand: Sync {
+    /// Name of the command, e.g. `register`.
+    fn name(&self) -> &'static str;
+
+    /// Other names of the command, e.g. `reg`.
+    fn aliases(&self) -> &'static [&'static str];
+
+    /// Forms of the command, in the order of the help.
+    fn usages(&self) -> &'static [Usage];
+
+    /// Arguments and options of the command with their constraints, in the order of the usages.
+    fn arguments(&self) -> &'static [ArgumentHelp];
+
+    /// Examples of the command, shown in its help.
+    fn examples(&self) -> &'static [Example];
+
+    /// Arguments and options accepted by the command.
+    fn schema(&self) -> ArgumentSchema;
+
+    /// Parses the arguments of the command, already checked against its schema,
+    /// into a [`Command`] executed by this command.
+    ///
+    /// # Errors
+    ///
+    /// An [`ArgumentError`] naming the invalid argument.
+    fn parse_command(
+        &'static self,
+        arguments: &[&str],
+        options: &HashMap<String, String>,
+    ) -> Result<Command, ArgumentError>;
+}
+
+impl<T: BotCommand> RegisteredCommand for T {
+    fn name(&self) -> &'static str {
+        BotCommand::name(self)
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        BotCommand::aliases(self)
+    }
+
+    fn usages(&self) -> &'static [Usage] {
+        BotCommand::usages(self)
+    }
+
+    fn arguments(&self) -> &'static [ArgumentHelp] {
+        BotCommand::arguments(self)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        BotCommand::examples(self)
+    }
+
+    fn schema(&self) -> ArgumentSchema {
+        BotCommand::schema(self)
+    }
+
+    fn parse_command(
+        &'static self,
+        arguments: &[&str],
+        options: &HashMap<String, String>,
+    ) -> Result<Command, ArgumentError> {
+        BotCommand::parse(self, arguments, options).map(|arguments| Command::new(self, arguments))
+    }
+}
+
+/// Returns the command of the given name or alias.
+pub fn find_command(name: &str) -> Option<&'static dyn RegisteredCommand> {
+    COMMANDS
+        .iter()
+        .find(|command| command.name() == name || command.aliases().contains(&name))
+        .copied()
+}
+
+/// Returns the name or alias of a command closest to a mistyped name.
+///
+/// # Returns
+///
+/// The closest name or alias, `None` if no command is close enough.
+pub fn suggest_command(name: &str) -> Option<&'static str> {
+    find_closest(
+        name,
+        COMMANDS.iter().flat_map(|command| {
+            std::iter::once(command.name()).chain(command.aliases().iter().copied())
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::commands::command::CommandAddress;
+
+    #[test]
+    fn test_find_command() {
+        assert_eq!(find_command("register").unwrap().name(), "register");
+        assert_eq!(find_command("reg").unwrap().name(), "register");
+        assert!(find_command("unknown").is_none());
+    }
+
+    #[test]
+    fn test_suggest_command() {
+        assert_eq!(suggest_command("registr"), Some("register"));
+        assert_eq!(suggest_command("Alert"), Some("alerts"));
+        assert_eq!(suggest_command("unrg"), Some("unreg"));
+        assert_eq!(suggest_command("dance"), None);
+    }
+
+    #[test]
+    fn test_names_are_unique() {
+        let mut names = HashSet::new();
+
+        for command in COMMANDS {
+            assert!(names.insert(command.name()), "{}", command.name());
+            for alias in command.aliases() {
+                assert!(names.insert(alias), "{}", alias);
+            }
+        }
+    }
+
+    #[test]
+    fn test_commands_have_usages() {
+        for command in COMMANDS {
+            assert!(!command.usages().is_empty(), "{}", command.name());
+        }
+    }
+
+    #[test]
+    fn test_commands_have_examples() {
+        for command in COMMANDS {
+            assert!(!command.examples().is_empty(), "{}", command.name());
+        }
+    }
+
+    #[test]
+    fn test_examples_are_valid() {
+        let address = CommandAddress {
+            prefix: "!miou",
+            user_id: "@miou:example.com",
+            mention: None,
+            is_direct: false,
+        };
+
+        for command in COMMANDS {
+            for example in command.examples() {
+                let body = format!("!miou {} {}", command.name(), example.arguments);
+                let result = Command::parse(&address, &body);
+                assert!(
+                    result.is_ok_and(|parsed| parsed.name() == command.name()),
+                    "{}",
+                    body
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_schema_check() {
+        let schema = ArgumentSchema {
+            min_arguments: 1,
+            max_arguments: Some(2),
+            options: &["phases"],
+        };
+        let phases = HashMap::from([("phases".to_string(), "action".to_string())]);
+        let players = HashMap::from([("players".to_string(), "Bob".to_string())]);
+
+        assert_eq!(schema.check(&["game123"], &HashMap::new()), Ok(()));
+        assert_eq!(schema.check(&["game123", "Alice"], &phases), Ok(()));
+        assert_eq!(
+            schema.check(&[], &HashMap::new()),
+            Err(ArgumentError::MissingArguments)
+        );
+        assert_eq!(
+            schema.check(&["game123", "Alice", "60", "30"], &HashMap::new()),
+            Err(ArgumentError::UnexpectedArgument("60".to_string()))
+        );
+        assert_eq!(
+            schema.check(&["game123"], &players),
+            Err(ArgumentError::UnknownOption("players".to_string()))
+        );
+        assert_eq!(ArgumentSchema::NONE.check(&[], &HashMap::new()), Ok(()));
+        assert_eq!(
+            ArgumentSchema::NONE.check(&["extra"], &HashMap::new()),
+            Err(ArgumentError::UnexpectedArgument("extra".to_string()))
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! Tokenizer of the bot commands.
+//!
+//! This module provides the [`tokenize`] function which splits the words of a
+//! message following the address of the command, e.g. `!miou`, into its arguments
+//! and its `--name value` options, before they are parsed into a
+//! [`Command`](crate::commands::command::Command).
+//!
+//! # Syntax
+//!
+//! - Words are separated by whitespace
+//! - Text between double quotes (`"` or `“ ”`) is a single word, whitespace included
+//! - A backslash escapes the next character, e.g. `\"` or `\ `
+//! - A word starting with an unquoted `--` is an option, followed by its value
+//!
+//! Positions in the errors are the indexes of characters in the message, not bytes.
+
+use std::collections::HashMap;
+
+/// Errors that can occur when tokenizing a command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SyntaxError {
+    /// The quote at the given position is not closed
+    UnclosedQuote(usize),
+    /// The backslash at the given position ends the message
+    TrailingEscape(usize),
+    /// The option at the given position is not followed by a value
+    MissingOptionValue {
+        /// Option name, without the leading `--`
+        name: String,
+        /// Position of the option
+        position: usize,
+    },
+    /// The option at the given position was already given
+    DuplicateOption {
+        /// Option name, without the leading `--`
+        name: String,
+        /// Position of the second occurrence of the option
+        position: usize,
+    },
+}
+
+/// A command split into words.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TokenizedCommand {
+    /// Words of the command, in order, options excluded
+    pub arguments: Vec<String>,
+    /// Values of the options by name, without the leading `--`
+    pub options: HashMap<String, String>,
+}
+
+/// Word of a message.
+#[derive(Debug)]
+struct Token {
+    /// Text of the word, quotes and escapes removed
+    text: String,
+    /// Position of the first character of the word
+    position: usize,
+    /// Whether the word starts with an unquoted `--`
+    is_option: bool,
+}
+
+/// Splits the words of a message into a command.
+///
+/// # Arguments
+///
+/// * `body` - The message text
+/// * `start` - Position of the first character of the command, after its address
+///
+/// # Errors
+///
+/// - [`SyntaxError::UnclosedQuote`] if a quote is not closed
+/// - [`SyntaxError::TrailingEscape`] if the message ends with a backslash
+/// - [`SyntaxError::MissingOptionValue`] if an option is the last word or followed by another option
+/// - [`SyntaxError::DuplicateOption`] if an option is given twice
+///
+/// # Examples
+///
+/// ```
+/// # use miou::commands::tokenizer::tokenize;
+/// let command = tokenize(r#"!miou register game123 "Alice Smith" 60 --phases action"#, 5).unwrap();
+/// assert_eq!(command.arguments, vec!["register", "game123", "Alice Smith", "60"]);
+/// assert_eq!(command.options["phases"], "action");
+/// ```
+pub fn tokenize(body: &str, start: usize) -> Result<TokenizedCommand, SyntaxError> {
+    let mut tokens = split(body, start)?.into_iter().peekable();
+    let mut command = TokenizedCommand::default();
+
+    while let Some(token) = tokens.next() {
+        if !token.is_option {
+            command.arguments.push(token.text);
+            continue;
+        }
+
+        let name = token.text[2..].to_owned();
+        let value = tokens.next_if(|value| !value.is_option).ok_or_else(|| {
+            SyntaxError::MissingOptionValue {
+                name: name.clone(),
+                position: token.position,
+            }
+        })?;
+        if command.options.contains_key(&name) {
+            return Err(SyntaxError::DuplicateOption {
+                name,
+                position: token.position,
+            });
+        }
+        command.options.insert(name, value.text);
+    }
+
+    Ok(command)
+}
+
+/// Splits a message into words from the given position, removing their quotes and escapes.
+fn split(body: &str, start: usize) -> Result<Vec<Token>, SyntaxError> {
+    let mut tokens = Vec::new();
+    let mut chars = body.chars().enumerate().skip(start).peekable();
+
+    while let Some(&(position, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let mut text = String::new();
+        // Number of characters at the start of the word which are neither quoted nor escaped
+        let mut plain_length = 0;
+        // Whether no character of the word was quoted or escaped yet
+        let mut is_plain = true;
+        // Position of the opening quote and closing quote, inside quotes
+        let mut quote: Option<(usize, char)> = None;
+
+        while let Some((position, c)) =
+            chars.next_if(|&(_, c)| quote.is_some() || !c.is_whitespace())
+        {
+            match (quote, c) {
+                (_, '\\') => match chars.next() {
+                    Some((_, escaped)) => {
+                        is_plain = false;
+                        text.push(escaped);
+                    }
+                    None => return Err(SyntaxError::TrailingEscape(position)),
+                },
+                (None, '"') => {
+                    is_plain = false;
+                    quote = Some((position, '"'));
+                }
+                (None, '“') => {
+                    is_plain = false;
+                    quote = Some((position, '”'));
+                }
+                (Some((_, closing)), c) if c == closing => quote = None,
+                (_, c) => {
+                    if is_plain {
+                        plain_length += 1;
+                    }
+                    text.push(c);
+                }
+            }
+        }
+
+        if let Some((position, _)) = quote {
+            return Err(SyntaxError::UnclosedQuote(position));
+        }
+
+        let is_option = plain_length > 2 && text.starts_with("--");
+        tokens.push(Token {
+            text,
+            position,
+            is_option,
+        });
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tokenizes a message addressed with the `!miou` prefix.
+    fn tokenize(body: &str) -> Result<TokenizedCommand, SyntaxError> {
+        super::tokenize(body, "!miou".len())
+    }
+
+    #[test]
+    fn test_tokenize_words() {
+        let command = tokenize("!miou   register game123\tAlice 60 ").unwrap();
+
+        assert_eq!(
+            command.arguments,
+            vec!["register", "game123", "Alice", "60"]
+        );
+        assert!(command.options.is_empty());
+    }
+
+    #[test]
+    fn test_tokenize_without_arguments() {
+        let command = tokenize("!miou").unwrap();
+
+        assert!(command.arguments.is_empty());
+    }
+
+    #[test]
+    fn test_tokenize_after_mention() {
+        let start = "@miou:example.com:".len();
+
+        let command = super::tokenize("@miou:example.com: game game123", start).unwrap();
+        assert_eq!(command.arguments, vec!["game", "game123"]);
+
+        // Positions are counted from the start of the message
+        assert_eq!(
+            super::tokenize("@miou:example.com: \"Alice Smith\" \"Bob", start),
+            Err(SyntaxError::UnclosedQuote(33))
+        );
+    }
+
+    #[test]
+    fn test_tokenize_quoted_words() {
+        let command = tokenize(r#"!miou "Alice Smith" “Bob  Jones” "" a"b c"d"#).unwrap();
+
+        assert_eq!(
+            command.arguments,
+            vec!["Alice Smith", "Bob  Jones", "", "ab cd"]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_escapes() {
+        let command = tokenize(r#"!miou Alice\ Smith "say \"hi\"" \\ \--phases"#).unwrap();
+
+        assert_eq!(
+            command.arguments,
+            vec!["Alice Smith", "say \"hi\"", "\\", "--phases"]
+        );
+        assert!(command.options.is_empty());
+    }
+
+    #[test]
+    fn test_tokenize_options() {
+        let command =
+            tokenize(r#"!miou register --phases action,production game123 "--now" -- 60"#).unwrap();
+
+        assert_eq!(
+            command.arguments,
+            vec!["register", "game123", "--now", "--", "60"]
+        );
+        assert_eq!(
+            command.options,
+            HashMap::from([("phases".to_string(), "action,production".to_string())])
+        );
+    }
+
+    #[test]
+    fn test_tokenize_quoted_option_value() {
+        let command = tokenize(r#"!miou --name "Alice Smith""#).unwrap();
+
+        assert_eq!(command.options["name"], "Alice Smith");
+    }
+
+    #[test]
+    fn test_tokenize_unclosed_quote() {
+        assert_eq!(
+            tokenize(r#"!miou register "Alice Smith 60"#),
+            Err(SyntaxError::UnclosedQuote(15))
+        );
+        assert_eq!(tokenize("!miou “Alice"), Err(SyntaxError::UnclosedQuote(6)));
+    }
+
+    #[test]
+    fn test_tokenize_trailing_escape() {
+        assert_eq!(
+            tokenize(r"!miou Alice\"),
+            Err(SyntaxError::TrailingEscape(11))
+        );
+    }
+
+    #[test]
+    fn test_tokenize_missing_option_value() {
+        let expected_error = SyntaxError::MissingOptionValue {
+            name: "phases".to_string(),
+            position: 9,
+        };
+
+        assert_eq!(tokenize("!miou 60 --phases"), Err(expected_error.clone()));
+        assert_eq!(
+            tokenize("!miou 60 --phases --repeat 30"),
+            Err(expected_error)
+        );
+    }
+
+    #[test]
+    fn test_tokenize_duplicate_option() {
+        assert_eq!(
+            tokenize("!miou --phases action --phases research"),
+            Err(SyntaxError::DuplicateOption {
+                name: "phases".to_string(),
+                position: 22,
+            })
+        );
+    }
+
+    #[test]
+    fn test_tokenize_positions_count_characters() {
+        // The smart quotes take 3 bytes each
+        assert_eq!(
+            tokenize("!miou “Bob” \"Alice"),
+            Err(SyntaxError::UnclosedQuote(12))
+        );
+    }
+}
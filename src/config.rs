@@ -1,24 +1,45 @@
 //! Configuration file structures for the Miou bot.
 //!
 //! This module defines the configuration file format using YAML. The configuration
-//! is split into two main sections: TMars server settings and Matrix account settings.
+//! is split into two main sections: TMars servers settings and Matrix account settings.
 //!
 //! # Configuration File Format
 //!
 //! The bot uses a YAML configuration file with the following structure:
 //!
 //! ```yaml
-//! # TMars Server Configuration
+//! # TMars Servers Configuration
 //! tmars:
-//!   # Base URL of the Terraforming Mars server
-//!   url: "https://terraforming-mars.herokuapp.com"
-//!
-//!   # Secret server ID for API authentication
-//!   server_id: "abc123xyz"
-//!
 //!   # Polling interval in seconds (how often to check for game updates)
 //!   polling_interval: 120
 //!
+//!   # Interval in seconds of the cheap checks for changed games between two polls (optional)
+//!   check_interval: 15
+//!
+//!   # Limits of the requests to the servers (optional, defaults shown)
+//!   requests:
+//!     # Maximum number of requests in flight to each server
+//!     max_in_flight: 8
+//!     # Timeout of a request in seconds
+//!     timeout: 30
+//!     # Maximum number of requests per second to each server
+//!     per_second: 10
+//!
+//!   # Number of polls a game can be missing before its alerts, watches and ongoing turns are removed (optional)
+//!   alerts_grace_polls: 3
+//!
+//!   # Server used by the rooms which didn't choose one (optional)
+//!   default_server: "main"
+//!
+//!   # Terraforming Mars servers, indexed by name
+//!   servers:
+//!     main:
+//!       # Base URL of the Terraforming Mars server
+//!       url: "https://terraforming-mars.herokuapp.com"
+//!
+//!       # Secret server ID for API authentication
+//!       server_id: "abc123xyz"
+//!
 //! # Matrix Account Configuration
 //! matrix:
 //!   # Fully qualified Matrix user ID for the bot account
@@ -30,6 +51,9 @@
 //!
 //!   # E2EE recovery passphrase
 //!   passphrase: "recovery-passphrase"
+//!
+//!   # Prefix of the commands in the rooms which didn't choose one (optional)
+//!   command_prefix: "!miou"
 //! ```
 //!
 //! # Environment Variables
@@ -38,35 +62,56 @@
 //! The structure follows the nested path in the YAML file, separated by double underscores (`__`).
 //!
 //! Examples:
-//! - `MIOU_TMARS__URL` overrides `tmars.url`
-//! - `MIOU_TMARS__SERVER_ID` overrides `tmars.server_id`
+//! - `MIOU_TMARS__SERVERS__MAIN__URL` overrides `tmars.servers.main.url`
+//! - `MIOU_TMARS__SERVERS__MAIN__SERVER_ID` overrides `tmars.servers.main.server_id`
 //! - `MIOU_MATRIX__USER_ID` overrides `matrix.user_id`
 //! - `MIOU_MATRIX__PASSWORD` overrides `matrix.password`
 //!
 //! ```bash
-//! export MIOU_TMARS__URL="https://terraforming-mars.herokuapp.com"
-//! export MIOU_TMARS__SERVER_ID="your-server-id"
+//! export MIOU_TMARS__SERVERS__MAIN__URL="https://terraforming-mars.herokuapp.com"
+//! export MIOU_TMARS__SERVERS__MAIN__SERVER_ID="your-server-id"
 //! export MIOU_MATRIX__USER_ID="@miou:matrix.org"
 //! export MIOU_MATRIX__PASSWORD="your-password"
 //! export MIOU_MATRIX__PASSPHRASE="your-passphrase"
+//! export MIOU_MATRIX__COMMAND_PREFIX="!tm"
 //! miou --config config.yaml --data ./data
 //! ```
+//!
+//! # Legacy Server Keys
+//!
+//! The deprecated `tmars.url` and `tmars.server_id` keys of the single server
+//! configurations, and their `MIOU_TMARS__URL` and `MIOU_TMARS__SERVER_ID`
+//! environment variables, are still accepted. They configure the
+//! [`LEGACY_SERVER_NAME`] server, which becomes the default server.
+
+use std::collections::BTreeMap;
 
 use figment::{
     Figment,
     providers::{Env, Format, Yaml},
 };
+use log::warn;
 use serde::Deserialize;
 
+/// Number of polls a game can be missing before its alerts are removed, by default.
+pub const DEFAULT_ALERTS_GRACE_POLLS: u32 = 3;
+
+/// Prefix of the commands, by default.
+pub const DEFAULT_COMMAND_PREFIX: &str = "!miou";
+
+/// Name of the server configured with the deprecated `tmars.url` and
+/// `tmars.server_id` keys.
+pub const LEGACY_SERVER_NAME: &str = "main";
+
 /// Root configuration structure for the Miou bot.
 ///
 /// This structure represents the complete bot configuration, containing both
-/// TMars server settings and Matrix account credentials.
+/// TMars servers settings and Matrix account credentials.
 ///
 /// # Structure
 ///
 /// The configuration is divided into two sections:
-/// - [`TMars`] - Terraforming Mars server connection settings
+/// - [`TMars`] - Terraforming Mars servers connection settings
 /// - [`Matrix`] - Matrix account credentials and settings
 ///
 /// # Examples
@@ -78,14 +123,14 @@ use serde::Deserialize;
 /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
 /// let config = Config::load("config.yaml")?;
 ///
-/// println!("TMars URL: {}", config.tmars.url);
+/// println!("TMars default server: {}", config.tmars.get_default_server());
 /// println!("Matrix User: {}", config.matrix.user_id);
 /// # Ok(())
 /// # }
 /// ```
 #[derive(Deserialize, Debug)]
 pub struct Config {
-    /// TMars server configuration
+    /// TMars servers configuration
     pub tmars: TMars,
     /// Matrix account configuration
     pub matrix: Matrix,
@@ -109,18 +154,31 @@ impl Config {
     /// - The YAML is invalid
     /// - Required fields are missing
     /// - Types are incorrect
+    /// - No server is configured, a server name contains a colon, or the default
+    ///   server isn't one of them
+    /// - The check interval or a request limit is zero
+    /// - The command prefix is empty or contains whitespace
+    /// - Only one of the legacy `tmars.url` and `tmars.server_id` keys is set, or
+    ///   they are set along with a [`LEGACY_SERVER_NAME`] server
     ///
     /// # Environment Variable Format
     ///
     /// Environment variables use the `MIOU_` prefix followed by the configuration
     /// path with sections separated by double underscores (`__`):
     ///
-    /// - `MIOU_TMARS__URL` → `tmars.url`
-    /// - `MIOU_TMARS__SERVER_ID` → `tmars.server_id`
     /// - `MIOU_TMARS__POLLING_INTERVAL` → `tmars.polling_interval`
+    /// - `MIOU_TMARS__CHECK_INTERVAL` → `tmars.check_interval`
+    /// - `MIOU_TMARS__REQUESTS__MAX_IN_FLIGHT` → `tmars.requests.max_in_flight`
+    /// - `MIOU_TMARS__REQUESTS__TIMEOUT` → `tmars.requests.timeout`
+    /// - `MIOU_TMARS__REQUESTS__PER_SECOND` → `tmars.requests.per_second`
+    /// - `MIOU_TMARS__ALERTS_GRACE_POLLS` → `tmars.alerts_grace_polls`
+    /// - `MIOU_TMARS__DEFAULT_SERVER` → `tmars.default_server`
+    /// - `MIOU_TMARS__SERVERS__<NAME>__URL` → `tmars.servers.<name>.url`
+    /// - `MIOU_TMARS__SERVERS__<NAME>__SERVER_ID` → `tmars.servers.<name>.server_id`
     /// - `MIOU_MATRIX__USER_ID` → `matrix.user_id`
     /// - `MIOU_MATRIX__PASSWORD` → `matrix.password`
     /// - `MIOU_MATRIX__PASSPHRASE` → `matrix.passphrase`
+    /// - `MIOU_MATRIX__COMMAND_PREFIX` → `matrix.command_prefix`
     ///
     /// # Examples
     ///
@@ -145,33 +203,280 @@ impl Config {
     /// ```
     #[allow(clippy::result_large_err)]
     pub fn load(path: &str) -> Result<Self, figment::Error> {
-        Figment::new()
+        let figment = Figment::new()
             .merge(Yaml::file(path))
-            .merge(Env::prefixed("MIOU_").split("__"))
-            .extract()
+            .merge(Env::prefixed("MIOU_").split("__"));
+        let mut config: Config = figment.extract()?;
+
+        let legacy_server: LegacyTMarsServer = figment.extract_inner("tmars")?;
+        match (legacy_server.url, legacy_server.server_id) {
+            (None, None) => {}
+            (Some(url), Some(server_id)) => {
+                if config.tmars.servers.contains_key(LEGACY_SERVER_NAME) {
+                    return Err(figment::Error::from(format!(
+                        "tmars url and server_id conflict with the tmars server {}",
+                        LEGACY_SERVER_NAME
+                    )));
+                }
+                warn!(
+                    "tmars url and server_id are deprecated, configure them as tmars.servers.{} instead",
+                    LEGACY_SERVER_NAME
+                );
+                config.tmars.servers.insert(
+                    LEGACY_SERVER_NAME.to_owned(),
+                    TMarsServer { url, server_id },
+                );
+                config
+                    .tmars
+                    .default_server
+                    .get_or_insert_with(|| LEGACY_SERVER_NAME.to_owned());
+            }
+            _ => {
+                return Err(figment::Error::from(
+                    "tmars url and server_id must be set together".to_owned(),
+                ));
+            }
+        }
+
+        if config.tmars.servers.is_empty() {
+            return Err(figment::Error::from(
+                "at least one tmars server must be configured".to_owned(),
+            ));
+        }
+        if let Some(server_name) = config.tmars.servers.keys().find(|name| name.contains(':')) {
+            return Err(figment::Error::from(format!(
+                "invalid tmars server name {}, it must not contain a colon",
+                server_name
+            )));
+        }
+        if let Some(default_server) = &config.tmars.default_server
+            && !config.tmars.servers.contains_key(default_server)
+        {
+            return Err(figment::Error::from(format!(
+                "unknown tmars default server {}",
+                default_server
+            )));
+        }
+        if config.tmars.check_interval == Some(0) {
+            return Err(figment::Error::from(
+                "tmars check interval must be greater than zero".to_owned(),
+            ));
+        }
+        let requests = &config.tmars.requests;
+        if requests.max_in_flight == 0 || requests.timeout == 0 || requests.per_second == 0 {
+            return Err(figment::Error::from(
+                "tmars request limits must be greater than zero".to_owned(),
+            ));
+        }
+        let command_prefix = config.matrix.get_command_prefix();
+        if command_prefix.is_empty() || command_prefix.contains(char::is_whitespace) {
+            return Err(figment::Error::from(format!(
+                "invalid matrix command prefix {:?}, it must not be empty nor contain whitespace",
+                command_prefix
+            )));
+        }
+
+        Ok(config)
     }
 }
 
-/// Terraforming Mars server configuration.
+/// Terraforming Mars servers configuration.
 ///
-/// Contains all settings required to connect to and poll a TMars server.
+/// Contains all settings required to connect to and poll the TMars servers.
 ///
 /// # YAML Section
 ///
 /// ```yaml
 /// tmars:
-///   url: "https://terraforming-mars.herokuapp.com"
-///   server_id: "your-server-id"
 ///   polling_interval: 120
+///   check_interval: 15
+///   requests:
+///     max_in_flight: 8
+///     timeout: 30
+///     per_second: 10
+///   alerts_grace_polls: 3
+///   default_server: "main"
+///   servers:
+///     main:
+///       url: "https://terraforming-mars.herokuapp.com"
+///       server_id: "your-server-id"
+///     friends:
+///       url: "https://tmars.example.com"
+///       server_id: "other-server-id"
 /// ```
 ///
 /// # Environment Variables
 ///
-/// - `MIOU_TMARS__URL`
-/// - `MIOU_TMARS__SERVER_ID`
 /// - `MIOU_TMARS__POLLING_INTERVAL`
+/// - `MIOU_TMARS__CHECK_INTERVAL`
+/// - `MIOU_TMARS__REQUESTS__<LIMIT>`
+/// - `MIOU_TMARS__ALERTS_GRACE_POLLS`
+/// - `MIOU_TMARS__DEFAULT_SERVER`
+/// - `MIOU_TMARS__SERVERS__<NAME>__URL`
+/// - `MIOU_TMARS__SERVERS__<NAME>__SERVER_ID`
 #[derive(Deserialize, Debug)]
 pub struct TMars {
+    /// Polling interval in seconds.
+    ///
+    /// How frequently the bot checks the TMars servers for game updates.
+    ///
+    /// # Environment Variable
+    ///
+    /// Can be overridden with `MIOU_TMARS__POLLING_INTERVAL`
+    pub polling_interval: u64,
+
+    /// Interval in seconds of the checks for changed games between two polls.
+    ///
+    /// A check only asks the servers whether the known games changed since the
+    /// previous poll, which is much cheaper than a poll. New games are still
+    /// discovered at the polling interval. Checks are disabled when missing.
+    ///
+    /// # Environment Variable
+    ///
+    /// Can be overridden with `MIOU_TMARS__CHECK_INTERVAL`
+    #[serde(default)]
+    pub check_interval: Option<u64>,
+
+    /// Limits of the requests to each server.
+    ///
+    /// Defaults to the [`TMarsRequests`] defaults when missing.
+    #[serde(default)]
+    pub requests: TMarsRequests,
+
+    /// Number of consecutive polls a game can be missing before its alerts,
+    /// watches and ongoing turns are removed.
+    ///
+    /// A game may be missing from a poll because of a hiccup of its server, its
+    /// alerts, watches and ongoing turns are kept meanwhile. Those of an ended game are removed
+    /// right away.
+    /// Defaults to [`DEFAULT_ALERTS_GRACE_POLLS`] when missing.
+    ///
+    /// # Environment Variable
+    ///
+    /// Can be overridden with `MIOU_TMARS__ALERTS_GRACE_POLLS`
+    #[serde(default)]
+    pub alerts_grace_polls: Option<u32>,
+
+    /// Name of the server used by the rooms which didn't choose one.
+    ///
+    /// Game IDs without a server name in commands refer to the games of this server.
+    /// Defaults to the first server in alphabetical order.
+    ///
+    /// # Environment Variable
+    ///
+    /// Can be overridden with `MIOU_TMARS__DEFAULT_SERVER`
+    #[serde(default)]
+    pub default_server: Option<String>,
+
+    /// Terraforming Mars servers, indexed by name.
+    ///
+    /// The name of a server namespaces the IDs of its games, e.g. `main:g123`, so
+    /// it must not contain a colon.
+    /// Holds the [`LEGACY_SERVER_NAME`] server of the deprecated `tmars.url` and
+    /// `tmars.server_id` keys once loaded.
+    #[serde(default)]
+    pub servers: BTreeMap<String, TMarsServer>,
+}
+
+/// Deprecated keys of the single TMars server configurations.
+///
+/// # Environment Variables
+///
+/// - `MIOU_TMARS__URL`
+/// - `MIOU_TMARS__SERVER_ID`
+#[derive(Deserialize, Debug)]
+struct LegacyTMarsServer {
+    /// Base URL of the Terraforming Mars server.
+    #[serde(default)]
+    url: Option<String>,
+    /// Secret server ID for API authentication.
+    #[serde(default)]
+    server_id: Option<String>,
+}
+
+impl TMars {
+    /// Returns the name of the server used by the rooms which didn't choose one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no server is configured, which [`Config::load`] rejects.
+    pub fn get_default_server(&self) -> &str {
+        match &self.default_server {
+            Some(default_server) => default_server,
+            None => self
+                .servers
+                .keys()
+                .next()
+                .expect("at least one tmars server is configured"),
+        }
+    }
+
+    /// Returns the number of consecutive polls a game can be missing before its
+    /// alerts and watches are removed.
+    pub fn get_alerts_grace_polls(&self) -> u32 {
+        self.alerts_grace_polls
+            .unwrap_or(DEFAULT_ALERTS_GRACE_POLLS)
+    }
+}
+
+/// Limits of the requests to a Terraforming Mars server.
+///
+/// They keep a slow or overloaded server from stalling the synchronization, and
+/// the synchronization from flooding the server.
+///
+/// # YAML Section
+///
+/// ```yaml
+/// requests:
+///   max_in_flight: 8
+///   timeout: 30
+///   per_second: 10
+/// ```
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct TMarsRequests {
+    /// Maximum number of requests in flight to a server, defaults to 8.
+    ///
+    /// # Environment Variable
+    ///
+    /// Can be overridden with `MIOU_TMARS__REQUESTS__MAX_IN_FLIGHT`
+    pub max_in_flight: usize,
+
+    /// Timeout of a request in seconds, defaults to 30.
+    ///
+    /// # Environment Variable
+    ///
+    /// Can be overridden with `MIOU_TMARS__REQUESTS__TIMEOUT`
+    pub timeout: u64,
+
+    /// Maximum number of requests started per second to a server, defaults to 10.
+    ///
+    /// # Environment Variable
+    ///
+    /// Can be overridden with `MIOU_TMARS__REQUESTS__PER_SECOND`
+    pub per_second: u32,
+}
+
+impl Default for TMarsRequests {
+    fn default() -> Self {
+        TMarsRequests {
+            max_in_flight: 8,
+            timeout: 30,
+            per_second: 10,
+        }
+    }
+}
+
+/// Connection settings of a Terraforming Mars server.
+///
+/// # YAML Section
+///
+/// ```yaml
+/// url: "https://terraforming-mars.herokuapp.com"
+/// server_id: "your-server-id"
+/// ```
+#[derive(Deserialize, Debug)]
+pub struct TMarsServer {
     /// Base URL of the Terraforming Mars server.
     ///
     /// # Examples
@@ -181,7 +486,7 @@ pub struct TMars {
     ///
     /// # Environment Variable
     ///
-    /// Can be overridden with `MIOU_TMARS__URL`
+    /// Can be overridden with `MIOU_TMARS__SERVERS__<NAME>__URL`
     pub url: String,
 
     /// Secret server ID for API authentication.
@@ -191,17 +496,8 @@ pub struct TMars {
     ///
     /// # Environment Variable
     ///
-    /// Can be overridden with `MIOU_TMARS__SERVER_ID`
+    /// Can be overridden with `MIOU_TMARS__SERVERS__<NAME>__SERVER_ID`
     pub server_id: String,
-
-    /// Polling interval in seconds.
-    ///
-    /// How frequently the bot checks the TMars server for game updates.
-    ///
-    /// # Environment Variable
-    ///
-    /// Can be overridden with `MIOU_TMARS__POLLING_INTERVAL`
-    pub polling_interval: u64,
 }
 
 /// Matrix account configuration.
@@ -215,6 +511,7 @@ pub struct TMars {
 ///   user_id: "@miou:matrix.org"
 ///   password: "your-password"
 ///   passphrase: "your-recovery-passphrase"
+///   command_prefix: "!miou"
 /// ```
 ///
 /// # Environment Variables
@@ -222,6 +519,7 @@ pub struct TMars {
 /// - `MIOU_MATRIX__USER_ID`
 /// - `MIOU_MATRIX__PASSWORD`
 /// - `MIOU_MATRIX__PASSPHRASE`
+/// - `MIOU_MATRIX__COMMAND_PREFIX`
 #[derive(Deserialize, Debug)]
 pub struct Matrix {
     /// Fully qualified Matrix user ID.
@@ -257,6 +555,27 @@ pub struct Matrix {
     ///
     /// Can be overridden with `MIOU_MATRIX__PASSPHRASE`
     pub passphrase: String,
+
+    /// Prefix of the commands in the rooms which didn't choose one.
+    ///
+    /// Lets two bots share a room, or the users shorten the commands. The commands
+    /// starting with a mention of the bot are accepted whatever the prefix.
+    /// Defaults to [`DEFAULT_COMMAND_PREFIX`] when missing.
+    ///
+    /// # Environment Variable
+    ///
+    /// Can be overridden with `MIOU_MATRIX__COMMAND_PREFIX`
+    #[serde(default)]
+    pub command_prefix: Option<String>,
+}
+
+impl Matrix {
+    /// Returns the prefix of the commands in the rooms which didn't choose one.
+    pub fn get_command_prefix(&self) -> &str {
+        self.command_prefix
+            .as_deref()
+            .unwrap_or(DEFAULT_COMMAND_PREFIX)
+    }
 }
 
 #[cfg(test)]
@@ -270,12 +589,21 @@ mod tests {
     // Helper to clean up all MIOU_ env vars before each test
     fn cleanup_env_vars() {
         let vars_to_clean = [
+            "MIOU_TMARS__SERVERS__MAIN__URL",
+            "MIOU_TMARS__SERVERS__MAIN__SERVER_ID",
+            "MIOU_TMARS__POLLING_INTERVAL",
+            "MIOU_TMARS__CHECK_INTERVAL",
+            "MIOU_TMARS__REQUESTS__MAX_IN_FLIGHT",
+            "MIOU_TMARS__REQUESTS__TIMEOUT",
+            "MIOU_TMARS__REQUESTS__PER_SECOND",
+            "MIOU_TMARS__ALERTS_GRACE_POLLS",
+            "MIOU_TMARS__DEFAULT_SERVER",
             "MIOU_TMARS__URL",
             "MIOU_TMARS__SERVER_ID",
-            "MIOU_TMARS__POLLING_INTERVAL",
             "MIOU_MATRIX__USER_ID",
             "MIOU_MATRIX__PASSWORD",
             "MIOU_MATRIX__PASSPHRASE",
+            "MIOU_MATRIX__COMMAND_PREFIX",
         ];
 
         unsafe {
@@ -295,9 +623,11 @@ mod tests {
 
         let yaml_content = r#"
 tmars:
-  url: "http://localhost:9090"
-  server_id: "test123"
   polling_interval: 60
+  servers:
+    main:
+      url: "http://localhost:9090"
+      server_id: "test123"
 
 matrix:
   user_id: "@bot:matrix.org"
@@ -309,9 +639,11 @@ matrix:
 
         let config = Config::load(config_path.to_str().unwrap()).unwrap();
 
-        assert_eq!(config.tmars.url, "http://localhost:9090");
-        assert_eq!(config.tmars.server_id, "test123");
+        assert_eq!(config.tmars.servers["main"].url, "http://localhost:9090");
+        assert_eq!(config.tmars.servers["main"].server_id, "test123");
         assert_eq!(config.tmars.polling_interval, 60);
+        assert_eq!(config.tmars.check_interval, None);
+        assert_eq!(config.tmars.requests, TMarsRequests::default());
         assert_eq!(config.matrix.user_id, "@bot:matrix.org");
         assert_eq!(config.matrix.password, "pass123");
         assert_eq!(config.matrix.passphrase, "phrase123");
@@ -319,6 +651,174 @@ matrix:
         cleanup_env_vars();
     }
 
+    #[test]
+    #[serial]
+    fn test_requests() {
+        cleanup_env_vars();
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+
+        let yaml_content = r#"
+tmars:
+  polling_interval: 60
+  requests:
+    max_in_flight: 2
+  servers:
+    main:
+      url: "http://localhost:9090"
+      server_id: "test123"
+
+matrix:
+  user_id: "@bot:matrix.org"
+  password: "pass123"
+  passphrase: "phrase123"
+"#;
+        fs::write(&config_path, yaml_content).unwrap();
+
+        unsafe {
+            env::set_var("MIOU_TMARS__REQUESTS__TIMEOUT", "5");
+        }
+
+        // The missing limits keep their default
+        let config = Config::load(config_path.to_str().unwrap()).unwrap();
+        assert_eq!(
+            config.tmars.requests,
+            TMarsRequests {
+                max_in_flight: 2,
+                timeout: 5,
+                per_second: 10,
+            }
+        );
+
+        unsafe {
+            env::set_var("MIOU_TMARS__REQUESTS__PER_SECOND", "0");
+        }
+        assert!(Config::load(config_path.to_str().unwrap()).is_err());
+
+        cleanup_env_vars();
+    }
+
+    #[test]
+    #[serial]
+    fn test_check_interval() {
+        cleanup_env_vars();
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+
+        let yaml_content = r#"
+tmars:
+  polling_interval: 60
+  check_interval: 10
+  servers:
+    main:
+      url: "http://localhost:9090"
+      server_id: "test123"
+
+matrix:
+  user_id: "@bot:matrix.org"
+  password: "pass123"
+  passphrase: "phrase123"
+"#;
+        fs::write(&config_path, yaml_content).unwrap();
+
+        let config = Config::load(config_path.to_str().unwrap()).unwrap();
+        assert_eq!(config.tmars.check_interval, Some(10));
+
+        // A zero interval would check continuously
+        unsafe {
+            env::set_var("MIOU_TMARS__CHECK_INTERVAL", "0");
+        }
+        assert!(Config::load(config_path.to_str().unwrap()).is_err());
+
+        cleanup_env_vars();
+    }
+
+    #[test]
+    #[serial]
+    fn test_alerts_grace_polls() {
+        cleanup_env_vars();
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+
+        let yaml_content = r#"
+tmars:
+  polling_interval: 60
+  servers:
+    main:
+      url: "http://localhost:9090"
+      server_id: "test123"
+
+matrix:
+  user_id: "@bot:matrix.org"
+  password: "pass123"
+  passphrase: "phrase123"
+"#;
+        fs::write(&config_path, yaml_content).unwrap();
+
+        let config = Config::load(config_path.to_str().unwrap()).unwrap();
+        assert_eq!(
+            config.tmars.get_alerts_grace_polls(),
+            DEFAULT_ALERTS_GRACE_POLLS
+        );
+
+        // Zero removes the alerts at the first poll missing the game
+        unsafe {
+            env::set_var("MIOU_TMARS__ALERTS_GRACE_POLLS", "0");
+        }
+        let config = Config::load(config_path.to_str().unwrap()).unwrap();
+        assert_eq!(config.tmars.get_alerts_grace_polls(), 0);
+
+        cleanup_env_vars();
+    }
+
+    #[test]
+    #[serial]
+    fn test_command_prefix() {
+        cleanup_env_vars();
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+
+        let yaml_content = r#"
+tmars:
+  polling_interval: 60
+  servers:
+    main:
+      url: "http://localhost:9090"
+      server_id: "test123"
+
+matrix:
+  user_id: "@bot:matrix.org"
+  password: "pass123"
+  passphrase: "phrase123"
+"#;
+        fs::write(&config_path, yaml_content).unwrap();
+
+        let config = Config::load(config_path.to_str().unwrap()).unwrap();
+        assert_eq!(config.matrix.get_command_prefix(), DEFAULT_COMMAND_PREFIX);
+
+        unsafe {
+            env::set_var("MIOU_MATRIX__COMMAND_PREFIX", "!tm");
+        }
+        let config = Config::load(config_path.to_str().unwrap()).unwrap();
+        assert_eq!(config.matrix.get_command_prefix(), "!tm");
+
+        // The prefix must be a single word
+        unsafe {
+            env::set_var("MIOU_MATRIX__COMMAND_PREFIX", "! tm");
+        }
+        assert!(Config::load(config_path.to_str().unwrap()).is_err());
+        unsafe {
+            env::set_var("MIOU_MATRIX__COMMAND_PREFIX", "");
+        }
+        assert!(Config::load(config_path.to_str().unwrap()).is_err());
+
+        cleanup_env_vars();
+    }
+
     #[test]
     #[serial]
     fn test_env_var_overrides() {
@@ -329,9 +829,11 @@ matrix:
 
         let yaml_content = r#"
 tmars:
-  url: "http://localhost:9090"
-  server_id: "test123"
   polling_interval: 60
+  servers:
+    main:
+      url: "http://localhost:9090"
+      server_id: "test123"
 
 matrix:
   user_id: "@bot:matrix.org"
@@ -343,18 +845,18 @@ matrix:
 
         // Set environment variables
         unsafe {
-            env::set_var("MIOU_TMARS__URL", "http://env-override:8080");
+            env::set_var("MIOU_TMARS__SERVERS__MAIN__URL", "http://env-override:8080");
             env::set_var("MIOU_MATRIX__PASSWORD", "env-password");
         }
 
         let config = Config::load(config_path.to_str().unwrap()).unwrap();
 
         // Check that env vars override file values
-        assert_eq!(config.tmars.url, "http://env-override:8080");
+        assert_eq!(config.tmars.servers["main"].url, "http://env-override:8080");
         assert_eq!(config.matrix.password, "env-password");
 
         // Check that non-overridden values remain from file
-        assert_eq!(config.tmars.server_id, "test123");
+        assert_eq!(config.tmars.servers["main"].server_id, "test123");
         assert_eq!(config.matrix.user_id, "@bot:matrix.org");
 
         // Cleanup
@@ -403,8 +905,10 @@ tmars:
 
         let incomplete_yaml = r#"
 tmars:
-  url: "http://localhost:9090"
-  server_id: "test123"
+  servers:
+    main:
+      url: "http://localhost:9090"
+      server_id: "test123"
   # Missing polling_interval
 "#;
 
@@ -427,9 +931,11 @@ tmars:
         // Create a minimal YAML file
         let yaml_content = r#"
 tmars:
-  url: "placeholder"
-  server_id: "placeholder"
   polling_interval: 1
+  servers:
+    main:
+      url: "placeholder"
+      server_id: "placeholder"
 
 matrix:
   user_id: "placeholder"
@@ -441,8 +947,8 @@ matrix:
 
         // Override everything with env vars
         unsafe {
-            env::set_var("MIOU_TMARS__URL", "http://env-only:9090");
-            env::set_var("MIOU_TMARS__SERVER_ID", "env-server");
+            env::set_var("MIOU_TMARS__SERVERS__MAIN__URL", "http://env-only:9090");
+            env::set_var("MIOU_TMARS__SERVERS__MAIN__SERVER_ID", "env-server");
             env::set_var("MIOU_TMARS__POLLING_INTERVAL", "120");
             env::set_var("MIOU_MATRIX__USER_ID", "@env:matrix.org");
             env::set_var("MIOU_MATRIX__PASSWORD", "env-pass");
@@ -451,8 +957,8 @@ matrix:
 
         let config = Config::load(config_path.to_str().unwrap()).unwrap();
 
-        assert_eq!(config.tmars.url, "http://env-only:9090");
-        assert_eq!(config.tmars.server_id, "env-server");
+        assert_eq!(config.tmars.servers["main"].url, "http://env-only:9090");
+        assert_eq!(config.tmars.servers["main"].server_id, "env-server");
         assert_eq!(config.tmars.polling_interval, 120);
         assert_eq!(config.matrix.user_id, "@env:matrix.org");
         assert_eq!(config.matrix.password, "env-pass");
@@ -461,4 +967,239 @@ matrix:
         // Cleanup
         cleanup_env_vars();
     }
+
+    #[test]
+    #[serial]
+    fn test_load_multiple_servers() {
+        cleanup_env_vars();
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+
+        let yaml_content = r#"
+tmars:
+  polling_interval: 60
+  servers:
+    main:
+      url: "http://localhost:9090"
+      server_id: "test123"
+    friends:
+      url: "http://localhost:9091"
+      server_id: "test456"
+
+matrix:
+  user_id: "@bot:matrix.org"
+  password: "pass123"
+  passphrase: "phrase123"
+"#;
+
+        fs::write(&config_path, yaml_content).unwrap();
+
+        let config = Config::load(config_path.to_str().unwrap()).unwrap();
+
+        assert_eq!(config.tmars.servers.len(), 2);
+        assert_eq!(config.tmars.servers["friends"].url, "http://localhost:9091");
+        assert_eq!(config.tmars.servers["friends"].server_id, "test456");
+        // Without default server, the first server in alphabetical order is used
+        assert_eq!(config.tmars.get_default_server(), "friends");
+
+        // The default server can be chosen
+        unsafe {
+            env::set_var("MIOU_TMARS__DEFAULT_SERVER", "main");
+        }
+        let config = Config::load(config_path.to_str().unwrap()).unwrap();
+        assert_eq!(config.tmars.get_default_server(), "main");
+
+        cleanup_env_vars();
+    }
+
+    #[test]
+    #[serial]
+    fn test_unknown_default_server_error() {
+        cleanup_env_vars();
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+
+        let yaml_content = r#"
+tmars:
+  polling_interval: 60
+  default_server: "unknown"
+  servers:
+    main:
+      url: "http://localhost:9090"
+      server_id: "test123"
+
+matrix:
+  user_id: "@bot:matrix.org"
+  password: "pass123"
+  passphrase: "phrase123"
+"#;
+
+        fs::write(&config_path, yaml_content).unwrap();
+
+        let result = Config::load(config_path.to_str().unwrap());
+        assert!(result.is_err());
+
+        cleanup_env_vars();
+    }
+
+    #[test]
+    #[serial]
+    fn test_invalid_servers_error() {
+        cleanup_env_vars();
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+
+        // No server
+        let yaml_content = r#"
+tmars:
+  polling_interval: 60
+  servers: {}
+
+matrix:
+  user_id: "@bot:matrix.org"
+  password: "pass123"
+  passphrase: "phrase123"
+"#;
+        fs::write(&config_path, yaml_content).unwrap();
+        assert!(Config::load(config_path.to_str().unwrap()).is_err());
+
+        // Server name with a colon
+        let yaml_content = r#"
+tmars:
+  polling_interval: 60
+  servers:
+    "main:1":
+      url: "http://localhost:9090"
+      server_id: "test123"
+
+matrix:
+  user_id: "@bot:matrix.org"
+  password: "pass123"
+  passphrase: "phrase123"
+"#;
+        fs::write(&config_path, yaml_content).unwrap();
+        assert!(Config::load(config_path.to_str().unwrap()).is_err());
+
+        cleanup_env_vars();
+    }
+
+    #[test]
+    #[serial]
+    fn test_legacy_server() {
+        cleanup_env_vars();
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+
+        let yaml_content = r#"
+tmars:
+  polling_interval: 60
+  url: "http://localhost:9090"
+  server_id: "test123"
+
+matrix:
+  user_id: "@bot:matrix.org"
+  password: "pass123"
+  passphrase: "phrase123"
+"#;
+        fs::write(&config_path, yaml_content).unwrap();
+
+        let config = Config::load(config_path.to_str().unwrap()).unwrap();
+        assert_eq!(config.tmars.servers.len(), 1);
+        assert_eq!(
+            config.tmars.servers[LEGACY_SERVER_NAME].url,
+            "http://localhost:9090"
+        );
+        assert_eq!(
+            config.tmars.servers[LEGACY_SERVER_NAME].server_id,
+            "test123"
+        );
+        assert_eq!(
+            config.tmars.default_server.as_deref(),
+            Some(LEGACY_SERVER_NAME)
+        );
+
+        // The legacy environment variables still override the legacy keys
+        unsafe {
+            env::set_var("MIOU_TMARS__URL", "http://env-override:8080");
+        }
+        let config = Config::load(config_path.to_str().unwrap()).unwrap();
+        assert_eq!(
+            config.tmars.servers[LEGACY_SERVER_NAME].url,
+            "http://env-override:8080"
+        );
+
+        cleanup_env_vars();
+
+        // The legacy server sits along the other servers
+        let yaml_content = r#"
+tmars:
+  polling_interval: 60
+  url: "http://localhost:9090"
+  server_id: "test123"
+  servers:
+    friends:
+      url: "http://localhost:9091"
+      server_id: "test456"
+
+matrix:
+  user_id: "@bot:matrix.org"
+  password: "pass123"
+  passphrase: "phrase123"
+"#;
+        fs::write(&config_path, yaml_content).unwrap();
+
+        let config = Config::load(config_path.to_str().unwrap()).unwrap();
+        assert_eq!(config.tmars.servers.len(), 2);
+        assert_eq!(config.tmars.get_default_server(), LEGACY_SERVER_NAME);
+
+        cleanup_env_vars();
+    }
+
+    #[test]
+    #[serial]
+    fn test_legacy_server_error() {
+        cleanup_env_vars();
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+
+        // Server ID without URL
+        let yaml_content = r#"
+tmars:
+  polling_interval: 60
+  server_id: "test123"
+
+matrix:
+  user_id: "@bot:matrix.org"
+  password: "pass123"
+  passphrase: "phrase123"
+"#;
+        fs::write(&config_path, yaml_content).unwrap();
+        assert!(Config::load(config_path.to_str().unwrap()).is_err());
+
+        // Legacy keys along a server of the same name
+        let yaml_content = r#"
+tmars:
+  polling_interval: 60
+  url: "http://localhost:9090"
+  server_id: "test123"
+  servers:
+    main:
+      url: "http://localhost:9091"
+      server_id: "test456"
+
+matrix:
+  user_id: "@bot:matrix.org"
+  password: "pass123"
+  passphrase: "phrase123"
+"#;
+        fs::write(&config_path, yaml_content).unwrap();
+        assert!(Config::load(config_path.to_str().unwrap()).is_err());
+
+        cleanup_env_vars();
+    }
 }
@@ -0,0 +1,422 @@
+//! History controller for recording the turns of the games.
+//!
+//! This module provides the [`HistoryController`] which compares the waited players
+//! of successive synchronizations with the TMars server to record when each turn
+//! starts and ends.
+
+use std::collections::{HashMap, HashSet};
+
+use log::info;
+use tokio::sync::Mutex;
+
+use crate::{
+    history::{HistoryLoader, Turn, turn::TurnHistory},
+    tmars::{Game, get_game_server_name},
+    utils::get_timestamp,
+};
+
+type GamesMap = HashMap<String, Game>;
+
+/// Records the turns of the players of every game.
+///
+/// A turn starts when a player appears in the waited players of a game and ends
+/// when the player leaves them. Turns are only as precise as the polling interval.
+///
+/// # State Management
+///
+/// The controller keeps the start of the ongoing turns along with the ended ones,
+/// and persists both to disk whenever a turn starts or ends. An ongoing turn thus
+/// survives a restart of the bot, a turn ending while the bot is stopped is
+/// recorded as ending at the first synchronization after the restart.
+///
+/// The ongoing turns of a game removed from its server are dropped, its ended turns
+/// are kept for the statistics. A game only missing from the games of its server is
+/// kept for a grace period of polls, like its alerts and watches, and the games of a
+/// server which didn't answer are kept until it does.
+///
+/// # Thread Safety
+///
+/// All public methods are async and use internal locking, the controller can be
+/// shared between tasks behind an [`Arc`](std::sync::Arc).
+///
+/// # Examples
+///
+/// ```no_run
+/// use miou::history::HistoryController;
+/// use std::collections::{HashMap, HashSet};
+///
+/// # async fn example() {
+/// let controller = HistoryController::new("history.json".to_string(), 3).await;
+///
+/// let games_map = HashMap::new(); // Normally populated with actual games
+/// let synced_servers = HashSet::from(["main".to_string()]);
+/// controller
+///     .update_history(&games_map, &HashSet::new(), &synced_servers)
+///     .await;
+///
+/// let turns_map = controller.get_turns_map().await;
+/// # }
+/// ```
+pub struct HistoryController {
+    /// Ended and ongoing turns of all the games
+    turn_history: Mutex<TurnHistory>,
+    /// Loader for persisting and loading the history from disk
+    history_loader: HistoryLoader,
+    /// Number of consecutive polls a game with ongoing turns has been missing, by game ID
+    missing_polls: Mutex<HashMap<String, u32>>,
+    /// Number of polls a game can be missing before its ongoing turns are dropped
+    grace_polls: u32,
+}
+
+impl HistoryController {
+    /// Creates a new `HistoryController` and loads the existing history from disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `history_path` - Path to the JSON file where the history is persisted
+    /// * `grace_polls` - Number of consecutive polls a game can be missing before
+    ///   its ongoing turns are dropped
+    ///
+    /// # Returns
+    ///
+    /// A new `HistoryController` instance with the history loaded from the specified
+    /// file. If the file doesn't exist or is corrupted, starts with an empty history.
+    pub async fn new(history_path: String, grace_polls: u32) -> Self {
+        let history_loader = HistoryLoader::new(history_path);
+        let turn_history = Mutex::new(history_loader.load().await);
+
+        HistoryController {
+            turn_history,
+            history_loader,
+            missing_polls: Mutex::new(HashMap::new()),
+            grace_polls,
+        }
+    }
+
+    /// Returns a snapshot of the ended turns, indexed by game ID.
+    pub async fn get_turns_map(&self) -> HashMap<String, Vec<Turn>> {
+        self.turn_history.lock().await.turns.clone()
+    }
+
+    /// Records the turns started and ended since the previous synchronization,
+    /// and persists the history if any changed.
+    ///
+    /// The checks between two polls don't list the games, the servers only checked
+    /// must not be in `synced_servers`.
+    ///
+    /// # Arguments
+    ///
+    /// * `games_map` - Current state of all active games
+    /// * `removed_game_ids` - IDs of the games ended or removed from their server
+    /// * `synced_servers` - Names of the servers which listed their games during the poll
+    pub async fn update_history(
+        &self,
+        games_map: &GamesMap,
+        removed_game_ids: &HashSet<String>,
+        synced_servers: &HashSet<String>,
+    ) {
+        let mut turn_history = self.turn_history.lock().await;
+        let mut missing_polls = self.missing_polls.lock().await;
+
+        let dropped = Self::drop_ongoing_turns(
+            &mut turn_history,
+            &mut missing_polls,
+            self.grace_polls,
+            games_map,
+            removed_game_ids,
+            synced_servers,
+        );
+        let recorded = Self::record_turns(&mut turn_history, games_map, get_timestamp());
+
+        if dropped || recorded {
+            self.history_loader.persist_history(&turn_history).await;
+        }
+    }
+
+    /// Drops the ongoing turns of the games removed from their server, and of the
+    /// games missing from a synced server for more than the grace period.
+    ///
+    /// # Returns
+    ///
+    /// `true` if ongoing turns were dropped.
+    fn drop_ongoing_turns(
+        turn_history: &mut TurnHistory,
+        missing_polls: &mut HashMap<String, u32>,
+        grace_polls: u32,
+        games_map: &GamesMap,
+        removed_game_ids: &HashSet<String>,
+        synced_servers: &HashSet<String>,
+    ) -> bool {
+        let mut changed = false;
+
+        missing_polls.retain(|game_id, _| !games_map.contains_key(game_id));
+        turn_history.ongoing_turns.retain(|game_id, _| {
+            if games_map.contains_key(game_id)
+                || !get_game_server_name(game_id)
+                    .is_some_and(|server_name| synced_servers.contains(server_name))
+            {
+                return true;
+            }
+
+            let missing_count = missing_polls.entry(game_id.clone()).or_default();
+            *missing_count += 1;
+            if !removed_game_ids.contains(game_id) && *missing_count <= grace_polls {
+                info!(
+                    "game {} missing for {} polls, keeping its ongoing turns",
+                    game_id, missing_count
+                );
+                return true;
+            }
+
+            info!("dropping ongoing turns of non-existing game {}", game_id);
+            missing_polls.remove(game_id);
+            changed = true;
+            false
+        });
+
+        changed
+    }
+
+    /// Compares the ongoing turns with the waited players of the games.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a turn started or ended.
+    fn record_turns(turn_history: &mut TurnHistory, games_map: &GamesMap, now: u64) -> bool {
+        let TurnHistory {
+            turns,
+            ongoing_turns,
+        } = turn_history;
+        let mut changed = false;
+
+        for game in games_map.values() {
+            let game_ongoing_turns = ongoing_turns.entry(game.id.clone()).or_default();
+
+            // Players who left the game can't end their turn
+            let ongoing_turns_count = game_ongoing_turns.len();
+            game_ongoing_turns
+                .retain(|player_id, _| game.players.iter().any(|player| player.id == *player_id));
+            changed |= game_ongoing_turns.len() != ongoing_turns_count;
+
+            for player in &game.players {
+                let waited = game.waited_players.contains(&player.id);
+                match (waited, game_ongoing_turns.get(&player.id)) {
+                    (true, None) => {
+                        game_ongoing_turns.insert(player.id.clone(), now);
+                        changed = true;
+                    }
+                    (false, Some(started_at)) => {
+                        info!(
+                            "turn of player {} in game {} ended after {} seconds",
+                            player.id,
+                            game.id,
+                            now.saturating_sub(*started_at)
+                        );
+                        turns.entry(game.id.clone()).or_default().push(Turn {
+                            player_id: player.id.clone(),
+                            player_name: player.name.clone(),
+                            started_at: *started_at,
+                            ended_at: now,
+                        });
+                        game_ongoing_turns.remove(&player.id);
+                        changed = true;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        ongoing_turns.retain(|_, game_ongoing_turns| !game_ongoing_turns.is_empty());
+
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmars::Phase;
+    use tempfile::NamedTempFile;
+
+    fn create_test_game(id: &str, waited_players: &[&str]) -> Game {
+        Game::test(id, Phase::Action, waited_players)
+    }
+
+    fn create_games_map(games: Vec<Game>) -> GamesMap {
+        games
+            .into_iter()
+            .map(|game| (game.id.clone(), game))
+            .collect()
+    }
+
+    #[test]
+    fn test_record_turns() {
+        let mut turn_history = TurnHistory::default();
+
+        // Alice starts playing
+        let games_map = create_games_map(vec![create_test_game("main:game1", &["player1"])]);
+        assert!(HistoryController::record_turns(
+            &mut turn_history,
+            &games_map,
+            1000
+        ));
+        assert!(turn_history.turns.is_empty());
+
+        // Nothing changed
+        assert!(!HistoryController::record_turns(
+            &mut turn_history,
+            &games_map,
+            1300
+        ));
+
+        // Alice ended her turn, Bob starts playing
+        let games_map = create_games_map(vec![create_test_game("main:game1", &["player2"])]);
+        assert!(HistoryController::record_turns(
+            &mut turn_history,
+            &games_map,
+            1600
+        ));
+
+        assert_eq!(
+            turn_history.turns.get("main:game1"),
+            Some(&vec![Turn {
+                player_id: "player1".to_string(),
+                player_name: "Alice".to_string(),
+                started_at: 1000,
+                ended_at: 1600,
+            }])
+        );
+        assert_eq!(
+            turn_history.ongoing_turns.get("main:game1"),
+            Some(&HashMap::from([("player2".to_string(), 1600)]))
+        );
+    }
+
+    #[test]
+    fn test_drop_ongoing_turns_of_removed_games() {
+        let mut turn_history = TurnHistory::default();
+        let mut missing_polls = HashMap::new();
+        let synced_servers = HashSet::from(["main".to_string()]);
+
+        let games_map = create_games_map(vec![create_test_game("main:game1", &["player1"])]);
+        HistoryController::record_turns(&mut turn_history, &games_map, 1000);
+        let games_map = create_games_map(vec![create_test_game("main:game1", &["player2"])]);
+        HistoryController::record_turns(&mut turn_history, &games_map, 1600);
+
+        // The game ended, its ended turns are kept
+        assert!(HistoryController::drop_ongoing_turns(
+            &mut turn_history,
+            &mut missing_polls,
+            3,
+            &HashMap::new(),
+            &HashSet::from(["main:game1".to_string()]),
+            &synced_servers,
+        ));
+        assert!(turn_history.ongoing_turns.is_empty());
+        assert_eq!(turn_history.turns.get("main:game1").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_drop_ongoing_turns_of_missing_games_after_grace_polls() {
+        let mut turn_history = TurnHistory::default();
+        let mut missing_polls = HashMap::new();
+        let synced_servers = HashSet::from(["main".to_string()]);
+
+        let games_map = create_games_map(vec![create_test_game("main:game1", &["player1"])]);
+        HistoryController::record_turns(&mut turn_history, &games_map, 1000);
+
+        // game1 is kept during the grace period of a poll
+        let mut drop_ongoing_turns = |turn_history: &mut TurnHistory| {
+            HistoryController::drop_ongoing_turns(
+                turn_history,
+                &mut missing_polls,
+                1,
+                &HashMap::new(),
+                &HashSet::new(),
+                &synced_servers,
+            )
+        };
+        assert!(!drop_ongoing_turns(&mut turn_history));
+        assert!(turn_history.ongoing_turns.contains_key("main:game1"));
+        assert!(drop_ongoing_turns(&mut turn_history));
+        assert!(turn_history.ongoing_turns.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_history_keeps_ongoing_turns_of_down_server() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+
+        let controller1 = HistoryController::new(path.clone(), 1).await;
+        let games_map = create_games_map(vec![create_test_game("main:game1", &["player1"])]);
+        let synced_servers = HashSet::from(["main".to_string()]);
+        controller1
+            .update_history(&games_map, &HashSet::new(), &synced_servers)
+            .await;
+
+        // The server is down when the bot restarts, no game is known
+        let controller2 = HistoryController::new(path, 1).await;
+        for _ in 0..3 {
+            controller2
+                .update_history(&HashMap::new(), &HashSet::new(), &HashSet::new())
+                .await;
+        }
+
+        // The turn started before the restart ends once the server is back
+        let games_map = create_games_map(vec![create_test_game("main:game1", &[])]);
+        controller2
+            .update_history(&games_map, &HashSet::new(), &synced_servers)
+            .await;
+
+        let turns_map = controller2.get_turns_map().await;
+        let turns = turns_map.get("main:game1").unwrap();
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].player_name, "Alice");
+    }
+
+    #[test]
+    fn test_record_turns_drops_ongoing_turns_of_removed_players() {
+        let mut turn_history = TurnHistory {
+            turns: HashMap::new(),
+            ongoing_turns: HashMap::from([(
+                "main:game1".to_string(),
+                HashMap::from([("player3".to_string(), 1000)]),
+            )]),
+        };
+
+        // player3 left the game during the turn
+        let games_map = create_games_map(vec![create_test_game("main:game1", &[])]);
+
+        assert!(HistoryController::record_turns(
+            &mut turn_history,
+            &games_map,
+            1600
+        ));
+        assert!(turn_history.ongoing_turns.is_empty());
+        assert!(turn_history.turns.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_history_persists_ongoing_turns() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+
+        let synced_servers = HashSet::from(["main".to_string()]);
+        let controller1 = HistoryController::new(path.clone(), 1).await;
+        let games_map = create_games_map(vec![create_test_game("main:game1", &["player1"])]);
+        controller1
+            .update_history(&games_map, &HashSet::new(), &synced_servers)
+            .await;
+
+        // A new controller resumes the ongoing turn
+        let controller2 = HistoryController::new(path, 1).await;
+        let games_map = create_games_map(vec![create_test_game("main:game1", &[])]);
+        controller2
+            .update_history(&games_map, &HashSet::new(), &synced_servers)
+            .await;
+
+        let turns_map = controller2.get_turns_map().await;
+        let turns = turns_map.get("main:game1").unwrap();
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].player_name, "Alice");
+    }
+}
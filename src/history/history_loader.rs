@@ -0,0 +1,155 @@
+//! History persistence layer for loading and saving turns to disk.
+//!
+//! This module provides the [`HistoryLoader`] for persisting the turns of the
+//! games between bot restarts. The history is serialized to JSON and stored in a file.
+
+use log::{error, info, warn};
+use tokio::fs;
+
+use crate::history::turn::TurnHistory;
+
+/// Handles loading and persisting the turn history to disk.
+///
+/// Like the alerts loader, it is fault-tolerant: if loading fails (file missing
+/// or corrupted), it returns an empty history rather than panicking.
+///
+/// # Examples
+///
+/// ```no_run
+/// use miou::history::HistoryLoader;
+///
+/// # async fn example() {
+/// let loader = HistoryLoader::new("history.json".to_string());
+///
+/// // Load the existing history or get an empty one
+/// let turn_history = loader.load().await;
+///
+/// // Later, persist the history
+/// loader.persist_history(&turn_history).await;
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct HistoryLoader {
+    /// Path to the JSON file where the history is stored.
+    path: String,
+}
+
+impl HistoryLoader {
+    /// Creates a new `HistoryLoader` for the specified file path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path where the history will be loaded from and saved to
+    pub fn new(path: String) -> Self {
+        HistoryLoader { path }
+    }
+
+    /// Loads the turn history from disk.
+    ///
+    /// # Returns
+    ///
+    /// The persisted history, or an empty history if the file doesn't exist or
+    /// cannot be deserialized.
+    pub async fn load(&self) -> TurnHistory {
+        let Ok(serialized_history) = fs::read_to_string(&self.path).await else {
+            warn!("no persisted history found, starting with an empty history");
+            return TurnHistory::default();
+        };
+
+        let Ok(turn_history) = serde_json::from_str(&serialized_history) else {
+            error!("failed to deserialize persisted history, starting with an empty history");
+            return TurnHistory::default();
+        };
+
+        // The history grows with every turn, only its size is logged
+        info!(
+            "loaded persisted history of {} bytes",
+            serialized_history.len()
+        );
+
+        turn_history
+    }
+
+    /// Persists the turn history to disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `turn_history` - Reference to the history to persist
+    ///
+    /// # Error Handling
+    ///
+    /// Errors are logged but not propagated, allowing the bot to continue operating
+    /// even if persistence fails.
+    pub async fn persist_history(&self, turn_history: &TurnHistory) {
+        let serialized_history = match serde_json::to_string(turn_history) {
+            Ok(serialized) => serialized,
+            Err(e) => {
+                error!("failed to serialize history: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = fs::write(&self.path, &serialized_history).await {
+            error!("failed to persist history: {}", e);
+            return;
+        }
+
+        info!("persisted history");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::history::Turn;
+    use tempfile::NamedTempFile;
+
+    #[tokio::test]
+    async fn test_load_nonexistent_file_returns_empty_history() {
+        let loader = HistoryLoader::new("nonexistent_file.json".to_string());
+        let turn_history = loader.load().await;
+
+        assert_eq!(turn_history, TurnHistory::default());
+    }
+
+    #[tokio::test]
+    async fn test_persist_and_load_history() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let loader = HistoryLoader::new(path);
+
+        let turn_history = TurnHistory {
+            turns: HashMap::from([(
+                "main:game1".to_string(),
+                vec![Turn {
+                    player_id: "player1".to_string(),
+                    player_name: "Alice".to_string(),
+                    started_at: 1000,
+                    ended_at: 1600,
+                }],
+            )]),
+            ongoing_turns: HashMap::from([(
+                "main:game1".to_string(),
+                HashMap::from([("player2".to_string(), 1600)]),
+            )]),
+        };
+
+        loader.persist_history(&turn_history).await;
+
+        assert_eq!(loader.load().await, turn_history);
+    }
+
+    #[tokio::test]
+    async fn test_load_corrupted_json_returns_empty_history() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+
+        fs::write(&path, "{ this is not valid json ").await.unwrap();
+
+        let loader = HistoryLoader::new(path);
+
+        assert_eq!(loader.load().await, TurnHistory::default());
+    }
+}
@@ -0,0 +1,39 @@
+//! Turn history and response-time statistics of Terraforming Mars games.
+//!
+//! This module records when the turn of each player starts and ends, to tell who
+//! takes the longest to play. The system consists of these components:
+//!
+//! - [`Turn`]: An ended turn of a player
+//! - [`TurnStats`]: Average and median turn durations per player, and the slowest turns
+//! - [`HistoryController`]: Records the turns from the waited players of successive synchronizations
+//! - [`HistoryLoader`]: Handles loading and saving the history to disk
+//!
+//! # Example Usage
+//!
+//! ```no_run
+//! use miou::history::{HistoryController, TurnStats};
+//!
+//! # async fn example(games_map: std::collections::HashMap<String, miou::tmars::Game>) {
+//! use std::collections::HashSet;
+//!
+//! let controller = HistoryController::new("history.json".to_string(), 3).await;
+//!
+//! // After each synchronization with the TMars servers
+//! let synced_servers = HashSet::from(["main".to_string()]);
+//! controller
+//!     .update_history(&games_map, &HashSet::new(), &synced_servers)
+//!     .await;
+//!
+//! let turn_stats = TurnStats::from_turns_map(&controller.get_turns_map().await);
+//! # }
+//! ```
+
+mod history_controller;
+mod history_loader;
+mod turn;
+mod turn_stats;
+
+pub use crate::history::{
+    history_controller::HistoryController, history_loader::HistoryLoader, turn::Turn,
+    turn_stats::TurnStats,
+};
@@ -0,0 +1,76 @@
+//! Recorded turns.
+//!
+//! This module provides the [`Turn`] struct describing an ended turn of a player,
+//! and the [`TurnHistory`] struct gathering the turns of all games.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// An ended turn of a player.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::history::Turn;
+/// let turn = Turn {
+///     player_id: "player1".to_string(),
+///     player_name: "Alice".to_string(),
+///     started_at: 1000,
+///     ended_at: 1600,
+/// };
+/// assert_eq!(turn.get_duration(), 600);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Turn {
+    /// ID of the player in the game
+    pub player_id: String,
+    /// Name of the player, kept to show the statistics of ended games
+    pub player_name: String,
+    /// Unix timestamp in seconds when the game started waiting for the player
+    pub started_at: u64,
+    /// Unix timestamp in seconds when the game stopped waiting for the player
+    pub ended_at: u64,
+}
+
+impl Turn {
+    /// Returns the duration of the turn in seconds.
+    pub fn get_duration(&self) -> u64 {
+        self.ended_at.saturating_sub(self.started_at)
+    }
+}
+
+/// Turns of all the games, as persisted on disk.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TurnHistory {
+    /// Ended turns indexed by game ID, in the order they ended.
+    #[serde(default)]
+    pub turns: HashMap<String, Vec<Turn>>,
+    /// Start timestamps of the ongoing turns, indexed by game ID then player ID.
+    #[serde(default)]
+    pub ongoing_turns: HashMap<String, HashMap<String, u64>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_duration() {
+        let turn = Turn {
+            player_id: "player1".to_string(),
+            player_name: "Alice".to_string(),
+            started_at: 1000,
+            ended_at: 1600,
+        };
+
+        assert_eq!(turn.get_duration(), 600);
+    }
+
+    #[test]
+    fn test_deserialize_empty_history() {
+        let turn_history: TurnHistory = serde_json::from_str("{}").unwrap();
+
+        assert_eq!(turn_history, TurnHistory::default());
+    }
+}
@@ -0,0 +1,213 @@
+//! Response-time statistics of the recorded turns.
+//!
+//! This module provides the [`TurnStats`] struct summarizing how long each player
+//! takes to play, and listing the slowest turns.
+
+use std::collections::HashMap;
+
+use crate::history::Turn;
+
+/// Maximum number of turns listed in [`TurnStats::slowest_turns`].
+const SLOWEST_TURNS_COUNT: usize = 3;
+
+/// Response time of a player.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlayerStats {
+    /// Name of the player
+    pub player_name: String,
+    /// Number of ended turns of the player
+    pub turn_count: usize,
+    /// Average duration of the turns in seconds
+    pub average_duration: u64,
+    /// Median duration of the turns in seconds
+    pub median_duration: u64,
+}
+
+/// Response-time statistics of a set of games.
+///
+/// Players are identified by their name, so that the turns of a player are
+/// gathered across games.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::history::{Turn, TurnStats};
+/// # use std::collections::HashMap;
+/// let turns_map = HashMap::from([(
+///     "main:game1".to_string(),
+///     vec![Turn {
+///         player_id: "player1".to_string(),
+///         player_name: "Alice".to_string(),
+///         started_at: 1000,
+///         ended_at: 1600,
+///     }],
+/// )]);
+/// let turn_stats = TurnStats::from_turns_map(&turns_map);
+/// assert_eq!(turn_stats.players[0].average_duration, 600);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TurnStats {
+    /// Statistics of each player, from the slowest average to the fastest
+    pub players: Vec<PlayerStats>,
+    /// Longest turns with the ID of their game, from the longest
+    pub slowest_turns: Vec<(String, Turn)>,
+}
+
+impl TurnStats {
+    /// Computes the statistics of the recorded turns.
+    ///
+    /// # Arguments
+    ///
+    /// * `turns_map` - Ended turns indexed by game ID
+    ///
+    /// # Returns
+    ///
+    /// The statistics of every player with at least one ended turn. Ties are
+    /// broken by name and game ID for a stable output.
+    pub fn from_turns_map(turns_map: &HashMap<String, Vec<Turn>>) -> Self {
+        let mut durations_by_player: HashMap<&str, Vec<u64>> = HashMap::new();
+        for turn in turns_map.values().flatten() {
+            durations_by_player
+                .entry(&turn.player_name)
+                .or_default()
+                .push(turn.get_duration());
+        }
+
+        let mut players: Vec<PlayerStats> = durations_by_player
+            .into_iter()
+            .map(|(player_name, mut durations)| {
+                durations.sort_unstable();
+                PlayerStats {
+                    player_name: player_name.to_owned(),
+                    turn_count: durations.len(),
+                    average_duration: durations.iter().sum::<u64>() / durations.len() as u64,
+                    median_duration: get_median(&durations),
+                }
+            })
+            .collect();
+        players.sort_by(|a, b| {
+            b.average_duration
+                .cmp(&a.average_duration)
+                .then_with(|| a.player_name.cmp(&b.player_name))
+        });
+
+        let mut slowest_turns: Vec<(String, Turn)> = turns_map
+            .iter()
+            .flat_map(|(game_id, turns)| turns.iter().map(|turn| (game_id.clone(), turn.clone())))
+            .collect();
+        slowest_turns.sort_by(|(a_game_id, a), (b_game_id, b)| {
+            b.get_duration()
+                .cmp(&a.get_duration())
+                .then_with(|| a.player_name.cmp(&b.player_name))
+                .then_with(|| a_game_id.cmp(b_game_id))
+        });
+        slowest_turns.truncate(SLOWEST_TURNS_COUNT);
+
+        TurnStats {
+            players,
+            slowest_turns,
+        }
+    }
+}
+
+/// Returns the median of sorted durations, which must not be empty.
+fn get_median(sorted_durations: &[u64]) -> u64 {
+    let middle = sorted_durations.len() / 2;
+    match sorted_durations.len() % 2 {
+        0 => (sorted_durations[middle - 1] + sorted_durations[middle]) / 2,
+        _ => sorted_durations[middle],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_turn(player_name: &str, duration: u64) -> Turn {
+        Turn {
+            player_id: player_name.to_lowercase(),
+            player_name: player_name.to_string(),
+            started_at: 1000,
+            ended_at: 1000 + duration,
+        }
+    }
+
+    #[test]
+    fn test_from_turns_map() {
+        let turns_map = HashMap::from([
+            (
+                "main:game1".to_string(),
+                vec![
+                    create_turn("Alice", 100),
+                    create_turn("Bob", 600),
+                    create_turn("Alice", 300),
+                ],
+            ),
+            (
+                "main:game2".to_string(),
+                vec![create_turn("Alice", 800), create_turn("Bob", 200)],
+            ),
+        ]);
+
+        let turn_stats = TurnStats::from_turns_map(&turns_map);
+
+        // Turns of a player are gathered across games
+        assert_eq!(
+            turn_stats.players,
+            vec![
+                PlayerStats {
+                    player_name: "Alice".to_string(),
+                    turn_count: 3,
+                    average_duration: 400,
+                    median_duration: 300,
+                },
+                PlayerStats {
+                    player_name: "Bob".to_string(),
+                    turn_count: 2,
+                    average_duration: 400,
+                    median_duration: 400,
+                },
+            ]
+        );
+        assert_eq!(
+            turn_stats.slowest_turns,
+            vec![
+                ("main:game2".to_string(), create_turn("Alice", 800)),
+                ("main:game1".to_string(), create_turn("Bob", 600)),
+                ("main:game1".to_string(), create_turn("Alice", 300)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_turns_map_sorts_players_by_average() {
+        let turns_map = HashMap::from([(
+            "main:game1".to_string(),
+            vec![create_turn("Alice", 100), create_turn("Bob", 600)],
+        )]);
+
+        let turn_stats = TurnStats::from_turns_map(&turns_map);
+
+        let player_names: Vec<&str> = turn_stats
+            .players
+            .iter()
+            .map(|player| player.player_name.as_str())
+            .collect();
+        assert_eq!(player_names, vec!["Bob", "Alice"]);
+    }
+
+    #[test]
+    fn test_from_turns_map_empty() {
+        let turn_stats = TurnStats::from_turns_map(&HashMap::new());
+
+        assert!(turn_stats.players.is_empty());
+        assert!(turn_stats.slowest_turns.is_empty());
+    }
+
+    #[test]
+    fn test_get_median() {
+        assert_eq!(get_median(&[100]), 100);
+        assert_eq!(get_median(&[100, 200, 900]), 200);
+        assert_eq!(get_median(&[100, 200, 300, 900]), 250);
+    }
+}
@@ -13,8 +13,14 @@
 //!
 //! - **Turn Notifications**: Get notified in Matrix when it's your turn in a TMars game
 //! - **Configurable Delays**: Set custom notification delays (1 minute to 1 week)
+//! - **Quiet Hours**: Defer notifications falling into each user's do-not-disturb window
 //! - **Multi-Game Support**: Monitor multiple games simultaneously
+//! - **Multi-Server Support**: Follow the games of several TMars servers, each room picking its own
+//! - **Game Progress**: Show the generation, global parameters and terraform ratings of each game
 //! - **Room-Based Alerts**: Register alerts in different Matrix rooms for the same game
+//! - **Game Watch**: Announce every turn change of a game in a Matrix room
+//! - **Game End Results**: Announce the final scores of ended games where they were followed
+//! - **Turn Statistics**: Record the turns of the players and show who takes the longest to play
 //! - **Automatic Cleanup**: Removes alerts when games end
 //! - **Session Persistence**: Maintains Matrix login sessions across restarts
 //! - **YAML Configuration**: Simple configuration file format with environment variable support
@@ -25,14 +31,19 @@
 //!
 //! ```yaml
 //! tmars:
-//!   url: "https://terraforming-mars.herokuapp.com"
-//!   server_id: "your-server-id"
 //!   polling_interval: 120
+//!   check_interval: 15
+//!   servers:
+//!     main:
+//!       url: "https://terraforming-mars.herokuapp.com"
+//!       server_id: "your-server-id"
 //!
 //! matrix:
 //!   user_id: "@miou:matrix.org"
 //!   password: "your-password"
 //!   passphrase: "your-recovery-passphrase"
+//!   # Prefix of the commands in the rooms which didn't choose one (optional)
+//!   command_prefix: "!miou"
 //! ```
 //!
 //! # Environment Variable Overrides
@@ -40,8 +51,8 @@
 //! Override any configuration value using environment variables with the `MIOU_` prefix:
 //!
 //! ```bash
-//! export MIOU_TMARS__URL="https://terraforming-mars.herokuapp.com"
-//! export MIOU_TMARS__SERVER_ID="your-server-id"
+//! export MIOU_TMARS__SERVERS__MAIN__URL="https://terraforming-mars.herokuapp.com"
+//! export MIOU_TMARS__SERVERS__MAIN__SERVER_ID="your-server-id"
 //! export MIOU_MATRIX__USER_ID="@miou:matrix.org"
 //! export MIOU_MATRIX__PASSWORD="your-password"
 //! export MIOU_MATRIX__PASSPHRASE="your-passphrase"
@@ -55,13 +66,25 @@
 //!
 //! # Bot Commands
 //!
-//! Once running, users can interact with the bot using these commands in Matrix:
+//! Once running, users can interact with the bot using these commands in Matrix.
+//! The commands start with the prefix of the room, `!miou` unless configured
+//! otherwise, or with a mention of the bot, e.g. `@miou:matrix.org games`, and need
+//! no prefix at all in a direct chat with the bot:
 //!
 //! - `!miou help` - Display help information
 //! - `!miou games` - List all ongoing games
+//! - `!miou game <game_id>` - Show the details of a game
 //! - `!miou alerts` - List your registered alerts
-//! - `!miou register <game_id> <player_name> <delay>` - Register for turn notifications
+//! - `!miou register <game_id> <player_name> <delay> [repeat] [max_reminders] [--phases <phase,...>]` - Register for turn notifications
 //! - `!miou unregister <game_id>` - Stop receiving notifications for a game
+//! - `!miou watch <game_id>` - Announce every turn change of a game in the room
+//! - `!miou unwatch <game_id>` - Stop announcing the turn changes of a game in the room
+//! - `!miou settings` - Show your settings
+//! - `!miou settings quiet <start>-<end> <timezone>` - Set your quiet hours
+//! - `!miou server [server_name]` - Show or set the server of the game IDs in the room
+//! - `!miou stats [game_id]` - Show the turn times of the players of a game, or of all games
+//! - `!miou template [set "<template>" | reset]` - Show, set or reset the template of the turn notifications of the room
+//! - `!miou prefix [<prefix> | reset]` - Show, set or reset the prefix of the commands in the room
 //!
 //! # Architecture
 //!
@@ -71,16 +94,20 @@
 //! - [`bot`] - Main bot logic coordinating Matrix and TMars synchronization
 //! - [`commands`] - Command parsing and execution with validation
 //! - [`config`] - YAML configuration file structures and loading with environment variable support
+//! - [`history`] - Turn history of the players and response-time statistics
 //! - [`matrix`] - Matrix client integration and session management
-//! - [`tmars`] - TMars server API client and game state synchronization
-//! - [`utils`] - Utility functions for path handling
+//! - [`settings`] - User preferences like quiet hours, and their persistence
+//! - [`tmars`] - TMars server API client, game state synchronization and game events
+//! - [`utils`] - Utility functions for path and time handling
+//! - [`watches`] - Room-wide game watches and turn change announcements
 //!
 //! # Runtime Behavior
 //!
 //! Once started, the bot runs three concurrent tasks:
 //!
 //! 1. **TMars Sync Task**: Polls the TMars server every `polling_interval` seconds
-//!    to fetch current game states and trigger notifications
+//!    to fetch current game states, trigger notifications and announce turn changes.
+//!    In between, checks which games changed every `check_interval` seconds
 //! 2. **Matrix Sync Task**: Listens for Matrix messages and processes bot commands
 //! 3. **Alert Persistence Task**: Saves the alerts map to disk every minute
 //!
@@ -102,9 +129,12 @@ mod alerts;
 mod bot;
 mod commands;
 mod config;
+mod history;
 mod matrix;
+mod settings;
 mod tmars;
 mod utils;
+mod watches;
 
 /// Command-line arguments for the Miou bot.
 ///
@@ -158,6 +188,8 @@ struct Args {
     /// This directory will contain:
     /// - `session/` - Matrix session data (authentication tokens, device keys)
     /// - `alerts` - JSON file with registered alerts
+    /// - `settings` - JSON file with user settings
+    /// - `watches` - JSON file with the games watched by each room
     ///
     /// # Security Considerations
     ///
@@ -242,8 +274,8 @@ struct Args {
 /// Run with environment variable overrides for sensitive data:
 ///
 /// ```bash
-/// export MIOU_TMARS__URL="https://terraforming-mars.herokuapp.com"
-/// export MIOU_TMARS__SERVER_ID="your-server-id"
+/// export MIOU_TMARS__SERVERS__MAIN__URL="https://terraforming-mars.herokuapp.com"
+/// export MIOU_TMARS__SERVERS__MAIN__SERVER_ID="your-server-id"
 /// export MIOU_MATRIX__USER_ID="@miou:matrix.org"
 /// export MIOU_MATRIX__PASSWORD="your-password"
 /// export MIOU_MATRIX__PASSPHRASE="your-passphrase"
@@ -269,9 +301,11 @@ async fn main() {
         }
     };
 
-    // Normalize TMars URL by removing trailing slash if present
-    if config.tmars.url.ends_with('/') {
-        config.tmars.url.pop();
+    // Normalize TMars URLs by removing trailing slash if present
+    for server in config.tmars.servers.values_mut() {
+        if server.url.ends_with('/') {
+            server.url.pop();
+        }
     }
 
     let avatar_bytes = include_bytes!("../assets/miou.png");
@@ -124,11 +124,13 @@ impl MatrixClient {
     ///
     /// # Arguments
     ///
-    /// * `on_message` - Callback invoked for each text message with parameters:
+    /// * `on_message` - Callback invoked for each text message of another user with parameters:
     ///   - `body`: The message text content
     ///   - `room_id`: The room where the message was sent
     ///   - `sender_id`: The user who sent the message
     ///   - `event_id`: The unique event identifier
+    ///   - `is_direct`: Whether the room is a direct chat with the bot
+    ///   - `mention`: The text of the mention of the bot starting the message, if any
     ///
     /// # Returns
     ///
@@ -139,7 +141,7 @@ impl MatrixClient {
     /// ```no_run
     /// # use miou::matrix::client::MatrixClient;
     /// # async fn example(client: MatrixClient) -> Result<(), anyhow::Error> {
-    /// client.sync(|body, room_id, sender_id, event_id| {
+    /// client.sync(|body, room_id, sender_id, event_id, _, _| {
     ///     println!("[{room_id}] {sender_id}: {body}");
     /// }).await?;
     /// # Ok(())
@@ -147,7 +149,7 @@ impl MatrixClient {
     /// ```
     pub async fn sync<F>(&self, on_message: F) -> Result<(), anyhow::Error>
     where
-        F: Fn(String, String, String, String) + Send + Sync + 'static + Clone,
+        F: Fn(String, String, String, String, bool, Option<String>) + Send + Sync + 'static + Clone,
     {
         match self.matrix_sync.sync(on_message).await {
             Ok(_) => info!("matrix sync ended successfully"),
@@ -157,15 +159,17 @@ impl MatrixClient {
         Ok(())
     }
 
-    /// Sends a message with a mention to a specific user.
+    /// Sends an HTML message with a mention to a specific user.
     ///
-    /// The message body is formatted as Markdown and includes a mention of the
-    /// specified user, which will trigger a notification for them.
+    /// The message includes a mention of the specified user, which will trigger a
+    /// notification for them. The plain text body is displayed by the clients
+    /// without HTML support.
     ///
     /// # Arguments
     ///
     /// * `room_id` - The Matrix room ID where the message should be sent
-    /// * `body` - The message content (supports Markdown formatting)
+    /// * `body` - The plain text content of the message
+    /// * `html_body` - The HTML content of the message
     /// * `sender_id` - The user ID to mention in the message
     ///
     /// # Examples
@@ -174,20 +178,42 @@ impl MatrixClient {
     /// # use miou::matrix::client::MatrixClient;
     /// # async fn example(client: MatrixClient) {
     /// client.send_mention(
-    ///     "!room:example.com".to_string(),
-    ///     "Hello! Your turn is ready.".to_string(),
-    ///     "@user:example.com".to_string(),
+    ///     "!room:example.com",
+    ///     "Hello! Your turn is ready.",
+    ///     "Hello! Your turn is <b>ready</b>.",
+    ///     "@user:example.com",
     /// ).await;
     /// # }
     /// ```
-    pub async fn send_mention(&self, room_id: &str, body: &str, sender_id: &str) {
+    pub async fn send_mention(&self, room_id: &str, body: &str, html_body: &str, sender_id: &str) {
         let sender = UserId::parse(sender_id).unwrap();
-        let content = RoomMessageEventContent::text_markdown(body)
+        let content = RoomMessageEventContent::text_html(body, html_body)
             .add_mentions(Mentions::with_user_ids([sender]));
 
         self.send(room_id, content).await;
     }
 
+    /// Sends a message to a room without mentioning anyone.
+    ///
+    /// # Arguments
+    ///
+    /// * `room_id` - The Matrix room ID where the message should be sent
+    /// * `body` - The message content (supports Markdown formatting)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use miou::matrix::client::MatrixClient;
+    /// # async fn example(client: MatrixClient) {
+    /// client.send_message("!room:example.com", "Bob is now up.").await;
+    /// # }
+    /// ```
+    pub async fn send_message(&self, room_id: &str, body: &str) {
+        let content = RoomMessageEventContent::text_markdown(body);
+
+        self.send(room_id, content).await;
+    }
+
     /// Sends a threaded reply to a specific message.
     ///
     /// Creates a reply to an existing message, maintaining proper thread context
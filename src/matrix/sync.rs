@@ -21,8 +21,8 @@
 //! let matrix_sync = MatrixSync::new(&client, &session);
 //!
 //! // Start syncing with a message handler
-//! matrix_sync.sync(|body, room_id, sender_id, event_id| {
-//!     println!("Message from {sender_id} in {room_id}: {body}");
+//! matrix_sync.sync(|body, room_id, sender_id, event_id, is_direct, _| {
+//!     println!("Message from {sender_id} in {room_id} (direct: {is_direct}): {body}");
 //! }).await?;
 //! # Ok(())
 //! # }
@@ -39,7 +39,7 @@ use matrix_sdk::{
         api::client::filter::FilterDefinition,
         events::room::{
             member::StrippedRoomMemberEvent,
-            message::{MessageType, OriginalSyncRoomMessageEvent},
+            message::{MessageFormat, MessageType, OriginalSyncRoomMessageEvent},
         },
     },
 };
@@ -111,12 +111,16 @@ impl MatrixSync {
     ///
     /// # Arguments
     ///
-    /// * `on_message` - Callback invoked for each text message in a joined room.
-    ///   Parameters are: `(body, room_id, sender_id, event_id)`
+    /// * `on_message` - Callback invoked for each text message of another user in a
+    ///   joined room. Parameters are:
+    ///   `(body, room_id, sender_id, event_id, is_direct, mention)`
     ///   - `body`: The message text content
     ///   - `room_id`: The room where the message was sent
     ///   - `sender_id`: The user who sent the message
     ///   - `event_id`: The unique event identifier
+    ///   - `is_direct`: Whether the room is a direct chat with the bot
+    ///   - `mention`: The text of the mention of the bot starting the message, if any,
+    ///     e.g. its display name when the client sends a pill
     ///
     /// # Returns
     ///
@@ -137,7 +141,7 @@ impl MatrixSync {
     ///
     /// # async fn example(matrix_sync: MatrixSync) -> Result<(), anyhow::Error> {
     /// // Simple message logger
-    /// matrix_sync.sync(|body, room_id, sender, event_id| {
+    /// matrix_sync.sync(|body, room_id, sender, event_id, _, _| {
     ///     println!("[{room_id}] {sender}: {body}");
     /// }).await?;
     /// # Ok(())
@@ -145,7 +149,7 @@ impl MatrixSync {
     /// ```
     pub async fn sync<F>(&self, on_message: F) -> Result<()>
     where
-        F: Fn(String, String, String, String) + Send + Sync + 'static + Clone,
+        F: Fn(String, String, String, String, bool, Option<String>) + Send + Sync + 'static + Clone,
     {
         info!("start syncing");
 
@@ -272,9 +276,10 @@ async fn auto_join_rooms(room_member: StrippedRoomMemberEvent, client: Client, r
 /// Handles incoming room messages and delegates to the user callback.
 ///
 /// This internal function:
-/// 1. Filters out messages from non-joined rooms
+/// 1. Filters out messages from non-joined rooms and messages of the bot
 /// 2. Extracts text content from message events
-/// 3. Invokes the user-provided callback with message details
+/// 3. Finds the mention of the bot starting the message, see [`find_mention`]
+/// 4. Invokes the user-provided callback with message details
 ///
 /// Non-text messages (images, files, etc.) are silently ignored.
 ///
@@ -287,25 +292,162 @@ async fn auto_join_rooms(room_member: StrippedRoomMemberEvent, client: Client, r
 /// # Type Parameters
 ///
 /// * `F` - The callback function type with signature:
-///   `Fn(String, String, String, String)` for `(body, room_id, sender, event_id)`
+///   `Fn(String, String, String, String, bool, Option<String>)` for
+///   `(body, room_id, sender, event_id, is_direct, mention)`
 async fn on_room_message<F>(event: OriginalSyncRoomMessageEvent, room: Room, on_message: &Arc<F>)
 where
-    F: Fn(String, String, String, String) + Send + Sync + 'static,
+    F: Fn(String, String, String, String, bool, Option<String>) + Send + Sync + 'static,
 {
     // Ignore messages from non-joined rooms
     if room.state() != RoomState::Joined {
         return;
     }
 
+    // Ignore the messages of the bot, its replies in a direct chat would be read as commands
+    if event.sender == room.own_user_id() {
+        return;
+    }
+
     // Only handle text messages
     let MessageType::Text(text_content) = event.content.msgtype else {
         return;
     };
 
+    let formatted_body = text_content
+        .formatted
+        .as_ref()
+        .filter(|formatted| formatted.format == MessageFormat::Html)
+        .map(|formatted| formatted.body.as_str());
+    let is_mentioned = event
+        .content
+        .mentions
+        .is_some_and(|mentions| mentions.user_ids.contains(room.own_user_id()));
+    let mention =
+        match formatted_body.and_then(|html| find_mention(html, room.own_user_id().as_str())) {
+            Some(mention) => Some(mention),
+            // Without a pill, the mention can only be the display name of the bot in the room
+            None if is_mentioned => get_display_name(&room).await,
+            None => None,
+        };
+
+    let is_direct = room.is_direct().await.unwrap_or_else(|err| {
+        warn!(
+            "could not check if room {} is direct: {:?}",
+            room.room_id(),
+            err
+        );
+        false
+    });
+
     on_message(
         text_content.body,
         room.room_id().to_string(),
         event.sender.to_string(),
         event.event_id.to_string(),
+        is_direct,
+        mention,
     );
 }
+
+/// Returns the display name of the bot in a room.
+async fn get_display_name(room: &Room) -> Option<String> {
+    match room.get_member_no_sync(room.own_user_id()).await {
+        Ok(member) => member.and_then(|member| member.display_name().map(str::to_owned)),
+        Err(err) => {
+            warn!(
+                "could not get the display name of the bot in room {}: {:?}",
+                room.room_id(),
+                err
+            );
+            None
+        }
+    }
+}
+
+/// Finds the mention of a user starting the HTML body of a message.
+///
+/// The clients mention users with a pill, a link to their matrix.to permalink whose
+/// text is their display name, e.g. `<a href="https://matrix.to/#/@miou:example.com">Miou</a>: games`.
+/// The plain text body of the message then starts with the display name, not the user ID.
+///
+/// # Arguments
+///
+/// * `html` - The HTML body of the message
+/// * `user_id` - The Matrix user ID of the mentioned user
+///
+/// # Returns
+///
+/// The text of the pill, `None` if the message doesn't start with a pill of the user.
+fn find_mention(html: &str, user_id: &str) -> Option<String> {
+    let rest = html.trim_start().strip_prefix("<a ")?;
+    let (attributes, rest) = rest.split_once('>')?;
+    let (text, _) = rest.split_once("</a>")?;
+
+    let href = attributes.split_whitespace().find_map(|attribute| {
+        let value = attribute.strip_prefix("href=")?;
+        value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .or_else(|| {
+                value
+                    .strip_prefix('\'')
+                    .and_then(|value| value.strip_suffix('\''))
+            })
+    })?;
+    // The permalink may have routing parameters and an encoded user ID
+    let permalink = href.strip_prefix("https://matrix.to/#/")?;
+    let permalink = permalink.split('?').next().unwrap_or_default();
+    let mentioned_id = permalink
+        .replace("%40", "@")
+        .replace("%3A", ":")
+        .replace("%3a", ":");
+    if mentioned_id != user_id {
+        return None;
+    }
+
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_mention_pill() {
+        let html = "<a href=\"https://matrix.to/#/@miou:example.com\">Miou</a>: games";
+        assert_eq!(
+            find_mention(html, "@miou:example.com"),
+            Some("Miou".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_mention_encoded_permalink() {
+        let html = "<a href='https://matrix.to/#/%40miou%3Aexample.com?via=example.com'>Miou &amp; co</a> games";
+        assert_eq!(
+            find_mention(html, "@miou:example.com"),
+            Some("Miou & co".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_mention_other_user() {
+        let html = "<a href=\"https://matrix.to/#/@alice:example.com\">Alice</a>: games";
+        assert_eq!(find_mention(html, "@miou:example.com"), None);
+    }
+
+    #[test]
+    fn test_find_mention_not_at_start() {
+        let html = "hello <a href=\"https://matrix.to/#/@miou:example.com\">Miou</a>";
+        assert_eq!(find_mention(html, "@miou:example.com"), None);
+
+        let html = "<a href=\"https://example.com\">link</a> games";
+        assert_eq!(find_mention(html, "@miou:example.com"), None);
+    }
+}
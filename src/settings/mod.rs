@@ -0,0 +1,43 @@
+//! User and room preferences for Terraforming Mars game notifications.
+//!
+//! This module stores the preferences of Matrix users and rooms and makes them
+//! available to the rest of the bot. The system consists of these components:
+//!
+//! - [`UserSettings`]: Preferences of a single user
+//! - [`RoomSettings`]: Preferences of a single room
+//! - [`QuietHours`]: Daily window during which a user must not be notified
+//! - [`NotificationTemplate`]: Text of the turn notifications of a room
+//! - [`SettingsController`]: Thread-safe access to the preferences of all users and rooms
+//! - [`SettingsLoader`]: Handles loading and saving settings to disk
+//!
+//! # Example Usage
+//!
+//! ```no_run
+//! use miou::settings::{QuietHours, SettingsController, UserSettings};
+//!
+//! # async fn example() {
+//! let controller = SettingsController::new("settings.json".to_string()).await;
+//!
+//! // Don't notify the user at night
+//! let user_settings = UserSettings {
+//!     quiet_hours: QuietHours::parse("23:00-08:00", "Europe/Paris").ok(),
+//! };
+//! controller.update_user_settings("@user:example.com", &user_settings).await;
+//! # }
+//! ```
+
+mod notification_template;
+mod quiet_hours;
+mod room_settings;
+mod settings_controller;
+mod settings_loader;
+mod user_settings;
+
+pub use crate::settings::{
+    notification_template::{NotificationTemplate, Placeholder, TemplateError},
+    quiet_hours::{QuietHours, QuietHoursError},
+    room_settings::RoomSettings,
+    settings_controller::SettingsController,
+    settings_loader::SettingsLoader,
+    user_settings::UserSettings,
+};
@@ -0,0 +1,281 @@
+//! Notification template of a room.
+//!
+//! This module provides the [`NotificationTemplate`] struct, the text of the turn
+//! notifications chosen by a room. The template contains placeholders like
+//! `{player}` which are replaced by the context of the turn when the notification
+//! is sent.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of characters of a template.
+const MAX_LENGTH: usize = 1000;
+
+/// Errors that can occur when parsing a notification template.
+#[derive(Debug, PartialEq)]
+pub enum TemplateError {
+    /// The template is empty or too long.
+    InvalidLength,
+    /// A `{` is not closed by a `}`.
+    UnclosedPlaceholder,
+    /// A placeholder is not known to the bot.
+    UnknownPlaceholder(String),
+}
+
+/// Value of the context of a turn which can be inserted in a notification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Placeholder {
+    /// Matrix user ID of the notified user
+    User,
+    /// Name of the player in the game
+    Player,
+    /// ID of the game
+    Game,
+    /// Phase of the game
+    Phase,
+    /// URL of the player's game page
+    Url,
+    /// Other players the game is waiting for
+    WaitedFor,
+}
+
+impl Placeholder {
+    /// All the placeholders, in the order they are documented.
+    pub const ALL: [Placeholder; 6] = [
+        Placeholder::User,
+        Placeholder::Player,
+        Placeholder::Game,
+        Placeholder::Phase,
+        Placeholder::Url,
+        Placeholder::WaitedFor,
+    ];
+
+    /// Returns the name of the placeholder, written between braces in a template.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Placeholder::User => "user",
+            Placeholder::Player => "player",
+            Placeholder::Game => "game",
+            Placeholder::Phase => "phase",
+            Placeholder::Url => "url",
+            Placeholder::WaitedFor => "waited_for",
+        }
+    }
+
+    /// Returns the placeholder of the given name, `None` if it is unknown.
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|placeholder| placeholder.name() == name)
+    }
+}
+
+/// Part of a parsed template.
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    /// Text copied as is in the notification
+    Text(String),
+    /// Placeholder replaced by the context of the turn
+    Placeholder(Placeholder),
+}
+
+/// Text of the turn notifications of a room.
+///
+/// The template is stored as written by the user and parsed into segments of text
+/// and placeholders. Placeholders are the name of a [`Placeholder`] between braces,
+/// there is no way to escape a brace.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::settings::{NotificationTemplate, Placeholder};
+/// let template = NotificationTemplate::parse("{player}, wake up! {url}").unwrap();
+/// let notification = template.render(
+///     |text| text.to_owned(),
+///     |placeholder| match placeholder {
+///         Placeholder::Player => "Alice".to_owned(),
+///         Placeholder::Url => "https://example.com/player?id=p123".to_owned(),
+///         _ => String::new(),
+///     },
+/// );
+/// assert_eq!(notification, "Alice, wake up! https://example.com/player?id=p123");
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct NotificationTemplate {
+    /// Template as written by the user
+    template: String,
+    /// Parsed template
+    segments: Vec<Segment>,
+}
+
+impl NotificationTemplate {
+    /// Parses a notification template.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template, e.g. `{player}, it's your turn in {game}`
+    ///
+    /// # Errors
+    ///
+    /// - [`TemplateError::InvalidLength`] if the template is empty or longer than 1000 characters
+    /// - [`TemplateError::UnclosedPlaceholder`] if a `{` is not closed
+    /// - [`TemplateError::UnknownPlaceholder`] if a placeholder is unknown
+    pub fn parse(template: &str) -> Result<Self, TemplateError> {
+        if template.trim().is_empty() || template.chars().count() > MAX_LENGTH {
+            return Err(TemplateError::InvalidLength);
+        }
+
+        let mut segments = Vec::new();
+        let mut remaining = template;
+
+        while let Some(start) = remaining.find('{') {
+            if start > 0 {
+                segments.push(Segment::Text(remaining[..start].to_owned()));
+            }
+
+            let end = remaining[start..]
+                .find('}')
+                .ok_or(TemplateError::UnclosedPlaceholder)?
+                + start;
+            let name = &remaining[start + 1..end];
+            let placeholder = Placeholder::from_name(name)
+                .ok_or_else(|| TemplateError::UnknownPlaceholder(name.to_owned()))?;
+            segments.push(Segment::Placeholder(placeholder));
+
+            remaining = &remaining[end + 1..];
+        }
+
+        if !remaining.is_empty() {
+            segments.push(Segment::Text(remaining.to_owned()));
+        }
+
+        Ok(NotificationTemplate {
+            template: template.to_owned(),
+            segments,
+        })
+    }
+
+    /// Renders the template.
+    ///
+    /// # Arguments
+    ///
+    /// * `format_text` - Formats the text of the template, e.g. to escape it
+    /// * `format_placeholder` - Returns the value of a placeholder
+    ///
+    /// # Returns
+    ///
+    /// The notification, the concatenation of the formatted segments.
+    pub fn render(
+        &self,
+        format_text: impl Fn(&str) -> String,
+        format_placeholder: impl Fn(Placeholder) -> String,
+    ) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => format_text(text),
+                Segment::Placeholder(placeholder) => format_placeholder(*placeholder),
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<String> for NotificationTemplate {
+    type Error = String;
+
+    fn try_from(template: String) -> Result<Self, Self::Error> {
+        NotificationTemplate::parse(&template)
+            .map_err(|e| format!("invalid notification template: {:?}", e))
+    }
+}
+
+impl From<NotificationTemplate> for String {
+    fn from(template: NotificationTemplate) -> Self {
+        template.template
+    }
+}
+
+impl fmt::Display for NotificationTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_names(template: &NotificationTemplate) -> String {
+        template.render(
+            |text| text.to_uppercase(),
+            |placeholder| format!("<{}>", placeholder.name()),
+        )
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let template =
+            NotificationTemplate::parse("{user}: {player} in {game} ({phase}) {url}, {waited_for}")
+                .unwrap();
+
+        assert_eq!(
+            render_names(&template),
+            "<user>: <player> IN <game> (<phase>) <url>, <waited_for>"
+        );
+    }
+
+    #[test]
+    fn test_parse_without_placeholder() {
+        let template = NotificationTemplate::parse("Play!").unwrap();
+
+        assert_eq!(render_names(&template), "PLAY!");
+    }
+
+    #[test]
+    fn test_parse_invalid_length() {
+        assert_eq!(
+            NotificationTemplate::parse(" "),
+            Err(TemplateError::InvalidLength)
+        );
+        assert_eq!(
+            NotificationTemplate::parse(&"a".repeat(MAX_LENGTH + 1)),
+            Err(TemplateError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_parse_unclosed_placeholder() {
+        assert_eq!(
+            NotificationTemplate::parse("{player}, play {game"),
+            Err(TemplateError::UnclosedPlaceholder)
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_placeholder() {
+        assert_eq!(
+            NotificationTemplate::parse("{player}, play {gaem}"),
+            Err(TemplateError::UnknownPlaceholder("gaem".to_string()))
+        );
+        assert_eq!(
+            NotificationTemplate::parse("{}"),
+            Err(TemplateError::UnknownPlaceholder(String::new()))
+        );
+    }
+
+    #[test]
+    fn test_serialization_keeps_the_template() {
+        let template = NotificationTemplate::parse("{player}, play!").unwrap();
+
+        let serialized = serde_json::to_string(&template).unwrap();
+
+        assert_eq!(serialized, "\"{player}, play!\"");
+        assert_eq!(
+            serde_json::from_str::<NotificationTemplate>(&serialized).unwrap(),
+            template
+        );
+        assert!(serde_json::from_str::<NotificationTemplate>("\"{gaem}\"").is_err());
+    }
+}
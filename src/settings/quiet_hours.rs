@@ -0,0 +1,274 @@
+//! Quiet hours of a user.
+//!
+//! This module provides the [`QuietHours`] struct describing a daily do-not-disturb
+//! window in the user's timezone. Notifications falling into this window are
+//! deferred to its end.
+
+use std::fmt;
+
+use chrono::{DateTime, NaiveTime};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::resolve_local;
+
+/// Format of the start and end times of the quiet hours.
+const TIME_FORMAT: &str = "%H:%M";
+
+/// Errors that can occur when parsing quiet hours.
+#[derive(Debug, PartialEq)]
+pub enum QuietHoursError {
+    /// The window is not formatted as `HH:MM-HH:MM` or starts and ends at the same time.
+    InvalidWindow,
+    /// The timezone is not a known IANA timezone name.
+    UnknownTimezone,
+}
+
+/// Daily do-not-disturb window of a user.
+///
+/// The window is expressed in the local time of `timezone` and can span midnight
+/// (e.g. `23:00-08:00`). The start time is included in the window, the end time is not.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::settings::QuietHours;
+/// let quiet_hours = QuietHours::parse("23:00-08:00", "Europe/Paris").unwrap();
+/// assert_eq!(quiet_hours.to_string(), "23:00-08:00 (Europe/Paris)");
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuietHours {
+    /// Local time at which the quiet hours start.
+    pub start: NaiveTime,
+    /// Local time at which the quiet hours end.
+    pub end: NaiveTime,
+    /// Timezone of the start and end times.
+    pub timezone: Tz,
+}
+
+impl QuietHours {
+    /// Parses quiet hours from a window and a timezone name.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - The window formatted as `HH:MM-HH:MM`
+    /// * `timezone` - An IANA timezone name like `Europe/Paris`
+    ///
+    /// # Errors
+    ///
+    /// - [`QuietHoursError::InvalidWindow`] if the window can't be parsed or is empty
+    /// - [`QuietHoursError::UnknownTimezone`] if the timezone is unknown
+    pub fn parse(window: &str, timezone: &str) -> Result<Self, QuietHoursError> {
+        let (start, end) = window
+            .split_once('-')
+            .ok_or(QuietHoursError::InvalidWindow)?;
+
+        let start = NaiveTime::parse_from_str(start.trim(), TIME_FORMAT)
+            .map_err(|_| QuietHoursError::InvalidWindow)?;
+        let end = NaiveTime::parse_from_str(end.trim(), TIME_FORMAT)
+            .map_err(|_| QuietHoursError::InvalidWindow)?;
+
+        if start == end {
+            return Err(QuietHoursError::InvalidWindow);
+        }
+
+        let timezone = timezone
+            .parse::<Tz>()
+            .map_err(|_| QuietHoursError::UnknownTimezone)?;
+
+        Ok(QuietHours {
+            start,
+            end,
+            timezone,
+        })
+    }
+
+    /// Returns the end of the quiet hours if the given time falls into them.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - Unix timestamp (in seconds) to check
+    ///
+    /// # Returns
+    ///
+    /// The Unix timestamp (in seconds) at which the current quiet hours end, or
+    /// `None` if `timestamp` is outside the quiet hours.
+    pub fn get_end_after(&self, timestamp: u64) -> Option<u64> {
+        let local = DateTime::from_timestamp(timestamp as i64, 0)?.with_timezone(&self.timezone);
+        let time = local.time();
+        let date = local.date_naive();
+
+        let end_date = if self.start < self.end {
+            // Window within the same day, e.g. 12:00-14:00
+            if time < self.start || time >= self.end {
+                return None;
+            }
+            date
+        } else if time >= self.start {
+            // Window spanning midnight, before midnight: ends the next day
+            date.succ_opt()?
+        } else if time < self.end {
+            // Window spanning midnight, after midnight: ends the same day
+            date
+        } else {
+            return None;
+        };
+
+        // The end may not exist in the local time when the clocks move forward
+        resolve_local(self.timezone, end_date.and_time(self.end))
+    }
+}
+
+impl fmt::Display for QuietHours {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}-{} ({})",
+            self.start.format(TIME_FORMAT),
+            self.end.format(TIME_FORMAT),
+            self.timezone
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::get_local_timestamp;
+
+    #[test]
+    fn test_parse() {
+        let quiet_hours = QuietHours::parse("23:00-08:00", "Europe/Paris").unwrap();
+
+        assert_eq!(
+            quiet_hours.start,
+            NaiveTime::from_hms_opt(23, 0, 0).unwrap()
+        );
+        assert_eq!(quiet_hours.end, NaiveTime::from_hms_opt(8, 0, 0).unwrap());
+        assert_eq!(quiet_hours.timezone, chrono_tz::Europe::Paris);
+    }
+
+    #[test]
+    fn test_parse_invalid_window() {
+        assert_eq!(
+            QuietHours::parse("23:00", "Europe/Paris"),
+            Err(QuietHoursError::InvalidWindow)
+        );
+        assert_eq!(
+            QuietHours::parse("25:00-08:00", "Europe/Paris"),
+            Err(QuietHoursError::InvalidWindow)
+        );
+        assert_eq!(
+            QuietHours::parse("night-day", "Europe/Paris"),
+            Err(QuietHoursError::InvalidWindow)
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_window() {
+        assert_eq!(
+            QuietHours::parse("08:00-08:00", "Europe/Paris"),
+            Err(QuietHoursError::InvalidWindow)
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_timezone() {
+        assert_eq!(
+            QuietHours::parse("23:00-08:00", "Mars/Olympus_Mons"),
+            Err(QuietHoursError::UnknownTimezone)
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let quiet_hours = QuietHours::parse("23:00-08:00", "Europe/Paris").unwrap();
+        assert_eq!(quiet_hours.to_string(), "23:00-08:00 (Europe/Paris)");
+    }
+
+    #[test]
+    fn test_get_end_after_outside_window() {
+        let quiet_hours = QuietHours::parse("23:00-08:00", "Europe/Paris").unwrap();
+        let timestamp = get_local_timestamp(chrono_tz::Europe::Paris, "2025-03-10 12:00");
+
+        assert_eq!(quiet_hours.get_end_after(timestamp), None);
+    }
+
+    #[test]
+    fn test_get_end_after_before_midnight() {
+        let quiet_hours = QuietHours::parse("23:00-08:00", "Europe/Paris").unwrap();
+        let timestamp = get_local_timestamp(chrono_tz::Europe::Paris, "2025-03-10 23:30");
+
+        assert_eq!(
+            quiet_hours.get_end_after(timestamp),
+            Some(get_local_timestamp(
+                chrono_tz::Europe::Paris,
+                "2025-03-11 08:00"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_get_end_after_after_midnight() {
+        let quiet_hours = QuietHours::parse("23:00-08:00", "Europe/Paris").unwrap();
+        let timestamp = get_local_timestamp(chrono_tz::Europe::Paris, "2025-03-11 03:00");
+
+        assert_eq!(
+            quiet_hours.get_end_after(timestamp),
+            Some(get_local_timestamp(
+                chrono_tz::Europe::Paris,
+                "2025-03-11 08:00"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_get_end_after_same_day_window() {
+        let quiet_hours = QuietHours::parse("12:00-14:00", "America/New_York").unwrap();
+        let inside = get_local_timestamp(chrono_tz::America::New_York, "2025-03-10 13:00");
+        let outside = get_local_timestamp(chrono_tz::America::New_York, "2025-03-10 14:00");
+
+        assert_eq!(
+            quiet_hours.get_end_after(inside),
+            Some(get_local_timestamp(
+                chrono_tz::America::New_York,
+                "2025-03-10 14:00"
+            ))
+        );
+        assert_eq!(quiet_hours.get_end_after(outside), None);
+    }
+
+    #[test]
+    fn test_get_end_after_uses_timezone() {
+        // 23:30 in Paris is 22:30 in UTC during winter
+        let quiet_hours = QuietHours::parse("23:00-08:00", "UTC").unwrap();
+        let timestamp = get_local_timestamp(chrono_tz::Europe::Paris, "2025-01-10 23:30");
+
+        assert_eq!(quiet_hours.get_end_after(timestamp), None);
+    }
+
+    #[test]
+    fn test_get_end_after_end_in_dst_gap() {
+        // Clocks move from 02:00 to 03:00 on 2025-03-30 in Paris
+        let quiet_hours = QuietHours::parse("23:00-02:30", "Europe/Paris").unwrap();
+        let timestamp = get_local_timestamp(chrono_tz::Europe::Paris, "2025-03-29 23:30");
+
+        assert_eq!(
+            quiet_hours.get_end_after(timestamp),
+            Some(get_local_timestamp(
+                chrono_tz::Europe::Paris,
+                "2025-03-30 03:30"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_serialization() {
+        let quiet_hours = QuietHours::parse("23:00-08:00", "Europe/Paris").unwrap();
+
+        let serialized = serde_json::to_string(&quiet_hours).unwrap();
+        let deserialized: QuietHours = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(deserialized, quiet_hours);
+    }
+}
@@ -0,0 +1,41 @@
+//! Room preferences.
+//!
+//! This module provides the [`RoomSettings`] struct holding the preferences shared
+//! by all the users of a Matrix room.
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::NotificationTemplate;
+
+/// Preferences of a Matrix room.
+///
+/// All the preferences are optional, a room without settings gets the default ones.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::settings::RoomSettings;
+/// let room_settings = RoomSettings {
+///     server: Some("main".to_string()),
+///     template: None,
+///     prefix: Some("!mi".to_string()),
+/// };
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoomSettings {
+    /// Name of the TMars server of the game IDs without a server name.
+    ///
+    /// `None` if the room uses the default server of the bot.
+    #[serde(default)]
+    pub server: Option<String>,
+    /// Template of the turn notifications of the alerts of the room.
+    ///
+    /// `None` if the room uses the default notifications.
+    #[serde(default)]
+    pub template: Option<NotificationTemplate>,
+    /// Prefix of the commands in the room, e.g. `!mi`.
+    ///
+    /// `None` if the room uses the prefix of the bot configuration.
+    #[serde(default)]
+    pub prefix: Option<String>,
+}
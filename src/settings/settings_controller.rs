@@ -0,0 +1,273 @@
+//! Settings controller for reading and updating user preferences.
+//!
+//! This module provides the [`SettingsController`] which gives thread-safe access
+//! to the preferences of the users and rooms and persists them on every change.
+
+use std::sync::Arc;
+
+use log::info;
+use tokio::sync::Mutex;
+
+use crate::settings::{RoomSettings, SettingsLoader, UserSettings, user_settings::Settings};
+
+/// Manages the preferences of the users and rooms.
+///
+/// Unlike alerts, settings rarely change, so they are persisted to disk as soon
+/// as they are updated instead of periodically.
+///
+/// # Thread Safety
+///
+/// All public methods are async and use internal locking, the controller can be
+/// shared between tasks behind an [`Arc`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use miou::settings::SettingsController;
+///
+/// # async fn example() {
+/// let controller = SettingsController::new("settings.json".to_string()).await;
+/// let user_settings = controller.get_user_settings("@user:example.com").await;
+/// # }
+/// ```
+pub struct SettingsController {
+    /// Thread-safe reference to the settings of all users and rooms
+    settings: Arc<Mutex<Settings>>,
+    /// Loader for persisting and loading settings from disk
+    settings_loader: SettingsLoader,
+}
+
+impl SettingsController {
+    /// Creates a new `SettingsController` and loads existing settings from disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings_path` - Path to the JSON file where settings are persisted
+    ///
+    /// # Returns
+    ///
+    /// A new `SettingsController` instance with settings loaded from the specified file.
+    /// If the file doesn't exist or is corrupted, starts with the default settings.
+    pub async fn new(settings_path: String) -> Self {
+        let settings_loader = SettingsLoader::new(settings_path);
+        let settings = Arc::new(Mutex::new(settings_loader.load().await));
+
+        SettingsController {
+            settings,
+            settings_loader,
+        }
+    }
+
+    /// Returns the settings of a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Matrix user ID
+    ///
+    /// # Returns
+    ///
+    /// The settings of the user, or the default settings if the user has none.
+    pub async fn get_user_settings(&self, user_id: &str) -> UserSettings {
+        self.settings
+            .lock()
+            .await
+            .users
+            .get(user_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Replaces the settings of a user and persists them to disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Matrix user ID
+    /// * `user_settings` - The new settings of the user
+    pub async fn update_user_settings(&self, user_id: &str, user_settings: &UserSettings) {
+        let mut settings = self.settings.lock().await;
+
+        settings
+            .users
+            .insert(user_id.to_owned(), user_settings.to_owned());
+        self.settings_loader.persist_settings(&settings).await;
+
+        info!("updated settings of user {}", user_id);
+    }
+
+    /// Returns the settings of a room.
+    ///
+    /// # Arguments
+    ///
+    /// * `room_id` - The Matrix room ID
+    ///
+    /// # Returns
+    ///
+    /// The settings of the room, or the default settings if the room has none.
+    pub async fn get_room_settings(&self, room_id: &str) -> RoomSettings {
+        self.settings
+            .lock()
+            .await
+            .rooms
+            .get(room_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Replaces the settings of a room and persists them to disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `room_id` - The Matrix room ID
+    /// * `room_settings` - The new settings of the room
+    pub async fn update_room_settings(&self, room_id: &str, room_settings: &RoomSettings) {
+        let mut settings = self.settings.lock().await;
+
+        settings
+            .rooms
+            .insert(room_id.to_owned(), room_settings.to_owned());
+        self.settings_loader.persist_settings(&settings).await;
+
+        info!("updated settings of room {}", room_id);
+    }
+
+    /// Returns the end of the user's quiet hours if the given time falls into them.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The Matrix user ID
+    /// * `timestamp` - Unix timestamp (in seconds) to check
+    ///
+    /// # Returns
+    ///
+    /// The Unix timestamp (in seconds) at which the quiet hours end, or `None` if
+    /// the user has no quiet hours or `timestamp` is outside of them.
+    pub async fn get_quiet_hours_end(&self, user_id: &str, timestamp: u64) -> Option<u64> {
+        self.settings
+            .lock()
+            .await
+            .users
+            .get(user_id)?
+            .quiet_hours
+            .as_ref()?
+            .get_end_after(timestamp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::{NotificationTemplate, QuietHours};
+    use tempfile::NamedTempFile;
+
+    fn create_test_user_settings() -> UserSettings {
+        UserSettings {
+            quiet_hours: QuietHours::parse("00:00-23:59", "UTC").ok(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_user_settings_default() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let controller = SettingsController::new(path).await;
+
+        let user_settings = controller.get_user_settings("@user1:example.com").await;
+
+        assert_eq!(user_settings, UserSettings::default());
+    }
+
+    #[tokio::test]
+    async fn test_update_user_settings() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let controller = SettingsController::new(path).await;
+
+        controller
+            .update_user_settings("@user1:example.com", &create_test_user_settings())
+            .await;
+
+        assert_eq!(
+            controller.get_user_settings("@user1:example.com").await,
+            create_test_user_settings()
+        );
+        assert_eq!(
+            controller.get_user_settings("@user2:example.com").await,
+            UserSettings::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_user_settings_persists() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+
+        let controller1 = SettingsController::new(path.clone()).await;
+        controller1
+            .update_user_settings("@user1:example.com", &create_test_user_settings())
+            .await;
+
+        // A new controller loads the persisted settings
+        let controller2 = SettingsController::new(path).await;
+        assert_eq!(
+            controller2.get_user_settings("@user1:example.com").await,
+            create_test_user_settings()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_room_settings_persists() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let room_settings = RoomSettings {
+            server: Some("main".to_string()),
+            template: NotificationTemplate::parse("{player}, it's your turn!").ok(),
+            prefix: Some("!mi".to_string()),
+        };
+
+        let controller1 = SettingsController::new(path.clone()).await;
+        assert_eq!(
+            controller1.get_room_settings("!room1:example.com").await,
+            RoomSettings::default()
+        );
+        controller1
+            .update_room_settings("!room1:example.com", &room_settings)
+            .await;
+
+        // A new controller loads the persisted settings
+        let controller2 = SettingsController::new(path).await;
+        assert_eq!(
+            controller2.get_room_settings("!room1:example.com").await,
+            room_settings
+        );
+        assert_eq!(
+            controller2.get_room_settings("!room2:example.com").await,
+            RoomSettings::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_quiet_hours_end() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let controller = SettingsController::new(path).await;
+
+        controller
+            .update_user_settings("@user1:example.com", &create_test_user_settings())
+            .await;
+
+        // 2025-03-10T12:00:00Z is in the quiet hours, which end at 23:59
+        assert_eq!(
+            controller
+                .get_quiet_hours_end("@user1:example.com", 1_741_608_000)
+                .await,
+            Some(1_741_651_140)
+        );
+        // No quiet hours for this user
+        assert_eq!(
+            controller
+                .get_quiet_hours_end("@user2:example.com", 1_741_608_000)
+                .await,
+            None
+        );
+    }
+}
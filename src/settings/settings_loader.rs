@@ -0,0 +1,143 @@
+//! Settings persistence layer for loading and saving settings to disk.
+//!
+//! This module provides the [`SettingsLoader`] for persisting user preferences
+//! between bot restarts. Settings are serialized to JSON and stored in a file.
+
+use log::{error, info, warn};
+use tokio::fs;
+
+use crate::settings::user_settings::Settings;
+
+/// Handles loading and persisting settings to disk.
+///
+/// Like the alerts loader, it is fault-tolerant: if loading fails (file missing
+/// or corrupted), it returns the default settings rather than panicking.
+///
+/// # Examples
+///
+/// ```no_run
+/// use miou::settings::SettingsLoader;
+///
+/// # async fn example() {
+/// let loader = SettingsLoader::new("settings.json".to_string());
+///
+/// // Load existing settings or get the default ones
+/// let settings = loader.load().await;
+///
+/// // Later, persist the settings
+/// loader.persist_settings(&settings).await;
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct SettingsLoader {
+    /// Path to the JSON file where settings are stored.
+    path: String,
+}
+
+impl SettingsLoader {
+    /// Creates a new `SettingsLoader` for the specified file path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path where settings will be loaded from and saved to
+    pub fn new(path: String) -> Self {
+        SettingsLoader { path }
+    }
+
+    /// Loads settings from disk.
+    ///
+    /// # Returns
+    ///
+    /// The persisted settings, or the default settings if the file doesn't exist
+    /// or cannot be deserialized.
+    pub async fn load(&self) -> Settings {
+        let Ok(serialized_settings) = fs::read_to_string(&self.path).await else {
+            warn!("no persisted settings found, starting with default settings");
+            return Settings::default();
+        };
+
+        let Ok(settings) = serde_json::from_str(&serialized_settings) else {
+            error!("failed to deserialize persisted settings, starting with default settings");
+            return Settings::default();
+        };
+
+        info!("loaded persisted settings {}", serialized_settings);
+
+        settings
+    }
+
+    /// Persists the settings to disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - Reference to the settings to persist
+    ///
+    /// # Error Handling
+    ///
+    /// Errors are logged but not propagated, allowing the bot to continue operating
+    /// even if persistence fails.
+    pub async fn persist_settings(&self, settings: &Settings) {
+        let serialized_settings = match serde_json::to_string(settings) {
+            Ok(serialized) => serialized,
+            Err(e) => {
+                error!("failed to serialize settings: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = fs::write(&self.path, &serialized_settings).await {
+            error!("failed to persist settings: {}", e);
+            return;
+        }
+
+        info!("persisted settings");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::{QuietHours, UserSettings};
+    use tempfile::NamedTempFile;
+
+    #[tokio::test]
+    async fn test_load_nonexistent_file_returns_default_settings() {
+        let loader = SettingsLoader::new("nonexistent_file.json".to_string());
+        let settings = loader.load().await;
+
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[tokio::test]
+    async fn test_persist_and_load_settings() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let loader = SettingsLoader::new(path);
+
+        let mut settings = Settings::default();
+        settings.users.insert(
+            "@user1:example.com".to_string(),
+            UserSettings {
+                quiet_hours: QuietHours::parse("23:00-08:00", "Europe/Paris").ok(),
+            },
+        );
+
+        loader.persist_settings(&settings).await;
+
+        let loaded_settings = loader.load().await;
+        assert_eq!(loaded_settings, settings);
+    }
+
+    #[tokio::test]
+    async fn test_load_corrupted_json_returns_default_settings() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+
+        fs::write(&path, "{ this is not valid json ").await.unwrap();
+
+        let loader = SettingsLoader::new(path);
+        let settings = loader.load().await;
+
+        assert_eq!(settings, Settings::default());
+    }
+}
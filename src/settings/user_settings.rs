@@ -0,0 +1,43 @@
+//! User preferences.
+//!
+//! This module provides the [`UserSettings`] struct holding the preferences of a
+//! Matrix user, and the [`Settings`] struct gathering the preferences of all users
+//! and rooms.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::{QuietHours, RoomSettings};
+
+/// Preferences of a Matrix user.
+///
+/// All the preferences are optional, a user without settings gets the default ones.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::settings::{QuietHours, UserSettings};
+/// let user_settings = UserSettings {
+///     quiet_hours: QuietHours::parse("23:00-08:00", "Europe/Paris").ok(),
+/// };
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserSettings {
+    /// Daily window during which the user must not be notified.
+    ///
+    /// `None` if the user can be notified at any time.
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHours>,
+}
+
+/// Preferences of all the users and rooms, as persisted on disk.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    /// Preferences indexed by Matrix user ID.
+    #[serde(default)]
+    pub users: HashMap<String, UserSettings>,
+    /// Preferences indexed by Matrix room ID.
+    #[serde(default)]
+    pub rooms: HashMap<String, RoomSettings>,
+}
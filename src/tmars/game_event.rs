@@ -0,0 +1,266 @@
+//! Typed changes of the synchronized games.
+//!
+//! This module defines the [`GameEvent`] enum emitted by [`TMarsSync`](crate::tmars::TMarsSync)
+//! after each synchronization, so that consumers don't have to compare successive
+//! snapshots of the games themselves.
+
+use std::collections::HashMap;
+
+use crate::tmars::structs::{Game, Phase, PlayerScore};
+
+/// A change of a game between two synchronizations.
+///
+/// Ended games are filtered out during the synchronization: a game seen ending is
+/// reported as [`GameEvent::GameEnded`] with its final scores, instead of
+/// [`GameEvent::GameRemoved`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameEvent {
+    /// A game appeared on the server
+    GameAdded {
+        /// ID of the game
+        game_id: String,
+    },
+    /// A game disappeared from the server
+    GameRemoved {
+        /// ID of the game
+        game_id: String,
+    },
+    /// A game moved to the end phase
+    GameEnded {
+        /// ID of the game
+        game_id: String,
+        /// Final scores of the players, from the highest to the lowest,
+        /// empty if they couldn't be fetched
+        scores: Vec<PlayerScore>,
+    },
+    /// A game moved to another phase
+    PhaseChanged {
+        /// ID of the game
+        game_id: String,
+        /// Phase during the previous synchronization
+        previous_phase: Phase,
+        /// Current phase
+        phase: Phase,
+    },
+    /// The game started waiting for a player
+    TurnStarted {
+        /// ID of the game
+        game_id: String,
+        /// ID of the player the game is waiting for
+        player_id: String,
+    },
+    /// The game stopped waiting for a player
+    TurnEnded {
+        /// ID of the game
+        game_id: String,
+        /// ID of the player the game was waiting for
+        player_id: String,
+    },
+}
+
+impl GameEvent {
+    /// Returns the ID of the game the event is about.
+    pub fn game_id(&self) -> &str {
+        match self {
+            GameEvent::GameAdded { game_id }
+            | GameEvent::GameRemoved { game_id }
+            | GameEvent::GameEnded { game_id, .. }
+            | GameEvent::PhaseChanged { game_id, .. }
+            | GameEvent::TurnStarted { game_id, .. }
+            | GameEvent::TurnEnded { game_id, .. } => game_id,
+        }
+    }
+
+    /// Compares two snapshots of the games and returns the events in between.
+    ///
+    /// Games are compared in the order of their IDs. For each game, the phase change
+    /// comes first, then the ended turns and the started turns, in the order of the
+    /// players of the game. No turn event is emitted for added games.
+    ///
+    /// Disappeared games are reported as [`GameEvent::GameRemoved`], telling ended
+    /// games apart is up to the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous_games` - The games during the previous synchronization
+    /// * `games` - The games during the current synchronization
+    ///
+    /// # Returns
+    ///
+    /// The events turning `previous_games` into `games`.
+    pub fn diff(
+        previous_games: &HashMap<String, Game>,
+        games: &HashMap<String, Game>,
+    ) -> Vec<Self> {
+        let mut game_ids: Vec<&String> = previous_games.keys().chain(games.keys()).collect();
+        game_ids.sort();
+        game_ids.dedup();
+
+        let mut events = Vec::new();
+        for game_id in game_ids {
+            match (previous_games.get(game_id), games.get(game_id)) {
+                (None, Some(_)) => events.push(GameEvent::GameAdded {
+                    game_id: game_id.clone(),
+                }),
+                (Some(_), None) => events.push(GameEvent::GameRemoved {
+                    game_id: game_id.clone(),
+                }),
+                (Some(previous_game), Some(game)) => {
+                    events.extend(Self::diff_game(previous_game, game))
+                }
+                (None, None) => {}
+            }
+        }
+
+        events
+    }
+
+    /// Returns the phase and turn events between two snapshots of a game.
+    fn diff_game(previous_game: &Game, game: &Game) -> Vec<Self> {
+        let mut events = Vec::new();
+
+        if previous_game.phase != game.phase {
+            events.push(GameEvent::PhaseChanged {
+                game_id: game.id.clone(),
+                previous_phase: previous_game.phase.clone(),
+                phase: game.phase.clone(),
+            });
+        }
+
+        for player in &game.players {
+            let was_waited = previous_game.waited_players.contains(&player.id);
+            if was_waited && !game.waited_players.contains(&player.id) {
+                events.push(GameEvent::TurnEnded {
+                    game_id: game.id.clone(),
+                    player_id: player.id.clone(),
+                });
+            }
+        }
+
+        for player in &game.players {
+            let was_waited = previous_game.waited_players.contains(&player.id);
+            if !was_waited && game.waited_players.contains(&player.id) {
+                events.push(GameEvent::TurnStarted {
+                    game_id: game.id.clone(),
+                    player_id: player.id.clone(),
+                });
+            }
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_game(id: &str, phase: Phase, waited_players: &[&str]) -> Game {
+        Game::test(id, phase, waited_players)
+    }
+
+    fn create_games_map(games: Vec<Game>) -> HashMap<String, Game> {
+        games
+            .into_iter()
+            .map(|game| (game.id.clone(), game))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_no_change() {
+        let games = create_games_map(vec![create_game("game1", Phase::Action, &["player1"])]);
+
+        assert!(GameEvent::diff(&games, &games.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_game_added_and_removed() {
+        let previous_games =
+            create_games_map(vec![create_game("game1", Phase::Action, &["player1"])]);
+        let games = create_games_map(vec![create_game("game2", Phase::Action, &["player1"])]);
+
+        assert_eq!(
+            GameEvent::diff(&previous_games, &games),
+            vec![
+                GameEvent::GameRemoved {
+                    game_id: "game1".to_string()
+                },
+                GameEvent::GameAdded {
+                    game_id: "game2".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_turn_change() {
+        let previous_games =
+            create_games_map(vec![create_game("game1", Phase::Action, &["player1"])]);
+        let games = create_games_map(vec![create_game("game1", Phase::Action, &["player2"])]);
+
+        assert_eq!(
+            GameEvent::diff(&previous_games, &games),
+            vec![
+                GameEvent::TurnEnded {
+                    game_id: "game1".to_string(),
+                    player_id: "player1".to_string(),
+                },
+                GameEvent::TurnStarted {
+                    game_id: "game1".to_string(),
+                    player_id: "player2".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_phase_change() {
+        let previous_games = create_games_map(vec![create_game("game1", Phase::Action, &[])]);
+        let games = create_games_map(vec![create_game(
+            "game1",
+            Phase::Research,
+            &["player1", "player2"],
+        )]);
+
+        assert_eq!(
+            GameEvent::diff(&previous_games, &games),
+            vec![
+                GameEvent::PhaseChanged {
+                    game_id: "game1".to_string(),
+                    previous_phase: Phase::Action,
+                    phase: Phase::Research,
+                },
+                GameEvent::TurnStarted {
+                    game_id: "game1".to_string(),
+                    player_id: "player1".to_string(),
+                },
+                GameEvent::TurnStarted {
+                    game_id: "game1".to_string(),
+                    player_id: "player2".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_added_game_has_no_turn_events() {
+        let games = create_games_map(vec![create_game("game1", Phase::Action, &["player1"])]);
+
+        assert_eq!(
+            GameEvent::diff(&HashMap::new(), &games),
+            vec![GameEvent::GameAdded {
+                game_id: "game1".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_game_id() {
+        let event = GameEvent::TurnStarted {
+            game_id: "game1".to_string(),
+            player_id: "player1".to_string(),
+        };
+
+        assert_eq!(event.game_id(), "game1");
+    }
+}
@@ -5,32 +5,45 @@
 //!
 //! # Modules
 //!
+//! - `game_event` - Typed changes of the games emitted after each synchronization
 //! - `requester` - HTTP client for making API requests to the Terraforming Mars server
 //! - `response_structs` - Internal data structures for API responses
-//! - `structs` - Public data structures representing games, players, and game states
+//! - `server_health` - Retries with backoff of the synchronization of a failing server
+//! - `structs` - Public data structures representing games, players, scores, and game progress
 //! - `sync` - Synchronization logic for fetching and updating game data
 //!
 //! # Examples
 //!
 //! ```no_run
+//! use miou::config::TMarsRequests;
 //! use miou::tmars::TMarsRequester;
 //!
 //! let requester = TMarsRequester::new(
-//!     "server_id".to_string(),
-//!     "https://tmars.example.com".to_string()
+//!     "https://tmars.example.com",
+//!     "server_id",
+//!     &TMarsRequests::default(),
 //! );
 //! // Fetch games and sync state
 //! ```
 
+mod game_event;
 mod requester;
 mod response_structs;
+mod server_health;
 mod structs;
 mod sync;
 
+use std::fmt;
+
+use reqwest::StatusCode;
+
+pub use crate::tmars::game_event::GameEvent;
 pub use crate::tmars::requester::TMarsRequester;
-pub use crate::tmars::structs::Game;
-#[cfg(test)]
-pub use crate::tmars::structs::{Phase, Player};
+pub use crate::tmars::server_health::ServerHealth;
+pub use crate::tmars::structs::{
+    GAME_ID_SEPARATOR, Game, GameProgress, Phase, Player, PlayerScore, get_game_server_name,
+    is_namespaced_game_id, namespace_game_id,
+};
 pub use crate::tmars::sync::TMarsSync;
 
 /// Errors that can occur during synchronization with the TMars API.
@@ -40,12 +53,50 @@ pub use crate::tmars::sync::TMarsSync;
 ///
 /// # Variants
 ///
-/// * `AccessError` - Authentication or authorization failure (HTTP 401/403)
+/// * `Access` - Authentication or authorization failure (HTTP 401/403)
+/// * `Network` - The server can't be reached or fails to answer
+/// * `Timeout` - The server doesn't answer in time
+/// * `Decode` - The answer of the server can't be decoded
 #[derive(Debug)]
 pub enum SyncError {
     /// Authentication or authorization failure.
     ///
     /// Returned when the TMars API responds with HTTP 401 (Unauthorized) or
     /// HTTP 403 (Forbidden) status codes.
-    AccessError,
+    Access,
+    /// The server can't be reached, or responds with another error status code.
+    Network(String),
+    /// The server doesn't answer before the request timeout.
+    Timeout,
+    /// The response of the server can't be decoded.
+    Decode(String),
+}
+
+impl From<reqwest::Error> for SyncError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            return SyncError::Timeout;
+        }
+        if matches!(
+            err.status(),
+            Some(StatusCode::FORBIDDEN | StatusCode::UNAUTHORIZED)
+        ) {
+            return SyncError::Access;
+        }
+        if err.is_decode() {
+            return SyncError::Decode(err.to_string());
+        }
+        SyncError::Network(err.to_string())
+    }
+}
+
+impl fmt::Display for SyncError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SyncError::Access => write!(f, "access denied"),
+            SyncError::Network(message) => write!(f, "network error: {}", message),
+            SyncError::Timeout => write!(f, "request timed out"),
+            SyncError::Decode(message) => write!(f, "invalid response: {}", message),
+        }
+    }
 }
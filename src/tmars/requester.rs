@@ -9,21 +9,46 @@
 //! (4xx and 5xx status codes) into Rust errors. This ensures that authentication
 //! failures (401), authorization failures (403), and server errors (5xx) are
 //! properly detected and can be handled by calling code.
+//!
+//! # Request Limits
+//!
+//! The requests are limited by the [`TMarsRequests`] configuration: a request times
+//! out after a while, and waits for a slot while too many requests are in flight or
+//! the requests per second budget is spent.
+
+use std::time::Duration;
 
 use log::{debug, info};
 use mockall::automock;
 use reqwest::{Client, Error};
-
-use crate::tmars::response_structs::{GameDetail, GameResponse, WaitingForResponse};
+use tokio::{
+    sync::{Mutex, Semaphore, SemaphorePermit},
+    time::{self, Instant},
+};
+
+use crate::{
+    config::TMarsRequests,
+    tmars::{
+        response_structs::{GameDetail, GameResponse, SpectatorResponse, WaitingForResponse},
+        structs::GameAge,
+    },
+};
 
 /// HTTP client for requesting data from the Terraforming Mars server.
 ///
 /// # Examples
 ///
 /// ```no_run
-/// let mars_requester = TMarsRequester::new("your_server_id".to_string(), "http://your_tmars_server_url".to_string());
-/// let games = mars_requester.get_games().await.unwrap();
-/// println!("Games: {:?}", games);
+/// use miou::config::TMarsRequests;
+/// use miou::tmars::{TMarsRequester, TMarsSync};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let mars_requester = TMarsRequester::new("http://your_tmars_server_url", "your_server_id", &TMarsRequests::default());
+/// let mut tmars_sync = TMarsSync::new("main", mars_requester);
+/// tmars_sync.sync().await.unwrap();
+/// println!("Games: {:?}", tmars_sync.get_games());
+/// # }
 /// ```
 pub struct TMarsRequester {
     /// Terraforming mars secret server id
@@ -32,8 +57,14 @@ pub struct TMarsRequester {
     server_id: String,
     /// Terraforming mars server url
     url: String,
-    /// HTTP client
+    /// HTTP client, with the request timeout
     client: Client,
+    /// Slots of the requests in flight
+    in_flight: Semaphore,
+    /// Earliest start of the next request, to respect the requests per second budget
+    next_request_at: Mutex<Instant>,
+    /// Minimum duration between the starts of two requests
+    request_spacing: Duration,
 }
 
 /// Trait for making requests to the TMars server.
@@ -59,8 +90,27 @@ pub trait Requester {
     ///
     /// Returns an error with status code if the server responds with 4xx or 5xx.
     async fn get_waited_players(&self, player_id: &str) -> Result<WaitingForResponse, Error>;
+    /// Fetches the list of players being waited for in a game, and whether the game
+    /// changed since the given age.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with status code if the server responds with 4xx or 5xx.
+    async fn get_waited_players_since(
+        &self,
+        spectator_id: &str,
+        game_age: &GameAge,
+    ) -> Result<WaitingForResponse, Error>;
+    /// Fetches the state of a game as seen by its spectator.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with status code if the server responds with 4xx or 5xx.
+    async fn get_spectator(&self, spectator_id: &str) -> Result<SpectatorResponse, Error>;
     /// Constructs the URL for a specific player.
     fn get_player_url(&self, player_id: &str) -> String;
+    /// Constructs the URL of the spectator page of a game.
+    fn get_spectator_url(&self, spectator_id: &str) -> String;
 }
 
 impl TMarsRequester {
@@ -70,14 +120,52 @@ impl TMarsRequester {
     ///
     /// * `url` - The base URL of the Terraforming Mars server.
     /// * `server_id` - The secret server ID for authentication.
-    pub fn new(url: &str, server_id: &str) -> Self {
-        let client = reqwest::Client::new();
+    /// * `requests` - The limits of the requests to the server.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client can't be initialized, like [`Client::new`].
+    pub fn new(url: &str, server_id: &str, requests: &TMarsRequests) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(requests.timeout))
+            .build()
+            .expect("failed to initialize the http client");
         TMarsRequester {
             server_id: server_id.to_string(),
             url: url.to_string(),
             client,
+            in_flight: Semaphore::new(requests.max_in_flight),
+            next_request_at: Mutex::new(Instant::now()),
+            request_spacing: Duration::from_secs(1) / requests.per_second.max(1),
         }
     }
+
+    /// Waits for a slot to send a request.
+    ///
+    /// A slot is free when less than the maximum number of requests are in flight,
+    /// and once the previous request started long enough ago for the requests per
+    /// second budget.
+    ///
+    /// # Returns
+    ///
+    /// The permit of the slot, to hold until the response is read.
+    async fn acquire_request_slot(&self) -> SemaphorePermit<'_> {
+        let permit = self
+            .in_flight
+            .acquire()
+            .await
+            .expect("the semaphore of the requests is never closed");
+
+        let request_at = {
+            let mut next_request_at = self.next_request_at.lock().await;
+            let request_at = (*next_request_at).max(Instant::now());
+            *next_request_at = request_at + self.request_spacing;
+            request_at
+        };
+        time::sleep_until(request_at).await;
+
+        permit
+    }
 }
 
 impl Requester for TMarsRequester {
@@ -104,10 +192,11 @@ impl Requester for TMarsRequester {
     /// println!("Games: {:?}", games);
     /// ```
     async fn get_games(&self) -> Result<Vec<GameResponse>, Error> {
-        let url = format!("{}/api/games", &self.url);
+        let url = format!("{}/api/games", self.url);
         info!("request games");
-        debug!("request {}?serverId={}", &url, &self.server_id);
+        debug!("request {}?serverId={}", url, self.server_id);
 
+        let _permit = self.acquire_request_slot().await;
         let game_responses: Vec<GameResponse> = self
             .client
             .get(&url)
@@ -118,7 +207,7 @@ impl Requester for TMarsRequester {
             .json()
             .await?;
 
-        debug!("response from {} -> {:?}", &url, &game_responses);
+        debug!("response from {} -> {:?}", url, game_responses);
 
         Ok(game_responses)
     }
@@ -153,10 +242,11 @@ impl Requester for TMarsRequester {
     /// println!("Game detail: {:?}", game_detail);
     /// ```
     async fn get_game_details(&self, game_id: &str) -> Result<GameDetail, Error> {
-        let url = format!("{}/api/game", &self.url);
-        info!("request game details of {}", &game_id);
-        debug!("request {}?id={}", &url, &game_id);
+        let url = format!("{}/api/game", self.url);
+        info!("request game details of {}", game_id);
+        debug!("request {}?id={}", url, game_id);
 
+        let _permit = self.acquire_request_slot().await;
         let game_detail: GameDetail = self
             .client
             .get(&url)
@@ -167,10 +257,7 @@ impl Requester for TMarsRequester {
             .json()
             .await?;
 
-        debug!(
-            "response from {}?id={} -> {:?}",
-            &url, &game_id, &game_detail
-        );
+        debug!("response from {}?id={} -> {:?}", url, game_id, game_detail);
 
         Ok(game_detail)
     }
@@ -200,17 +287,82 @@ impl Requester for TMarsRequester {
     /// println!("Waiting for players: {:?}", waiting_for);
     /// ```
     async fn get_waited_players(&self, player_id: &str) -> Result<WaitingForResponse, Error> {
-        let url = format!("{}/api/waitingfor", &self.url);
+        let url = format!("{}/api/waitingfor", self.url);
+        info!("request list of waited players for spectator {}", player_id);
+        debug!("request {}?id={}", url, player_id);
+
+        let _permit = self.acquire_request_slot().await;
+        let waiting_for_response: WaitingForResponse = self
+            .client
+            .get(&url)
+            .query(&[("id", player_id)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        debug!(
+            "response from {}?id={} -> {:?}",
+            url, player_id, waiting_for_response
+        );
+
+        Ok(waiting_for_response)
+    }
+
+    /// Request `/api/waitingfor?id={spectatorId}&gameAge={gameAge}&undoCount={undoCount}` to get
+    /// the waited players and whether the game changed since the given age.
+    ///
+    /// This api call returns a json object with the result of the comparison:
+    /// ```
+    /// {
+    ///   result: "WAIT",
+    ///   waitingFor: ["green", "red"]
+    /// }
+    /// ```
+    /// The result is `"REFRESH"` if the game changed since the given age, `"WAIT"` otherwise.
+    /// This method transforms this json into a [`WaitingForResponse`].
+    ///
+    /// # Arguments
+    ///
+    /// * `spectator_id` - The spectator id of the game.
+    /// * `game_age` - The age of the game seen during the previous request.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`reqwest::Error`] with status code for HTTP errors (4xx, 5xx).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let game_age = GameAge { game_age: 42, undo_count: 0 };
+    /// let waiting_for = tmars_requester.get_waited_players_since("spectator_id", &game_age).await.unwrap();
+    /// println!("Game changed: {:?}", waiting_for.result);
+    /// ```
+    async fn get_waited_players_since(
+        &self,
+        spectator_id: &str,
+        game_age: &GameAge,
+    ) -> Result<WaitingForResponse, Error> {
+        let url = format!("{}/api/waitingfor", self.url);
         info!(
-            "request list of waited players for spectator {}",
-            &player_id
+            "request list of waited players for spectator {} since age {}",
+            spectator_id, game_age.game_age
+        );
+        debug!(
+            "request {}?id={}&gameAge={}&undoCount={}",
+            url, spectator_id, game_age.game_age, game_age.undo_count
         );
-        debug!("request {}?id={}", &url, &player_id);
 
+        let _permit = self.acquire_request_slot().await;
         let waiting_for_response: WaitingForResponse = self
             .client
             .get(&url)
-            .query(&[("id", player_id)])
+            .query(&[
+                ("id", spectator_id.to_owned()),
+                ("gameAge", game_age.game_age.to_string()),
+                ("undoCount", game_age.undo_count.to_string()),
+            ])
             .send()
             .await?
             .error_for_status()?
@@ -219,20 +371,118 @@ impl Requester for TMarsRequester {
 
         debug!(
             "response from {}?id={} -> {:?}",
-            &url, &player_id, &waiting_for_response
+            url, spectator_id, waiting_for_response
         );
 
         Ok(waiting_for_response)
     }
 
+    /// Request `/api/spectator?id={spectatorId}` to get the state of a game as seen by its spectator.
+    ///
+    /// This api call returns a json object with the public state of the game and its players:
+    /// ```
+    /// {
+    ///   game: {
+    ///     generation: 5, temperature: -12, oxygenLevel: 6, oceans: 4, venusScaleLevel: 10,
+    ///     gameOptions: { expansions: { venus: true, ... }, ... },
+    ///     ...
+    ///   },
+    ///   players: [
+    ///     { name: "Alice", color: "green", terraformRating: 32, victoryPointsBreakdown: { total: 85, ... }, ... },
+    ///     { name: "Bob", color: "red", terraformRating: 28, victoryPointsBreakdown: { total: 72, ... }, ... }
+    ///   ],
+    ///   ...
+    /// }
+    /// ```
+    /// This method transforms this json into a [`SpectatorResponse`].
+    ///
+    /// # Arguments
+    ///
+    /// * `spectator_id` - The spectator id of the game.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`reqwest::Error`] with status code for HTTP errors (4xx, 5xx).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let spectator = tmars_requester.get_spectator("spectator_id").await.unwrap();
+    /// println!("Players: {:?}", spectator.players);
+    /// ```
+    async fn get_spectator(&self, spectator_id: &str) -> Result<SpectatorResponse, Error> {
+        let url = format!("{}/api/spectator", self.url);
+        info!("request spectator view {}", spectator_id);
+        debug!("request {}?id={}", url, spectator_id);
+
+        let _permit = self.acquire_request_slot().await;
+        let spectator_response: SpectatorResponse = self
+            .client
+            .get(&url)
+            .query(&[("id", spectator_id)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        debug!(
+            "response from {}?id={} -> {:?}",
+            url, spectator_id, spectator_response
+        );
+
+        Ok(spectator_response)
+    }
+
     fn get_player_url(&self, player_id: &str) -> String {
-        format!("{}/player?id={}", &self.url, player_id)
+        format!("{}/player?id={}", self.url, player_id)
+    }
+
+    fn get_spectator_url(&self, spectator_id: &str) -> String {
+        format!("{}/spectator?id={}", self.url, spectator_id)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tmars::response_structs::WaitingForResult;
+
+    #[tokio::test]
+    async fn test_acquire_request_slot_limits_in_flight_requests() {
+        let requests = TMarsRequests {
+            max_in_flight: 2,
+            timeout: 30,
+            per_second: 1000,
+        };
+        let tmars_requester = TMarsRequester::new("http://tmars.server", "server_id", &requests);
+
+        let first_permit = tmars_requester.acquire_request_slot().await;
+        let _second_permit = tmars_requester.acquire_request_slot().await;
+        assert_eq!(tmars_requester.in_flight.available_permits(), 0);
+
+        // The slot is freed when the permit is dropped
+        drop(first_permit);
+        assert_eq!(tmars_requester.in_flight.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_request_slot_respects_requests_per_second() {
+        let requests = TMarsRequests {
+            max_in_flight: 8,
+            timeout: 30,
+            per_second: 20,
+        };
+        let tmars_requester = TMarsRequester::new("http://tmars.server", "server_id", &requests);
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            let _permit = tmars_requester.acquire_request_slot().await;
+        }
+
+        // The first request starts right away, the next ones every 50 milliseconds
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
 
     #[tokio::test]
     async fn test_get_games() {
@@ -253,7 +503,7 @@ mod tests {
             .create_async()
             .await;
 
-        let tmars_requester = TMarsRequester::new(&url, server_id);
+        let tmars_requester = TMarsRequester::new(&url, server_id, &TMarsRequests::default());
         let games = tmars_requester.get_games().await.unwrap();
         assert_eq!(games.len(), 2);
         assert_eq!(games.first().unwrap().game_id, "game1");
@@ -279,7 +529,7 @@ mod tests {
             .create_async()
             .await;
 
-        let tmars_requester = TMarsRequester::new(&url, "server_id");
+        let tmars_requester = TMarsRequester::new(&url, "server_id", &TMarsRequests::default());
         let game = tmars_requester.get_game_details(game_id).await.unwrap();
         assert_eq!(game.id, game_id);
         assert_eq!(game.spectator_id, "specId");
@@ -312,7 +562,7 @@ mod tests {
             .create_async()
             .await;
 
-        let tmars_requester = TMarsRequester::new(&url, "server_id");
+        let tmars_requester = TMarsRequester::new(&url, "server_id", &TMarsRequests::default());
         let waiting_for_response = tmars_requester
             .get_waited_players(spectator_id)
             .await
@@ -322,15 +572,95 @@ mod tests {
         assert_eq!(waiting_for_response.waiting_for.last().unwrap(), "red");
     }
 
+    #[tokio::test]
+    async fn test_get_waited_players_since() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+        let body = r#"{"result": "REFRESH", "waitingFor": ["green"]}"#;
+
+        server
+            .mock("GET", "/api/waitingfor")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("id".to_owned(), "specId".to_owned()),
+                mockito::Matcher::UrlEncoded("gameAge".to_owned(), "42".to_owned()),
+                mockito::Matcher::UrlEncoded("undoCount".to_owned(), "1".to_owned()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let tmars_requester = TMarsRequester::new(&url, "server_id", &TMarsRequests::default());
+        let game_age = GameAge {
+            game_age: 42,
+            undo_count: 1,
+        };
+        let waiting_for_response = tmars_requester
+            .get_waited_players_since("specId", &game_age)
+            .await
+            .unwrap();
+        assert_eq!(waiting_for_response.result, Some(WaitingForResult::Refresh));
+        assert_eq!(waiting_for_response.waiting_for, vec!["green"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_spectator() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+        let spectator_id = "specId";
+        let body = r#"{"id": "specId", "game": {"generation": 5, "temperature": -12, "oxygenLevel": 6, "oceans": 4, "venusScaleLevel": 10}, "players": [{"name": "Alice", "color": "green", "terraformRating": 32, "victoryPointsBreakdown": {"total": 85}}, {"name": "Bob", "color": "red", "terraformRating": 28, "victoryPointsBreakdown": {"total": 72}}]}"#;
+
+        server
+            .mock("GET", "/api/spectator")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "id".to_owned(),
+                spectator_id.to_owned(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let tmars_requester = TMarsRequester::new(&url, "server_id", &TMarsRequests::default());
+        let spectator = tmars_requester.get_spectator(spectator_id).await.unwrap();
+        assert_eq!(spectator.game.generation, 5);
+        assert_eq!(spectator.game.oceans, 4);
+        assert_eq!(spectator.players.len(), 2);
+        assert_eq!(spectator.players[0].name, "Alice");
+        assert_eq!(spectator.players[0].terraform_rating, 32);
+        assert_eq!(spectator.players[0].victory_points_breakdown.total, 85);
+        assert_eq!(spectator.players[1].name, "Bob");
+        assert_eq!(spectator.players[1].victory_points_breakdown.total, 72);
+    }
+
     #[test]
     fn test_get_player_url() {
-        let tmars_requester = TMarsRequester::new("http://tmars.server", "server_id");
+        let tmars_requester = TMarsRequester::new(
+            "http://tmars.server",
+            "server_id",
+            &TMarsRequests::default(),
+        );
         assert_eq!(
             tmars_requester.get_player_url("123"),
             "http://tmars.server/player?id=123"
         )
     }
 
+    #[test]
+    fn test_get_spectator_url() {
+        let tmars_requester = TMarsRequester::new(
+            "http://tmars.server",
+            "server_id",
+            &TMarsRequests::default(),
+        );
+        assert_eq!(
+            tmars_requester.get_spectator_url("spec123"),
+            "http://tmars.server/spectator?id=spec123"
+        )
+    }
+
     #[tokio::test]
     async fn test_get_games_with_401_unauthorized() {
         let mut server = mockito::Server::new_async().await;
@@ -349,7 +679,7 @@ mod tests {
             .create_async()
             .await;
 
-        let tmars_requester = TMarsRequester::new(&url, server_id);
+        let tmars_requester = TMarsRequester::new(&url, server_id, &TMarsRequests::default());
         let result = tmars_requester.get_games().await;
 
         assert!(result.is_err());
@@ -375,7 +705,7 @@ mod tests {
             .create_async()
             .await;
 
-        let tmars_requester = TMarsRequester::new(&url, "server_id");
+        let tmars_requester = TMarsRequester::new(&url, "server_id", &TMarsRequests::default());
         let result = tmars_requester.get_game_details(game_id).await;
 
         assert!(result.is_err());
@@ -401,7 +731,7 @@ mod tests {
             .create_async()
             .await;
 
-        let tmars_requester = TMarsRequester::new(&url, "server_id");
+        let tmars_requester = TMarsRequester::new(&url, "server_id", &TMarsRequests::default());
         let result = tmars_requester.get_waited_players(spectator_id).await;
 
         assert!(result.is_err());
@@ -427,7 +757,7 @@ mod tests {
             .create_async()
             .await;
 
-        let tmars_requester = TMarsRequester::new(&url, server_id);
+        let tmars_requester = TMarsRequester::new(&url, server_id, &TMarsRequests::default());
         let result = tmars_requester.get_games().await;
 
         assert!(result.is_err());
@@ -75,20 +75,157 @@ impl fmt::Display for PlayerDetail {
 
 /// Response from `/api/waitingfor?id={spectatorId}`.
 ///
-/// Contains the list of player colors that are currently expected to take action.
+/// Contains the list of player colors that are currently expected to take action
+/// and, when the request gives the age of the game, whether the game changed since.
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct WaitingForResponse {
+    /// Whether the game changed since the given age, missing on older servers.
+    #[serde(default)]
+    pub result: Option<WaitingForResult>,
     /// List of player colors who are being waited for.
     pub waiting_for: Vec<String>,
 }
 
 impl fmt::Display for WaitingForResponse {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "waiting_for={:?}", self.waiting_for)
+        write!(
+            f,
+            "result={:?}, waiting_for={:?}",
+            self.result, self.waiting_for
+        )
+    }
+}
+
+/// Result of `/api/waitingfor` compared to the age of the game given in the request.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum WaitingForResult {
+    /// The player of the request can play, never returned for a spectator
+    Go,
+    /// The game changed since the given age
+    Refresh,
+    /// The game didn't change since the given age
+    Wait,
+}
+
+/// Response from `/api/spectator?id={spectatorId}`.
+///
+/// Only the progress of the game and the players' terraform rating and victory
+/// points are kept.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpectatorResponse {
+    /// Public state of the game.
+    pub game: SpectatorGame,
+    /// List of players in the game.
+    pub players: Vec<SpectatorPlayer>,
+}
+
+impl fmt::Display for SpectatorResponse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "game={}, players={:?}", self.game, self.players)
+    }
+}
+
+/// Public state of a game from `/api/spectator?id={spectatorId}`.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpectatorGame {
+    /// Current phase of the game, missing on older servers.
+    #[serde(default)]
+    pub phase: Option<String>,
+    /// Number of actions played in the game, missing on older servers.
+    #[serde(default)]
+    pub game_age: u64,
+    /// Number of undone actions in the game, missing on older servers.
+    #[serde(default)]
+    pub undo_count: u64,
+    /// Current generation.
+    pub generation: u32,
+    /// Temperature in degrees Celsius.
+    pub temperature: i32,
+    /// Oxygen level in percent.
+    pub oxygen_level: i32,
+    /// Number of placed oceans.
+    pub oceans: i32,
+    /// Venus scale level in percent.
+    pub venus_scale_level: i32,
+    /// Options of the game, missing on older servers.
+    #[serde(default)]
+    pub game_options: GameOptions,
+}
+
+impl fmt::Display for SpectatorGame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "generation={}, temperature={}, oxygen_level={}, oceans={}, venus_scale_level={}",
+            self.generation,
+            self.temperature,
+            self.oxygen_level,
+            self.oceans,
+            self.venus_scale_level
+        )
     }
 }
 
+/// Options of a game from `/api/spectator?id={spectatorId}`.
+///
+/// Only the enabled expansions are kept.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GameOptions {
+    /// Expansions enabled in the game.
+    #[serde(default)]
+    pub expansions: Expansions,
+}
+
+/// Expansions enabled in a game.
+///
+/// Only the expansions changing the displayed progress are kept.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Expansions {
+    /// Whether the Venus Next expansion is enabled.
+    #[serde(default)]
+    pub venus: bool,
+}
+
+/// Representation of a player in a game from `/api/spectator?id={spectatorId}`.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpectatorPlayer {
+    /// Player's display name.
+    pub name: String,
+    /// Player's color.
+    pub color: String,
+    /// Player's terraform rating.
+    pub terraform_rating: i32,
+    /// Detail of the player's victory points.
+    pub victory_points_breakdown: VictoryPointsBreakdown,
+}
+
+impl fmt::Display for SpectatorPlayer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "name={}, color={}, terraform_rating={}, victory_points={}",
+            self.name, self.color, self.terraform_rating, self.victory_points_breakdown.total
+        )
+    }
+}
+
+/// Detail of the victory points of a player.
+///
+/// Only the total is kept, the detail per source is ignored.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VictoryPointsBreakdown {
+    /// Total victory points of the player.
+    pub total: i32,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,6 +272,7 @@ mod tests {
     #[test]
     fn test_waiting_for_response_display() {
         let response = WaitingForResponse {
+            result: None,
             waiting_for: vec!["red".to_string(), "blue".to_string()],
         };
 
@@ -144,6 +282,25 @@ mod tests {
         assert!(display.contains("blue"));
     }
 
+    #[test]
+    fn test_waiting_for_response_with_result() {
+        let json = r#"{"result": "REFRESH", "waitingFor": ["red"]}"#;
+
+        let response: WaitingForResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(response.result, Some(WaitingForResult::Refresh));
+        assert_eq!(response.waiting_for, vec!["red".to_string()]);
+    }
+
+    #[test]
+    fn test_waiting_for_response_without_result() {
+        let json = r#"{"waitingFor": []}"#;
+
+        let response: WaitingForResponse = serde_json::from_str(json).unwrap();
+
+        assert!(response.result.is_none());
+    }
+
     #[test]
     fn test_game_detail_with_multiple_players() {
         let json = r#"{
@@ -164,4 +321,72 @@ mod tests {
         assert_eq!(game.players[2].name, "Charlie");
         assert_eq!(game.players[3].color, "yellow");
     }
+
+    #[test]
+    fn test_spectator_player_display() {
+        let player = SpectatorPlayer {
+            name: "Alice".to_string(),
+            color: "green".to_string(),
+            terraform_rating: 42,
+            victory_points_breakdown: VictoryPointsBreakdown { total: 85 },
+        };
+
+        assert_eq!(
+            format!("{}", player),
+            "name=Alice, color=green, terraform_rating=42, victory_points=85"
+        );
+    }
+
+    #[test]
+    fn test_spectator_response_ignores_other_fields() {
+        let json = r#"{
+            "id": "spec888",
+            "game": {
+                "phase": "action",
+                "generation": 5,
+                "temperature": -12,
+                "oxygenLevel": 6,
+                "oceans": 4,
+                "venusScaleLevel": 10,
+                "gameAge": 42,
+                "undoCount": 1,
+                "gameOptions": {"expansions": {"corpera": true, "venus": true}}
+            },
+            "players": [
+                {"name": "Alice", "color": "red", "terraformRating": 32, "victoryPointsBreakdown": {"total": 85, "terraformRating": 40}},
+                {"name": "Bob", "color": "blue", "terraformRating": 28, "victoryPointsBreakdown": {"total": 72, "terraformRating": 35}}
+            ]
+        }"#;
+
+        let spectator: SpectatorResponse = serde_json::from_str(json).unwrap();
+
+        assert_eq!(spectator.game.phase.as_deref(), Some("action"));
+        assert_eq!(spectator.game.game_age, 42);
+        assert_eq!(spectator.game.undo_count, 1);
+        assert_eq!(spectator.game.generation, 5);
+        assert_eq!(spectator.game.temperature, -12);
+        assert_eq!(spectator.game.oxygen_level, 6);
+        assert!(spectator.game.game_options.expansions.venus);
+        assert_eq!(spectator.players.len(), 2);
+        assert_eq!(spectator.players[0].name, "Alice");
+        assert_eq!(spectator.players[0].terraform_rating, 32);
+        assert_eq!(spectator.players[1].victory_points_breakdown.total, 72);
+    }
+
+    #[test]
+    fn test_spectator_game_without_game_options() {
+        let json = r#"{
+            "generation": 1,
+            "temperature": -30,
+            "oxygenLevel": 0,
+            "oceans": 0,
+            "venusScaleLevel": 0
+        }"#;
+
+        let game: SpectatorGame = serde_json::from_str(json).unwrap();
+
+        assert!(!game.game_options.expansions.venus);
+        assert!(game.phase.is_none());
+        assert_eq!(game.game_age, 0);
+    }
 }
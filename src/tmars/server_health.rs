@@ -0,0 +1,192 @@
+//! Health of the synchronization with a tmars server.
+//!
+//! This module defines the [`ServerHealth`] struct which spaces out the attempts to
+//! synchronize a failing server, and tells when its outage and its recovery must
+//! be announced.
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::tmars::SyncError;
+
+/// Maximum delay between two attempts to synchronize a failing server.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+
+/// Number of consecutive failures after which the outage of a server is announced.
+///
+/// An access error is announced right away, it won't fix itself.
+const ANNOUNCED_FAILURES: u32 = 3;
+
+/// Tracks the failures of the synchronization with a tmars server.
+///
+/// After a failure, the next attempt is delayed with an exponential backoff: the
+/// delay starts at the base delay and doubles after each consecutive failure, up to
+/// an hour. A successful synchronization resets the backoff.
+///
+/// # Examples
+///
+/// ```
+/// use miou::tmars::{ServerHealth, SyncError};
+/// use std::time::Duration;
+/// use tokio::time::Instant;
+///
+/// let mut server_health = ServerHealth::new(Duration::from_secs(60));
+/// let now = Instant::now();
+///
+/// // An access error is announced right away
+/// assert!(server_health.record_failure(&SyncError::Access, now));
+/// assert!(!server_health.is_ready(now));
+/// assert!(server_health.is_ready(now + Duration::from_secs(60)));
+///
+/// // The recovery is announced since the outage was
+/// assert!(server_health.record_success());
+/// ```
+pub struct ServerHealth {
+    /// Delay before the first retry, doubled after each consecutive failure
+    base_delay: Duration,
+    /// Number of consecutive failures
+    failures: u32,
+    /// Earliest instant of the next attempt, `None` if the server is healthy
+    retry_at: Option<Instant>,
+    /// Whether the outage of the server was announced
+    outage_announced: bool,
+}
+
+impl ServerHealth {
+    /// Creates the health of a server which didn't fail yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_delay` - Delay before the first retry after a failure.
+    pub fn new(base_delay: Duration) -> Self {
+        ServerHealth {
+            base_delay,
+            failures: 0,
+            retry_at: None,
+            outage_announced: false,
+        }
+    }
+
+    /// Returns whether the server can be synchronized at the given instant.
+    pub fn is_ready(&self, now: Instant) -> bool {
+        self.retry_at.is_none_or(|retry_at| now >= retry_at)
+    }
+
+    /// Returns whether the last synchronization of the server failed.
+    pub fn is_failing(&self) -> bool {
+        self.failures > 0
+    }
+
+    /// Records a failed synchronization and delays the next attempt.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The error of the synchronization.
+    /// * `now` - The instant of the attempt.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the outage must be announced, only once per outage.
+    pub fn record_failure(&mut self, error: &SyncError, now: Instant) -> bool {
+        self.failures += 1;
+
+        let backoff = 2u32.saturating_pow(self.failures - 1);
+        let delay = self.base_delay.saturating_mul(backoff).min(MAX_RETRY_DELAY);
+        self.retry_at = Some(now + delay);
+
+        let announce = !self.outage_announced
+            && (matches!(error, SyncError::Access) || self.failures >= ANNOUNCED_FAILURES);
+        self.outage_announced |= announce;
+
+        announce
+    }
+
+    /// Records a successful synchronization and resets the backoff.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the recovery must be announced, when the outage was.
+    pub fn record_success(&mut self) -> bool {
+        let announce = self.outage_announced;
+
+        self.failures = 0;
+        self.retry_at = None;
+        self.outage_announced = false;
+
+        announce
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn network_error() -> SyncError {
+        SyncError::Network("connection refused".to_string())
+    }
+
+    #[test]
+    fn test_new_server_is_ready() {
+        let server_health = ServerHealth::new(Duration::from_secs(60));
+
+        assert!(server_health.is_ready(Instant::now()));
+        assert!(!server_health.is_failing());
+    }
+
+    #[test]
+    fn test_record_failure_doubles_the_delay() {
+        let mut server_health = ServerHealth::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        server_health.record_failure(&network_error(), now);
+        assert!(server_health.is_failing());
+        assert!(!server_health.is_ready(now + Duration::from_secs(59)));
+        assert!(server_health.is_ready(now + Duration::from_secs(60)));
+
+        let now = now + Duration::from_secs(60);
+        server_health.record_failure(&network_error(), now);
+        assert!(!server_health.is_ready(now + Duration::from_secs(119)));
+        assert!(server_health.is_ready(now + Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn test_record_failure_caps_the_delay() {
+        let mut server_health = ServerHealth::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        for _ in 0..40 {
+            server_health.record_failure(&network_error(), now);
+        }
+
+        assert!(server_health.is_ready(now + MAX_RETRY_DELAY));
+    }
+
+    #[test]
+    fn test_record_failure_announces_the_outage_once() {
+        let mut server_health = ServerHealth::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        // A network error may be transient
+        assert!(!server_health.record_failure(&network_error(), now));
+        assert!(!server_health.record_failure(&SyncError::Timeout, now));
+        assert!(server_health.record_failure(&network_error(), now));
+        assert!(!server_health.record_failure(&SyncError::Access, now));
+    }
+
+    #[test]
+    fn test_record_success_announces_the_recovery() {
+        let mut server_health = ServerHealth::new(Duration::from_secs(60));
+        let now = Instant::now();
+
+        // The outage wasn't announced, neither is the recovery
+        server_health.record_failure(&network_error(), now);
+        assert!(!server_health.record_success());
+        assert!(server_health.is_ready(now));
+
+        server_health.record_failure(&SyncError::Access, now);
+        assert!(server_health.record_success());
+        assert!(!server_health.is_failing());
+        assert!(!server_health.record_success());
+    }
+}
@@ -5,26 +5,81 @@
 
 use std::{collections::HashSet, fmt};
 
+use serde::{Deserialize, Serialize};
+
+/// Separator between the name of the server and the ID of the game in a namespaced game ID.
+pub const GAME_ID_SEPARATOR: char = ':';
+
+/// Namespaces the ID of a game with the name of its server.
+///
+/// Game IDs are only unique on their server, so the bot refers to games with
+/// namespaced IDs like `main:g123`.
+///
+/// # Arguments
+///
+/// * `server_name` - Name of the server hosting the game
+/// * `game_id` - ID of the game on the server
+///
+/// # Returns
+///
+/// The namespaced game ID.
+pub fn namespace_game_id(server_name: &str, game_id: &str) -> String {
+    format!("{}{}{}", server_name, GAME_ID_SEPARATOR, game_id)
+}
+
+/// Returns whether a game ID is namespaced with the name of a server.
+pub fn is_namespaced_game_id(game_id: &str) -> bool {
+    game_id.contains(GAME_ID_SEPARATOR)
+}
+
+/// Returns the name of the server of a namespaced game ID.
+///
+/// # Returns
+///
+/// The name of the server, `None` if the game ID is not namespaced.
+pub fn get_game_server_name(game_id: &str) -> Option<&str> {
+    game_id
+        .split_once(GAME_ID_SEPARATOR)
+        .map(|(server_name, _)| server_name)
+}
+
+/// Returns the ID of a game on its server, without the name of the server.
+///
+/// # Returns
+///
+/// The ID of the game on its server, the game ID itself if it is not namespaced.
+pub fn get_server_game_id(game_id: &str) -> &str {
+    game_id
+        .split_once(GAME_ID_SEPARATOR)
+        .map_or(game_id, |(_, game_id)| game_id)
+}
+
 /// Represents a game with its complete state information.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Game {
     /// Unique identifier for the game
     pub id: String,
-    /// Current generation of the game
+    /// Current phase of the game
     pub phase: Phase,
     /// Unique identifier for spectator
     pub spectator_id: String,
+    /// URL to the spectator page of the game
+    pub spectator_url: String,
     /// Collection of player's ids in the game
     pub players: Vec<Player>,
     /// Player's ids where its they turn to play
     pub waited_players: HashSet<String>,
+    /// Generation and global parameters of the game
+    ///
+    /// `None` until they are fetched from the spectator endpoint
+    pub progress: Option<GameProgress>,
 }
 
 impl fmt::Display for Game {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "id={}, phase={:?}, spectator_id={}, players={:?}, waited_players={:?}",
+            "id={}, phase={}, spectator_id={}, players={:?}, waited_players={:?}",
             self.id, self.phase, self.spectator_id, self.players, self.waited_players
         )
     }
@@ -45,6 +100,15 @@ pub struct Player {
     ///
     /// Direct link to access this player's view of the game on the Terraforming Mars server
     pub url: String,
+    /// Player's terraform rating
+    ///
+    /// `None` until it is fetched from the spectator endpoint
+    pub terraform_rating: Option<i32>,
+    /// Timestamp of the first synchronization where the game was waiting for the player
+    ///
+    /// `None` when the game isn't waiting for the player. After a restart, the turn
+    /// is considered started at the first synchronization.
+    pub waiting_since: Option<u64>,
 }
 
 impl fmt::Display for Player {
@@ -57,11 +121,84 @@ impl fmt::Display for Player {
     }
 }
 
+#[cfg(test)]
+impl Player {
+    /// Returns a player for the tests, with a URL derived from its ID.
+    pub fn test(id: &str, color: &str, name: &str) -> Player {
+        Player {
+            id: id.to_owned(),
+            color: color.to_owned(),
+            name: name.to_owned(),
+            url: format!("http://example.com/{}", id),
+            terraform_rating: None,
+            waiting_since: None,
+        }
+    }
+}
+
+#[cfg(test)]
+impl Game {
+    /// Returns a game for the tests between Alice (`player1`, red) and Bob
+    /// (`player2`, blue), waiting for the given players.
+    pub fn test(id: &str, phase: Phase, waited_players: &[&str]) -> Game {
+        Game {
+            id: id.to_owned(),
+            phase,
+            spectator_id: format!("spec_{}", id),
+            spectator_url: "http://example.com/spectator".to_owned(),
+            players: vec![
+                Player::test("player1", "red", "Alice"),
+                Player::test("player2", "blue", "Bob"),
+            ],
+            waited_players: waited_players.iter().map(|id| id.to_string()).collect(),
+            progress: None,
+        }
+    }
+}
+
+/// Generation and global parameters of a game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameProgress {
+    /// Current generation
+    pub generation: u32,
+    /// Temperature in degrees Celsius, from -30 to 8
+    pub temperature: i32,
+    /// Oxygen level in percent, from 0 to 14
+    pub oxygen: i32,
+    /// Number of placed oceans, from 0 to 9
+    pub oceans: i32,
+    /// Venus scale level in percent, from 0 to 30
+    ///
+    /// `None` if the game is played without the Venus Next expansion
+    pub venus: Option<i32>,
+}
+
+/// Age of a game, as counted by the tmars server.
+///
+/// The server increments the age at every action and the undo count at every undo,
+/// so a game whose age and undo count are unchanged didn't change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameAge {
+    /// Number of actions played since the start of the game
+    pub game_age: u64,
+    /// Number of undone actions since the start of the game
+    pub undo_count: u64,
+}
+
+/// Final score of a player in an ended game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerScore {
+    /// Player's display name
+    pub name: String,
+    /// Total victory points of the player at the end of the game
+    pub victory_points: i32,
+}
+
 /// Different phases of a Terraforming Mars game.
 ///
 /// Based on the official implementation:
 /// <https://github.com/terraforming-mars/terraforming-mars/blob/main/src/common/Phase.ts>
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Phase {
     /// Not part of the rulebook, initial drafting includes project cards and
     /// prelude cards (maybe others ongoing?) Transitions to RESEARCH
@@ -87,20 +224,85 @@ pub enum Phase {
     Intergeneration,
     /// The game is over.
     End,
+    /// A phase added to the server after this bot, with its name on the server.
+    Unknown(String),
+}
+
+impl Phase {
+    /// Phases known to this bot, by their name on the server.
+    const NAMES: [(&str, Phase); 10] = [
+        ("initialDrafting", Phase::InitialDrafting),
+        ("preludes", Phase::Preludes),
+        ("ceos", Phase::Ceos),
+        ("research", Phase::Research),
+        ("drafting", Phase::Drafting),
+        ("action", Phase::Action),
+        ("production", Phase::Production),
+        ("solar", Phase::Solar),
+        ("intergeneration", Phase::Intergeneration),
+        ("end", Phase::End),
+    ];
+
+    /// Returns the phase of the given name on the server, ignoring the case.
+    ///
+    /// # Returns
+    ///
+    /// [`Phase::Unknown`] with the name if this bot doesn't know the phase.
+    pub fn from_name(name: &str) -> Phase {
+        Self::NAMES
+            .into_iter()
+            .find(|(phase_name, _)| phase_name.eq_ignore_ascii_case(name))
+            .map(|(_, phase)| phase)
+            .unwrap_or_else(|| Phase::Unknown(name.to_owned()))
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Unknown(name) => write!(f, "{}", name),
+            phase => write!(f, "{:?}", phase),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_namespace_game_id() {
+        assert_eq!(namespace_game_id("main", "g123"), "main:g123");
+    }
+
+    #[test]
+    fn test_is_namespaced_game_id() {
+        assert!(is_namespaced_game_id("main:g123"));
+        assert!(!is_namespaced_game_id("g123"));
+    }
+
+    #[test]
+    fn test_get_game_server_name() {
+        assert_eq!(get_game_server_name("main:g123"), Some("main"));
+        assert_eq!(get_game_server_name("g123"), None);
+    }
+
+    #[test]
+    fn test_get_server_game_id() {
+        assert_eq!(get_server_game_id("main:g123"), "g123");
+        assert_eq!(get_server_game_id("g123"), "g123");
+    }
+
     #[test]
     fn test_game_display() {
         let game = Game {
             id: "game789".to_string(),
             phase: Phase::Production,
             spectator_id: "spec999".to_string(),
+            spectator_url: "http://example.com/spectator".to_string(),
             players: vec![],
             waited_players: HashSet::new(),
+            progress: None,
         };
 
         let display = format!("{}", game);
@@ -109,6 +311,22 @@ mod tests {
         assert!(display.contains("spectator_id=spec999"));
     }
 
+    #[test]
+    fn test_phase_from_name() {
+        assert_eq!(Phase::from_name("initialDrafting"), Phase::InitialDrafting);
+        assert_eq!(Phase::from_name("Action"), Phase::Action);
+        assert_eq!(
+            Phase::from_name("auction"),
+            Phase::Unknown("auction".to_string())
+        );
+    }
+
+    #[test]
+    fn test_phase_display() {
+        assert_eq!(Phase::InitialDrafting.to_string(), "InitialDrafting");
+        assert_eq!(Phase::Unknown("auction".to_string()).to_string(), "auction");
+    }
+
     #[test]
     fn test_player_display() {
         let player = Player {
@@ -116,6 +334,8 @@ mod tests {
             color: "yellow".to_string(),
             name: "Diana".to_string(),
             url: "https://example.com/player/p456".to_string(),
+            terraform_rating: None,
+            waiting_since: None,
         };
 
         let display = format!("{}", player);
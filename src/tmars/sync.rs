@@ -4,14 +4,27 @@
 //! synchronization with the TMars server to keep game state up to date.
 
 use crate::tmars::SyncError;
+use crate::tmars::game_event::GameEvent;
 use crate::tmars::requester::Requester;
-use crate::tmars::response_structs::{GameDetail, PlayerDetail};
-use crate::tmars::structs::{Game, Phase, Player};
+use crate::tmars::response_structs::{
+    GameDetail, PlayerDetail, SpectatorPlayer, SpectatorResponse, WaitingForResult,
+};
+use crate::tmars::structs::{
+    Game, GameAge, GameProgress, Phase, Player, PlayerScore, get_server_game_id, namespace_game_id,
+};
+use crate::utils::get_timestamp;
 use futures::future::join_all;
 
 use log::{debug, error, info, warn};
-use reqwest::StatusCode;
+use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
+use tokio::sync::broadcast;
+
+/// Maximum number of game events kept for subscribers that didn't receive them yet.
+const GAME_EVENTS_CAPACITY: usize = 256;
+
+/// Number of synchronizations between two requests of the details of every game.
+const FULL_REFRESH_SYNCS: u32 = 10;
 
 /// Manages synchronization of game state with the Terraforming Mars server.
 ///
@@ -19,23 +32,54 @@ use std::collections::{HashMap, HashSet};
 /// It interacts with a tmars server through a [Requester] implementation to fetch game information.
 /// It maintains an internal state of synchronized games and their players.
 ///
+/// A [TMarsSync] is bound to a single named server: the IDs of its games are
+/// namespaced with the name of the server, see [`namespace_game_id`].
+///
+/// After each successful synchronization, the changes of the games are broadcast as
+/// [`GameEvent`]s to the receivers returned by [`TMarsSync::subscribe`].
+///
+/// The age of each game is kept between synchronizations, so that [`TMarsSync::check`]
+/// can cheaply detect the games that changed in between.
+///
+/// The details and the progress of the games are cached between synchronizations:
+/// they are only requested for the new games and the games whose waited players
+/// changed, and for every game once every [`FULL_REFRESH_SYNCS`] synchronizations.
+/// The waited players of every game are still requested at each synchronization.
+///
 /// # Examples
 ///
 /// ```no_run
+/// use miou::config::TMarsRequests;
 /// use miou::tmars::{TMarsSync, TMarsRequester};
 ///
 /// # #[tokio::main]
 /// # async fn main() {
-/// let tmars_requester = TMarsRequester::new("your_server_id".to_string(), "http://your_tmars_server_url".to_string());
-/// let mut tmars_sync = TMarsSync::new(tmars_requester);
+/// let tmars_requester = TMarsRequester::new("http://your_tmars_server_url", "your_server_id", &TMarsRequests::default());
+/// let mut tmars_sync = TMarsSync::new("main", tmars_requester);
 /// tmars_sync.sync().await;
 /// # }
 /// ```
 pub struct TMarsSync<R: Requester> {
+    /// Name of the tmars server, used to namespace the game IDs
+    server_name: String,
     /// TMars requester to interact with the tmars server
     tmars_requester: R,
     /// Synchronized games
     games: HashMap<String, Game>,
+    /// Age of the synchronized games when their progress was last requested
+    game_ages: HashMap<String, GameAge>,
+    /// IDs of the games whose details must be requested at the next synchronization
+    stale_game_ids: HashSet<String>,
+    /// IDs of the games whose details were received during the current synchronization
+    refreshed_game_ids: HashSet<String>,
+    /// IDs of the ended games still listed on the server, they are not requested anymore
+    ended_game_ids: HashSet<String>,
+    /// Number of synchronizations since the details of every game were requested
+    syncs_since_full_refresh: u32,
+    /// Phases unknown to this bot met on the server, each is warned about once
+    unknown_phases: HashSet<String>,
+    /// Sender of the game events to the subscribers
+    game_events_sender: broadcast::Sender<GameEvent>,
 }
 
 impl<R: Requester> TMarsSync<R> {
@@ -43,157 +87,445 @@ impl<R: Requester> TMarsSync<R> {
     ///
     /// # Arguments
     ///
+    /// * `server_name` - Name of the tmars server, used to namespace the game IDs.
     /// * `tmars_requester` - An implementation of the [Requester] trait to interact with the tmars server.
-    pub fn new(tmars_requester: R) -> Self {
+    pub fn new(server_name: &str, tmars_requester: R) -> Self {
         let games = HashMap::new();
+        let (game_events_sender, _) = broadcast::channel(GAME_EVENTS_CAPACITY);
 
         TMarsSync {
+            server_name: server_name.to_owned(),
             tmars_requester,
             games,
+            game_ages: HashMap::new(),
+            stale_game_ids: HashSet::new(),
+            refreshed_game_ids: HashSet::new(),
+            ended_game_ids: HashSet::new(),
+            syncs_since_full_refresh: 0,
+            unknown_phases: HashSet::new(),
+            game_events_sender,
         }
     }
 
+    /// Returns the name of the tmars server.
+    pub fn get_server_name(&self) -> &str {
+        &self.server_name
+    }
+
+    /// Subscribes to the game events.
+    ///
+    /// The receiver gets the events of every synchronization done after the
+    /// subscription. A receiver that doesn't keep up misses the oldest events.
+    ///
+    /// # Returns
+    ///
+    /// A [`broadcast::Receiver`] of [`GameEvent`]s.
+    pub fn subscribe(&self) -> broadcast::Receiver<GameEvent> {
+        self.game_events_sender.subscribe()
+    }
+
     /// Synchronizes game state by fetching game details and waiting players.
     ///
     /// This method performs a full synchronization cycle by:
-    /// 1. Fetching all active games and their details
-    /// 2. Updating the list of players being waited for in each game
+    /// 1. Fetching all active games, and the details of the new and stale ones
+    /// 2. Updating the list of players being waited for in each game, and since when
+    /// 3. Fetching the details of the games whose waited players changed
+    /// 4. Updating the progress of the games whose details were fetched and the
+    ///    terraform rating of their players
+    /// 5. Fetching the final scores of the games that ended since the previous synchronization
+    /// 6. Broadcasting the changes since the previous synchronization as [`GameEvent`]s
     ///
     /// This should be called periodically to keep the internal state current.
     ///
     /// # Examples
     ///
     /// ```no_run
+    /// use miou::config::TMarsRequests;
     /// use miou::tmars::{TMarsSync, TMarsRequester};
     ///
     /// # #[tokio::main]
     /// # async fn main() {
-    /// let tmars_requester = TMarsRequester::new("server_id".to_string(), "http://server_url".to_string());
-    /// let mut tmars_sync = TMarsSync::new(tmars_requester);
+    /// let tmars_requester = TMarsRequester::new("http://server_url", "server_id", &TMarsRequests::default());
+    /// let mut tmars_sync = TMarsSync::new("main", tmars_requester);
     /// tmars_sync.sync().await;
     /// # }
     /// ```
     pub async fn sync(&mut self) -> Result<(), SyncError> {
-        self.pool_games().await?;
+        let previous_games = self.games.clone();
+        self.refreshed_game_ids.clear();
+
+        let mut ended_game_ids = self.pool_games().await?;
         self.pool_waited_players().await;
+        self.update_waiting_since(&previous_games);
+        self.mark_stale_games(&previous_games);
+        ended_game_ids.extend(self.refresh_stale_games().await);
+        let mut final_scores = self.pool_game_progress().await;
+
+        // The progress of the games ended in their details was not requested
+        for game_id in ended_game_ids {
+            if let Some(game) = previous_games.get(&game_id) {
+                let scores = self.request_final_scores(game).await;
+                final_scores.insert(game_id, scores);
+            }
+        }
+
+        self.send_game_events(&previous_games, &final_scores).await;
         Ok(())
     }
 
+    /// Checks which games changed since the previous synchronization, and updates them.
+    ///
+    /// This method is a cheap alternative to [`TMarsSync::sync`] by:
+    /// 1. Asking the server whether each game changed since its known age
+    /// 2. Updating the players being waited for in the changed games, and since when
+    /// 3. Updating the progress of the changed games, and removing the ended ones
+    /// 4. Broadcasting the changes as [`GameEvent`]s
+    ///
+    /// Only the games whose age was recorded by a previous synchronization are checked,
+    /// new games are only discovered by [`TMarsSync::sync`]. Errors are logged and the
+    /// game is considered unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use miou::config::TMarsRequests;
+    /// use miou::tmars::{TMarsSync, TMarsRequester};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let tmars_requester = TMarsRequester::new("http://server_url", "server_id", &TMarsRequests::default());
+    /// let mut tmars_sync = TMarsSync::new("main", tmars_requester);
+    /// tmars_sync.sync().await;
+    /// // Between two synchronizations
+    /// tmars_sync.check().await;
+    /// # }
+    /// ```
+    pub async fn check(&mut self) {
+        info!("check games changes on tmars server");
+        let previous_games = self.games.clone();
+
+        // Collect game info first to avoid borrow checker issues
+        let game_info: Vec<(String, String, GameAge)> = self
+            .games
+            .values()
+            .filter_map(|game| {
+                self.game_ages
+                    .get(&game.id)
+                    .map(|game_age| (game.id.clone(), game.spectator_id.clone(), *game_age))
+            })
+            .collect();
+
+        let waiting_for_futures = game_info.iter().map(|(_, spectator_id, game_age)| {
+            self.tmars_requester
+                .get_waited_players_since(spectator_id, game_age)
+        });
+        let waiting_for_results = join_all(waiting_for_futures).await;
+
+        let mut changed_games: Vec<(String, String)> = vec![];
+        for ((game_id, spectator_id, _), waiting_for_result) in
+            game_info.iter().zip(waiting_for_results)
+        {
+            let waiting_for = match waiting_for_result {
+                Ok(waiting_for) => waiting_for,
+                Err(e) => {
+                    debug!("error while checking game {}: {}", game_id, e);
+                    continue;
+                }
+            };
+
+            if let Some(game) = self.games.get_mut(game_id) {
+                let waited_players = Self::map_waited_players(game, &waiting_for.waiting_for);
+
+                // Older servers don't compare the age, the waited players still tell
+                if waiting_for.result != Some(WaitingForResult::Refresh)
+                    && waited_players == game.waited_players
+                {
+                    continue;
+                }
+
+                debug!("game {}: changed since the previous check", game.id);
+                game.waited_players = waited_players;
+                changed_games.push((game_id.clone(), spectator_id.clone()));
+            }
+        }
+
+        let final_scores = self.request_game_progress(&changed_games).await;
+        self.update_waiting_since(&previous_games);
+        self.mark_stale_games(&previous_games);
+        self.send_game_events(&previous_games, &final_scores).await;
+        info!(
+            "finished checking games changes on tmars server, {} changed",
+            changed_games.len()
+        );
+    }
+
+    /// Broadcasts the changes between the previous and the current games.
+    ///
+    /// Removed games that are in the end phase are broadcast as [`GameEvent::GameEnded`]
+    /// with their final scores.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous_games` - The games before the synchronization
+    /// * `final_scores` - The final scores of the games in the end phase on the server
+    async fn send_game_events(
+        &self,
+        previous_games: &HashMap<String, Game>,
+        final_scores: &HashMap<String, Vec<PlayerScore>>,
+    ) {
+        for event in GameEvent::diff(previous_games, &self.games) {
+            let event = match event {
+                GameEvent::GameRemoved { game_id } if final_scores.contains_key(&game_id) => {
+                    let scores = final_scores[&game_id].clone();
+                    GameEvent::GameEnded { game_id, scores }
+                }
+                event => event,
+            };
+
+            debug!("game event {:?}", event);
+            // Fails only when nobody subscribed, the event can be dropped
+            let _ = self.game_events_sender.send(event);
+        }
+    }
+
     /// Fetches all games from the server and updates internal state.
     ///
     /// This method:
     /// - Requests the list of all active games
-    /// - Fetches detailed information for each game
-    /// - Filters out ended games
-    /// - Updates the internal `games` HashMap with current data
+    /// - Removes the games which are not listed anymore
+    /// - Fetches detailed information for the new and stale games, or for every game
+    ///   during a full refresh
+    /// - Filters out ended games, and skips them while they stay listed
+    /// - Updates the internal `games` HashMap with the fetched data
     ///
-    /// The previous game state is completely replaced with the new data.
+    /// The other games are kept as they were, a stale game whose details can't be
+    /// fetched too, see [`TMarsSync::update_game_details`].
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the ended games, which are not kept in the internal state.
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```ignore
+    /// use miou::config::TMarsRequests;
     /// use miou::tmars::{TMarsSync, TMarsRequester};
     ///
     /// # #[tokio::main]
     /// # async fn main() {
-    /// let tmars_requester = TMarsRequester::new("server_id".to_string(), "http://server_url".to_string());
-    /// let mut tmars_sync = TMarsSync::new(tmars_requester);
+    /// let tmars_requester = TMarsRequester::new("http://server_url", "server_id", &TMarsRequests::default());
+    /// let mut tmars_sync = TMarsSync::new("main", tmars_requester);
     /// tmars_sync.pool_games().await;
     /// # }
     /// ```
-    async fn pool_games(&mut self) -> Result<(), SyncError> {
+    async fn pool_games(&mut self) -> Result<HashSet<String>, SyncError> {
         info!("request games from tmars server");
-        let game_details = self.request_games().await?;
-        // Clear existing games to avoid stale data
-        self.games.clear();
+        let game_ids = self.request_game_ids().await?;
+
+        // Forget the games which are not on the server anymore
+        let listed_game_ids: HashSet<String> = game_ids
+            .iter()
+            .map(|game_id| namespace_game_id(&self.server_name, game_id))
+            .collect();
+        self.games
+            .retain(|game_id, _| listed_game_ids.contains(game_id));
+        self.stale_game_ids
+            .retain(|game_id| listed_game_ids.contains(game_id));
+        self.ended_game_ids
+            .retain(|game_id| listed_game_ids.contains(game_id));
+
+        let full_refresh = self.syncs_since_full_refresh == 0;
+        self.syncs_since_full_refresh = (self.syncs_since_full_refresh + 1) % FULL_REFRESH_SYNCS;
+
+        let game_ids_to_request: Vec<String> = game_ids
+            .into_iter()
+            .filter(|game_id| {
+                let game_id = namespace_game_id(&self.server_name, game_id);
+                !self.ended_game_ids.contains(&game_id)
+                    && (full_refresh
+                        || !self.games.contains_key(&game_id)
+                        || self.stale_game_ids.contains(&game_id))
+            })
+            .collect();
+        info!(
+            "request details of {} of {} games",
+            game_ids_to_request.len(),
+            listed_game_ids.len()
+        );
+        let ended_game_ids = self.update_game_details(&game_ids_to_request).await;
+
+        debug!("all games {:?}", self.games);
+        info!("finished requesting games from tmars server");
+        Ok(ended_game_ids)
+    }
+
+    /// Fetches the details of the stale games which were not fetched yet during this
+    /// synchronization.
+    ///
+    /// The games whose waited players changed are marked stale after their details
+    /// were requested by [`TMarsSync::pool_games`], their details are fetched again
+    /// in the same synchronization. A game whose details can't be fetched stays stale.
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the ended games, which are not kept in the internal state.
+    async fn refresh_stale_games(&mut self) -> HashSet<String> {
+        // The details received earlier in this synchronization are recent enough
+        let refreshed_game_ids = &self.refreshed_game_ids;
+        self.stale_game_ids
+            .retain(|game_id| !refreshed_game_ids.contains(game_id));
+        if self.stale_game_ids.is_empty() {
+            return HashSet::new();
+        }
+
+        let game_ids: Vec<String> = self
+            .stale_game_ids
+            .iter()
+            .map(|game_id| get_server_game_id(game_id).to_owned())
+            .collect();
+        info!(
+            "request details of {} games whose waited players changed",
+            game_ids.len()
+        );
+
+        self.update_game_details(&game_ids).await
+    }
+
+    /// Fetches the details of the given games and rebuilds them.
+    ///
+    /// A rebuilt game keeps the state that is not part of its details: its waited
+    /// players, its progress, and the terraform rating of its players and since when
+    /// they are waited for. They are updated by the next requests, and kept as they
+    /// were if these requests fail.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_ids` - The IDs of the games, not namespaced.
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the ended games, which are not kept in the internal state.
+    async fn update_game_details(&mut self, game_ids: &[String]) -> HashSet<String> {
+        let game_details = self.request_game_details(game_ids).await;
+        let mut ended_game_ids = HashSet::new();
 
-        game_details.into_iter().for_each(|game_detail| {
+        for game_detail in game_details {
             debug!("sync game detail {}", game_detail);
 
+            let game_id = namespace_game_id(&self.server_name, &game_detail.id);
+            self.stale_game_ids.remove(&game_id);
+            self.refreshed_game_ids.insert(game_id.clone());
+
             // Ignore ended games
             if game_detail.phase == "end" {
-                debug!("ignore game {}, phase=end", game_detail.id);
-                return;
+                debug!("ignore game {}, phase=end", game_id);
+                self.games.remove(&game_id);
+                self.ended_game_ids.insert(game_id.clone());
+                ended_game_ids.insert(game_id);
+                continue;
             }
 
             // Convert the deserialized players into internal Player structs
-            let players = self.convert_players(game_detail.players);
+            let mut players = self.convert_players(game_detail.players);
             // Convert phase string to Phase enum
             let phase = self.convert_phase(&game_detail.phase);
 
+            let cached_game = self.games.get(&game_id);
+            if let Some(cached_game) = cached_game {
+                for player in players.iter_mut() {
+                    if let Some(cached_player) = cached_game
+                        .players
+                        .iter()
+                        .find(|cached_player| cached_player.id == player.id)
+                    {
+                        player.terraform_rating = cached_player.terraform_rating;
+                        player.waiting_since = cached_player.waiting_since;
+                    }
+                }
+            }
+
             let game = Game {
-                id: game_detail.id.to_owned(),
+                id: game_id,
                 phase,
+                spectator_url: self
+                    .tmars_requester
+                    .get_spectator_url(&game_detail.spectator_id),
                 spectator_id: game_detail.spectator_id.to_owned(),
                 players,
-                waited_players: HashSet::new(),
+                waited_players: cached_game
+                    .map(|cached_game| cached_game.waited_players.clone())
+                    .unwrap_or_default(),
+                progress: cached_game.and_then(|cached_game| cached_game.progress.clone()),
             };
 
-            if !self.games.contains_key(&game.id) {
+            if cached_game.is_none() {
                 info!("add new game {}", game);
             }
 
             debug!("synced game {}", game);
             self.games.insert(game.id.to_owned(), game);
-        });
+        }
 
-        debug!("all games {:?}", self.games);
-        info!("finished requesting games from tmars server");
-        Ok(())
+        ended_game_ids
     }
 
-    /// Requests the list of games and fetches details for each.
-    ///
-    /// Makes parallel requests for game details to improve performance.
-    /// Errors for individual games are logged and filtered out.
+    /// Requests the IDs of the games of the server.
     ///
     /// # Returns
     ///
-    /// A vector of successfully fetched [`GameDetail`] objects.
+    /// The IDs of the games, not namespaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`SyncError`] matching the failure of the request, so that the
+    /// games are kept as they were instead of being removed.
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```ignore
+    /// use miou::config::TMarsRequests;
     /// use miou::tmars::{TMarsSync, TMarsRequester};
     ///
     /// # #[tokio::main]
     /// # async fn main() {
-    /// let tmars_requester = TMarsRequester::new("server_id".to_string(), "http://server_url".to_string());
-    /// let tmars_sync = TMarsSync::new(tmars_requester);
-    /// let game_details = tmars_sync.request_games().await;
-    /// println!("Game details: {:?}", game_details);
+    /// let tmars_requester = TMarsRequester::new("http://server_url", "server_id", &TMarsRequests::default());
+    /// let tmars_sync = TMarsSync::new("main", tmars_requester);
+    /// let game_ids = tmars_sync.request_game_ids().await;
+    /// println!("Game IDs: {:?}", game_ids);
     /// # }
     /// ```
-    async fn request_games(&self) -> Result<Vec<GameDetail>, SyncError> {
-        // Request list of games
-        let game_ids = match self.tmars_requester.get_games().await {
-            Ok(games) => games,
+    async fn request_game_ids(&self) -> Result<Vec<String>, SyncError> {
+        match self.tmars_requester.get_games().await {
+            Ok(games) => Ok(games.into_iter().map(|game| game.game_id).collect()),
             Err(err) => {
                 error!("error while requesting games: {}", err);
-                // Check for access errors
-                if let Some(status_code) = err.status()
-                    && matches!(
-                        status_code,
-                        StatusCode::FORBIDDEN | StatusCode::UNAUTHORIZED
-                    )
-                {
-                    return Err(SyncError::AccessError);
-                }
-
-                vec![]
+                Err(SyncError::from(err))
             }
-        };
+        }
+    }
 
-        // Request details for each game
+    /// Fetches the details of the given games.
+    ///
+    /// Makes parallel requests for game details to improve performance.
+    /// Errors for individual games are logged and filtered out.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_ids` - The IDs of the games, not namespaced.
+    ///
+    /// # Returns
+    ///
+    /// A vector of successfully fetched [`GameDetail`] objects.
+    async fn request_game_details(&self, game_ids: &[String]) -> Vec<GameDetail> {
         let game_details = join_all(
             game_ids
                 .iter()
-                .map(|g| self.tmars_requester.get_game_details(&g.game_id)),
+                .map(|game_id| self.tmars_requester.get_game_details(game_id)),
         )
         .await;
 
         // Filter out errors and collect valid game details
-        let filtered_game_details = game_details
+        game_details
             .into_iter()
             // Keep only successful game detail responses
             .filter_map(|g| match g {
@@ -203,9 +535,7 @@ impl<R: Requester> TMarsSync<R> {
                     None
                 }
             })
-            .collect();
-
-        Ok(filtered_game_details)
+            .collect()
     }
 
     /// Converts a phase string from the API into a [`Phase`] enum.
@@ -216,7 +546,9 @@ impl<R: Requester> TMarsSync<R> {
     ///
     /// # Returns
     ///
-    /// The corresponding [`Phase`] enum variant, or [`Phase::Research`] if unknown.
+    /// The corresponding [`Phase`] enum variant, or [`Phase::Unknown`] with the
+    /// name of the phase if this bot doesn't know it. The first time a phase is met,
+    /// a warning is logged along with the number of unknown phases met so far.
     ///
     /// # Examples
     ///
@@ -227,28 +559,26 @@ impl<R: Requester> TMarsSync<R> {
     ///
     /// # fn main() {
     /// let mock_requester = MockRequester::new();
-    /// let tmars_sync = TMarsSync::new(mock_requester);
+    /// let mut tmars_sync = TMarsSync::new("main", mock_requester);
     /// let phase = tmars_sync.convert_phase("research");
     /// assert!(matches!(phase, Phase::Research));
     /// # }
     /// ```
-    fn convert_phase(&self, phase: &str) -> Phase {
-        match phase {
-            "initialDrafting" => Phase::InitialDrafting,
-            "preludes" => Phase::Preludes,
-            "ceos" => Phase::Ceos,
-            "research" => Phase::Research,
-            "drafting" => Phase::Drafting,
-            "action" => Phase::Action,
-            "production" => Phase::Production,
-            "solar" => Phase::Solar,
-            "intergeneration" => Phase::Intergeneration,
-            "end" => Phase::End,
-            _ => {
-                warn!("unknown phase string: {}", phase);
-                Phase::Research // Default to Research if unknown
-            }
+    fn convert_phase(&mut self, phase: &str) -> Phase {
+        let converted_phase = Phase::from_name(phase);
+
+        if matches!(converted_phase, Phase::Unknown(_))
+            && self.unknown_phases.insert(phase.to_owned())
+        {
+            warn!(
+                "unknown phase {} on tmars server {}, {} unknown phases so far",
+                phase,
+                self.server_name,
+                self.unknown_phases.len()
+            );
         }
+
+        converted_phase
     }
 
     /// Converts API player details into internal player representations.
@@ -273,7 +603,7 @@ impl<R: Requester> TMarsSync<R> {
     ///
     /// # fn main() {
     /// let mock_requester = MockRequester::new();
-    /// let tmars_sync = TMarsSync::new(mock_requester);
+    /// let tmars_sync = TMarsSync::new("main", mock_requester);
     /// let player_details = vec![PlayerDetail {
     ///     id: "1".to_string(),
     ///     name: "Alice".to_string(),
@@ -292,6 +622,8 @@ impl<R: Requester> TMarsSync<R> {
                 color: player_detail.color.to_owned(),
                 name: player_detail.name.to_owned(),
                 url: self.tmars_requester.get_player_url(&player_detail.id),
+                terraform_rating: None,
+                waiting_since: None,
             })
             .collect()
     }
@@ -303,17 +635,19 @@ impl<R: Requester> TMarsSync<R> {
     /// 2. Converts player colors to player IDs
     /// 3. Updates the game's `waited_players` field
     ///
-    /// Requests are made in parallel for better performance.
+    /// Requests are made in parallel for better performance. A game whose request
+    /// fails keeps its waited players, for a server error not to end the turns.
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```ignore
+    /// use miou::config::TMarsRequests;
     /// use miou::tmars::{TMarsSync, TMarsRequester};
     ///
     /// # #[tokio::main]
     /// # async fn main() {
-    /// let tmars_requester = TMarsRequester::new("server_id".to_string(), "http://server_url".to_string());
-    /// let mut tmars_sync = TMarsSync::new(tmars_requester);
+    /// let tmars_requester = TMarsRequester::new("http://server_url", "server_id", &TMarsRequests::default());
+    /// let mut tmars_sync = TMarsSync::new("main", tmars_requester);
     /// tmars_sync.pool_waited_players().await;
     /// # }
     /// ```
@@ -335,41 +669,241 @@ impl<R: Requester> TMarsSync<R> {
 
         // Update games with waited players
         for ((game_id, _), waited_players_colors) in game_info.iter().zip(waited_players_results) {
+            let Some(waited_players_colors) = waited_players_colors else {
+                continue;
+            };
+
             if let Some(game) = self.games.get_mut(game_id) {
-                if waited_players_colors.is_empty() {
-                    debug!("no players are being waited for in game {}", game.id);
-                    game.waited_players.clear();
+                game.waited_players = Self::map_waited_players(game, &waited_players_colors);
+            }
+        }
+
+        info!("finished requesting waited players from tmars server");
+    }
+
+    /// Maps the colors of the waited players to the IDs of the players of a game.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game of the waited players.
+    /// * `waited_players_colors` - Colors of the players being waited for.
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the players being waited for.
+    fn map_waited_players(game: &Game, waited_players_colors: &[String]) -> HashSet<String> {
+        if waited_players_colors.is_empty() {
+            debug!("no players are being waited for in game {}", game.id);
+            return HashSet::new();
+        }
+
+        debug!(
+            "game {}: waiting for players with colors {:?}",
+            game.id, waited_players_colors
+        );
+
+        let waited_players: HashSet<String> = game
+            .players
+            .iter()
+            .filter(|player| waited_players_colors.contains(&player.color))
+            .map(|player| player.id.to_owned())
+            .collect();
+
+        debug!(
+            "game {}: mapped to player IDs {:?}",
+            game.id, waited_players
+        );
+
+        waited_players
+    }
+
+    /// Updates since when each game is waiting for its players.
+    ///
+    /// A player already waited for during the previous synchronization keeps its
+    /// timestamp, a newly waited player gets the current one.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous_games` - The games before the synchronization
+    fn update_waiting_since(&mut self, previous_games: &HashMap<String, Game>) {
+        let now = get_timestamp();
+
+        for game in self.games.values_mut() {
+            let previous_game = previous_games.get(&game.id);
+
+            for player in game.players.iter_mut() {
+                player.waiting_since = match game.waited_players.contains(&player.id) {
+                    false => None,
+                    true => previous_game
+                        .and_then(|previous_game| {
+                            previous_game.players.iter().find(|p| p.id == player.id)
+                        })
+                        .and_then(|previous_player| previous_player.waiting_since)
+                        .or(Some(now)),
+                };
+            }
+        }
+    }
+
+    /// Fetches and updates the progress of the refreshed games and the terraform rating
+    /// of their players.
+    ///
+    /// For each game whose details were fetched during this synchronization, this method:
+    /// 1. Requests the spectator view of the game from the server
+    /// 2. Updates the game's `progress` field with the generation and global parameters
+    /// 3. Updates the `terraform_rating` field of the players, matched by color
+    ///
+    /// Requests are made in parallel for better performance. Games whose request fails
+    /// keep the progress they had, if any, until the next synchronization.
+    ///
+    /// # Returns
+    ///
+    /// The final scores of the games which ended, see [`TMarsSync::request_game_progress`].
+    async fn pool_game_progress(&mut self) -> HashMap<String, Vec<PlayerScore>> {
+        info!("request game progress from tmars server");
+
+        // Collect game info first to avoid borrow checker issues
+        let game_info: Vec<(String, String)> = self
+            .games
+            .values()
+            .filter(|game| self.refreshed_game_ids.contains(&game.id))
+            .map(|game| (game.id.clone(), game.spectator_id.clone()))
+            .collect();
+        info!(
+            "request progress of {} of {} games",
+            game_info.len(),
+            self.games.len()
+        );
+
+        let final_scores = self.request_game_progress(&game_info).await;
+
+        // Forget the age of the games that no longer exist
+        self.game_ages
+            .retain(|game_id, _| self.games.contains_key(game_id));
+
+        info!("finished requesting game progress from tmars server");
+        final_scores
+    }
+
+    /// Requests the spectator view of the given games and updates them.
+    ///
+    /// A game whose spectator view is in the end phase is removed, and its final
+    /// scores are taken from the same spectator view.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_info` - The ID and spectator ID of each game.
+    ///
+    /// # Returns
+    ///
+    /// The final scores of the removed games by game ID.
+    async fn request_game_progress(
+        &mut self,
+        game_info: &[(String, String)],
+    ) -> HashMap<String, Vec<PlayerScore>> {
+        let spectator_futures = game_info
+            .iter()
+            .map(|(_, spectator_id)| self.tmars_requester.get_spectator(spectator_id));
+        let spectator_results = join_all(spectator_futures).await;
+
+        let mut final_scores = HashMap::new();
+        for ((game_id, _), spectator_result) in game_info.iter().zip(spectator_results) {
+            let spectator = match spectator_result {
+                Ok(spectator) => spectator,
+                Err(e) => {
+                    debug!("error while requesting progress of game {}: {}", game_id, e);
                     continue;
                 }
+            };
 
-                debug!(
-                    "game {}: waiting for players with colors {:?}",
-                    game.id, waited_players_colors
-                );
+            if spectator.game.phase.as_deref() == Some("end") {
+                info!("game {} ended", game_id);
+                self.games.remove(game_id);
+                self.game_ages.remove(game_id);
+                self.ended_game_ids.insert(game_id.clone());
+                final_scores.insert(game_id.clone(), Self::convert_scores(spectator.players));
+                continue;
+            }
 
-                // Map colors to player IDs
-                let waited_players: Vec<String> = game
-                    .players
-                    .iter()
-                    .filter_map(|player| {
-                        if waited_players_colors.contains(&player.color) {
-                            Some(player.id.to_owned())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
+            self.update_game_progress(game_id, spectator);
+        }
+
+        final_scores
+    }
 
+    /// Marks the games whose waited players changed as stale, their details are
+    /// requested again by the current synchronization, or by the next one after a
+    /// check.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous_games` - The games before the synchronization
+    fn mark_stale_games(&mut self, previous_games: &HashMap<String, Game>) {
+        for game in self.games.values() {
+            if let Some(previous_game) = previous_games.get(&game.id)
+                && previous_game.waited_players != game.waited_players
+            {
                 debug!(
-                    "game {}: mapped to player IDs {:?}",
-                    game.id, waited_players
+                    "game {}: waited players changed, details are stale",
+                    game.id
                 );
-
-                game.waited_players = waited_players.into_iter().collect();
+                self.stale_game_ids.insert(game.id.clone());
             }
         }
+    }
+
+    /// Updates a game from its spectator view.
+    ///
+    /// Updates the progress of the game, the terraform rating of its players matched
+    /// by color, and records the age of the game for the next check.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_id` - The ID of the game.
+    /// * `spectator` - The spectator view of the game.
+    fn update_game_progress(&mut self, game_id: &str, spectator: SpectatorResponse) {
+        let spectator_game = spectator.game;
+        let phase = spectator_game
+            .phase
+            .as_deref()
+            .map(|phase| self.convert_phase(phase));
+
+        let Some(game) = self.games.get_mut(game_id) else {
+            return;
+        };
+
+        if let Some(phase) = phase {
+            game.phase = phase;
+        }
+        game.progress = Some(GameProgress {
+            generation: spectator_game.generation,
+            temperature: spectator_game.temperature,
+            oxygen: spectator_game.oxygen_level,
+            oceans: spectator_game.oceans,
+            venus: spectator_game
+                .game_options
+                .expansions
+                .venus
+                .then_some(spectator_game.venus_scale_level),
+        });
 
-        info!("finished requesting waited players from tmars server");
+        for player in game.players.iter_mut() {
+            player.terraform_rating = spectator
+                .players
+                .iter()
+                .find(|spectator_player| spectator_player.color == player.color)
+                .map(|spectator_player| spectator_player.terraform_rating);
+        }
+
+        debug!("game {}: progress {:?}", game.id, game.progress);
+
+        self.game_ages.insert(
+            game_id.to_owned(),
+            GameAge {
+                game_age: spectator_game.game_age,
+                undo_count: spectator_game.undo_count,
+            },
+        );
     }
 
     /// Requests the list of player colors being waited for.
@@ -380,34 +914,73 @@ impl<R: Requester> TMarsSync<R> {
     ///
     /// # Returns
     ///
-    /// Vector of player colors (strings) being waited for, or empty vector on error.
+    /// Vector of player colors (strings) being waited for, or `None` on error.
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```ignore
+    /// use miou::config::TMarsRequests;
     /// use miou::tmars::{TMarsSync, TMarsRequester};
     ///
     /// # #[tokio::main]
     /// # async fn main() {
-    /// let tmars_requester = TMarsRequester::new("server_id".to_string(), "http://server_url".to_string());
-    /// let tmars_sync = TMarsSync::new(tmars_requester);
+    /// let tmars_requester = TMarsRequester::new("http://server_url", "server_id", &TMarsRequests::default());
+    /// let tmars_sync = TMarsSync::new("main", tmars_requester);
     /// let waited_players = tmars_sync.request_waited_players("spectator_id").await;
     /// println!("Waited players: {:?}", waited_players);
     /// # }
     /// ```
-    async fn request_waited_players(&self, spectator_id: &str) -> Vec<String> {
+    async fn request_waited_players(&self, spectator_id: &str) -> Option<Vec<String>> {
         match self.tmars_requester.get_waited_players(spectator_id).await {
-            Ok(waited_players) => waited_players.waiting_for,
+            Ok(waited_players) => Some(waited_players.waiting_for),
             Err(e) => {
                 debug!(
                     "error while requesting waited players {}: {}",
                     spectator_id, e
                 );
+                None
+            }
+        }
+    }
+
+    /// Requests the final scores of a game whose details are in the end phase.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The ended game, as seen during the previous synchronization.
+    ///
+    /// # Returns
+    ///
+    /// The scores of the players sorted from the highest to the lowest, or empty vector on error.
+    async fn request_final_scores(&self, game: &Game) -> Vec<PlayerScore> {
+        match self.tmars_requester.get_spectator(&game.spectator_id).await {
+            Ok(spectator) => Self::convert_scores(spectator.players),
+            Err(e) => {
+                error!("error while requesting final scores of {}: {}", game.id, e);
                 vec![]
             }
         }
     }
 
+    /// Converts the players of the spectator view of an ended game into their final scores.
+    ///
+    /// # Returns
+    ///
+    /// The scores of the players sorted from the highest to the lowest.
+    fn convert_scores(players: Vec<SpectatorPlayer>) -> Vec<PlayerScore> {
+        let mut scores: Vec<PlayerScore> = players
+            .into_iter()
+            .map(|player| PlayerScore {
+                name: player.name,
+                victory_points: player.victory_points_breakdown.total,
+            })
+            .collect();
+        // Stable sort, tied players keep the order of the game
+        scores.sort_by_key(|score| Reverse(score.victory_points));
+
+        scores
+    }
+
     /// Returns a clone of all synchronized games.
     ///
     /// # Returns
@@ -422,7 +995,7 @@ impl<R: Requester> TMarsSync<R> {
     ///
     /// # fn main() {
     /// let mock_requester = MockRequester::new();
-    /// let tmars_sync = TMarsSync::new(mock_requester);
+    /// let tmars_sync = TMarsSync::new("main", mock_requester);
     /// let games = tmars_sync.get_games();
     /// assert_eq!(games.len(), 0); // No games synchronized yet
     /// # }
@@ -436,9 +1009,28 @@ impl<R: Requester> TMarsSync<R> {
 mod tests {
     use super::*;
     use crate::tmars::requester::MockRequester;
-    use crate::tmars::response_structs::{GameResponse, WaitingForResponse};
+    use crate::tmars::response_structs::{
+        Expansions, GameOptions, GameResponse, SpectatorGame, SpectatorPlayer, SpectatorResponse,
+        VictoryPointsBreakdown, WaitingForResponse,
+    };
 
     // Helper function to create a mock reqwest error for testing
+    fn create_spectator_game(venus: bool) -> SpectatorGame {
+        SpectatorGame {
+            phase: None,
+            game_age: 10,
+            undo_count: 0,
+            generation: 5,
+            temperature: -12,
+            oxygen_level: 6,
+            oceans: 4,
+            venus_scale_level: 10,
+            game_options: GameOptions {
+                expansions: Expansions { venus },
+            },
+        }
+    }
+
     fn create_mock_error() -> reqwest::Error {
         // Create a reqwest error by using an invalid URL
         reqwest::Client::new()
@@ -451,6 +1043,10 @@ mod tests {
     async fn test_pool_games() {
         let mut mock_requester = MockRequester::new();
 
+        mock_requester
+            .expect_get_spectator_url()
+            .returning(|id| format!("http://example.com/spectator?id={}", id));
+
         // Mock get_player_url calls for all players
         mock_requester
             .expect_get_player_url()
@@ -525,7 +1121,7 @@ mod tests {
                 })
             });
 
-        let mut tmars_sync = TMarsSync::new(mock_requester);
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
 
         // Test pool_games method
         tmars_sync.pool_games().await.unwrap();
@@ -533,15 +1129,15 @@ mod tests {
         // Verify that games have been populated correctly
         assert_eq!(tmars_sync.games.len(), 2);
 
-        let game1 = tmars_sync.games.get("game1").unwrap();
-        assert_eq!(game1.id, "game1");
+        let game1 = tmars_sync.games.get("main:game1").unwrap();
+        assert_eq!(game1.id, "main:game1");
         assert_eq!(game1.spectator_id, "spec1");
         assert_eq!(game1.players.len(), 2);
         assert_eq!(game1.players[0].name, "Alice");
         assert_eq!(game1.players[1].name, "Bob");
 
-        let game2 = tmars_sync.games.get("game2").unwrap();
-        assert_eq!(game2.id, "game2");
+        let game2 = tmars_sync.games.get("main:game2").unwrap();
+        assert_eq!(game2.id, "main:game2");
         assert_eq!(game2.spectator_id, "spec2");
         assert_eq!(game2.players.len(), 1);
         assert_eq!(game2.players[0].name, "Charlie");
@@ -558,38 +1154,47 @@ mod tests {
             .times(1)
             .returning(|_| {
                 Ok(WaitingForResponse {
+                    result: None,
                     waiting_for: vec!["red".to_owned(), "blue".to_owned()],
                 })
             });
 
-        let mut tmars_sync = TMarsSync::new(mock_requester);
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
 
         // Manually add a game to test waited players functionality
         let game = Game {
             id: "game1".to_owned(),
             phase: Phase::Research,
             spectator_id: "spec1".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
             players: vec![
                 Player {
                     id: "player1".to_owned(),
                     name: "Alice".to_owned(),
                     color: "red".to_owned(),
                     url: "http://example.com/player1".to_owned(),
+                    terraform_rating: None,
+                    waiting_since: None,
                 },
                 Player {
                     id: "player2".to_owned(),
                     name: "Bob".to_owned(),
                     color: "blue".to_owned(),
                     url: "http://example.com/player2".to_owned(),
+                    terraform_rating: None,
+                    waiting_since: None,
                 },
                 Player {
                     id: "player3".to_owned(),
                     name: "Charlie".to_owned(),
                     color: "green".to_owned(),
                     url: "http://example.com/player3".to_owned(),
+                    terraform_rating: None,
+                    waiting_since: None,
                 },
             ],
             waited_players: HashSet::new(),
+            progress: None,
         };
 
         tmars_sync.games.insert("game1".to_owned(), game);
@@ -608,7 +1213,7 @@ mod tests {
     #[test]
     fn test_convert_phase() {
         let mock_requester = MockRequester::new();
-        let tmars_sync = TMarsSync::new(mock_requester);
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
 
         assert!(matches!(
             tmars_sync.convert_phase("research"),
@@ -620,10 +1225,10 @@ mod tests {
             Phase::Production
         ));
         assert!(matches!(tmars_sync.convert_phase("end"), Phase::End));
-        assert!(matches!(
+        assert_eq!(
             tmars_sync.convert_phase("unknown"),
-            Phase::Research
-        )); // Default
+            Phase::Unknown("unknown".to_owned())
+        );
     }
 
     #[test]
@@ -643,7 +1248,7 @@ mod tests {
             .times(1)
             .returning(|id| format!("http://example.com/{}", id));
 
-        let tmars_sync = TMarsSync::new(mock_requester);
+        let tmars_sync = TMarsSync::new("main", mock_requester);
 
         let player_details = vec![
             PlayerDetail {
@@ -674,34 +1279,42 @@ mod tests {
     #[tokio::test]
     async fn test_get_games() {
         let mock_requester = MockRequester::new();
-        let mut tmars_sync = TMarsSync::new(mock_requester);
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
 
         // Manually add a game to test
         let game = Game {
             id: "game1".to_owned(),
             phase: Phase::Research,
             spectator_id: "spec1".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
             players: vec![
                 Player {
                     id: "player1".to_owned(),
                     name: "Alice".to_owned(),
                     color: "red".to_owned(),
                     url: "http://example.com/player1".to_owned(),
+                    terraform_rating: None,
+                    waiting_since: None,
                 },
                 Player {
                     id: "player2".to_owned(),
                     name: "Bob".to_owned(),
                     color: "blue".to_owned(),
                     url: "http://example.com/player2".to_owned(),
+                    terraform_rating: None,
+                    waiting_since: None,
                 },
                 Player {
                     id: "player3".to_owned(),
                     name: "Charlie".to_owned(),
                     color: "green".to_owned(),
                     url: "http://example.com/player3".to_owned(),
+                    terraform_rating: None,
+                    waiting_since: None,
                 },
             ],
             waited_players: HashSet::new(),
+            progress: None,
         };
 
         tmars_sync.games.insert("game1".to_owned(), game.clone());
@@ -715,6 +1328,10 @@ mod tests {
     async fn test_sync_basic() {
         let mut mock_requester = MockRequester::new();
 
+        mock_requester
+            .expect_get_spectator_url()
+            .returning(|id| format!("http://example.com/spectator?id={}", id));
+
         // Mock get_player_url calls
         mock_requester
             .expect_get_player_url()
@@ -767,153 +1384,420 @@ mod tests {
             .times(1)
             .returning(|_| {
                 Ok(WaitingForResponse {
+                    result: None,
                     waiting_for: vec!["red".to_owned()],
                 })
             });
 
-        let mut tmars_sync = TMarsSync::new(mock_requester);
-
-        // Call sync
+        // Mock get_spectator
+        mock_requester
+            .expect_get_spectator()
+            .with(mockall::predicate::eq("spec1"))
+            .times(1)
+            .returning(|_| {
+                Ok(SpectatorResponse {
+                    game: create_spectator_game(true),
+                    players: vec![
+                        SpectatorPlayer {
+                            name: "Alice".to_owned(),
+                            color: "red".to_owned(),
+                            terraform_rating: 32,
+                            victory_points_breakdown: VictoryPointsBreakdown { total: 40 },
+                        },
+                        SpectatorPlayer {
+                            name: "Bob".to_owned(),
+                            color: "blue".to_owned(),
+                            terraform_rating: 28,
+                            victory_points_breakdown: VictoryPointsBreakdown { total: 35 },
+                        },
+                    ],
+                })
+            });
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+
+        // Call sync
         tmars_sync.sync().await.unwrap();
 
         // Verify games were synced
         let games = tmars_sync.get_games();
         assert_eq!(games.len(), 1);
 
-        let game = games.get("game1").unwrap();
-        assert_eq!(game.id, "game1");
+        let game = games.get("main:game1").unwrap();
+        assert_eq!(game.id, "main:game1");
         assert_eq!(game.spectator_id, "spec1");
         assert_eq!(game.players.len(), 2);
         assert_eq!(game.waited_players.len(), 1);
         assert!(game.waited_players.contains("player1"));
         assert!(!game.waited_players.contains("player2"));
+
+        // Verify progress was synced
+        assert_eq!(
+            game.progress,
+            Some(GameProgress {
+                generation: 5,
+                temperature: -12,
+                oxygen: 6,
+                oceans: 4,
+                venus: Some(10),
+            })
+        );
+        assert_eq!(game.players[0].terraform_rating, Some(32));
+        assert_eq!(game.players[1].terraform_rating, Some(28));
     }
 
     #[tokio::test]
-    async fn test_pool_games_with_get_games_error() {
+    async fn test_sync_sends_game_events() {
         let mut mock_requester = MockRequester::new();
 
-        // Mock get_games to return an error
         mock_requester
-            .expect_get_games()
-            .times(1)
-            .returning(|| Err(create_mock_error()));
+            .expect_get_spectator_url()
+            .returning(|id| format!("http://example.com/spectator?id={}", id));
 
-        let mut tmars_sync = TMarsSync::new(mock_requester);
+        mock_requester
+            .expect_get_player_url()
+            .returning(|id| format!("http://example.com/{}", id));
 
-        // Call pool_games - should handle error gracefully
-        tmars_sync.pool_games().await.unwrap();
+        mock_requester.expect_get_games().times(2).returning(|| {
+            Ok(vec![GameResponse {
+                game_id: "game1".to_owned(),
+            }])
+        });
 
-        // Verify that games map is empty due to error
-        assert_eq!(tmars_sync.games.len(), 0);
+        // Requested when the game is new, then when its waited players change
+        mock_requester
+            .expect_get_game_details()
+            .with(mockall::predicate::eq("game1"))
+            .times(2)
+            .returning(|_| {
+                Ok(GameDetail {
+                    id: "game1".to_owned(),
+                    phase: "action".to_owned(),
+                    spectator_id: "spec1".to_owned(),
+                    players: vec![
+                        PlayerDetail {
+                            id: "player1".to_owned(),
+                            name: "Alice".to_owned(),
+                            color: "red".to_owned(),
+                        },
+                        PlayerDetail {
+                            id: "player2".to_owned(),
+                            name: "Bob".to_owned(),
+                            color: "blue".to_owned(),
+                        },
+                    ],
+                })
+            });
+
+        // Alice plays first, then Bob
+        let mut waited_colors = vec!["blue", "red"];
+        mock_requester
+            .expect_get_waited_players()
+            .with(mockall::predicate::eq("spec1"))
+            .times(2)
+            .returning(move |_| {
+                Ok(WaitingForResponse {
+                    result: None,
+                    waiting_for: vec![waited_colors.pop().unwrap().to_owned()],
+                })
+            });
+
+        mock_requester
+            .expect_get_spectator()
+            .times(2)
+            .returning(|_| Err(create_mock_error()));
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+        let mut game_events = tmars_sync.subscribe();
+
+        // First sync: the game is discovered
+        tmars_sync.sync().await.unwrap();
+        assert_eq!(
+            game_events.try_recv().unwrap(),
+            GameEvent::GameAdded {
+                game_id: "main:game1".to_owned()
+            }
+        );
+        assert!(game_events.try_recv().is_err());
+
+        // Second sync: the turn moves from Alice to Bob
+        tmars_sync.sync().await.unwrap();
+        assert_eq!(
+            game_events.try_recv().unwrap(),
+            GameEvent::TurnEnded {
+                game_id: "main:game1".to_owned(),
+                player_id: "player1".to_owned(),
+            }
+        );
+        assert_eq!(
+            game_events.try_recv().unwrap(),
+            GameEvent::TurnStarted {
+                game_id: "main:game1".to_owned(),
+                player_id: "player2".to_owned(),
+            }
+        );
+        assert!(game_events.try_recv().is_err());
     }
 
     #[tokio::test]
-    async fn test_pool_games_with_get_game_details_error() {
+    async fn test_sync_requests_progress_of_refreshed_games() {
         let mut mock_requester = MockRequester::new();
 
-        // Mock get_games response
-        mock_requester.expect_get_games().times(1).returning(|| {
-            Ok(vec![
-                GameResponse {
-                    game_id: "game1".to_owned(),
-                },
-                GameResponse {
-                    game_id: "game2".to_owned(),
-                },
-            ])
-        });
+        mock_requester
+            .expect_get_spectator_url()
+            .returning(|id| format!("http://example.com/spectator?id={}", id));
 
-        // Mock get_game_details for game1 to return error
         mock_requester
-            .expect_get_game_details()
-            .with(mockall::predicate::eq("game1"))
-            .times(1)
-            .returning(|_| Err(create_mock_error()));
+            .expect_get_player_url()
+            .returning(|id| format!("http://example.com/{}", id));
 
-        // Mock get_game_details for game2 to succeed
+        mock_requester.expect_get_games().times(3).returning(|| {
+            Ok(vec![GameResponse {
+                game_id: "game1".to_owned(),
+            }])
+        });
+
+        // Requested when the game is new, then when its waited players change
         mock_requester
             .expect_get_game_details()
-            .with(mockall::predicate::eq("game2"))
-            .times(1)
+            .with(mockall::predicate::eq("game1"))
+            .times(2)
             .returning(|_| {
                 Ok(GameDetail {
-                    id: "game2".to_owned(),
+                    id: "game1".to_owned(),
                     phase: "action".to_owned(),
-                    spectator_id: "spec2".to_owned(),
+                    spectator_id: "spec1".to_owned(),
                     players: vec![PlayerDetail {
                         id: "player1".to_owned(),
-                        name: "Charlie".to_owned(),
-                        color: "green".to_owned(),
+                        name: "Alice".to_owned(),
+                        color: "red".to_owned(),
                     }],
                 })
             });
 
-        // Mock get_player_url for player1
+        // Popped from the last: Alice ends her turn at the second sync
+        let mut waited_colors = vec![vec![], vec![], vec!["red"]];
         mock_requester
-            .expect_get_player_url()
-            .with(mockall::predicate::eq("player1"))
-            .times(1)
-            .returning(|id| format!("http://example.com/{}", id));
+            .expect_get_waited_players()
+            .with(mockall::predicate::eq("spec1"))
+            .times(3)
+            .returning(move |_| {
+                Ok(WaitingForResponse {
+                    result: None,
+                    waiting_for: waited_colors
+                        .pop()
+                        .unwrap()
+                        .into_iter()
+                        .map(str::to_owned)
+                        .collect(),
+                })
+            });
 
-        let mut tmars_sync = TMarsSync::new(mock_requester);
+        // Requested with the details, the second request fails
+        let mut spectator_responses = vec![
+            Err(create_mock_error()),
+            Ok(SpectatorResponse {
+                game: create_spectator_game(true),
+                players: vec![SpectatorPlayer {
+                    name: "Alice".to_owned(),
+                    color: "red".to_owned(),
+                    terraform_rating: 32,
+                    victory_points_breakdown: VictoryPointsBreakdown { total: 40 },
+                }],
+            }),
+        ];
+        mock_requester
+            .expect_get_spectator()
+            .with(mockall::predicate::eq("spec1"))
+            .times(2)
+            .returning(move |_| spectator_responses.pop().unwrap());
 
-        // Call pool_games
-        tmars_sync.pool_games().await.unwrap();
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
 
-        // Verify that only game2 was added (game1 failed)
-        assert_eq!(tmars_sync.games.len(), 1);
-        assert!(tmars_sync.games.contains_key("game2"));
-        assert!(!tmars_sync.games.contains_key("game1"));
+        for _ in 0..3 {
+            tmars_sync.sync().await.unwrap();
+        }
+
+        // The rebuilt game keeps its progress and the terraform rating of its players
+        let game = &tmars_sync.games["main:game1"];
+        assert!(game.waited_players.is_empty());
+        assert_eq!(game.progress.as_ref().unwrap().generation, 5);
+        assert_eq!(game.players[0].terraform_rating, Some(32));
+        assert!(tmars_sync.stale_game_ids.is_empty());
     }
 
-    #[tokio::test]
-    async fn test_pool_games_filters_ended_games() {
+    /// Creates a mock requester serving game1 in the action phase, then in the end phase.
+    fn create_ending_game_requester() -> MockRequester {
         let mut mock_requester = MockRequester::new();
 
-        // Mock get_player_url for player1
+        mock_requester
+            .expect_get_spectator_url()
+            .returning(|id| format!("http://example.com/spectator?id={}", id));
+
         mock_requester
             .expect_get_player_url()
-            .with(mockall::predicate::eq("player1"))
-            .times(1)
             .returning(|id| format!("http://example.com/{}", id));
 
-        // Mock get_games response
-        mock_requester.expect_get_games().times(1).returning(|| {
-            Ok(vec![
-                GameResponse {
-                    game_id: "game1".to_owned(),
-                },
-                GameResponse {
-                    game_id: "game2".to_owned(),
-                },
-            ])
+        mock_requester.expect_get_games().times(2).returning(|| {
+            Ok(vec![GameResponse {
+                game_id: "game1".to_owned(),
+            }])
         });
 
-        // Mock get_game_details for game1 (ended game)
+        let mut phases = vec!["end", "action"];
         mock_requester
             .expect_get_game_details()
             .with(mockall::predicate::eq("game1"))
-            .times(1)
-            .returning(|_| {
+            .times(2)
+            .returning(move |_| {
                 Ok(GameDetail {
                     id: "game1".to_owned(),
-                    phase: "end".to_owned(),
+                    phase: phases.pop().unwrap().to_owned(),
                     spectator_id: "spec1".to_owned(),
-                    players: vec![],
+                    players: vec![
+                        PlayerDetail {
+                            id: "player1".to_owned(),
+                            name: "Alice".to_owned(),
+                            color: "red".to_owned(),
+                        },
+                        PlayerDetail {
+                            id: "player2".to_owned(),
+                            name: "Bob".to_owned(),
+                            color: "blue".to_owned(),
+                        },
+                    ],
                 })
             });
 
-        // Mock get_game_details for game2 (active game)
+        // Ended games are not synced, so waited players are only requested once
         mock_requester
-            .expect_get_game_details()
-            .with(mockall::predicate::eq("game2"))
+            .expect_get_waited_players()
+            .with(mockall::predicate::eq("spec1"))
             .times(1)
+            .returning(|_| {
+                Ok(WaitingForResponse {
+                    result: None,
+                    waiting_for: vec!["red".to_owned()],
+                })
+            });
+
+        mock_requester
+    }
+
+    #[tokio::test]
+    async fn test_sync_sends_game_ended_with_scores() {
+        let mut mock_requester = create_ending_game_requester();
+
+        // Requested for the progress during the first sync, then for the final scores
+        mock_requester
+            .expect_get_spectator()
+            .with(mockall::predicate::eq("spec1"))
+            .times(2)
+            .returning(|_| {
+                Ok(SpectatorResponse {
+                    game: create_spectator_game(false),
+                    players: vec![
+                        SpectatorPlayer {
+                            name: "Alice".to_owned(),
+                            color: "red".to_owned(),
+                            terraform_rating: 40,
+                            victory_points_breakdown: VictoryPointsBreakdown { total: 72 },
+                        },
+                        SpectatorPlayer {
+                            name: "Bob".to_owned(),
+                            color: "blue".to_owned(),
+                            terraform_rating: 45,
+                            victory_points_breakdown: VictoryPointsBreakdown { total: 85 },
+                        },
+                    ],
+                })
+            });
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+        let mut game_events = tmars_sync.subscribe();
+
+        tmars_sync.sync().await.unwrap();
+        game_events.try_recv().unwrap();
+
+        // The game changed, its details are requested again
+        tmars_sync.stale_game_ids.insert("main:game1".to_owned());
+        tmars_sync.sync().await.unwrap();
+        assert_eq!(
+            game_events.try_recv().unwrap(),
+            GameEvent::GameEnded {
+                game_id: "main:game1".to_owned(),
+                scores: vec![
+                    PlayerScore {
+                        name: "Bob".to_owned(),
+                        victory_points: 85,
+                    },
+                    PlayerScore {
+                        name: "Alice".to_owned(),
+                        victory_points: 72,
+                    },
+                ],
+            }
+        );
+        assert!(game_events.try_recv().is_err());
+        assert!(tmars_sync.games.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sync_sends_game_ended_without_scores_on_error() {
+        let mut mock_requester = create_ending_game_requester();
+
+        mock_requester
+            .expect_get_spectator()
+            .times(2)
+            .returning(|_| Err(create_mock_error()));
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+        let mut game_events = tmars_sync.subscribe();
+
+        tmars_sync.sync().await.unwrap();
+        game_events.try_recv().unwrap();
+
+        // The game changed, its details are requested again
+        tmars_sync.stale_game_ids.insert("main:game1".to_owned());
+        tmars_sync.sync().await.unwrap();
+        assert_eq!(
+            game_events.try_recv().unwrap(),
+            GameEvent::GameEnded {
+                game_id: "main:game1".to_owned(),
+                scores: vec![],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_skips_ended_games() {
+        let mut mock_requester = MockRequester::new();
+
+        mock_requester
+            .expect_get_spectator_url()
+            .returning(|id| format!("http://example.com/spectator?id={}", id));
+
+        mock_requester
+            .expect_get_player_url()
+            .returning(|id| format!("http://example.com/{}", id));
+
+        // The ended game stays listed on the server
+        mock_requester.expect_get_games().times(3).returning(|| {
+            Ok(vec![GameResponse {
+                game_id: "game1".to_owned(),
+            }])
+        });
+
+        // Requested when the game is new, then when its waited players change
+        mock_requester
+            .expect_get_game_details()
+            .with(mockall::predicate::eq("game1"))
+            .times(2)
             .returning(|_| {
                 Ok(GameDetail {
-                    id: "game2".to_owned(),
+                    id: "game1".to_owned(),
                     phase: "action".to_owned(),
-                    spectator_id: "spec2".to_owned(),
+                    spectator_id: "spec1".to_owned(),
                     players: vec![PlayerDetail {
                         id: "player1".to_owned(),
                         name: "Alice".to_owned(),
@@ -922,228 +1806,1156 @@ mod tests {
                 })
             });
 
-        let mut tmars_sync = TMarsSync::new(mock_requester);
+        let mut waited_colors = vec![vec![], vec!["red"]];
+        mock_requester
+            .expect_get_waited_players()
+            .with(mockall::predicate::eq("spec1"))
+            .times(2)
+            .returning(move |_| {
+                Ok(WaitingForResponse {
+                    result: None,
+                    waiting_for: waited_colors
+                        .pop()
+                        .unwrap()
+                        .into_iter()
+                        .map(str::to_owned)
+                        .collect(),
+                })
+            });
+
+        // The game ends during the second sync, its final scores are taken from the progress
+        let mut phases = vec![Some("end".to_owned()), None];
+        mock_requester
+            .expect_get_spectator()
+            .with(mockall::predicate::eq("spec1"))
+            .times(2)
+            .returning(move |_| {
+                let mut game = create_spectator_game(false);
+                game.phase = phases.pop().unwrap();
+                Ok(SpectatorResponse {
+                    game,
+                    players: vec![SpectatorPlayer {
+                        name: "Alice".to_owned(),
+                        color: "red".to_owned(),
+                        terraform_rating: 40,
+                        victory_points_breakdown: VictoryPointsBreakdown { total: 72 },
+                    }],
+                })
+            });
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+        let mut game_events = tmars_sync.subscribe();
+
+        tmars_sync.sync().await.unwrap();
+        game_events.try_recv().unwrap();
+
+        tmars_sync.sync().await.unwrap();
+        assert_eq!(
+            game_events.try_recv().unwrap(),
+            GameEvent::GameEnded {
+                game_id: "main:game1".to_owned(),
+                scores: vec![PlayerScore {
+                    name: "Alice".to_owned(),
+                    victory_points: 72,
+                }],
+            }
+        );
+
+        // Nothing is requested for the ended game anymore
+        tmars_sync.sync().await.unwrap();
+        assert!(game_events.try_recv().is_err());
+        assert!(tmars_sync.games.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pool_games_with_get_games_error() {
+        let mut mock_requester = MockRequester::new();
+
+        // Mock get_games to return an error
+        mock_requester
+            .expect_get_games()
+            .times(1)
+            .returning(|| Err(create_mock_error()));
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+        let game = Game {
+            id: "main:game1".to_owned(),
+            phase: Phase::Action,
+            spectator_id: "spec1".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
+            players: vec![],
+            waited_players: HashSet::new(),
+            progress: None,
+        };
+        tmars_sync.games.insert("main:game1".to_owned(), game);
+
+        let result = tmars_sync.pool_games().await;
+
+        // The error is returned and the last known games are kept
+        assert!(matches!(result, Err(SyncError::Network(_))));
+        assert_eq!(tmars_sync.games.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sync_with_server_error() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        server
+            .mock("GET", "/api/games")
+            .match_query(mockito::Matcher::Any)
+            .with_status(503)
+            .create_async()
+            .await;
+
+        let real_requester = crate::tmars::requester::TMarsRequester::new(
+            &url,
+            "test_id",
+            &crate::config::TMarsRequests::default(),
+        );
+        let mut tmars_sync = TMarsSync::new("main", real_requester);
+
+        let result = tmars_sync.sync().await;
+
+        assert!(matches!(result, Err(SyncError::Network(_))));
+    }
+
+    #[tokio::test]
+    async fn test_sync_with_invalid_response() {
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+
+        server
+            .mock("GET", "/api/games")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("not json")
+            .create_async()
+            .await;
+
+        let real_requester = crate::tmars::requester::TMarsRequester::new(
+            &url,
+            "test_id",
+            &crate::config::TMarsRequests::default(),
+        );
+        let mut tmars_sync = TMarsSync::new("main", real_requester);
+
+        let result = tmars_sync.sync().await;
+
+        assert!(matches!(result, Err(SyncError::Decode(_))));
+    }
+
+    #[tokio::test]
+    async fn test_pool_games_with_get_game_details_error() {
+        let mut mock_requester = MockRequester::new();
+
+        mock_requester
+            .expect_get_spectator_url()
+            .returning(|id| format!("http://example.com/spectator?id={}", id));
+
+        // Mock get_games response
+        mock_requester.expect_get_games().times(1).returning(|| {
+            Ok(vec![
+                GameResponse {
+                    game_id: "game1".to_owned(),
+                },
+                GameResponse {
+                    game_id: "game2".to_owned(),
+                },
+            ])
+        });
+
+        // Mock get_game_details for game1 to return error
+        mock_requester
+            .expect_get_game_details()
+            .with(mockall::predicate::eq("game1"))
+            .times(1)
+            .returning(|_| Err(create_mock_error()));
+
+        // Mock get_game_details for game2 to succeed
+        mock_requester
+            .expect_get_game_details()
+            .with(mockall::predicate::eq("game2"))
+            .times(1)
+            .returning(|_| {
+                Ok(GameDetail {
+                    id: "game2".to_owned(),
+                    phase: "action".to_owned(),
+                    spectator_id: "spec2".to_owned(),
+                    players: vec![PlayerDetail {
+                        id: "player1".to_owned(),
+                        name: "Charlie".to_owned(),
+                        color: "green".to_owned(),
+                    }],
+                })
+            });
+
+        // Mock get_player_url for player1
+        mock_requester
+            .expect_get_player_url()
+            .with(mockall::predicate::eq("player1"))
+            .times(1)
+            .returning(|id| format!("http://example.com/{}", id));
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+
+        // Call pool_games
+        tmars_sync.pool_games().await.unwrap();
+
+        // Verify that only game2 was added (game1 failed)
+        assert_eq!(tmars_sync.games.len(), 1);
+        assert!(tmars_sync.games.contains_key("main:game2"));
+        assert!(!tmars_sync.games.contains_key("main:game1"));
+    }
+
+    #[tokio::test]
+    async fn test_pool_games_filters_ended_games() {
+        let mut mock_requester = MockRequester::new();
+
+        mock_requester
+            .expect_get_spectator_url()
+            .returning(|id| format!("http://example.com/spectator?id={}", id));
+
+        // Mock get_player_url for player1
+        mock_requester
+            .expect_get_player_url()
+            .with(mockall::predicate::eq("player1"))
+            .times(1)
+            .returning(|id| format!("http://example.com/{}", id));
+
+        // Mock get_games response
+        mock_requester.expect_get_games().times(1).returning(|| {
+            Ok(vec![
+                GameResponse {
+                    game_id: "game1".to_owned(),
+                },
+                GameResponse {
+                    game_id: "game2".to_owned(),
+                },
+            ])
+        });
+
+        // Mock get_game_details for game1 (ended game)
+        mock_requester
+            .expect_get_game_details()
+            .with(mockall::predicate::eq("game1"))
+            .times(1)
+            .returning(|_| {
+                Ok(GameDetail {
+                    id: "game1".to_owned(),
+                    phase: "end".to_owned(),
+                    spectator_id: "spec1".to_owned(),
+                    players: vec![],
+                })
+            });
+
+        // Mock get_game_details for game2 (active game)
+        mock_requester
+            .expect_get_game_details()
+            .with(mockall::predicate::eq("game2"))
+            .times(1)
+            .returning(|_| {
+                Ok(GameDetail {
+                    id: "game2".to_owned(),
+                    phase: "action".to_owned(),
+                    spectator_id: "spec2".to_owned(),
+                    players: vec![PlayerDetail {
+                        id: "player1".to_owned(),
+                        name: "Alice".to_owned(),
+                        color: "red".to_owned(),
+                    }],
+                })
+            });
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+
+        // Call pool_games
+        tmars_sync.pool_games().await.unwrap();
+
+        // Verify that only game2 was added (game1 is ended)
+        assert_eq!(tmars_sync.games.len(), 1);
+        assert!(tmars_sync.games.contains_key("main:game2"));
+        assert!(!tmars_sync.games.contains_key("main:game1"));
+    }
+
+    #[tokio::test]
+    async fn test_pool_waited_players_with_error() {
+        let mut mock_requester = MockRequester::new();
+
+        // Setup mock for get_waited_players to return error
+        mock_requester
+            .expect_get_waited_players()
+            .with(mockall::predicate::eq("spec1"))
+            .times(1)
+            .returning(|_| Err(create_mock_error()));
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+
+        // Manually add a game to test waited players functionality
+        let game = Game {
+            id: "game1".to_owned(),
+            phase: Phase::Research,
+            spectator_id: "spec1".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
+            players: vec![Player {
+                id: "player1".to_owned(),
+                name: "Alice".to_owned(),
+                color: "red".to_owned(),
+                url: "http://example.com/player1".to_owned(),
+                terraform_rating: None,
+                waiting_since: None,
+            }],
+            waited_players: HashSet::from(["player1".to_owned()]),
+            progress: None,
+        };
+
+        tmars_sync.games.insert("game1".to_owned(), game);
+
+        // Call pool_waited_players - should handle error gracefully
+        tmars_sync.pool_waited_players().await;
+
+        // Verify the waited players are kept on error
+        let game = tmars_sync.games.get("game1").unwrap();
+        assert_eq!(game.waited_players, HashSet::from(["player1".to_owned()]));
+    }
+
+    #[tokio::test]
+    async fn test_pool_waited_players_empty_response() {
+        let mut mock_requester = MockRequester::new();
+
+        // Setup mock for get_waited_players to return empty list
+        mock_requester
+            .expect_get_waited_players()
+            .with(mockall::predicate::eq("spec1"))
+            .times(1)
+            .returning(|_| {
+                Ok(WaitingForResponse {
+                    result: None,
+                    waiting_for: vec![],
+                })
+            });
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+
+        // Manually add a game with some pre-existing waited players
+        let mut waited_players = HashSet::new();
+        waited_players.insert("player1".to_owned());
+
+        let game = Game {
+            id: "game1".to_owned(),
+            phase: Phase::Research,
+            spectator_id: "spec1".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
+            players: vec![Player {
+                id: "player1".to_owned(),
+                name: "Alice".to_owned(),
+                color: "red".to_owned(),
+                url: "http://example.com/player1".to_owned(),
+                terraform_rating: None,
+                waiting_since: None,
+            }],
+            waited_players,
+            progress: None,
+        };
+
+        tmars_sync.games.insert("game1".to_owned(), game);
+
+        // Call pool_waited_players
+        tmars_sync.pool_waited_players().await;
+
+        // Verify waited players was cleared
+        let game = tmars_sync.games.get("game1").unwrap();
+        assert_eq!(game.waited_players.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_pool_waited_players_color_not_matching() {
+        let mut mock_requester = MockRequester::new();
+
+        // Setup mock for get_waited_players with colors that don't match
+        mock_requester
+            .expect_get_waited_players()
+            .with(mockall::predicate::eq("spec1"))
+            .times(1)
+            .returning(|_| {
+                Ok(WaitingForResponse {
+                    result: None,
+                    waiting_for: vec!["purple".to_owned(), "yellow".to_owned()],
+                })
+            });
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+
+        // Add a game with players that don't match the waited colors
+        let game = Game {
+            id: "game1".to_owned(),
+            phase: Phase::Research,
+            spectator_id: "spec1".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
+            players: vec![
+                Player {
+                    id: "player1".to_owned(),
+                    name: "Alice".to_owned(),
+                    color: "red".to_owned(),
+                    url: "http://example.com/player1".to_owned(),
+                    terraform_rating: None,
+                    waiting_since: None,
+                },
+                Player {
+                    id: "player2".to_owned(),
+                    name: "Bob".to_owned(),
+                    color: "blue".to_owned(),
+                    url: "http://example.com/player2".to_owned(),
+                    terraform_rating: None,
+                    waiting_since: None,
+                },
+            ],
+            waited_players: HashSet::new(),
+            progress: None,
+        };
+
+        tmars_sync.games.insert("game1".to_owned(), game);
+
+        // Call pool_waited_players
+        tmars_sync.pool_waited_players().await;
+
+        // Verify no waited players were added (colors don't match)
+        let game = tmars_sync.games.get("game1").unwrap();
+        assert_eq!(game.waited_players.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_request_waited_players_error() {
+        let mut mock_requester = MockRequester::new();
+
+        // Setup mock for get_waited_players to return error
+        mock_requester
+            .expect_get_waited_players()
+            .with(mockall::predicate::eq("spec1"))
+            .times(1)
+            .returning(|_| Err(create_mock_error()));
+
+        let tmars_sync = TMarsSync::new("main", mock_requester);
+
+        // Call request_waited_players
+        let waited_players = tmars_sync.request_waited_players("spec1").await;
+
+        // Verify no result on error
+        assert_eq!(waited_players, None);
+    }
+
+    #[tokio::test]
+    async fn test_sync_with_partial_failures() {
+        let mut mock_requester = MockRequester::new();
+
+        mock_requester
+            .expect_get_spectator_url()
+            .returning(|id| format!("http://example.com/spectator?id={}", id));
+
+        // Mock get_player_url
+        mock_requester
+            .expect_get_player_url()
+            .with(mockall::predicate::eq("player1"))
+            .times(1)
+            .returning(|id| format!("http://example.com/{}", id));
+
+        // Mock get_games response
+        mock_requester.expect_get_games().times(1).returning(|| {
+            Ok(vec![GameResponse {
+                game_id: "game1".to_owned(),
+            }])
+        });
+
+        // Mock get_game_details succeeds
+        mock_requester
+            .expect_get_game_details()
+            .with(mockall::predicate::eq("game1"))
+            .times(1)
+            .returning(|_| {
+                Ok(GameDetail {
+                    id: "game1".to_owned(),
+                    phase: "action".to_owned(),
+                    spectator_id: "spec1".to_owned(),
+                    players: vec![PlayerDetail {
+                        id: "player1".to_owned(),
+                        name: "Alice".to_owned(),
+                        color: "red".to_owned(),
+                    }],
+                })
+            });
+
+        // Mock get_waited_players fails
+        mock_requester
+            .expect_get_waited_players()
+            .with(mockall::predicate::eq("spec1"))
+            .times(1)
+            .returning(|_| Err(create_mock_error()));
+
+        // Mock get_spectator fails
+        mock_requester
+            .expect_get_spectator()
+            .with(mockall::predicate::eq("spec1"))
+            .times(1)
+            .returning(|_| Err(create_mock_error()));
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+
+        // Call sync
+        tmars_sync.sync().await.unwrap();
+
+        // Verify game was added but waited players and progress are empty
+        let games = tmars_sync.get_games();
+        assert_eq!(games.len(), 1);
+
+        let game = games.get("main:game1").unwrap();
+        assert_eq!(game.id, "main:game1");
+        assert_eq!(game.waited_players.len(), 0);
+        assert_eq!(game.progress, None);
+        assert_eq!(game.players[0].terraform_rating, None);
+    }
+
+    #[tokio::test]
+    async fn test_sync_keeps_turns_on_waited_players_error() {
+        let mut mock_requester = MockRequester::new();
+
+        mock_requester
+            .expect_get_spectator_url()
+            .returning(|id| format!("http://example.com/spectator?id={}", id));
+
+        mock_requester
+            .expect_get_player_url()
+            .returning(|id| format!("http://example.com/{}", id));
+
+        mock_requester.expect_get_games().times(3).returning(|| {
+            Ok(vec![GameResponse {
+                game_id: "game1".to_owned(),
+            }])
+        });
+
+        mock_requester
+            .expect_get_game_details()
+            .with(mockall::predicate::eq("game1"))
+            .times(1)
+            .returning(|_| {
+                Ok(GameDetail {
+                    id: "game1".to_owned(),
+                    phase: "action".to_owned(),
+                    spectator_id: "spec1".to_owned(),
+                    players: vec![PlayerDetail {
+                        id: "player1".to_owned(),
+                        name: "Alice".to_owned(),
+                        color: "red".to_owned(),
+                    }],
+                })
+            });
+
+        // Popped from the last: the request fails during the second sync
+        let mut waited_colors = vec![Some("red"), None, Some("red")];
+        mock_requester
+            .expect_get_waited_players()
+            .with(mockall::predicate::eq("spec1"))
+            .times(3)
+            .returning(move |_| match waited_colors.pop().unwrap() {
+                Some(color) => Ok(WaitingForResponse {
+                    result: None,
+                    waiting_for: vec![color.to_owned()],
+                }),
+                None => Err(create_mock_error()),
+            });
+
+        mock_requester
+            .expect_get_spectator()
+            .times(1)
+            .returning(|_| Err(create_mock_error()));
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+        let mut game_events = tmars_sync.subscribe();
+
+        tmars_sync.sync().await.unwrap();
+        game_events.try_recv().unwrap();
+
+        // Alice's turn goes on through the error
+        tmars_sync.sync().await.unwrap();
+        assert!(
+            tmars_sync.games["main:game1"]
+                .waited_players
+                .contains("player1")
+        );
+        tmars_sync.sync().await.unwrap();
+        assert!(game_events.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_update_waiting_since() {
+        let mut tmars_sync = TMarsSync::new("main", MockRequester::new());
+        let create_game = |waited_players: &[&str], waiting_since: Option<u64>| Game {
+            id: "game1".to_owned(),
+            phase: Phase::Action,
+            spectator_id: "spec1".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
+            players: ["player1", "player2"]
+                .iter()
+                .map(|id| Player {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    color: id.to_string(),
+                    url: format!("http://example.com/{}", id),
+                    terraform_rating: None,
+                    waiting_since: waiting_since.filter(|_| waited_players.contains(id)),
+                })
+                .collect(),
+            waited_players: waited_players.iter().map(|id| id.to_string()).collect(),
+            progress: None,
+        };
+
+        // player1 was already waited for, player2 starts being waited for
+        let previous_games =
+            HashMap::from([("game1".to_owned(), create_game(&["player1"], Some(1000)))]);
+        tmars_sync.games = HashMap::from([(
+            "game1".to_owned(),
+            create_game(&["player1", "player2"], None),
+        )]);
+
+        tmars_sync.update_waiting_since(&previous_games);
+
+        let game = tmars_sync.games.get("game1").unwrap();
+        assert_eq!(game.players[0].waiting_since, Some(1000));
+        assert!(game.players[1].waiting_since.unwrap() >= get_timestamp() - 1);
 
-        // Call pool_games
-        tmars_sync.pool_games().await.unwrap();
+        // Nobody is waited for anymore
+        let previous_games = tmars_sync.games.clone();
+        tmars_sync.games = HashMap::from([("game1".to_owned(), create_game(&[], None))]);
 
-        // Verify that only game2 was added (game1 is ended)
-        assert_eq!(tmars_sync.games.len(), 1);
-        assert!(tmars_sync.games.contains_key("game2"));
-        assert!(!tmars_sync.games.contains_key("game1"));
+        tmars_sync.update_waiting_since(&previous_games);
+
+        let game = tmars_sync.games.get("game1").unwrap();
+        assert!(game.players.iter().all(|p| p.waiting_since.is_none()));
     }
 
     #[tokio::test]
-    async fn test_pool_waited_players_with_error() {
+    async fn test_pool_game_progress_without_venus() {
         let mut mock_requester = MockRequester::new();
 
-        // Setup mock for get_waited_players to return error
         mock_requester
-            .expect_get_waited_players()
+            .expect_get_spectator()
             .with(mockall::predicate::eq("spec1"))
             .times(1)
-            .returning(|_| Err(create_mock_error()));
-
-        let mut tmars_sync = TMarsSync::new(mock_requester);
+            .returning(|_| {
+                Ok(SpectatorResponse {
+                    game: create_spectator_game(false),
+                    players: vec![],
+                })
+            });
 
-        // Manually add a game to test waited players functionality
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
         let game = Game {
             id: "game1".to_owned(),
-            phase: Phase::Research,
+            phase: Phase::Action,
             spectator_id: "spec1".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
             players: vec![Player {
                 id: "player1".to_owned(),
                 name: "Alice".to_owned(),
                 color: "red".to_owned(),
                 url: "http://example.com/player1".to_owned(),
+                terraform_rating: None,
+                waiting_since: None,
             }],
             waited_players: HashSet::new(),
+            progress: None,
         };
-
         tmars_sync.games.insert("game1".to_owned(), game);
+        tmars_sync.refreshed_game_ids.insert("game1".to_owned());
 
-        // Call pool_waited_players - should handle error gracefully
-        tmars_sync.pool_waited_players().await;
+        tmars_sync.pool_game_progress().await;
 
-        // Verify waited players is empty due to error
         let game = tmars_sync.games.get("game1").unwrap();
-        assert_eq!(game.waited_players.len(), 0);
+        assert_eq!(game.progress.as_ref().unwrap().generation, 5);
+        assert_eq!(game.progress.as_ref().unwrap().venus, None);
+        // Alice is missing from the spectator view
+        assert_eq!(game.players[0].terraform_rating, None);
+        assert_eq!(
+            tmars_sync.game_ages.get("game1"),
+            Some(&GameAge {
+                game_age: 10,
+                undo_count: 0,
+            })
+        );
+    }
+
+    fn create_game_detail(game_id: &str) -> GameDetail {
+        GameDetail {
+            id: game_id.to_owned(),
+            phase: "action".to_owned(),
+            spectator_id: format!("spec_{}", game_id),
+            players: vec![],
+        }
     }
 
     #[tokio::test]
-    async fn test_pool_waited_players_empty_response() {
+    async fn test_pool_games_requests_details_of_new_and_stale_games() {
         let mut mock_requester = MockRequester::new();
 
-        // Setup mock for get_waited_players to return empty list
         mock_requester
-            .expect_get_waited_players()
-            .with(mockall::predicate::eq("spec1"))
+            .expect_get_spectator_url()
+            .returning(|id| format!("http://example.com/spectator?id={}", id));
+
+        // Popped from the last
+        let mut game_ids = vec![
+            vec!["game1", "game2"],
+            vec!["game1", "game2"],
+            vec!["game1"],
+        ];
+        mock_requester
+            .expect_get_games()
+            .times(3)
+            .returning(move || {
+                Ok(game_ids
+                    .pop()
+                    .unwrap()
+                    .into_iter()
+                    .map(|game_id| GameResponse {
+                        game_id: game_id.to_owned(),
+                    })
+                    .collect())
+            });
+
+        // game1 is requested when new, then when stale
+        mock_requester
+            .expect_get_game_details()
+            .with(mockall::predicate::eq("game1"))
+            .times(2)
+            .returning(|game_id| Ok(create_game_detail(game_id)));
+
+        mock_requester
+            .expect_get_game_details()
+            .with(mockall::predicate::eq("game2"))
             .times(1)
-            .returning(|_| {
-                Ok(WaitingForResponse {
-                    waiting_for: vec![],
-                })
+            .returning(|game_id| Ok(create_game_detail(game_id)));
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+
+        // Only game1 is new
+        tmars_sync.pool_games().await.unwrap();
+        assert_eq!(tmars_sync.games.len(), 1);
+
+        // game1 is cached, game2 is new
+        tmars_sync
+            .games
+            .get_mut("main:game1")
+            .unwrap()
+            .waited_players
+            .insert("player1".to_owned());
+        tmars_sync.pool_games().await.unwrap();
+        assert_eq!(tmars_sync.games.len(), 2);
+        // A cached game is kept as it was
+        assert_eq!(tmars_sync.games["main:game1"].waited_players.len(), 1);
+
+        // game1 is stale, game2 is cached
+        tmars_sync.stale_game_ids.insert("main:game1".to_owned());
+        tmars_sync.pool_games().await.unwrap();
+        assert_eq!(tmars_sync.games.len(), 2);
+        assert!(tmars_sync.stale_game_ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pool_games_full_refresh() {
+        let mut mock_requester = MockRequester::new();
+
+        mock_requester
+            .expect_get_spectator_url()
+            .returning(|id| format!("http://example.com/spectator?id={}", id));
+
+        mock_requester
+            .expect_get_games()
+            .times(FULL_REFRESH_SYNCS as usize + 1)
+            .returning(|| {
+                Ok(vec![GameResponse {
+                    game_id: "game1".to_owned(),
+                }])
             });
 
-        let mut tmars_sync = TMarsSync::new(mock_requester);
+        // Requested during the first sync, then during the full refresh
+        mock_requester
+            .expect_get_game_details()
+            .with(mockall::predicate::eq("game1"))
+            .times(2)
+            .returning(|game_id| Ok(create_game_detail(game_id)));
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
 
-        // Manually add a game with some pre-existing waited players
-        let mut waited_players = HashSet::new();
-        waited_players.insert("player1".to_owned());
+        for _ in 0..=FULL_REFRESH_SYNCS {
+            tmars_sync.pool_games().await.unwrap();
+        }
+
+        assert_eq!(tmars_sync.games.len(), 1);
+        assert_eq!(tmars_sync.syncs_since_full_refresh, 1);
+    }
 
+    #[tokio::test]
+    async fn test_pool_games_keeps_stale_game_on_error() {
+        let mut mock_requester = MockRequester::new();
+
+        mock_requester.expect_get_games().times(1).returning(|| {
+            Ok(vec![GameResponse {
+                game_id: "game1".to_owned(),
+            }])
+        });
+
+        mock_requester
+            .expect_get_game_details()
+            .with(mockall::predicate::eq("game1"))
+            .times(1)
+            .returning(|_| Err(create_mock_error()));
+
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+        tmars_sync.syncs_since_full_refresh = 1;
         let game = Game {
-            id: "game1".to_owned(),
-            phase: Phase::Research,
+            id: "main:game1".to_owned(),
+            phase: Phase::Action,
             spectator_id: "spec1".to_owned(),
-            players: vec![Player {
-                id: "player1".to_owned(),
-                name: "Alice".to_owned(),
-                color: "red".to_owned(),
-                url: "http://example.com/player1".to_owned(),
-            }],
-            waited_players,
+            spectator_url: "http://example.com/spectator".to_string(),
+            players: vec![],
+            waited_players: HashSet::new(),
+            progress: None,
         };
+        tmars_sync.games.insert("main:game1".to_owned(), game);
+        tmars_sync.stale_game_ids.insert("main:game1".to_owned());
 
-        tmars_sync.games.insert("game1".to_owned(), game);
+        tmars_sync.pool_games().await.unwrap();
 
-        // Call pool_waited_players
-        tmars_sync.pool_waited_players().await;
+        // The game is kept, its details are requested again at the next sync
+        assert!(tmars_sync.games.contains_key("main:game1"));
+        assert!(tmars_sync.stale_game_ids.contains("main:game1"));
+    }
 
-        // Verify waited players was cleared
-        let game = tmars_sync.games.get("game1").unwrap();
-        assert_eq!(game.waited_players.len(), 0);
+    #[test]
+    fn test_mark_stale_games() {
+        let mock_requester = MockRequester::new();
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+        let game = Game {
+            id: "main:game1".to_owned(),
+            phase: Phase::Action,
+            spectator_id: "spec1".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
+            players: vec![],
+            waited_players: HashSet::new(),
+            progress: None,
+        };
+        let mut changed_game = game.clone();
+        changed_game.id = "main:game2".to_owned();
+        let previous_games = HashMap::from([
+            ("main:game1".to_owned(), game.clone()),
+            ("main:game2".to_owned(), changed_game.clone()),
+        ]);
+
+        changed_game.waited_players.insert("player1".to_owned());
+        tmars_sync.games = HashMap::from([
+            ("main:game1".to_owned(), game),
+            ("main:game2".to_owned(), changed_game),
+        ]);
+
+        tmars_sync.mark_stale_games(&previous_games);
+
+        assert_eq!(
+            tmars_sync.stale_game_ids,
+            HashSet::from(["main:game2".to_owned()])
+        );
     }
 
     #[tokio::test]
-    async fn test_pool_waited_players_color_not_matching() {
+    async fn test_pool_game_progress_removes_ended_games() {
         let mut mock_requester = MockRequester::new();
 
-        // Setup mock for get_waited_players with colors that don't match
         mock_requester
-            .expect_get_waited_players()
+            .expect_get_spectator()
             .with(mockall::predicate::eq("spec1"))
             .times(1)
             .returning(|_| {
-                Ok(WaitingForResponse {
-                    waiting_for: vec!["purple".to_owned(), "yellow".to_owned()],
+                let mut game = create_spectator_game(false);
+                game.phase = Some("end".to_owned());
+                Ok(SpectatorResponse {
+                    game,
+                    players: vec![],
                 })
             });
 
-        let mut tmars_sync = TMarsSync::new(mock_requester);
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
+        let game = Game {
+            id: "main:game1".to_owned(),
+            phase: Phase::Action,
+            spectator_id: "spec1".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
+            players: vec![],
+            waited_players: HashSet::new(),
+            progress: None,
+        };
+        tmars_sync.games.insert("main:game1".to_owned(), game);
+        tmars_sync
+            .refreshed_game_ids
+            .insert("main:game1".to_owned());
+
+        let final_scores = tmars_sync.pool_game_progress().await;
+
+        assert_eq!(
+            final_scores,
+            HashMap::from([("main:game1".to_owned(), vec![])])
+        );
+        assert!(tmars_sync.games.is_empty());
+        assert!(tmars_sync.ended_game_ids.contains("main:game1"));
+    }
 
-        // Add a game with players that don't match the waited colors
+    /// Creates a sync of the main server with game1 at age 10, waiting for Alice.
+    fn create_checked_sync(mock_requester: MockRequester) -> TMarsSync<MockRequester> {
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
         let game = Game {
-            id: "game1".to_owned(),
-            phase: Phase::Research,
+            id: "main:game1".to_owned(),
+            phase: Phase::Action,
             spectator_id: "spec1".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
             players: vec![
                 Player {
                     id: "player1".to_owned(),
                     name: "Alice".to_owned(),
                     color: "red".to_owned(),
                     url: "http://example.com/player1".to_owned(),
+                    terraform_rating: None,
+                    waiting_since: Some(1000),
                 },
                 Player {
                     id: "player2".to_owned(),
                     name: "Bob".to_owned(),
                     color: "blue".to_owned(),
                     url: "http://example.com/player2".to_owned(),
+                    terraform_rating: None,
+                    waiting_since: None,
                 },
             ],
-            waited_players: HashSet::new(),
+            waited_players: HashSet::from(["player1".to_owned()]),
+            progress: None,
         };
+        tmars_sync.games.insert("main:game1".to_owned(), game);
+        tmars_sync.game_ages.insert(
+            "main:game1".to_owned(),
+            GameAge {
+                game_age: 10,
+                undo_count: 0,
+            },
+        );
+        tmars_sync
+    }
 
-        tmars_sync.games.insert("game1".to_owned(), game);
+    #[tokio::test]
+    async fn test_check_updates_changed_games() {
+        let mut mock_requester = MockRequester::new();
 
-        // Call pool_waited_players
-        tmars_sync.pool_waited_players().await;
+        mock_requester
+            .expect_get_waited_players_since()
+            .withf(|spectator_id, game_age| spectator_id == "spec1" && game_age.game_age == 10)
+            .times(1)
+            .returning(|_, _| {
+                Ok(WaitingForResponse {
+                    result: Some(WaitingForResult::Refresh),
+                    waiting_for: vec!["blue".to_owned()],
+                })
+            });
 
-        // Verify no waited players were added (colors don't match)
-        let game = tmars_sync.games.get("game1").unwrap();
-        assert_eq!(game.waited_players.len(), 0);
+        mock_requester
+            .expect_get_spectator()
+            .with(mockall::predicate::eq("spec1"))
+            .times(1)
+            .returning(|_| {
+                let mut game = create_spectator_game(false);
+                game.game_age = 11;
+                Ok(SpectatorResponse {
+                    game,
+                    players: vec![],
+                })
+            });
+
+        let mut tmars_sync = create_checked_sync(mock_requester);
+        let mut game_events = tmars_sync.subscribe();
+
+        tmars_sync.check().await;
+
+        let game = tmars_sync.games.get("main:game1").unwrap();
+        assert_eq!(game.waited_players, HashSet::from(["player2".to_owned()]));
+        assert_eq!(game.players[0].waiting_since, None);
+        assert!(game.players[1].waiting_since.is_some());
+        assert_eq!(game.progress.as_ref().unwrap().generation, 5);
+        assert_eq!(
+            tmars_sync.game_ages.get("main:game1"),
+            Some(&GameAge {
+                game_age: 11,
+                undo_count: 0,
+            })
+        );
+
+        assert_eq!(
+            game_events.try_recv().unwrap(),
+            GameEvent::TurnEnded {
+                game_id: "main:game1".to_owned(),
+                player_id: "player1".to_owned(),
+            }
+        );
+        assert_eq!(
+            game_events.try_recv().unwrap(),
+            GameEvent::TurnStarted {
+                game_id: "main:game1".to_owned(),
+                player_id: "player2".to_owned(),
+            }
+        );
+        assert!(game_events.try_recv().is_err());
     }
 
     #[tokio::test]
-    async fn test_request_waited_players_error() {
+    async fn test_check_skips_unchanged_games() {
         let mut mock_requester = MockRequester::new();
 
-        // Setup mock for get_waited_players to return error
         mock_requester
-            .expect_get_waited_players()
-            .with(mockall::predicate::eq("spec1"))
+            .expect_get_waited_players_since()
+            .times(1)
+            .returning(|_, _| {
+                Ok(WaitingForResponse {
+                    result: Some(WaitingForResult::Wait),
+                    waiting_for: vec!["red".to_owned()],
+                })
+            });
+
+        // The progress of an unchanged game is not requested
+        mock_requester.expect_get_spectator().times(0);
+
+        let mut tmars_sync = create_checked_sync(mock_requester);
+        let mut game_events = tmars_sync.subscribe();
+
+        tmars_sync.check().await;
+
+        let game = tmars_sync.games.get("main:game1").unwrap();
+        assert_eq!(game.waited_players, HashSet::from(["player1".to_owned()]));
+        assert_eq!(game.players[0].waiting_since, Some(1000));
+        assert!(game_events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_without_result_compares_waited_players() {
+        let mut mock_requester = MockRequester::new();
+
+        // Older servers don't compare the age of the game
+        mock_requester
+            .expect_get_waited_players_since()
+            .times(1)
+            .returning(|_, _| {
+                Ok(WaitingForResponse {
+                    result: None,
+                    waiting_for: vec!["red".to_owned(), "blue".to_owned()],
+                })
+            });
+
+        mock_requester
+            .expect_get_spectator()
             .times(1)
             .returning(|_| Err(create_mock_error()));
 
-        let tmars_sync = TMarsSync::new(mock_requester);
+        let mut tmars_sync = create_checked_sync(mock_requester);
 
-        // Call request_waited_players
-        let waited_players = tmars_sync.request_waited_players("spec1").await;
+        tmars_sync.check().await;
 
-        // Verify empty result on error
-        assert_eq!(waited_players.len(), 0);
+        let game = tmars_sync.games.get("main:game1").unwrap();
+        assert_eq!(game.waited_players.len(), 2);
+        // The age is kept until the progress is requested successfully
+        assert_eq!(tmars_sync.game_ages.get("main:game1").unwrap().game_age, 10);
     }
 
     #[tokio::test]
-    async fn test_sync_with_partial_failures() {
+    async fn test_check_with_error_keeps_games() {
         let mut mock_requester = MockRequester::new();
 
-        // Mock get_player_url
         mock_requester
-            .expect_get_player_url()
-            .with(mockall::predicate::eq("player1"))
+            .expect_get_waited_players_since()
             .times(1)
-            .returning(|id| format!("http://example.com/{}", id));
+            .returning(|_, _| Err(create_mock_error()));
 
-        // Mock get_games response
-        mock_requester.expect_get_games().times(1).returning(|| {
-            Ok(vec![GameResponse {
-                game_id: "game1".to_owned(),
-            }])
-        });
+        let mut tmars_sync = create_checked_sync(mock_requester);
+        let mut game_events = tmars_sync.subscribe();
+
+        tmars_sync.check().await;
+
+        let game = tmars_sync.games.get("main:game1").unwrap();
+        assert_eq!(game.waited_players, HashSet::from(["player1".to_owned()]));
+        assert!(game_events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_removes_ended_games() {
+        let mut mock_requester = MockRequester::new();
 
-        // Mock get_game_details succeeds
         mock_requester
-            .expect_get_game_details()
-            .with(mockall::predicate::eq("game1"))
+            .expect_get_waited_players_since()
+            .times(1)
+            .returning(|_, _| {
+                Ok(WaitingForResponse {
+                    result: Some(WaitingForResult::Refresh),
+                    waiting_for: vec![],
+                })
+            });
+
+        // The final scores are taken from the progress
+        mock_requester
+            .expect_get_spectator()
+            .with(mockall::predicate::eq("spec1"))
             .times(1)
             .returning(|_| {
-                Ok(GameDetail {
-                    id: "game1".to_owned(),
-                    phase: "action".to_owned(),
-                    spectator_id: "spec1".to_owned(),
-                    players: vec![PlayerDetail {
-                        id: "player1".to_owned(),
+                let mut game = create_spectator_game(false);
+                game.phase = Some("end".to_owned());
+                Ok(SpectatorResponse {
+                    game,
+                    players: vec![SpectatorPlayer {
                         name: "Alice".to_owned(),
                         color: "red".to_owned(),
+                        terraform_rating: 40,
+                        victory_points_breakdown: VictoryPointsBreakdown { total: 72 },
                     }],
                 })
             });
 
-        // Mock get_waited_players fails
-        mock_requester
-            .expect_get_waited_players()
-            .with(mockall::predicate::eq("spec1"))
-            .times(1)
-            .returning(|_| Err(create_mock_error()));
+        let mut tmars_sync = create_checked_sync(mock_requester);
+        let mut game_events = tmars_sync.subscribe();
 
-        let mut tmars_sync = TMarsSync::new(mock_requester);
+        tmars_sync.check().await;
 
-        // Call sync
-        tmars_sync.sync().await.unwrap();
+        assert!(tmars_sync.games.is_empty());
+        assert!(tmars_sync.game_ages.is_empty());
+        assert_eq!(
+            game_events.try_recv().unwrap(),
+            GameEvent::GameEnded {
+                game_id: "main:game1".to_owned(),
+                scores: vec![PlayerScore {
+                    name: "Alice".to_owned(),
+                    victory_points: 72,
+                }],
+            }
+        );
+        assert!(game_events.try_recv().is_err());
+    }
 
-        // Verify game was added but waited players is empty
-        let games = tmars_sync.get_games();
-        assert_eq!(games.len(), 1);
+    #[tokio::test]
+    async fn test_check_ignores_games_without_age() {
+        // Nothing is requested for a game whose progress was never requested
+        let mock_requester = MockRequester::new();
 
-        let game = games.get("game1").unwrap();
-        assert_eq!(game.id, "game1");
-        assert_eq!(game.waited_players.len(), 0);
+        let mut tmars_sync = create_checked_sync(mock_requester);
+        tmars_sync.game_ages.clear();
+
+        tmars_sync.check().await;
+
+        assert_eq!(tmars_sync.games.len(), 1);
     }
 
     #[test]
     fn test_convert_phase_all_variants() {
         let mock_requester = MockRequester::new();
-        let tmars_sync = TMarsSync::new(mock_requester);
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
 
         assert!(matches!(
             tmars_sync.convert_phase("initialDrafting"),
@@ -1174,22 +2986,28 @@ mod tests {
         ));
         assert!(matches!(tmars_sync.convert_phase("end"), Phase::End));
 
-        // Test unknown phases default to Research
-        assert!(matches!(
+        // Test unknown phases keep their name
+        assert_eq!(
             tmars_sync.convert_phase("unknown_phase"),
-            Phase::Research
-        ));
-        assert!(matches!(tmars_sync.convert_phase(""), Phase::Research));
-        assert!(matches!(
+            Phase::Unknown("unknown_phase".to_owned())
+        );
+        assert_eq!(
             tmars_sync.convert_phase("INVALID"),
-            Phase::Research
-        ));
+            Phase::Unknown("INVALID".to_owned())
+        );
+
+        // Each unknown phase is warned about once
+        tmars_sync.convert_phase("INVALID");
+        assert_eq!(
+            tmars_sync.unknown_phases,
+            HashSet::from(["unknown_phase".to_owned(), "INVALID".to_owned()])
+        );
     }
 
     #[test]
     fn test_convert_players_empty_list() {
         let mock_requester = MockRequester::new();
-        let tmars_sync = TMarsSync::new(mock_requester);
+        let tmars_sync = TMarsSync::new("main", mock_requester);
 
         let player_details = vec![];
         let players = tmars_sync.convert_players(player_details);
@@ -1201,6 +3019,10 @@ mod tests {
     async fn test_pool_games_clears_existing_games() {
         let mut mock_requester = MockRequester::new();
 
+        mock_requester
+            .expect_get_spectator_url()
+            .returning(|id| format!("http://example.com/spectator?id={}", id));
+
         // First sync - add a game
         mock_requester
             .expect_get_player_url()
@@ -1231,20 +3053,22 @@ mod tests {
                 })
             });
 
-        let mut tmars_sync = TMarsSync::new(mock_requester);
+        let mut tmars_sync = TMarsSync::new("main", mock_requester);
 
         // Add game1
         tmars_sync.pool_games().await.unwrap();
         assert_eq!(tmars_sync.games.len(), 1);
-        assert!(tmars_sync.games.contains_key("game1"));
+        assert!(tmars_sync.games.contains_key("main:game1"));
 
         // Manually add game2 that shouldn't be in the server anymore
         let game2 = Game {
             id: "game2".to_owned(),
             phase: Phase::Research,
             spectator_id: "spec2".to_owned(),
+            spectator_url: "http://example.com/spectator".to_string(),
             players: vec![],
             waited_players: HashSet::new(),
+            progress: None,
         };
         tmars_sync.games.insert("game2".to_owned(), game2);
 
@@ -1281,14 +3105,22 @@ mod tests {
             .await;
 
         // Use real requester pointing to mock server
-        let real_requester = crate::tmars::requester::TMarsRequester::new(&url, "test_id");
-        let mut tmars_sync = TMarsSync::new(real_requester);
-
-        // Call sync and expect AccessError to propagate
+        let real_requester = crate::tmars::requester::TMarsRequester::new(
+            &url,
+            "test_id",
+            &crate::config::TMarsRequests::default(),
+        );
+        let mut tmars_sync = TMarsSync::new("main", real_requester);
+
+        // Call sync and expect an access error to propagate
         let result = tmars_sync.sync().await;
 
-        assert!(result.is_err(), "Expected AccessError, got: {:?}", result);
-        assert!(matches!(result.unwrap_err(), SyncError::AccessError));
+        assert!(
+            result.is_err(),
+            "Expected an access error, got: {:?}",
+            result
+        );
+        assert!(matches!(result.unwrap_err(), SyncError::Access));
     }
 
     #[tokio::test]
@@ -1310,13 +3142,21 @@ mod tests {
             .await;
 
         // Use real requester pointing to mock server
-        let real_requester = crate::tmars::requester::TMarsRequester::new(&url, "test_id");
-        let mut tmars_sync = TMarsSync::new(real_requester);
-
-        // Call sync and expect AccessError to propagate
+        let real_requester = crate::tmars::requester::TMarsRequester::new(
+            &url,
+            "test_id",
+            &crate::config::TMarsRequests::default(),
+        );
+        let mut tmars_sync = TMarsSync::new("main", real_requester);
+
+        // Call sync and expect an access error to propagate
         let result = tmars_sync.sync().await;
 
-        assert!(result.is_err(), "Expected AccessError, got: {:?}", result);
-        assert!(matches!(result.unwrap_err(), SyncError::AccessError));
+        assert!(
+            result.is_err(),
+            "Expected an access error, got: {:?}",
+            result
+        );
+        assert!(matches!(result.unwrap_err(), SyncError::Access));
     }
 }
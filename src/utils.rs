@@ -1,9 +1,15 @@
 //! Utility functions for path manipulation and common operations.
 //!
 //! This module provides helper functions used throughout the Miou bot application
-//! for file system operations and path construction.
+//! for file system operations, path construction, time handling and the
+//! suggestions of names close to a mistyped one.
 
-use std::path::PathBuf;
+use chrono::{NaiveDateTime, TimeDelta, TimeZone};
+use chrono_tz::Tz;
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 /// Constructs a file system path by joining a directory path with a subdirectory.
 ///
@@ -36,6 +42,142 @@ pub fn get_path(dir_path: &str, subdir_path: &str) -> String {
     sqlite_path_buf.to_str().unwrap().to_owned()
 }
 
+/// Returns the current time as a Unix timestamp in seconds.
+///
+/// Timestamps are used instead of [`std::time::Instant`] wherever the value has
+/// to be persisted to disk and survive a restart of the bot.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::utils::get_timestamp;
+/// let now = get_timestamp();
+/// assert!(now > 0);
+/// ```
+pub fn get_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// Resolves a local date and time of a timezone to a Unix timestamp.
+///
+/// An ambiguous local time, repeated when the clocks move backward, resolves to
+/// its earliest occurrence. A local time skipped when the clocks move forward
+/// resolves to an hour later.
+///
+/// # Arguments
+///
+/// * `timezone` - The timezone of the local date and time
+/// * `datetime` - The local date and time
+///
+/// # Returns
+///
+/// The Unix timestamp (in seconds) of the local date and time, `None` if it
+/// can't be resolved.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::utils::resolve_local;
+/// # use chrono::NaiveDate;
+/// // Clocks move from 02:00 to 03:00 on 2024-03-31 in Paris
+/// let skipped = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap().and_hms_opt(2, 0, 0).unwrap();
+/// let moved = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap().and_hms_opt(3, 0, 0).unwrap();
+///
+/// assert_eq!(
+///     resolve_local(chrono_tz::Europe::Paris, skipped),
+///     resolve_local(chrono_tz::Europe::Paris, moved)
+/// );
+/// ```
+pub fn resolve_local(timezone: Tz, datetime: NaiveDateTime) -> Option<u64> {
+    let resolved = timezone
+        .from_local_datetime(&datetime)
+        .earliest()
+        .or_else(|| {
+            timezone
+                .from_local_datetime(&(datetime + TimeDelta::hours(1)))
+                .earliest()
+        })?;
+
+    Some(resolved.timestamp() as u64)
+}
+
+/// Returns the Unix timestamp of a `%Y-%m-%d %H:%M` date and time in a timezone.
+#[cfg(test)]
+pub fn get_local_timestamp(timezone: Tz, datetime: &str) -> u64 {
+    let datetime = NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M").unwrap();
+    resolve_local(timezone, datetime).unwrap()
+}
+
+/// Returns the edit distance between two strings, ignoring the case.
+///
+/// The distance is the number of characters to insert, delete or substitute to
+/// turn one string into the other (Levenshtein distance).
+///
+/// # Examples
+///
+/// ```
+/// # use miou::utils::get_edit_distance;
+/// assert_eq!(get_edit_distance("register", "regster"), 1);
+/// assert_eq!(get_edit_distance("Alice", "alice"), 0);
+/// ```
+pub fn get_edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    // Distances between the first characters of a and the previous row of b
+    let mut previous: Vec<usize> = (0..=a.len()).collect();
+
+    for (j, b_char) in b.iter().enumerate() {
+        let mut current = vec![j + 1; a.len() + 1];
+        for (i, a_char) in a.iter().enumerate() {
+            let substitution = previous[i] + usize::from(a_char != b_char);
+            current[i + 1] = substitution.min(previous[i + 1] + 1).min(current[i] + 1);
+        }
+        previous = current;
+    }
+
+    previous[a.len()]
+}
+
+/// Returns the candidate closest to a mistyped name, to suggest it.
+///
+/// A candidate is close enough when at most a third of the characters of the
+/// name, and at least one, have to be edited. Among the closest candidates, the
+/// first in alphabetical order is returned, for the suggestion to be the same
+/// whatever the order of the candidates.
+///
+/// # Arguments
+///
+/// * `name` - The mistyped name
+/// * `candidates` - The known names
+///
+/// # Returns
+///
+/// The closest candidate, `None` if no candidate is close enough.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::utils::find_closest;
+/// assert_eq!(find_closest("regster", ["games", "register"]), Some("register"));
+/// assert_eq!(find_closest("dance", ["games", "register"]), None);
+/// ```
+pub fn find_closest<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (get_edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,4 +263,60 @@ mod tests {
         #[cfg(windows)]
         assert_eq!(final_path, "\\home\\user\\config");
     }
+
+    #[test]
+    fn test_get_edit_distance() {
+        assert_eq!(get_edit_distance("", ""), 0);
+        assert_eq!(get_edit_distance("games", ""), 5);
+        assert_eq!(get_edit_distance("", "games"), 5);
+        assert_eq!(get_edit_distance("games", "game"), 1);
+        assert_eq!(get_edit_distance("kitten", "sitting"), 3);
+        assert_eq!(get_edit_distance("unwatch", "watch"), 2);
+        assert_eq!(get_edit_distance("Bob", "BOB"), 0);
+        assert_eq!(get_edit_distance("Zoé", "zoe"), 1);
+    }
+
+    #[test]
+    fn test_find_closest() {
+        let commands = ["games", "game", "alerts", "register", "reg"];
+
+        assert_eq!(find_closest("registr", commands), Some("register"));
+        assert_eq!(find_closest("alert", commands), Some("alerts"));
+        assert_eq!(find_closest("dance", commands), None);
+        assert_eq!(find_closest("anything", []), None);
+    }
+
+    #[test]
+    fn test_find_closest_ties() {
+        // "g12" is one edit away from both, the first in alphabetical order wins
+        assert_eq!(find_closest("g12", ["g13", "g11"]), Some("g11"));
+        assert_eq!(find_closest("g12", ["g11", "g13"]), Some("g11"));
+    }
+
+    #[test]
+    fn test_resolve_local() {
+        let paris = chrono_tz::Europe::Paris;
+        let datetime = |value| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap();
+
+        // 2024-01-10 09:00 in Paris is 08:00 UTC
+        assert_eq!(
+            resolve_local(paris, datetime("2024-01-10 09:00")),
+            Some(1_704_873_600)
+        );
+        // 02:30 is skipped and 02:30 repeated when the clocks change in Paris
+        assert_eq!(
+            resolve_local(paris, datetime("2024-03-31 02:30")),
+            resolve_local(paris, datetime("2024-03-31 03:30"))
+        );
+        assert_eq!(
+            resolve_local(paris, datetime("2024-10-27 02:30")),
+            resolve_local(chrono_tz::UTC, datetime("2024-10-27 00:30"))
+        );
+    }
+
+    #[test]
+    fn test_get_timestamp_is_after_2025() {
+        // 2025-01-01T00:00:00Z
+        assert!(get_timestamp() > 1_735_689_600);
+    }
 }
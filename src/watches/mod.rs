@@ -0,0 +1,36 @@
+//! Room-wide watches of Terraforming Mars games.
+//!
+//! This module lets a Matrix room follow a whole game: every time the game waits
+//! for other players or moves to another phase, the change is announced in the
+//! room. The system consists of these components:
+//!
+//! - [`TurnChange`]: State of a game after its waited players or its phase changed
+//! - [`WatchController`]: Tracks the watched games of each room and selects the changes to announce
+//! - [`WatchLoader`]: Handles loading and saving watches to disk
+//!
+//! # Example Usage
+//!
+//! ```no_run
+//! use miou::watches::WatchController;
+//!
+//! # async fn example(
+//! #     games_map: std::collections::HashMap<String, miou::tmars::Game>,
+//! #     game_events: Vec<miou::tmars::GameEvent>,
+//! # ) {
+//! let controller = WatchController::new("watches.json".to_string(), 3).await;
+//!
+//! // Announce the turn changes of a game in a room
+//! controller.add_watch("game_id", "!room:example.com").await;
+//!
+//! // After each synchronization with the TMars server
+//! let turn_changes = controller.update_watches(&games_map, &game_events).await;
+//! # }
+//! ```
+
+mod turn_change;
+mod watch_controller;
+mod watch_loader;
+
+pub use crate::watches::{
+    turn_change::TurnChange, watch_controller::WatchController, watch_loader::WatchLoader,
+};
@@ -0,0 +1,96 @@
+//! Turn change of a watched game.
+//!
+//! This module provides the [`TurnChange`] struct describing the state of a game
+//! after its waited players or its phase changed.
+
+use crate::tmars::{Game, Phase};
+
+/// A change of the waited players or of the phase of a game.
+///
+/// # Examples
+///
+/// ```
+/// # use miou::watches::TurnChange;
+/// # use miou::tmars::Phase;
+/// let turn_change = TurnChange {
+///     game_id: "game123".to_string(),
+///     phase: Phase::Action,
+///     waited_player_names: vec!["Bob".to_string()],
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnChange {
+    /// ID of the game that changed
+    pub game_id: String,
+    /// Phase of the game after the change
+    pub phase: Phase,
+    /// Names of the players the game is waiting for after the change
+    pub waited_player_names: Vec<String>,
+}
+
+impl TurnChange {
+    /// Describes the current state of a game that just changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - The game after the change
+    ///
+    /// # Returns
+    ///
+    /// A [`TurnChange`] with the phase and the names of the waited players of the game.
+    pub fn from_game(game: &Game) -> Self {
+        // Keep the players order of the game to get a stable message
+        let waited_player_names = game
+            .players
+            .iter()
+            .filter(|player| game.waited_players.contains(&player.id))
+            .map(|player| player.name.clone())
+            .collect();
+
+        TurnChange {
+            game_id: game.id.clone(),
+            phase: game.phase.clone(),
+            waited_player_names,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_game(phase: Phase, waited_players: &[&str]) -> Game {
+        Game::test("game1", phase, waited_players)
+    }
+
+    #[test]
+    fn test_from_game() {
+        let game = create_game(Phase::Action, &["player2"]);
+
+        assert_eq!(
+            TurnChange::from_game(&game),
+            TurnChange {
+                game_id: "game1".to_string(),
+                phase: Phase::Action,
+                waited_player_names: vec!["Bob".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_game_keeps_players_order() {
+        let game = create_game(Phase::Research, &["player2", "player1"]);
+
+        assert_eq!(
+            TurnChange::from_game(&game).waited_player_names,
+            vec!["Alice".to_string(), "Bob".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_from_game_no_waited_players() {
+        let game = create_game(Phase::Action, &[]);
+
+        assert!(TurnChange::from_game(&game).waited_player_names.is_empty());
+    }
+}
@@ -0,0 +1,662 @@
+//! Watch controller for announcing turn changes in rooms.
+//!
+//! This module provides the [`WatchController`] which keeps track of the games
+//! watched by each Matrix room and turns the game events emitted by the
+//! synchronization with the TMars server into turn changes to announce.
+
+use std::collections::{HashMap, HashSet};
+
+use log::info;
+use tokio::sync::Mutex;
+
+use crate::{
+    tmars::{Game, GameEvent, get_game_server_name, is_namespaced_game_id, namespace_game_id},
+    watches::{TurnChange, WatchLoader},
+};
+
+type GamesMap = HashMap<String, Game>;
+
+/// Manages the games watched by Matrix rooms.
+///
+/// Unlike alerts, which notify a single user about a single player, a watch binds
+/// a whole game to a room: every change of the waited players or of the phase is
+/// announced in the room.
+///
+/// # State Management
+///
+/// The controller maintains a map of game IDs to the set of rooms watching them.
+/// Watches are persisted to disk as soon as they are added or removed.
+///
+/// Like alerts, the watches of a game missing from the polls of its server are kept
+/// for a grace period, see [`WatchController::clean_watches`], while the watches of
+/// an ended game are removed right away.
+///
+/// Changes are detected by [`TMarsSync`](crate::tmars::TMarsSync), which only emits
+/// the discovery of the games during the first synchronization after a restart, so
+/// no change is announced then.
+///
+/// # Thread Safety
+///
+/// All public methods are async and use internal locking, the controller can be
+/// shared between tasks behind an [`Arc`](std::sync::Arc).
+///
+/// # Examples
+///
+/// ```no_run
+/// use miou::watches::WatchController;
+/// use std::collections::HashMap;
+///
+/// # async fn example() {
+/// let controller = WatchController::new("watches.json".to_string(), 3).await;
+/// controller.add_watch("game123", "!room:example.com").await;
+///
+/// let games_map = HashMap::new(); // Normally populated with actual games
+/// let game_events = Vec::new(); // Normally received from the synchronization
+/// for (room_id, turn_change) in controller.update_watches(&games_map, &game_events).await {
+///     println!("{} changed in {}", turn_change.game_id, room_id);
+/// }
+/// # }
+/// ```
+pub struct WatchController {
+    /// Map of game IDs to the set of rooms watching them
+    watches_map: Mutex<HashMap<String, HashSet<String>>>,
+    /// Loader for persisting and loading watches from disk
+    watch_loader: WatchLoader,
+    /// Number of consecutive polls a watched game has been missing, by game ID
+    missing_polls: Mutex<HashMap<String, u32>>,
+    /// Number of polls a game can be missing before its watches are removed
+    grace_polls: u32,
+}
+
+impl WatchController {
+    /// Creates a new `WatchController` and loads existing watches from disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `watches_path` - Path to the JSON file where watches are persisted
+    /// * `grace_polls` - Number of consecutive polls a game can be missing before
+    ///   its watches are removed, see [`WatchController::clean_watches`]
+    ///
+    /// # Returns
+    ///
+    /// A new `WatchController` instance with watches loaded from the specified file.
+    /// If the file doesn't exist or is corrupted, starts with an empty watches map.
+    pub async fn new(watches_path: String, grace_polls: u32) -> Self {
+        let watch_loader = WatchLoader::new(watches_path);
+        let watches_map = Mutex::new(watch_loader.load().await);
+
+        WatchController {
+            watches_map,
+            watch_loader,
+            missing_polls: Mutex::new(HashMap::new()),
+            grace_polls,
+        }
+    }
+
+    /// Namespaces the game IDs of the watches added before the support of multiple
+    /// servers, and persists the watches if any was moved.
+    ///
+    /// Watches were added with the bare IDs of the games of a single server. They
+    /// are moved to the namespaced IDs of the games on `server_name`, the other
+    /// watches are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - Name of the server hosting the games of the bare IDs
+    pub async fn namespace_game_ids(&self, server_name: &str) {
+        let mut watches_map = self.watches_map.lock().await;
+
+        let game_ids: Vec<String> = watches_map
+            .keys()
+            .filter(|game_id| !is_namespaced_game_id(game_id))
+            .cloned()
+            .collect();
+        if game_ids.is_empty() {
+            return;
+        }
+
+        for game_id in game_ids {
+            let room_ids = watches_map.remove(&game_id).unwrap_or_default();
+            let namespaced_game_id = namespace_game_id(server_name, &game_id);
+            info!("move watches of game {} to {}", game_id, namespaced_game_id);
+            watches_map
+                .entry(namespaced_game_id)
+                .or_default()
+                .extend(room_ids);
+        }
+        self.watch_loader.persist_watches_map(&watches_map).await;
+    }
+
+    /// Binds a game to a room and persists the watches.
+    ///
+    /// Watching a game twice in the same room has no effect.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_id` - The ID of the game to watch
+    /// * `room_id` - The Matrix room where the turn changes are announced
+    pub async fn add_watch(&self, game_id: &str, room_id: &str) {
+        let mut watches_map = self.watches_map.lock().await;
+
+        watches_map
+            .entry(game_id.to_owned())
+            .or_default()
+            .insert(room_id.to_owned());
+        self.watch_loader.persist_watches_map(&watches_map).await;
+
+        info!("room {} is watching game {}", room_id, game_id);
+    }
+
+    /// Unbinds a game from a room and persists the watches.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_id` - The ID of the game to stop watching
+    /// * `room_id` - The Matrix room where the turn changes were announced
+    pub async fn remove_watch(&self, game_id: &str, room_id: &str) {
+        let mut watches_map = self.watches_map.lock().await;
+
+        let Some(room_ids) = watches_map.get_mut(game_id) else {
+            return;
+        };
+        room_ids.remove(room_id);
+        if room_ids.is_empty() {
+            watches_map.remove(game_id);
+        }
+        self.watch_loader.persist_watches_map(&watches_map).await;
+
+        info!("room {} stopped watching game {}", room_id, game_id);
+    }
+
+    /// Returns the rooms watching a game.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_id` - The ID of the game
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the rooms watching the game, empty if the game isn't watched.
+    pub async fn get_room_ids(&self, game_id: &str) -> HashSet<String> {
+        self.watches_map
+            .lock()
+            .await
+            .get(game_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns the turn changes to announce for the events of a synchronization.
+    ///
+    /// A game changing several times during a synchronization is announced once,
+    /// with its current state. Watches of the games missing from `games_map` are
+    /// left untouched, they are removed by [`WatchController::clean_watches`].
+    ///
+    /// # Arguments
+    ///
+    /// * `games_map` - Current state of all active games
+    /// * `game_events` - Events emitted by the synchronization
+    ///
+    /// # Returns
+    ///
+    /// A vector of tuples containing (room_id, turn_change) for each room watching
+    /// a game whose waited players or phase changed.
+    pub async fn update_watches(
+        &self,
+        games_map: &GamesMap,
+        game_events: &[GameEvent],
+    ) -> Vec<(String, TurnChange)> {
+        let watches_map = self.watches_map.lock().await;
+
+        let mut changed_game_ids: Vec<&str> = Vec::new();
+        for game_event in game_events {
+            match game_event {
+                GameEvent::PhaseChanged { .. }
+                | GameEvent::TurnStarted { .. }
+                | GameEvent::TurnEnded { .. } => {
+                    if !changed_game_ids.contains(&game_event.game_id()) {
+                        changed_game_ids.push(game_event.game_id());
+                    }
+                }
+                GameEvent::GameAdded { .. }
+                | GameEvent::GameRemoved { .. }
+                | GameEvent::GameEnded { .. } => {}
+            }
+        }
+
+        let mut turn_changes: Vec<(String, TurnChange)> = Vec::new();
+        for game_id in changed_game_ids {
+            let (Some(room_ids), Some(game)) = (watches_map.get(game_id), games_map.get(game_id))
+            else {
+                continue;
+            };
+
+            let turn_change = TurnChange::from_game(game);
+            room_ids.iter().for_each(|room_id| {
+                turn_changes.push((room_id.clone(), turn_change.clone()));
+            });
+        }
+
+        turn_changes
+    }
+
+    /// Removes watches for games that no longer exist and persists the watches
+    /// if any was removed.
+    ///
+    /// A game missing from a single poll may only be a hiccup of its server, so its
+    /// watches are removed once the game has been missing for more than the grace
+    /// period of consecutive polls. A game showing up again resets its count.
+    ///
+    /// Only the successful polls of its server count for a game: a failing server
+    /// keeps the games it had, or has none if it never answered.
+    ///
+    /// The checks between two polls don't list the games, the servers only checked
+    /// must not be in `synced_servers`.
+    ///
+    /// # Arguments
+    ///
+    /// * `games_map` - Current state of all active games
+    /// * `synced_servers` - Names of the servers which listed their games during the poll
+    pub async fn clean_watches(&self, games_map: &GamesMap, synced_servers: &HashSet<String>) {
+        let mut watches_map = self.watches_map.lock().await;
+        let mut missing_polls = self.missing_polls.lock().await;
+        let watches_count = watches_map.len();
+
+        missing_polls.retain(|game_id, _| !games_map.contains_key(game_id));
+        watches_map.retain(|game_id, _| {
+            if games_map.contains_key(game_id)
+                || !get_game_server_name(game_id)
+                    .is_some_and(|server_name| synced_servers.contains(server_name))
+            {
+                return true;
+            }
+
+            let missing_count = missing_polls.entry(game_id.clone()).or_default();
+            *missing_count += 1;
+            if *missing_count <= self.grace_polls {
+                info!(
+                    "game {} missing for {} polls, keeping its watches",
+                    game_id, missing_count
+                );
+                return true;
+            }
+
+            info!("removing watches for non-existing game {}", game_id);
+            missing_polls.remove(game_id);
+            false
+        });
+
+        if watches_map.len() != watches_count {
+            self.watch_loader.persist_watches_map(&watches_map).await;
+        }
+    }
+
+    /// Removes the watches of ended games right away and persists the watches if
+    /// any was removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ended_game_ids` - IDs of the games confirmed ended by their server
+    pub async fn remove_ended_games_watches(&self, ended_game_ids: &HashSet<String>) {
+        let mut watches_map = self.watches_map.lock().await;
+        let mut missing_polls = self.missing_polls.lock().await;
+        let watches_count = watches_map.len();
+
+        for game_id in ended_game_ids {
+            missing_polls.remove(game_id);
+            if watches_map.remove(game_id).is_some() {
+                info!("removing watches for ended game {}", game_id);
+            }
+        }
+
+        if watches_map.len() != watches_count {
+            self.watch_loader.persist_watches_map(&watches_map).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tmars::Phase;
+    use tempfile::NamedTempFile;
+
+    fn create_test_game(id: &str, phase: Phase, waited_players: &[&str]) -> Game {
+        Game::test(id, phase, waited_players)
+    }
+
+    fn create_games_map(games: Vec<Game>) -> GamesMap {
+        games
+            .into_iter()
+            .map(|game| (game.id.clone(), game))
+            .collect()
+    }
+
+    async fn create_test_controller() -> (WatchController, NamedTempFile) {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        (WatchController::new(path, 1).await, temp_file)
+    }
+
+    #[tokio::test]
+    async fn test_add_watch() {
+        let (controller, _temp_file) = create_test_controller().await;
+
+        controller.add_watch("game1", "!room1:example.com").await;
+        controller.add_watch("game1", "!room2:example.com").await;
+        controller.add_watch("game1", "!room1:example.com").await;
+
+        let watches_map = controller.watches_map.lock().await.clone();
+        assert_eq!(watches_map.get("game1").unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_namespace_game_ids() {
+        let (controller, temp_file) = create_test_controller().await;
+
+        controller.add_watch("game1", "!room1:example.com").await;
+        controller
+            .add_watch("friends:game2", "!room1:example.com")
+            .await;
+
+        controller.namespace_game_ids("main").await;
+
+        let expected = HashMap::from([
+            (
+                "main:game1".to_string(),
+                HashSet::from(["!room1:example.com".to_string()]),
+            ),
+            (
+                "friends:game2".to_string(),
+                HashSet::from(["!room1:example.com".to_string()]),
+            ),
+        ]);
+        assert_eq!(controller.watches_map.lock().await.clone(), expected);
+
+        // The moved watches are persisted
+        let path = temp_file.path().to_str().unwrap().to_string();
+        assert_eq!(
+            WatchController::new(path, 1).await.watches_map.into_inner(),
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_watch() {
+        let (controller, _temp_file) = create_test_controller().await;
+
+        controller.add_watch("game1", "!room1:example.com").await;
+        controller.add_watch("game1", "!room2:example.com").await;
+
+        controller.remove_watch("game1", "!room1:example.com").await;
+        assert_eq!(
+            controller.watches_map.lock().await.clone().get("game1"),
+            Some(&HashSet::from(["!room2:example.com".to_string()]))
+        );
+
+        controller.remove_watch("game1", "!room2:example.com").await;
+        assert!(controller.watches_map.lock().await.clone().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_remove_watch_unknown_game() {
+        let (controller, _temp_file) = create_test_controller().await;
+
+        controller.remove_watch("game1", "!room1:example.com").await;
+
+        assert!(controller.watches_map.lock().await.clone().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_watches_persist() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+
+        let controller1 = WatchController::new(path.clone(), 1).await;
+        controller1.add_watch("game1", "!room1:example.com").await;
+
+        // A new controller loads the persisted watches
+        let controller2 = WatchController::new(path, 1).await;
+        assert_eq!(
+            controller2.watches_map.lock().await.clone(),
+            controller1.watches_map.lock().await.clone()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_room_ids() {
+        let (controller, _temp_file) = create_test_controller().await;
+        controller.add_watch("game1", "!room1:example.com").await;
+        controller.add_watch("game1", "!room2:example.com").await;
+
+        assert_eq!(
+            controller.get_room_ids("game1").await,
+            HashSet::from([
+                "!room1:example.com".to_string(),
+                "!room2:example.com".to_string()
+            ])
+        );
+        assert!(controller.get_room_ids("game2").await.is_empty());
+    }
+
+    fn turn_started(game_id: &str, player_id: &str) -> GameEvent {
+        GameEvent::TurnStarted {
+            game_id: game_id.to_string(),
+            player_id: player_id.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_watches_game_added_announces_nothing() {
+        let (controller, _temp_file) = create_test_controller().await;
+        controller.add_watch("game1", "!room1:example.com").await;
+
+        let games_map =
+            create_games_map(vec![create_test_game("game1", Phase::Action, &["player1"])]);
+        let game_events = vec![GameEvent::GameAdded {
+            game_id: "game1".to_string(),
+        }];
+
+        assert!(
+            controller
+                .update_watches(&games_map, &game_events)
+                .await
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_watches_announces_turn_change() {
+        let (controller, _temp_file) = create_test_controller().await;
+        controller.add_watch("game1", "!room1:example.com").await;
+        controller.add_watch("game1", "!room2:example.com").await;
+
+        let games_map =
+            create_games_map(vec![create_test_game("game1", Phase::Action, &["player2"])]);
+        let game_events = vec![
+            GameEvent::TurnEnded {
+                game_id: "game1".to_string(),
+                player_id: "player1".to_string(),
+            },
+            turn_started("game1", "player2"),
+        ];
+        let mut turn_changes = controller.update_watches(&games_map, &game_events).await;
+        turn_changes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        // Both events of the game are announced once in each room
+        let turn_change = TurnChange {
+            game_id: "game1".to_string(),
+            phase: Phase::Action,
+            waited_player_names: vec!["Bob".to_string()],
+        };
+        assert_eq!(
+            turn_changes,
+            vec![
+                ("!room1:example.com".to_string(), turn_change.clone()),
+                ("!room2:example.com".to_string(), turn_change),
+            ]
+        );
+
+        // No event, nothing to announce
+        assert!(controller.update_watches(&games_map, &[]).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_watches_announces_phase_change() {
+        let (controller, _temp_file) = create_test_controller().await;
+        controller.add_watch("game1", "!room1:example.com").await;
+
+        let games_map = create_games_map(vec![create_test_game("game1", Phase::Production, &[])]);
+        let game_events = vec![GameEvent::PhaseChanged {
+            game_id: "game1".to_string(),
+            previous_phase: Phase::Action,
+            phase: Phase::Production,
+        }];
+        let turn_changes = controller.update_watches(&games_map, &game_events).await;
+
+        assert_eq!(turn_changes.len(), 1);
+        assert_eq!(turn_changes[0].1.phase, Phase::Production);
+    }
+
+    #[tokio::test]
+    async fn test_update_watches_ignores_unwatched_games() {
+        let (controller, _temp_file) = create_test_controller().await;
+        controller.add_watch("game1", "!room1:example.com").await;
+
+        let games_map = create_games_map(vec![
+            create_test_game("game1", Phase::Action, &["player1"]),
+            create_test_game("game2", Phase::Action, &["player2"]),
+        ]);
+        let game_events = vec![turn_started("game2", "player2")];
+
+        assert!(
+            controller
+                .update_watches(&games_map, &game_events)
+                .await
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_watches_keeps_missing_games() {
+        let (controller, _temp_file) = create_test_controller().await;
+        controller.add_watch("game1", "!room1:example.com").await;
+
+        // Only the polls clean the watches
+        controller.update_watches(&HashMap::new(), &[]).await;
+        controller.update_watches(&HashMap::new(), &[]).await;
+
+        assert!(controller.watches_map.lock().await.contains_key("game1"));
+    }
+
+    #[tokio::test]
+    async fn test_clean_watches_removes_non_existing_games() {
+        let (controller, temp_file) = create_test_controller().await;
+        controller
+            .add_watch("main:game1", "!room1:example.com")
+            .await;
+        controller
+            .add_watch("main:game2", "!room1:example.com")
+            .await;
+
+        let games_map = create_games_map(vec![create_test_game(
+            "main:game1",
+            Phase::Action,
+            &["player1"],
+        )]);
+        let synced_servers = HashSet::from(["main".to_string()]);
+
+        // The game is missing for the grace period, its watches are kept
+        controller.clean_watches(&games_map, &synced_servers).await;
+        assert!(
+            controller
+                .watches_map
+                .lock()
+                .await
+                .contains_key("main:game2")
+        );
+
+        controller.clean_watches(&games_map, &synced_servers).await;
+        let watches_map = controller.watches_map.lock().await.clone();
+        assert!(watches_map.contains_key("main:game1"));
+        assert!(!watches_map.contains_key("main:game2"));
+
+        // The removal is persisted
+        let path = temp_file.path().to_str().unwrap().to_string();
+        assert_eq!(
+            WatchController::new(path, 1).await.watches_map.into_inner(),
+            watches_map
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clean_watches_keeps_games_of_servers_not_synced() {
+        let (controller, _temp_file) = create_test_controller().await;
+        controller
+            .add_watch("friends:game1", "!room1:example.com")
+            .await;
+
+        // The server of the game never answered, only the other one did
+        let synced_servers = HashSet::from(["main".to_string()]);
+        for _ in 0..3 {
+            controller
+                .clean_watches(&HashMap::new(), &synced_servers)
+                .await;
+        }
+
+        assert!(
+            controller
+                .watches_map
+                .lock()
+                .await
+                .contains_key("friends:game1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clean_watches_resets_missing_polls_of_reappearing_games() {
+        let (controller, _temp_file) = create_test_controller().await;
+        controller
+            .add_watch("main:game1", "!room1:example.com")
+            .await;
+
+        let games_map = create_games_map(vec![create_test_game(
+            "main:game1",
+            Phase::Action,
+            &["player1"],
+        )]);
+        let synced_servers = HashSet::from(["main".to_string()]);
+
+        // Missing, back, then missing again: never missing for more than one poll
+        controller
+            .clean_watches(&HashMap::new(), &synced_servers)
+            .await;
+        controller.clean_watches(&games_map, &synced_servers).await;
+        controller
+            .clean_watches(&HashMap::new(), &synced_servers)
+            .await;
+
+        assert!(
+            controller
+                .watches_map
+                .lock()
+                .await
+                .contains_key("main:game1")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remove_ended_games_watches() {
+        let (controller, _temp_file) = create_test_controller().await;
+        controller.add_watch("game1", "!room1:example.com").await;
+        controller.add_watch("game2", "!room1:example.com").await;
+
+        controller
+            .remove_ended_games_watches(&HashSet::from(["game2".to_string()]))
+            .await;
+
+        let watches_map = controller.watches_map.lock().await.clone();
+        assert!(watches_map.contains_key("game1"));
+        assert!(!watches_map.contains_key("game2"));
+    }
+}
@@ -0,0 +1,140 @@
+//! Watch persistence layer for loading and saving watches to disk.
+//!
+//! This module provides the [`WatchLoader`] for persisting the watched games of
+//! each room between bot restarts. Watches are serialized to JSON and stored in a file.
+
+use std::collections::{HashMap, HashSet};
+
+use log::{error, info, warn};
+use tokio::fs;
+
+/// Handles loading and persisting watches to disk.
+///
+/// Like the alerts loader, it is fault-tolerant: if loading fails (file missing
+/// or corrupted), it returns an empty map rather than panicking.
+///
+/// # Examples
+///
+/// ```no_run
+/// use miou::watches::WatchLoader;
+///
+/// # async fn example() {
+/// let loader = WatchLoader::new("watches.json".to_string());
+///
+/// // Load existing watches or get an empty map
+/// let watches_map = loader.load().await;
+///
+/// // Later, persist the watches
+/// loader.persist_watches_map(&watches_map).await;
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct WatchLoader {
+    /// Path to the JSON file where watches are stored.
+    path: String,
+}
+
+impl WatchLoader {
+    /// Creates a new `WatchLoader` for the specified file path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path where watches will be loaded from and saved to
+    pub fn new(path: String) -> Self {
+        WatchLoader { path }
+    }
+
+    /// Loads watches from disk.
+    ///
+    /// # Returns
+    ///
+    /// A `HashMap` mapping game IDs to the set of rooms watching them, or an empty
+    /// map if the file doesn't exist or cannot be deserialized.
+    pub async fn load(&self) -> HashMap<String, HashSet<String>> {
+        let Ok(serialized_watches_map) = fs::read_to_string(&self.path).await else {
+            warn!("no persisted watches found, starting with an empty watches map");
+            return HashMap::new();
+        };
+
+        let Ok(watches_map) = serde_json::from_str(&serialized_watches_map) else {
+            error!("failed to deserialize persisted watches, starting with an empty watches map");
+            return HashMap::new();
+        };
+
+        info!("loaded persisted watches {}", serialized_watches_map);
+
+        watches_map
+    }
+
+    /// Persists the watches map to disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `watches_map` - Reference to the watches map to persist
+    ///
+    /// # Error Handling
+    ///
+    /// Errors are logged but not propagated, allowing the bot to continue operating
+    /// even if persistence fails.
+    pub async fn persist_watches_map(&self, watches_map: &HashMap<String, HashSet<String>>) {
+        let serialized_watches_map = match serde_json::to_string(watches_map) {
+            Ok(serialized) => serialized,
+            Err(e) => {
+                error!("failed to serialize watches map: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = fs::write(&self.path, &serialized_watches_map).await {
+            error!("failed to persist watches map: {}", e);
+            return;
+        }
+
+        info!("persisted watches map");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[tokio::test]
+    async fn test_load_nonexistent_file_returns_empty_map() {
+        let loader = WatchLoader::new("nonexistent_file.json".to_string());
+        let watches_map = loader.load().await;
+
+        assert!(watches_map.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_persist_and_load_watches_map() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+        let loader = WatchLoader::new(path);
+
+        let mut watches_map = HashMap::new();
+        watches_map.insert(
+            "game1".to_string(),
+            HashSet::from(["!room1:example.com".to_string()]),
+        );
+
+        loader.persist_watches_map(&watches_map).await;
+
+        let loaded_watches_map = loader.load().await;
+        assert_eq!(loaded_watches_map, watches_map);
+    }
+
+    #[tokio::test]
+    async fn test_load_corrupted_json_returns_empty_map() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_str().unwrap().to_string();
+
+        fs::write(&path, "{ this is not valid json ").await.unwrap();
+
+        let loader = WatchLoader::new(path);
+        let watches_map = loader.load().await;
+
+        assert!(watches_map.is_empty());
+    }
+}